@@ -0,0 +1,30 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use minidisplay::{Displays, Error};
+
+fn enumerate_displays(c: &mut Criterion) {
+    let mut displays = Displays::new();
+
+    // Nothing to benchmark on the unsupported platforms.
+    if let Err(Error::Unsupported) = displays.enumerate_displays() {
+        eprintln!("skipping `enumerate_displays`: unsupported platform");
+        return;
+    }
+
+    c.bench_function("enumerate_displays", |b| {
+        b.iter(|| displays.enumerate_displays().unwrap())
+    });
+}
+
+fn enumerate_rects_only(c: &mut Criterion) {
+    if let Err(Error::Unsupported) = Displays::enumerate_rects_only() {
+        eprintln!("skipping `enumerate_rects_only`: unsupported platform");
+        return;
+    }
+
+    c.bench_function("enumerate_rects_only", |b| {
+        b.iter(|| Displays::enumerate_rects_only().unwrap())
+    });
+}
+
+criterion_group!(benches, enumerate_displays, enumerate_rects_only);
+criterion_main!(benches);
@@ -0,0 +1,71 @@
+//! Rectangle math with arbitrary (including the extreme `i32` / `u32`) inputs must never panic.
+//!
+//! Run with `cargo fuzz run rectangle` (requires `cargo-fuzz` and a nightly toolchain).
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use minidisplay::{
+    closest_dimensions, ClipRectFlags, ClosestDimensionsFlags, Dimensions, DisplayMode, Position,
+    Rectangle, Strictness, UpscaleMode,
+};
+
+struct Input<'a>(&'a [u8]);
+
+impl Input<'_> {
+    fn u32(&mut self) -> u32 {
+        let mut bytes = [0; 4];
+        let len = self.0.len().min(4);
+        bytes[..len].copy_from_slice(&self.0[..len]);
+        self.0 = &self.0[len..];
+        u32::from_le_bytes(bytes)
+    }
+
+    fn i32(&mut self) -> i32 {
+        self.u32() as i32
+    }
+
+    fn dimensions(&mut self) -> Dimensions {
+        Dimensions::new(self.u32(), self.u32())
+    }
+
+    fn rect(&mut self) -> Rectangle {
+        Rectangle::new(Position::new(self.i32(), self.i32()), self.dimensions())
+    }
+}
+
+fuzz_target!(|data: &[u8]| {
+    let mut input = Input(data);
+
+    let rect = input.rect();
+    let bounds = input.rect();
+    let min_dimensions = input.dimensions();
+    let clip_flags = ClipRectFlags::from_bits_truncate(input.u32());
+
+    for strictness in [
+        Strictness::BestEffort,
+        Strictness::FailOnConflict,
+        Strictness::ShrinkToFit,
+    ] {
+        let _ = rect.clip_with(&bounds, clip_flags, strictness);
+        let _ = rect.clamp_with(min_dimensions, clip_flags, strictness);
+    }
+
+    let dimensions = input.dimensions();
+    let modes: Vec<_> = (0..1 + input.u32() % 8)
+        .map(|_| DisplayMode {
+            dimensions: input.dimensions(),
+            refresh_rate: 60,
+            refresh_rate_num: 60,
+            refresh_rate_denom: 1,
+            upscale_mode: UpscaleMode::Unknown,
+        })
+        .collect();
+
+    closest_dimensions(&modes, dimensions, ClosestDimensionsFlags::Closest);
+    closest_dimensions(
+        &modes,
+        dimensions,
+        ClosestDimensionsFlags::ClosestSmallerOrEqual,
+    );
+});
@@ -0,0 +1,150 @@
+use crate::{DisplayInfo, DisplayName, Error, PlatformInfo};
+
+#[cfg(windows)]
+use super::win::enumerate_displays_win as enumerate_displays_platform;
+
+#[cfg(not(windows))]
+use super::stub::enumerate_displays_stub as enumerate_displays_platform;
+
+/// Options the [`display backend`] enumerates the displays with.
+///
+/// [`display backend`]: trait.DisplayBackend.html
+#[derive(Clone, Copy, Debug)]
+pub struct EnumerateOptions<'a> {
+    /// Names of the previously enumerated displays.
+    /// The backend may reuse the unchanged ones instead of allocating new ones.
+    pub previous_names: &'a [DisplayName],
+    /// Also enumerate the displays whose monitor device is not reported active / attached
+    /// (e.g. while the monitor is turned off). `false` by default.
+    ///
+    /// Only the native backend on Windows enumerates such displays; see [`DisplayStateWin`].
+    ///
+    /// [`DisplayStateWin`]: struct.DisplayStateWin.html
+    pub include_inactive: bool,
+}
+
+bitflags! {
+    /// Features the [`display backend`] supports, as returned by [`DisplayBackend::capabilities`].
+    ///
+    /// Use this to probe what the current platform layer supports at runtime
+    /// (e.g. to hide the corresponding settings UI) instead of `cfg`-gating the features at compile time.
+    ///
+    /// [`display backend`]: trait.DisplayBackend.html
+    /// [`DisplayBackend::capabilities`]: trait.DisplayBackend.html#method.capabilities
+    #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+    pub struct BackendCapabilities: u32 {
+        /// Display modes, orientation and the rest of the display configuration may be changed
+        /// (see [`Displays::begin_config`](struct.Displays.html#method.begin_config)).
+        const MODE_SETTING = 1;
+        /// Display identifiers are stable across the enumerations,
+        /// so the display events are reliably reported (see [`Displays::on_display`](struct.Displays.html#method.on_display)).
+        const EVENTS = 1 << 1;
+        /// Display brightness may be read and matched
+        /// (see [`Displays::match_brightness`](struct.Displays.html#method.match_brightness)).
+        const BRIGHTNESS = 1 << 2;
+        /// Monitor settings may be controlled via DDC/CI (contrast, input source, etc.).
+        const DDC = 1 << 3;
+        /// Display DPI scale may be changed (see [`Displays::set_dpi_scale`](struct.Displays.html#method.set_dpi_scale)).
+        const DPI_SCALING = 1 << 4;
+        /// The OS projection mode may be queried and changed (see [`Displays::projection`](struct.Displays.html#method.projection)).
+        const PROJECTION = 1 << 5;
+        /// The OS night light state may be queried (see [`Displays::night_light`](struct.Displays.html#method.night_light)).
+        const NIGHT_LIGHT = 1 << 6;
+        /// Presentation mode may be queried and the displays may be kept on
+        /// (see [`Displays::set_thread_display_required`](struct.Displays.html#method.set_thread_display_required)).
+        const POWER = 1 << 7;
+        /// All monitors known to the system may be enumerated
+        /// (see [`Displays::enumerate_attached_hardware`](struct.Displays.html#method.enumerate_attached_hardware)).
+        const HARDWARE_INVENTORY = 1 << 8;
+    }
+}
+
+/// Single display info as returned by the [`display backend`].
+///
+/// [`display backend`]: trait.DisplayBackend.html
+#[derive(Clone, Debug)]
+pub struct EnumeratedDisplayInfo {
+    /// Generic display info.
+    pub info: DisplayInfo,
+    /// Platform-specific display info.
+    pub platform: PlatformInfo,
+}
+
+/// Source of the display info enumerated by the [`display manager`].
+///
+/// The display manager uses the [`native backend`] by default.
+/// Implement this to provide the display info from elsewhere (e.g. mock displays for tests,
+/// or the virtual displays of a cloud streaming session), and pass the backend to [`Displays::with_backend`].
+///
+/// NOTE - only the display enumeration goes through the backend.
+/// Display configuration and monitor control methods of the display manager (brightness, DDC/CI, etc.)
+/// use the displays' [`platform-specific info`] directly and fail for the displays the platform doesn't know about.
+///
+/// [`display manager`]: struct.Displays.html
+/// [`native backend`]: struct.NativeBackend.html
+/// [`Displays::with_backend`]: struct.Displays.html#method.with_backend
+/// [`platform-specific info`]: enum.PlatformInfo.html
+pub trait DisplayBackend: Send + Sync {
+    /// Enumerates the displays, in no particular order.
+    ///
+    /// The [`display manager`] reorders the displays to keep their display indices stable
+    /// and the primary display (if any) at display index `0`.
+    ///
+    /// [`display manager`]: struct.Displays.html
+    fn enumerate(
+        &self,
+        options: &EnumerateOptions<'_>,
+    ) -> Result<Vec<EnumeratedDisplayInfo>, Error>;
+
+    /// Returns the features the backend supports.
+    ///
+    /// By default, the backend only supports display enumeration.
+    fn capabilities(&self) -> BackendCapabilities {
+        BackendCapabilities::empty()
+    }
+}
+
+/// The [`display backend`] of the current platform (i.e. WinAPI on Windows).
+///
+/// On the unsupported platforms enumerates no displays and fails with [`Error::Unsupported`].
+///
+/// NOTE - the [`display manager`] only exposes the [`raw display configuration`]
+/// when using the native backend by default (i.e. when created with [`Displays::new`]).
+///
+/// [`display backend`]: trait.DisplayBackend.html
+/// [`Error::Unsupported`]: enum.Error.html#variant.Unsupported
+/// [`display manager`]: struct.Displays.html
+/// [`raw display configuration`]: struct.Displays.html#method.raw_display_config
+/// [`Displays::new`]: struct.Displays.html#method.new
+#[derive(Clone, Copy, Default, Debug)]
+pub struct NativeBackend;
+
+impl DisplayBackend for NativeBackend {
+    fn enumerate(
+        &self,
+        options: &EnumerateOptions<'_>,
+    ) -> Result<Vec<EnumeratedDisplayInfo>, Error> {
+        enumerate_displays_platform(options).map(|(displays, _)| displays)
+    }
+
+    fn capabilities(&self) -> BackendCapabilities {
+        if !cfg!(windows) {
+            return BackendCapabilities::empty();
+        }
+
+        let capabilities = BackendCapabilities::MODE_SETTING
+            | BackendCapabilities::EVENTS
+            | BackendCapabilities::BRIGHTNESS
+            | BackendCapabilities::DDC
+            | BackendCapabilities::PROJECTION
+            | BackendCapabilities::NIGHT_LIGHT
+            | BackendCapabilities::POWER
+            | BackendCapabilities::HARDWARE_INVENTORY;
+
+        if cfg!(feature = "unstable-scaling") {
+            capabilities | BackendCapabilities::DPI_SCALING
+        } else {
+            capabilities
+        }
+    }
+}
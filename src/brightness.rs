@@ -0,0 +1,395 @@
+use std::fmt::{Display, Formatter};
+
+use crate::DisplayIndex;
+
+/// Exponent of the (approximate sRGB) transfer function relating the gamma ramp values to the luminance.
+const GAMMA: f32 = 2.2;
+/// Number of entries in each channel of the display's gamma ramp.
+pub(crate) const GAMMA_RAMP_SIZE: usize = 256;
+/// Relative luminance below which the gamma ramp is never scaled, to keep the display readable.
+const MIN_GAMMA_RAMP_SCALE: f32 = 0.25;
+
+/// Brightness level of the display, as reported by the monitor via DDC/CI.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) struct BrightnessLevel {
+    pub(crate) current: u32,
+    pub(crate) min: u32,
+    pub(crate) max: u32,
+}
+
+impl BrightnessLevel {
+    /// Returns the current brightness level in `[0.0 .. 1.0]` range.
+    fn fraction(&self) -> f32 {
+        if self.max > self.min {
+            (self.current.clamp(self.min, self.max) - self.min) as f32
+                / (self.max - self.min) as f32
+        } else {
+            1.0
+        }
+    }
+
+    /// Returns the brightness level closest to the `fraction` in `[0.0 .. 1.0]` range.
+    fn level(&self, fraction: f32) -> u32 {
+        self.min + (fraction.clamp(0.0, 1.0) * (self.max - self.min) as f32).round() as u32
+    }
+}
+
+/// The display's brightness controls, as read by the platform.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(crate) struct DisplayBrightness {
+    /// The monitor's (hardware) brightness level, if it supports DDC/CI.
+    pub(crate) ddc: Option<BrightnessLevel>,
+    /// Relative luminance the display's gamma ramp scales the output to, if known.
+    pub(crate) gamma_ramp_scale: Option<f32>,
+}
+
+impl DisplayBrightness {
+    /// Returns the display's relative luminance in `[0.0 .. 1.0]` range,
+    /// treating the DDC/CI brightness level as linear in luminance.
+    fn luminance(&self) -> f32 {
+        self.ddc.map_or(1.0, |ddc| ddc.fraction()) * self.gamma_ramp_scale.unwrap_or(1.0)
+    }
+}
+
+/// How the display's brightness is adjusted, as part of the [`brightness adjustment`].
+///
+/// [`brightness adjustment`]: struct.BrightnessAdjustment.html
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum BrightnessSetting {
+    /// Set the monitor's (hardware) brightness to this level via DDC/CI.
+    Ddc(u32),
+    /// Scale the display's gamma ramp to this relative luminance in `[0.0 .. 1.0]` range
+    /// (for the displays which don't support DDC/CI, e.g. most laptop panels and TV's).
+    GammaRamp(f32),
+}
+
+impl Display for BrightnessSetting {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use BrightnessSetting::*;
+
+        match self {
+            Ddc(level) => write!(f, "DDC/CI brightness {}", level),
+            GammaRamp(scale) => write!(f, "gamma ramp scaled to {:.0}%", scale * 100.0),
+        }
+    }
+}
+
+/// Brightness adjustment of a display to match the brightness of the reference display,
+/// as computed by [`Displays::brightness_adjustments`].
+///
+/// [`Displays::brightness_adjustments`]: struct.Displays.html#method.brightness_adjustments
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct BrightnessAdjustment {
+    /// Index of the adjusted display.
+    pub display_index: DisplayIndex,
+    /// The display's current relative luminance in `[0.0 .. 1.0]` range.
+    pub current: f32,
+    /// The display's relative luminance after the adjustment (i.e. the reference display's one, if reachable).
+    pub target: f32,
+    /// How the display's brightness is adjusted.
+    pub setting: BrightnessSetting,
+}
+
+/// State of the adaptive brightness (i.e. the brightness automatically adjusted to the ambient light
+/// by the laptop's light sensor) of the internal display, as returned by [`Displays::adaptive_brightness`].
+///
+/// While active, the OS may override the brightness set manually or by the [`brightness adjustments`].
+///
+/// [`Displays::adaptive_brightness`]: struct.Displays.html#method.adaptive_brightness
+/// [`brightness adjustments`]: struct.Displays.html#method.match_brightness
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct AdaptiveBrightness {
+    /// Whether the adaptive brightness is enabled (in the active power plan on Windows) when on AC power.
+    pub enabled_on_ac: bool,
+    /// Whether the adaptive brightness is enabled (in the active power plan on Windows) when on battery power.
+    pub enabled_on_battery: bool,
+    /// Whether the system currently runs on battery power, if known.
+    pub on_battery: Option<bool>,
+}
+
+impl AdaptiveBrightness {
+    /// Returns `true` if the adaptive brightness is enabled for the current power source
+    /// (or for any power source, if the current one is unknown).
+    pub fn is_active(&self) -> bool {
+        match self.on_battery {
+            Some(true) => self.enabled_on_battery,
+            Some(false) => self.enabled_on_ac,
+            None => self.enabled_on_ac || self.enabled_on_battery,
+        }
+    }
+}
+
+impl Display for AdaptiveBrightness {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "adaptive brightness {}",
+            if self.is_active() {
+                "active"
+            } else {
+                "inactive"
+            }
+        )
+    }
+}
+
+/// Computes the adjustments of the displays' `brightness` (`None` for the displays with no brightness controls)
+/// to match the relative luminance of the display at `reference_index`.
+///
+/// Prefers the monitor's DDC/CI brightness level (accounting for the display's gamma ramp),
+/// and falls back to scaling the gamma ramp (in linear light) otherwise.
+/// Skips the reference display and the displays with no brightness controls.
+///
+/// Returns an error if `reference_index` is out of bounds or the reference display's brightness is unknown.
+pub(crate) fn brightness_adjustments(
+    brightness: &[Option<DisplayBrightness>],
+    reference_index: usize,
+    index_generation: u64,
+) -> Result<Vec<BrightnessAdjustment>, ()> {
+    let reference = brightness.get(reference_index).ok_or(())?.ok_or(())?;
+    let target = reference.luminance();
+
+    Ok(brightness
+        .iter()
+        .enumerate()
+        .filter(|&(display_index, _)| display_index != reference_index)
+        .filter_map(|(display_index, brightness)| {
+            let brightness = (*brightness)?;
+            let current = brightness.luminance();
+
+            let (setting, target) = match brightness.ddc {
+                Some(ddc) => {
+                    let gamma_ramp_scale = brightness.gamma_ramp_scale.unwrap_or(1.0);
+                    let fraction = if gamma_ramp_scale > 0.0 {
+                        target / gamma_ramp_scale
+                    } else {
+                        1.0
+                    };
+                    let level = ddc.level(fraction);
+
+                    (
+                        BrightnessSetting::Ddc(level),
+                        BrightnessLevel {
+                            current: level,
+                            ..ddc
+                        }
+                        .fraction()
+                            * gamma_ramp_scale,
+                    )
+                }
+                None => {
+                    let scale = target.clamp(MIN_GAMMA_RAMP_SCALE, 1.0);
+                    (BrightnessSetting::GammaRamp(scale), scale)
+                }
+            };
+
+            Some(BrightnessAdjustment {
+                display_index: DisplayIndex::new(display_index as u32, index_generation),
+                current,
+                target,
+                setting,
+            })
+        })
+        .collect())
+}
+
+/// Returns the sequence of the DDC/CI brightness levels to pulse the monitor with the brightness `level` through
+/// to make it stand out (see [`Displays::identify_via_ddc`]), ending with its current level.
+///
+/// Alternates the current level with the end of the monitor's brightness range farther from it.
+///
+/// [`Displays::identify_via_ddc`]: struct.Displays.html#method.identify_via_ddc
+pub(crate) fn identify_pulse(level: BrightnessLevel) -> Vec<u32> {
+    const NUM_PULSES: usize = 3;
+
+    let current = level.current.clamp(level.min, level.max);
+    let pulse = if current - level.min > level.max - current {
+        level.min
+    } else {
+        level.max
+    };
+
+    [pulse, current].repeat(NUM_PULSES)
+}
+
+/// Returns the (linear, per-channel) gamma ramp which scales the display's output to the relative luminance `scale`.
+pub(crate) fn gamma_ramp(scale: f32) -> Vec<u16> {
+    // Scaling the luminance scales the (gamma-encoded) ramp values by its inverse power.
+    let scale = scale.clamp(0.0, 1.0).powf(1.0 / GAMMA);
+
+    let channel = (0..GAMMA_RAMP_SIZE)
+        .map(|index| (index as f32 * 257.0 * scale).round() as u16)
+        .collect::<Vec<_>>();
+
+    channel.repeat(3)
+}
+
+/// Returns the relative luminance the (per-channel) gamma `ramp` scales the display's output to,
+/// or `None` if the `ramp` is malformed.
+#[cfg_attr(not(windows), allow(dead_code))]
+pub(crate) fn gamma_ramp_scale(ramp: &[u16]) -> Option<f32> {
+    if ramp.len() != GAMMA_RAMP_SIZE * 3 {
+        return None;
+    }
+
+    let max = ramp
+        .chunks(GAMMA_RAMP_SIZE)
+        .map(|channel| channel[GAMMA_RAMP_SIZE - 1])
+        .max()?;
+
+    Some((max as f32 / u16::MAX as f32).powf(GAMMA))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ddc(current: u32) -> BrightnessLevel {
+        BrightnessLevel {
+            current,
+            min: 0,
+            max: 100,
+        }
+    }
+
+    #[test]
+    fn adaptive_brightness() {
+        let state = |on_battery| AdaptiveBrightness {
+            enabled_on_ac: false,
+            enabled_on_battery: true,
+            on_battery,
+        };
+
+        assert!(state(Some(true)).is_active());
+        assert!(!state(Some(false)).is_active());
+        assert!(state(None).is_active());
+        assert_eq!(
+            state(Some(false)).to_string(),
+            "adaptive brightness inactive"
+        );
+
+        assert!(!AdaptiveBrightness {
+            enabled_on_battery: false,
+            ..state(None)
+        }
+        .is_active());
+    }
+
+    #[test]
+    fn gamma_ramp() {
+        let identity = super::gamma_ramp(1.0);
+        assert_eq!(identity.len(), GAMMA_RAMP_SIZE * 3);
+        assert_eq!(identity[0], 0);
+        assert_eq!(identity[GAMMA_RAMP_SIZE - 1], u16::MAX);
+        assert_eq!(gamma_ramp_scale(&identity), Some(1.0));
+
+        let dimmed = super::gamma_ramp(0.5);
+        assert!((gamma_ramp_scale(&dimmed).unwrap() - 0.5).abs() < 0.001);
+        // Gamma-correct - half the luminance is ~73% of the encoded value.
+        assert_eq!(dimmed[GAMMA_RAMP_SIZE - 1], 47_824);
+
+        assert_eq!(gamma_ramp_scale(&identity[1..]), None);
+    }
+
+    #[test]
+    fn identify_pulse() {
+        assert_eq!(super::identify_pulse(ddc(30)), [100, 30, 100, 30, 100, 30]);
+        assert_eq!(super::identify_pulse(ddc(80)), [0, 80, 0, 80, 0, 80]);
+        assert_eq!(super::identify_pulse(ddc(50)), [100, 50, 100, 50, 100, 50]);
+
+        // Out of range current level is clamped.
+        assert_eq!(
+            super::identify_pulse(BrightnessLevel {
+                current: 120,
+                min: 0,
+                max: 100
+            }),
+            [0, 100, 0, 100, 0, 100]
+        );
+    }
+
+    #[test]
+    fn brightness_adjustments() {
+        let brightness = [
+            // Reference - DDC/CI at 60%, identity gamma ramp.
+            Some(DisplayBrightness {
+                ddc: Some(ddc(60)),
+                gamma_ramp_scale: Some(1.0),
+            }),
+            // DDC/CI with a different range.
+            Some(DisplayBrightness {
+                ddc: Some(BrightnessLevel {
+                    current: 50,
+                    min: 0,
+                    max: 50,
+                }),
+                gamma_ramp_scale: None,
+            }),
+            // No brightness controls.
+            None,
+            // Gamma ramp only.
+            Some(DisplayBrightness {
+                ddc: None,
+                gamma_ramp_scale: Some(1.0),
+            }),
+            // DDC/CI with a dimmed gamma ramp.
+            Some(DisplayBrightness {
+                ddc: Some(ddc(100)),
+                gamma_ramp_scale: Some(0.75),
+            }),
+        ];
+
+        assert_eq!(
+            super::brightness_adjustments(&brightness, 0, 0),
+            Ok(vec![
+                BrightnessAdjustment {
+                    display_index: DisplayIndex::new(1, 0),
+                    current: 1.0,
+                    target: 0.6,
+                    setting: BrightnessSetting::Ddc(30),
+                },
+                BrightnessAdjustment {
+                    display_index: DisplayIndex::new(3, 0),
+                    current: 1.0,
+                    target: 0.6,
+                    setting: BrightnessSetting::GammaRamp(0.6),
+                },
+                BrightnessAdjustment {
+                    display_index: DisplayIndex::new(4, 0),
+                    current: 0.75,
+                    target: 0.6,
+                    setting: BrightnessSetting::Ddc(80),
+                },
+            ])
+        );
+
+        // Matching the display with the dimmed gamma ramp.
+        let adjustments = super::brightness_adjustments(&brightness, 4, 0).unwrap();
+        assert_eq!(adjustments[0].setting, BrightnessSetting::Ddc(75));
+        assert_eq!(adjustments[1].setting, BrightnessSetting::Ddc(38));
+        assert_eq!(adjustments[2].setting, BrightnessSetting::GammaRamp(0.75));
+
+        // The gamma ramp is never scaled too low.
+
+        let brightness = [
+            Some(DisplayBrightness {
+                ddc: Some(ddc(10)),
+                gamma_ramp_scale: None,
+            }),
+            Some(DisplayBrightness {
+                ddc: None,
+                gamma_ramp_scale: Some(1.0),
+            }),
+        ];
+        assert_eq!(
+            super::brightness_adjustments(&brightness, 0, 0).unwrap()[0].setting,
+            BrightnessSetting::GammaRamp(MIN_GAMMA_RAMP_SCALE)
+        );
+
+        assert_eq!(
+            super::brightness_adjustments(&[None, brightness[0]], 0, 0),
+            Err(())
+        );
+        assert_eq!(super::brightness_adjustments(&brightness, 2, 0), Err(()));
+    }
+}
@@ -0,0 +1,305 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::{DisplayBackend, DisplayEvent, Displays, DisplaysSnapshot, Error, PollingWatcher};
+
+/// Caches the enumerated [`display info`] for a configurable amount of time,
+/// so that it may be cheaply queried as often as necessary.
+///
+/// Display enumeration is relatively expensive, and the system's displays rarely change,
+/// so prefer this to calling [`enumerate_displays`] every time the display info is needed.
+///
+/// The cached display info is re-enumerated on next access when it gets older than the `max_age`,
+/// or when explicitly [`invalidated`] (e.g. by the application's display change event handler,
+/// like on `WM_DISPLAYCHANGE` / `WM_DPICHANGED`, and `WM_SETTINGCHANGE` with `SPI_SETWORKAREA` on Windows,
+/// or by the [`polling watcher`] started with [`watch`]).
+///
+/// [`display info`]: struct.Displays.html
+/// [`enumerate_displays`]: struct.Displays.html#method.enumerate_displays
+/// [`invalidated`]: #method.invalidate
+/// [`polling watcher`]: struct.PollingWatcher.html
+/// [`watch`]: #method.watch
+pub struct CachedDisplays {
+    max_age: Duration,
+    backend: Option<Arc<dyn DisplayBackend>>,
+    state: Mutex<CacheState>,
+}
+
+#[derive(Default)]
+struct CacheState {
+    cached: Option<CachedSnapshot>,
+    /// Incremented on each invalidation, so that the display info enumerated before it is not cached.
+    invalidations: u64,
+}
+
+struct CachedSnapshot {
+    snapshot: Arc<DisplaysSnapshot>,
+    enumerated_at: Instant,
+}
+
+impl CachedDisplays {
+    /// Creates a new, empty display info cache.
+    /// Cached display info is re-enumerated when it gets older than `max_age`.
+    ///
+    /// Pass `Duration::MAX` to only re-enumerate the display info when explicitly [`invalidated`].
+    ///
+    /// NOTE: display info is lazily enumerated on first call to [`get`].
+    ///
+    /// [`invalidated`]: #method.invalidate
+    /// [`get`]: #method.get
+    pub fn new(max_age: Duration) -> Self {
+        Self {
+            max_age,
+            backend: None,
+            state: Mutex::new(CacheState::default()),
+        }
+    }
+
+    /// Creates a new, empty display info cache which enumerates the displays via provided [`display backend`].
+    ///
+    /// See [`new`].
+    ///
+    /// [`display backend`]: trait.DisplayBackend.html
+    /// [`new`]: #method.new
+    pub fn with_backend(max_age: Duration, backend: Arc<dyn DisplayBackend>) -> Self {
+        Self {
+            backend: Some(backend),
+            ..Self::new(max_age)
+        }
+    }
+
+    /// Returns the max age of the cached display info.
+    pub fn max_age(&self) -> Duration {
+        self.max_age
+    }
+
+    /// Sets the max age of the cached display info.
+    /// Takes effect on next call to [`get`].
+    ///
+    /// [`get`]: #method.get
+    pub fn set_max_age(&mut self, max_age: Duration) {
+        self.max_age = max_age;
+    }
+
+    /// Returns the [`snapshot`] of the cached display info,
+    /// re-enumerating the displays first if the cached display info is missing, too old or was invalidated.
+    ///
+    /// Returned snapshot is immutable and is not affected by the following re-enumerations.
+    ///
+    /// NOTE: the displays are enumerated without holding the cache lock, so the threads concurrently calling this
+    /// on a missing / outdated cache may each re-enumerate the displays; the most recent enumeration is cached.
+    ///
+    /// [`snapshot`]: struct.DisplaysSnapshot.html
+    pub fn get(&self) -> Result<Arc<DisplaysSnapshot>, Error> {
+        let invalidations = {
+            let state = self.lock();
+
+            if let Some(cached) = state.cached.as_ref() {
+                if cached.enumerated_at.elapsed() < self.max_age {
+                    return Ok(cached.snapshot.clone());
+                }
+            }
+
+            state.invalidations
+        };
+
+        let enumerated_at = Instant::now();
+
+        let mut displays = match self.backend.as_ref() {
+            Some(backend) => Displays::with_backend(backend.clone()),
+            None => Displays::new(),
+        };
+        displays.enumerate_displays()?;
+        let snapshot = displays.snapshot();
+
+        let mut state = self.lock();
+
+        // Don't cache the display info if the cache was invalidated while enumerating,
+        // or if another thread has cached the more recently enumerated display info in the meantime.
+        if state.invalidations == invalidations
+            && state
+                .cached
+                .as_ref()
+                .is_none_or(|cached| cached.enumerated_at < enumerated_at)
+        {
+            state.cached.replace(CachedSnapshot {
+                snapshot: snapshot.clone(),
+                enumerated_at,
+            });
+        }
+
+        Ok(snapshot)
+    }
+
+    /// Calls `f` with the [`snapshot`] of the cached display info
+    /// (re-enumerating the displays first, same as [`get`]) and returns its result.
+    ///
+    /// All queries made through the snapshot within `f` see the same display enumeration,
+    /// even if the cache is invalidated / the displays are re-enumerated by other threads in the meantime,
+    /// avoiding the torn reads between the separate [`get`] calls.
+    ///
+    /// [`snapshot`]: struct.DisplaysSnapshot.html
+    /// [`get`]: #method.get
+    pub fn with_snapshot<F, R>(&self, f: F) -> Result<R, Error>
+    where
+        F: FnOnce(&DisplaysSnapshot) -> R,
+    {
+        let snapshot = self.get()?;
+
+        Ok(f(&snapshot))
+    }
+
+    /// Invalidates the cached display info, so that it is re-enumerated on next call to [`get`].
+    ///
+    /// Call this when notified about display configuration changes.
+    ///
+    /// [`get`]: #method.get
+    pub fn invalidate(&self) {
+        let mut state = self.lock();
+
+        state.cached.take();
+        state.invalidations += 1;
+    }
+
+    /// Starts the [`polling watcher`] which [`invalidates`] the `cache` whenever it detects a display change,
+    /// for the applications without a window / message loop to handle the display change notifications in.
+    ///
+    /// The displays are polled every `interval` and fully re-enumerated at least every [`max_age`] of the cache
+    /// (see [`PollingWatcher::new`]), via the cache's display backend, if any.
+    /// The watcher does not keep the `cache` alive.
+    ///
+    /// [`polling watcher`]: struct.PollingWatcher.html
+    /// [`invalidates`]: #method.invalidate
+    /// [`max_age`]: #method.max_age
+    /// [`PollingWatcher::new`]: struct.PollingWatcher.html#method.new
+    pub fn watch(cache: &Arc<Self>, interval: Duration) -> Result<PollingWatcher, Error> {
+        let weak_cache = Arc::downgrade(cache);
+
+        let invalidate = move |_: &DisplayEvent, _: &Displays| {
+            if let Some(cache) = weak_cache.upgrade() {
+                cache.invalidate();
+            }
+        };
+
+        match cache.backend.as_ref() {
+            Some(backend) => {
+                PollingWatcher::with_backend(interval, cache.max_age(), backend.clone(), invalidate)
+            }
+            None => PollingWatcher::new(interval, cache.max_age(), invalidate),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CacheState> {
+        // Cached display info is replaced as a whole, so a panic cannot leave it in an inconsistent state.
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{dual_mixed_dpi, single_1080p, triple_surround, FixtureBackend};
+
+    #[test]
+    fn with_snapshot() {
+        let backend = Arc::new(FixtureBackend::new(single_1080p()));
+        let cache = CachedDisplays::with_backend(Duration::MAX, backend.clone());
+
+        let (num_displays, adjacent) = cache
+            .with_snapshot(|snapshot| {
+                // Re-enumerate the displays while the snapshot is in use.
+                backend.set(dual_mixed_dpi());
+                cache.invalidate();
+                assert_eq!(cache.get().unwrap().num_displays(), 2);
+
+                (
+                    snapshot.num_displays(),
+                    snapshot.adjacency_info(0).unwrap().right,
+                )
+            })
+            .unwrap();
+
+        assert_eq!(num_displays, 1);
+        assert_eq!(adjacent, None);
+
+        assert_eq!(
+            cache.with_snapshot(|snapshot| snapshot.num_displays()),
+            Ok(2)
+        );
+    }
+
+    #[test]
+    fn max_age() {
+        let backend = Arc::new(FixtureBackend::new(single_1080p()));
+        let mut cache = CachedDisplays::with_backend(Duration::MAX, backend.clone());
+
+        // Lazily enumerated on first access, then cached.
+        let snapshot = cache.get().unwrap();
+        assert_eq!(snapshot.num_displays(), 1);
+        backend.set(dual_mixed_dpi());
+        assert!(Arc::ptr_eq(&cache.get().unwrap(), &snapshot));
+
+        cache.set_max_age(Duration::ZERO);
+
+        // Re-enumerated on each access.
+        assert_eq!(cache.get().unwrap().num_displays(), 2);
+        backend.set(triple_surround());
+        assert_eq!(cache.get().unwrap().num_displays(), 3);
+
+        cache.set_max_age(Duration::from_millis(50));
+        std::thread::sleep(Duration::from_millis(60));
+
+        let snapshot = cache.get().unwrap();
+        assert_eq!(snapshot.num_displays(), 3);
+        backend.set(single_1080p());
+        assert!(Arc::ptr_eq(&cache.get().unwrap(), &snapshot));
+
+        std::thread::sleep(Duration::from_millis(60));
+
+        assert_eq!(cache.get().unwrap().num_displays(), 1);
+        // Previously returned snapshots are not affected.
+        assert_eq!(snapshot.num_displays(), 3);
+    }
+
+    #[test]
+    fn invalidate() {
+        let backend = Arc::new(FixtureBackend::new(single_1080p()));
+        let cache = CachedDisplays::with_backend(Duration::MAX, backend.clone());
+
+        // Invalidating the empty cache is a no-op.
+        cache.invalidate();
+
+        let snapshot = cache.get().unwrap();
+        assert_eq!(snapshot.num_displays(), 1);
+
+        backend.set(dual_mixed_dpi());
+        assert_eq!(cache.get().unwrap().num_displays(), 1);
+
+        cache.invalidate();
+
+        assert_eq!(cache.get().unwrap().num_displays(), 2);
+        assert_eq!(snapshot.num_displays(), 1);
+    }
+
+    #[test]
+    fn watch() {
+        let backend = Arc::new(FixtureBackend::new(single_1080p()));
+        let cache = Arc::new(CachedDisplays::with_backend(Duration::MAX, backend.clone()));
+
+        assert_eq!(cache.get().unwrap().num_displays(), 1);
+
+        // Polls the cache's backend.
+        let _watcher = CachedDisplays::watch(&cache, Duration::from_millis(10)).unwrap();
+
+        backend.set(dual_mixed_dpi());
+
+        let start = Instant::now();
+
+        while cache.get().unwrap().num_displays() != 2 {
+            assert!(start.elapsed() < Duration::from_secs(5));
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+}
@@ -0,0 +1,176 @@
+/// Point in the CIE 1931 xy chromaticity diagram.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Chromaticity {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Chromaticity {
+    pub const fn new(x: f32, y: f32) -> Self {
+        Self { x, y }
+    }
+}
+
+/// Color primaries and the white point of a display / color space.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Colorimetry {
+    pub red: Chromaticity,
+    pub green: Chromaticity,
+    pub blue: Chromaticity,
+    pub white: Chromaticity,
+}
+
+impl Colorimetry {
+    /// sRGB / Rec. 709 primaries, D65 white point.
+    pub const SRGB: Self = Self {
+        red: Chromaticity::new(0.64, 0.33),
+        green: Chromaticity::new(0.30, 0.60),
+        blue: Chromaticity::new(0.15, 0.06),
+        white: Chromaticity::new(0.3127, 0.3290),
+    };
+
+    /// DCI-P3 primaries, D65 white point (i.e. Display P3).
+    pub const DCI_P3: Self = Self {
+        red: Chromaticity::new(0.680, 0.320),
+        green: Chromaticity::new(0.265, 0.690),
+        blue: Chromaticity::new(0.150, 0.060),
+        white: Chromaticity::new(0.3127, 0.3290),
+    };
+
+    /// Returns the fraction in `[0.0 .. 1.0]` range of the sRGB gamut covered by this gamut
+    /// (e.g. `~0.99` for a typical wide gamut display), in the CIE 1931 xy chromaticity diagram.
+    pub fn srgb_coverage(&self) -> f32 {
+        self.coverage(&Self::SRGB)
+    }
+
+    /// Returns the fraction in `[0.0 .. 1.0]` range of the DCI-P3 gamut covered by this gamut
+    /// (e.g. `~0.74` for a typical sRGB display), in the CIE 1931 xy chromaticity diagram.
+    pub fn dci_p3_coverage(&self) -> f32 {
+        self.coverage(&Self::DCI_P3)
+    }
+
+    /// Returns the fraction in `[0.0 .. 1.0]` range of the `other` gamut covered by this gamut,
+    /// in the CIE 1931 xy chromaticity diagram.
+    pub fn coverage(&self, other: &Colorimetry) -> f32 {
+        let (gamut, other_gamut) = (self.gamut(), other.gamut());
+        let other_area = area(&other_gamut);
+
+        if area(&gamut) <= 0.0 || other_area <= 0.0 {
+            return 0.0;
+        }
+
+        let covered = clip(&other_gamut, &gamut);
+
+        (area(&covered) / other_area).clamp(0.0, 1.0)
+    }
+
+    /// Returns the gamut triangle, counter-clockwise.
+    fn gamut(&self) -> Vec<Chromaticity> {
+        let triangle = vec![self.red, self.green, self.blue];
+
+        if signed_area(&triangle) < 0.0 {
+            triangle.into_iter().rev().collect()
+        } else {
+            triangle
+        }
+    }
+}
+
+/// Returns the signed area of the `polygon` - positive if it's counter-clockwise.
+fn signed_area(polygon: &[Chromaticity]) -> f32 {
+    let len = polygon.len();
+
+    (0..len)
+        .map(|i| {
+            let (p, q) = (polygon[i], polygon[(i + 1) % len]);
+            p.x * q.y - q.x * p.y
+        })
+        .sum::<f32>()
+        / 2.0
+}
+
+fn area(polygon: &[Chromaticity]) -> f32 {
+    signed_area(polygon).abs()
+}
+
+/// Clips the `polygon` by the convex counter-clockwise `clipper` polygon (Sutherland-Hodgman).
+fn clip(polygon: &[Chromaticity], clipper: &[Chromaticity]) -> Vec<Chromaticity> {
+    // Positive if `p` is to the left of (i.e. inside) the `a` -> `b` edge.
+    let side = |a: Chromaticity, b: Chromaticity, p: Chromaticity| {
+        (b.x - a.x) * (p.y - a.y) - (b.y - a.y) * (p.x - a.x)
+    };
+
+    let mut output = polygon.to_vec();
+
+    for i in 0..clipper.len() {
+        let (a, b) = (clipper[i], clipper[(i + 1) % clipper.len()]);
+
+        let input = std::mem::take(&mut output);
+
+        for j in 0..input.len() {
+            let (p, q) = (input[j], input[(j + 1) % input.len()]);
+            let (side_p, side_q) = (side(a, b, p), side(a, b, q));
+
+            if side_p >= 0.0 {
+                output.push(p);
+            }
+
+            // Crosses the edge.
+            if (side_p >= 0.0) != (side_q >= 0.0) {
+                let t = side_p / (side_p - side_q);
+                output.push(Chromaticity::new(
+                    p.x + t * (q.x - p.x),
+                    p.y + t * (q.y - p.y),
+                ));
+            }
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn coverage() {
+        let srgb = Colorimetry::SRGB;
+        let p3 = Colorimetry::DCI_P3;
+
+        assert_eq!(srgb.srgb_coverage(), 1.0);
+        assert_eq!(p3.srgb_coverage(), 1.0);
+        assert_eq!(p3.dci_p3_coverage(), 1.0);
+        assert_eq!((srgb.dci_p3_coverage() * 100.0).round(), 74.0);
+
+        // Winding order does not matter.
+        let reversed = Colorimetry {
+            red: srgb.blue,
+            blue: srgb.red,
+            ..srgb
+        };
+        assert_eq!(reversed.srgb_coverage(), 1.0);
+
+        // Typical "72% NTSC" office display - green is short of sRGB's.
+        let office = Colorimetry {
+            red: Chromaticity::new(0.64, 0.33),
+            green: Chromaticity::new(0.32, 0.56),
+            blue: Chromaticity::new(0.15, 0.06),
+            white: Colorimetry::SRGB.white,
+        };
+        let coverage = office.srgb_coverage();
+        assert!(coverage > 0.85 && coverage < 1.0);
+
+        // Degenerate.
+        let point = Colorimetry {
+            red: srgb.white,
+            green: srgb.white,
+            blue: srgb.white,
+            white: srgb.white,
+        };
+        assert_eq!(point.srgb_coverage(), 0.0);
+        assert_eq!(srgb.coverage(&point), 0.0);
+    }
+}
@@ -0,0 +1,169 @@
+use std::fmt::{Display, Formatter};
+
+use crate::{Position, Rectangle};
+
+/// Describes where the origin of the display space coordinates is.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CoordinateSpace {
+    /// The origin is at the top left corner of the primary display (used by the OS on Windows).
+    /// Displays to the left of / above the primary display have negative coordinates.
+    PrimaryOrigin,
+    /// The origin is at the top left corner of the virtual desktop (i.e. the bounding rectangle of all displays),
+    /// as used by some engines / frameworks.
+    /// All displays have non-negative coordinates.
+    VirtualDesktopOrigin,
+}
+
+impl Display for CoordinateSpace {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use CoordinateSpace::*;
+
+        match self {
+            PrimaryOrigin => write!(f, "primary display origin"),
+            VirtualDesktopOrigin => write!(f, "virtual desktop origin"),
+        }
+    }
+}
+
+/// DPI awareness of a process / thread, as returned by [`Displays::process_dpi_awareness`].
+///
+/// Display rectangles are always reported in physical pixels, while the OS virtualizes the coordinates
+/// (e.g. window rectangles) for the processes / threads which are not per-monitor DPI-aware
+/// (see [`Displays::coordinates_are_virtualized`]).
+///
+/// [`Displays::process_dpi_awareness`]: struct.Displays.html#method.process_dpi_awareness
+/// [`Displays::coordinates_are_virtualized`]: struct.Displays.html#method.coordinates_are_virtualized
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DpiAwareness {
+    /// DPI-unaware - the coordinates are virtualized (scaled) by the DPI scale on all displays.
+    Unaware,
+    /// System DPI-aware - the coordinates are virtualized on the displays with a DPI scale different from the system one.
+    System,
+    /// Per-monitor DPI-aware - the coordinates are not virtualized.
+    PerMonitor,
+    /// Per-monitor DPI-aware (v2, e.g. with the automatic non-client area scaling) - the coordinates are not virtualized.
+    PerMonitorV2,
+}
+
+impl DpiAwareness {
+    /// Returns `true` if the coordinates are not virtualized on any display.
+    pub fn is_per_monitor(self) -> bool {
+        matches!(self, DpiAwareness::PerMonitor | DpiAwareness::PerMonitorV2)
+    }
+}
+
+impl Display for DpiAwareness {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use DpiAwareness::*;
+
+        match self {
+            Unaware => write!(f, "DPI-unaware"),
+            System => write!(f, "system DPI-aware"),
+            PerMonitor => write!(f, "per-monitor DPI-aware"),
+            PerMonitorV2 => write!(f, "per-monitor DPI-aware (v2)"),
+        }
+    }
+}
+
+/// Values in display space which may be [`converted`] between the [`coordinate spaces`].
+///
+/// [`converted`]: struct.Displays.html#method.convert
+/// [`coordinate spaces`]: enum.CoordinateSpace.html
+pub trait Coordinates: Copy {
+    /// Returns the value moved by `left` / `top`, saturating at the `i32` range.
+    fn offset(self, left: i64, top: i64) -> Self;
+}
+
+impl Coordinates for Position {
+    fn offset(self, left: i64, top: i64) -> Self {
+        let saturate = |value: i64| value.clamp(i32::MIN as i64, i32::MAX as i64) as i32;
+
+        Position::new(
+            saturate(self.left as i64 + left),
+            saturate(self.top as i64 + top),
+        )
+    }
+}
+
+impl Coordinates for Rectangle {
+    fn offset(self, left: i64, top: i64) -> Self {
+        Rectangle::new(self.position.offset(left, top), self.dimensions)
+    }
+}
+
+/// Converts the `value` from the coordinate space `from` to the coordinate space `to`,
+/// given the top left corner of the virtual desktop (in primary display origin coordinates).
+pub(crate) fn convert<T: Coordinates>(
+    value: T,
+    from: CoordinateSpace,
+    to: CoordinateSpace,
+    virtual_desktop_origin: Position,
+) -> T {
+    // Origins of the coordinate spaces in the primary display origin coordinates.
+    let origin = |space| match space {
+        CoordinateSpace::PrimaryOrigin => Position::default(),
+        CoordinateSpace::VirtualDesktopOrigin => virtual_desktop_origin,
+    };
+
+    let (from, to) = (origin(from), origin(to));
+
+    value.offset(
+        from.left as i64 - to.left as i64,
+        from.top as i64 - to.top as i64,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Dimensions;
+
+    #[test]
+    fn convert() {
+        use CoordinateSpace::*;
+
+        // Secondary display to the left of and above the primary display.
+        let origin = Position::new(-1920, -360);
+
+        let point = Position::new(-100, 200);
+        let converted = super::convert(point, PrimaryOrigin, VirtualDesktopOrigin, origin);
+        assert_eq!(converted, Position::new(1820, 560));
+        assert_eq!(
+            super::convert(converted, VirtualDesktopOrigin, PrimaryOrigin, origin),
+            point
+        );
+        assert_eq!(
+            super::convert(point, PrimaryOrigin, PrimaryOrigin, origin),
+            point
+        );
+
+        let rect = Rectangle::new(Position::new(-1920, -360), Dimensions::new(1920, 1080));
+        assert_eq!(
+            super::convert(rect, PrimaryOrigin, VirtualDesktopOrigin, origin),
+            Rectangle::new(Position::new(0, 0), Dimensions::new(1920, 1080))
+        );
+
+        // Saturates.
+        assert_eq!(
+            super::convert(
+                Position::new(i32::MAX, 0),
+                PrimaryOrigin,
+                VirtualDesktopOrigin,
+                Position::new(i32::MIN, 0)
+            ),
+            Position::new(i32::MAX, 0)
+        );
+    }
+
+    #[test]
+    fn dpi_awareness() {
+        use DpiAwareness::*;
+
+        assert!(!Unaware.is_per_monitor());
+        assert!(!System.is_per_monitor());
+        assert!(PerMonitor.is_per_monitor());
+        assert!(PerMonitorV2.is_per_monitor());
+
+        assert_eq!(System.to_string(), "system DPI-aware");
+    }
+}
@@ -0,0 +1,28 @@
+use crate::Rectangle;
+
+#[cfg(windows)]
+use crate::win::CursorClipGuardWin as CursorClipGuardPlatform;
+
+#[cfg(not(windows))]
+use crate::stub::CursorClipGuardStub as CursorClipGuardPlatform;
+
+/// Confines the mouse cursor to a display's rectangle while alive,
+/// restoring the previous cursor clip rectangle when dropped.
+///
+/// Created by [`Displays::confine_cursor`].
+///
+/// NOTE - the cursor clip rectangle is global (not per-thread or per-window),
+/// and the OS may reset it at any time (e.g. when the foreground window changes),
+/// so the cursor should be re-confined when the application's window is (re)activated.
+///
+/// [`Displays::confine_cursor`]: struct.Displays.html#method.confine_cursor
+pub struct CursorClipGuard {
+    // Only held to restore the previous cursor clip rectangle when dropped.
+    _platform: CursorClipGuardPlatform,
+}
+
+impl CursorClipGuard {
+    pub(crate) fn new(rect: &Rectangle) -> Result<Self, ()> {
+        CursorClipGuardPlatform::new(rect).map(|_platform| Self { _platform })
+    }
+}
@@ -0,0 +1,310 @@
+use std::fmt::{Display, Formatter};
+
+/// MCCS VCP code of the contrast control.
+pub(crate) const VCP_CONTRAST: u8 = 0x12;
+/// MCCS VCP code of the input source select control.
+pub(crate) const VCP_INPUT_SOURCE: u8 = 0x60;
+/// MCCS VCP codes of the red / green / blue video black level controls.
+pub(crate) const VCP_BLACK_LEVEL: [u8; 3] = [0x6c, 0x6e, 0x70];
+/// MCCS VCP code of the display usage time (power-on hours) counter.
+pub(crate) const VCP_USAGE_HOURS: u8 = 0xc0;
+
+/// Current and maximum values of a monitor's continuous control (e.g. contrast), as read via DDC/CI.
+///
+/// The minimum value is always `0`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct VcpValue {
+    pub current: u32,
+    pub max: u32,
+}
+
+/// Physical monitor behind a display, as returned by [`Displays::physical_monitors`].
+///
+/// A display usually has a single physical monitor, but may have more (e.g. in the clone mode, or behind some docks).
+///
+/// [`Displays::physical_monitors`]: struct.Displays.html#method.physical_monitors
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PhysicalMonitor {
+    /// Index of the physical monitor among the display's physical monitors,
+    /// as passed to e.g. [`Displays::physical_monitor_vcp_feature`].
+    ///
+    /// [`Displays::physical_monitor_vcp_feature`]: struct.Displays.html#method.physical_monitor_vcp_feature
+    pub index: u32,
+    /// Physical monitor's description, as reported by the OS (often generic, e.g. `Generic PnP Monitor`).
+    pub description: String,
+}
+
+/// Monitor's video input source, as read / selected via DDC/CI
+/// (see [`Displays::input_source`] / [`Displays::set_input_source`]).
+///
+/// [`Displays::input_source`]: struct.Displays.html#method.input_source
+/// [`Displays::set_input_source`]: struct.Displays.html#method.set_input_source
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum InputSource {
+    Vga1,
+    Vga2,
+    Dvi1,
+    Dvi2,
+    Composite1,
+    Composite2,
+    SVideo1,
+    SVideo2,
+    Tuner1,
+    Tuner2,
+    Tuner3,
+    Component1,
+    Component2,
+    Component3,
+    DisplayPort1,
+    DisplayPort2,
+    Hdmi1,
+    Hdmi2,
+    /// Input source with a (vendor-specific) MCCS value not listed above (e.g. `USB-C` on some monitors).
+    Other(u8),
+}
+
+/// Input sources with the standard MCCS values, in the order of their values (starting with `1`).
+const INPUT_SOURCES: [InputSource; 18] = [
+    InputSource::Vga1,
+    InputSource::Vga2,
+    InputSource::Dvi1,
+    InputSource::Dvi2,
+    InputSource::Composite1,
+    InputSource::Composite2,
+    InputSource::SVideo1,
+    InputSource::SVideo2,
+    InputSource::Tuner1,
+    InputSource::Tuner2,
+    InputSource::Tuner3,
+    InputSource::Component1,
+    InputSource::Component2,
+    InputSource::Component3,
+    InputSource::DisplayPort1,
+    InputSource::DisplayPort2,
+    InputSource::Hdmi1,
+    InputSource::Hdmi2,
+];
+
+impl InputSource {
+    /// Returns the input source for the (current value of the) input source select VCP feature.
+    pub(crate) fn from_vcp(value: u32) -> Self {
+        // Only the low byte is significant.
+        let value = value as u8;
+
+        match value {
+            1..=18 => INPUT_SOURCES[value as usize - 1],
+            value => InputSource::Other(value),
+        }
+    }
+
+    /// Returns the value of the input source select VCP feature which selects this input source.
+    pub(crate) fn to_vcp(self) -> u32 {
+        match self {
+            InputSource::Other(value) => value as u32,
+            input_source => {
+                INPUT_SOURCES
+                    .iter()
+                    .position(|&source| source == input_source)
+                    .expect("Expected a standard input source.") as u32
+                    + 1
+            }
+        }
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use InputSource::*;
+
+        match self {
+            Vga1 => write!(f, "VGA 1"),
+            Vga2 => write!(f, "VGA 2"),
+            Dvi1 => write!(f, "DVI 1"),
+            Dvi2 => write!(f, "DVI 2"),
+            Composite1 => write!(f, "Composite 1"),
+            Composite2 => write!(f, "Composite 2"),
+            SVideo1 => write!(f, "S-Video 1"),
+            SVideo2 => write!(f, "S-Video 2"),
+            Tuner1 => write!(f, "Tuner 1"),
+            Tuner2 => write!(f, "Tuner 2"),
+            Tuner3 => write!(f, "Tuner 3"),
+            Component1 => write!(f, "Component 1"),
+            Component2 => write!(f, "Component 2"),
+            Component3 => write!(f, "Component 3"),
+            DisplayPort1 => write!(f, "DisplayPort 1"),
+            DisplayPort2 => write!(f, "DisplayPort 2"),
+            Hdmi1 => write!(f, "HDMI 1"),
+            Hdmi2 => write!(f, "HDMI 2"),
+            Other(value) => write!(f, "input 0x{:02x}", value),
+        }
+    }
+}
+
+/// Returns the input sources listed for the input source select VCP feature
+/// in the monitor's DDC/CI `capabilities` string (e.g. `(prot(monitor)vcp(10 12 60(0F 11 12))mccs_ver(2.1))`),
+/// in the order they are listed.
+///
+/// Returns `None` if the capabilities string does not list the input source select VCP feature or its values.
+pub(crate) fn parse_input_sources(capabilities: &str) -> Option<Vec<InputSource>> {
+    let vcp = find_capability(capabilities, "vcp")?;
+    let values = find_vcp_values(vcp, VCP_INPUT_SOURCE)?;
+
+    let mut input_sources = Vec::new();
+
+    for value in values.split_whitespace() {
+        let input_source = InputSource::from_vcp(u32::from_str_radix(value, 16).ok()?);
+
+        if !input_sources.contains(&input_source) {
+            input_sources.push(input_source);
+        }
+    }
+
+    Some(input_sources)
+}
+
+/// Returns the contents (between the parentheses) of the capability with the `name` (e.g. `vcp`)
+/// in the monitor's DDC/CI `capabilities` string, if any.
+fn find_capability<'c>(capabilities: &'c str, name: &str) -> Option<&'c str> {
+    let mut depth = 0;
+    // Start of the current identifier, if any.
+    let mut identifier_start = 0;
+
+    for (index, c) in capabilities.char_indices() {
+        match c {
+            '(' => {
+                // Top level capabilities may or may not be enclosed in parentheses.
+                if depth <= 1 && capabilities[identifier_start..index].eq_ignore_ascii_case(name) {
+                    return group_contents(&capabilities[index..]);
+                }
+
+                depth += 1;
+                identifier_start = index + 1;
+            }
+            ')' => {
+                depth -= 1;
+                identifier_start = index + 1;
+            }
+            c if c.is_ascii_alphanumeric() || c == '_' => {}
+            c => identifier_start = index + c.len_utf8(),
+        }
+    }
+
+    None
+}
+
+/// Returns the values (between the parentheses) listed for the VCP feature `code`
+/// in the contents of the `vcp` capability (e.g. `10 12 60(0F 11 12)`), if any.
+fn find_vcp_values(vcp: &str, code: u8) -> Option<&str> {
+    let mut rest = vcp.trim_start();
+
+    while !rest.is_empty() {
+        let end = rest
+            .find(|c: char| c.is_whitespace() || c == '(')
+            .unwrap_or(rest.len());
+        let (value, after) = rest.split_at(end);
+        let after = after.trim_start();
+
+        let (values, after) = if after.starts_with('(') {
+            let values = group_contents(after)?;
+            // Skip the values and the parentheses.
+            (Some(values), &after[values.len() + 2..])
+        } else {
+            (None, after)
+        };
+
+        if u8::from_str_radix(value, 16).ok() == Some(code) {
+            return values;
+        }
+
+        rest = after.trim_start();
+    }
+
+    None
+}
+
+/// Returns the contents of the (possibly nested) parenthesized group at the start of the `string`.
+fn group_contents(string: &str) -> Option<&str> {
+    debug_assert!(string.starts_with('('));
+
+    let mut depth = 0;
+
+    for (index, c) in string.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+
+                if depth == 0 {
+                    return Some(&string[1..index]);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn input_source() {
+        assert_eq!(InputSource::from_vcp(0x0f), InputSource::DisplayPort1);
+        assert_eq!(InputSource::from_vcp(0x11), InputSource::Hdmi1);
+        assert_eq!(InputSource::from_vcp(0x1b), InputSource::Other(0x1b));
+        assert_eq!(InputSource::from_vcp(0), InputSource::Other(0));
+        // High byte ignored.
+        assert_eq!(InputSource::from_vcp(0x0112), InputSource::Hdmi2);
+
+        assert_eq!(InputSource::Vga1.to_vcp(), 0x01);
+        assert_eq!(InputSource::Hdmi1.to_vcp(), 0x11);
+        assert_eq!(InputSource::Other(0x1b).to_vcp(), 0x1b);
+
+        for value in 0..=0xff {
+            assert_eq!(InputSource::from_vcp(value).to_vcp(), value);
+        }
+    }
+
+    #[test]
+    fn parse_input_sources() {
+        let capabilities = "(prot(monitor)type(LCD)model(U2720Q)cmds(01 02 03 07 0C E3 F3)vcp(02 04 05 08 10 12 14(01 05 08 0B 0C) 16 18 1A 52 60( 0F 11 12 1B) AA(01 02 04) AC AE B2 B6 C6 C8 C9 D6(01 04 05) DC(00 02 03 05) DF E0 E1 E2(00 1D 02 04 0E 12 14 23 24 27) F0(0C) F1 F2 FD)mswhql(1)asset_eep(40)mccs_ver(2.1))";
+
+        assert_eq!(
+            super::parse_input_sources(capabilities),
+            Some(vec![
+                InputSource::DisplayPort1,
+                InputSource::Hdmi1,
+                InputSource::Hdmi2,
+                InputSource::Other(0x1b),
+            ])
+        );
+
+        // No outer parentheses, lowercase, `vcpname` capability before the `vcp` one, duplicates.
+        assert_eq!(
+            super::parse_input_sources(
+                "prot(monitor) vcpname(60(Input)) vcp(10 12 60(01 03 03)) mccs_ver(2.2)"
+            ),
+            Some(vec![InputSource::Vga1, InputSource::Dvi1])
+        );
+
+        // No input source values.
+        assert_eq!(
+            super::parse_input_sources("(prot(monitor)vcp(10 12 60)mccs_ver(2.1))"),
+            None
+        );
+        // No input source select.
+        assert_eq!(
+            super::parse_input_sources("(prot(monitor)vcp(10 12 14(05 08))mccs_ver(2.1))"),
+            None
+        );
+        // No VCP capability.
+        assert_eq!(super::parse_input_sources("(prot(monitor))"), None);
+        // Malformed.
+        assert_eq!(
+            super::parse_input_sources("(prot(monitor)vcp(10 60(0F 11"),
+            None
+        );
+    }
+}
@@ -0,0 +1,410 @@
+use std::fmt::{Display, Formatter};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+#[cfg(not(feature = "no-modes"))]
+use crate::ModeValidation;
+
+use crate::{
+    DisplayError, DisplayIndex, DisplayMode, Displays, Orientation, PlatformInfo, Position,
+    StaleIndex,
+};
+
+#[cfg(windows)]
+use crate::win::{
+    apply_display_config_win as apply_display_config_platform,
+    DisplayConfigSnapshotWin as DisplayConfigSnapshotPlatform,
+};
+
+#[cfg(not(windows))]
+use crate::stub::{
+    apply_display_config_stub as apply_display_config_platform,
+    DisplayConfigSnapshotStub as DisplayConfigSnapshotPlatform,
+};
+
+/// Describes how the system's desktop is projected onto the internal and external displays,
+/// like the `Win+P` OS UI on Windows.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Projection {
+    /// Only the internal display (e.g. a laptop's built-in panel) is used.
+    InternalOnly,
+    /// The internal display is duplicated onto the external displays.
+    Clone,
+    /// The desktop is extended across all displays.
+    Extend,
+    /// Only the external displays are used.
+    ExternalOnly,
+}
+
+impl Display for Projection {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use Projection::*;
+
+        match self {
+            InternalOnly => write!(f, "internal only"),
+            Clone => write!(f, "clone"),
+            Extend => write!(f, "extend"),
+            ExternalOnly => write!(f, "external only"),
+        }
+    }
+}
+
+/// Desired display configuration changes for a single display.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct DisplayConfigChange {
+    /// New display mode, if changed.
+    pub(crate) mode: Option<DisplayMode>,
+    /// New display position in the virtual desktop (before the new primary display, if any, is moved to the origin), if changed.
+    pub(crate) position: Option<Position>,
+    /// New display orientation, if changed.
+    pub(crate) orientation: Option<Orientation>,
+    /// Whether the display is to be disabled (detached from the desktop).
+    pub(crate) disable: bool,
+}
+
+impl DisplayConfigChange {
+    fn is_empty(&self) -> bool {
+        self.mode.is_none()
+            && self.position.is_none()
+            && self.orientation.is_none()
+            && !self.disable
+    }
+}
+
+/// Captured system display configuration, which may be later [`restored`].
+///
+/// [`restored`]: #method.restore
+#[derive(Clone)]
+pub struct DisplayConfigSnapshot(DisplayConfigSnapshotPlatform);
+
+impl DisplayConfigSnapshot {
+    /// Captures the system's current display configuration.
+    pub fn capture() -> Result<Self, ()> {
+        DisplayConfigSnapshotPlatform::capture().map(Self)
+    }
+
+    /// Re-applies the captured display configuration.
+    pub fn restore(&self) -> Result<(), ()> {
+        self.0.restore()
+    }
+}
+
+/// Collects the desired display configuration changes (display modes, positions, orientations, enabled state, primary display)
+/// across the [`enumerated displays`], and applies them atomically, in one go.
+///
+/// Created by [`Displays::begin_config`].
+///
+/// NOTE - the displays must be re-enumerated after the changes are applied.
+///
+/// [`enumerated displays`]: struct.Displays.html
+/// [`Displays::begin_config`]: struct.Displays.html#method.begin_config
+pub struct DisplayConfigTransaction<'d> {
+    displays: &'d Displays,
+    changes: Vec<DisplayConfigChange>,
+    primary: Option<u32>,
+}
+
+impl<'d> DisplayConfigTransaction<'d> {
+    pub(crate) fn new(displays: &'d Displays) -> Self {
+        Self {
+            displays,
+            changes: vec![DisplayConfigChange::default(); displays.num_displays() as usize],
+            primary: None,
+        }
+    }
+
+    /// Sets the display `mode` for the display with the provided [`display index`].
+    ///
+    /// Returns an error if the `display_index` is stale,
+    /// or if the display `mode` is not [`supported`] by the display.
+    ///
+    /// NOTE - with the `no-modes` feature the display `mode` is not validated until the transaction is applied.
+    ///
+    /// [`display index`]: struct.DisplayIndex.html
+    /// [`supported`]: struct.DisplayInfo.html#method.validate_mode
+    pub fn set_mode(
+        &mut self,
+        display_index: DisplayIndex,
+        mode: DisplayMode,
+    ) -> Result<(), DisplayError> {
+        #[cfg_attr(feature = "no-modes", allow(unused_variables))]
+        let display_info = self.displays.display_info(display_index)?;
+
+        // Use the exact supported display mode.
+        #[cfg(not(feature = "no-modes"))]
+        let mode = match display_info.validate_mode(&mode) {
+            ModeValidation::Supported(mode) => mode,
+            ModeValidation::Unsupported(_) | ModeValidation::NoModes => {
+                return Err(DisplayError::Failed)
+            }
+        };
+
+        self.set_validated_mode(self.displays.check_index(display_index)?, mode);
+
+        Ok(())
+    }
+
+    /// Sets the display `mode` for the display with the provided `display_index`, which must be already validated.
+    pub(crate) fn set_validated_mode(&mut self, display_index: u32, mode: DisplayMode) {
+        self.changes[display_index as usize].mode.replace(mode);
+    }
+
+    /// Sets the `position` of the display with the provided `display_index` in the virtual desktop.
+    ///
+    /// Returns an error if the `display_index` is stale.
+    pub fn set_position(
+        &mut self,
+        display_index: DisplayIndex,
+        position: Position,
+    ) -> Result<(), StaleIndex> {
+        self.change_mut(display_index)?.position.replace(position);
+        Ok(())
+    }
+
+    /// Sets the `orientation` of the display with the provided `display_index`.
+    /// The display's desktop dimensions are swapped if the orientation changes between landscape and portrait.
+    ///
+    /// Returns an error if the `display_index` is stale.
+    pub fn set_orientation(
+        &mut self,
+        display_index: DisplayIndex,
+        orientation: Orientation,
+    ) -> Result<(), StaleIndex> {
+        self.change_mut(display_index)?
+            .orientation
+            .replace(orientation);
+        Ok(())
+    }
+
+    /// Enables or disables (detaches from the desktop) the display with the provided `display_index`.
+    ///
+    /// NOTE - only the currently enabled displays are enumerated, so enabling a display only cancels its previous disabling.
+    ///
+    /// Returns an error if the `display_index` is stale.
+    pub fn set_enabled(
+        &mut self,
+        display_index: DisplayIndex,
+        enabled: bool,
+    ) -> Result<(), StaleIndex> {
+        self.change_mut(display_index)?.disable = !enabled;
+        Ok(())
+    }
+
+    /// Makes the display with the provided `display_index` the system's primary display.
+    /// All displays are moved so that the new primary display is at the virtual desktop origin.
+    ///
+    /// Returns an error if the `display_index` is stale.
+    pub fn set_primary(&mut self, display_index: DisplayIndex) -> Result<(), StaleIndex> {
+        self.change_mut(display_index)?;
+        self.primary
+            .replace(self.displays.check_index(display_index)?);
+        Ok(())
+    }
+
+    /// Returns `true` if no changes were requested.
+    pub fn is_empty(&self) -> bool {
+        self.primary.is_none() && self.changes.iter().all(DisplayConfigChange::is_empty)
+    }
+
+    /// Checks whether the requested changes may be applied, without applying them.
+    pub fn validate(&self) -> Result<(), ()> {
+        self.apply_inner(true).map(|_| ())
+    }
+
+    /// Validates and applies the requested changes in one go.
+    /// Reverts to the previous display configuration if applying the changes failed.
+    ///
+    /// Returns the [`display configuration`] captured before the changes were applied,
+    /// which may be used to revert them later.
+    ///
+    /// [`display configuration`]: struct.DisplayConfigSnapshot.html
+    pub fn apply(self) -> Result<DisplayConfigSnapshot, ()> {
+        self.apply_inner(false)
+    }
+
+    /// Validates and applies the requested changes in one go, like [`apply`],
+    /// but automatically reverts them after the `timeout` unless [`confirmed`] before that.
+    ///
+    /// Use this to protect the user from applying a display mode their display can't actually display
+    /// (e.g. "Keep these display settings?" prompts).
+    ///
+    /// If the `timeout` is too large for the revert deadline to be representable (e.g. `Duration::MAX`),
+    /// the changes are never reverted automatically.
+    ///
+    /// [`apply`]: #method.apply
+    /// [`confirmed`]: struct.PendingDisplayConfig.html#method.confirm
+    pub fn apply_with_confirmation(self, timeout: Duration) -> Result<PendingDisplayConfig, ()> {
+        let previous = self.apply_inner(false)?;
+        Ok(PendingDisplayConfig::new(previous, timeout))
+    }
+
+    fn change_mut(
+        &mut self,
+        display_index: DisplayIndex,
+    ) -> Result<&mut DisplayConfigChange, StaleIndex> {
+        let display_index = self.displays.check_index(display_index)?;
+
+        // Valid indices of the current generation are always in bounds.
+        self.changes
+            .get_mut(display_index as usize)
+            .ok_or(StaleIndex)
+    }
+
+    fn apply_inner(&self, validate_only: bool) -> Result<DisplayConfigSnapshot, ()> {
+        // Disabling the new primary display makes no sense.
+        if let Some(primary) = self.primary {
+            if self.changes[primary as usize].disable {
+                return Err(());
+            }
+        }
+
+        let changes: Vec<_> = self
+            .changes
+            .iter()
+            .enumerate()
+            .filter(|(_, change)| !change.is_empty())
+            .filter_map(|(display_index, change)| {
+                self.displays
+                    .display_info_platform_inner(display_index as u32)
+                    .and_then(PlatformInfo::native)
+                    .map(|platform| (platform, *change))
+            })
+            .collect();
+
+        let primary = self
+            .primary
+            .and_then(|primary| self.displays.display_info_platform_inner(primary))
+            .and_then(PlatformInfo::native);
+
+        apply_display_config_platform(&changes, primary, validate_only).map(DisplayConfigSnapshot)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum PendingState {
+    Pending,
+    Confirmed,
+    Reverted,
+}
+
+struct PendingDisplayConfigInner {
+    state: Mutex<PendingState>,
+    condvar: Condvar,
+    previous: DisplayConfigSnapshot,
+}
+
+impl PendingDisplayConfigInner {
+    fn lock(&self) -> MutexGuard<'_, PendingState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Display configuration changes [`applied with confirmation`],
+/// which are automatically reverted after the timeout unless [`confirmed`].
+///
+/// NOTE - dropping this does not cancel the revert timer.
+///
+/// [`applied with confirmation`]: struct.DisplayConfigTransaction.html#method.apply_with_confirmation
+/// [`confirmed`]: #method.confirm
+pub struct PendingDisplayConfig {
+    inner: Arc<PendingDisplayConfigInner>,
+}
+
+impl PendingDisplayConfig {
+    fn new(previous: DisplayConfigSnapshot, timeout: Duration) -> Self {
+        let inner = Arc::new(PendingDisplayConfigInner {
+            state: Mutex::new(PendingState::Pending),
+            condvar: Condvar::new(),
+            previous,
+        });
+
+        let timer_inner = inner.clone();
+
+        let revert = move || {
+            // No deadline if it overflows.
+            let deadline = Instant::now().checked_add(timeout);
+            let mut state = timer_inner.lock();
+
+            // Wait until confirmed / reverted, or until the deadline.
+            while *state == PendingState::Pending {
+                let deadline = match deadline {
+                    Some(deadline) => deadline,
+                    None => {
+                        state = timer_inner
+                            .condvar
+                            .wait(state)
+                            .unwrap_or_else(|poisoned| poisoned.into_inner());
+                        continue;
+                    }
+                };
+
+                let now = Instant::now();
+
+                if now >= deadline {
+                    let _ = timer_inner.previous.restore();
+                    *state = PendingState::Reverted;
+                    break;
+                }
+
+                state = timer_inner
+                    .condvar
+                    .wait_timeout(state, deadline - now)
+                    .unwrap_or_else(|poisoned| poisoned.into_inner())
+                    .0;
+            }
+        };
+
+        // Spawning the thread may only fail on resource exhaustion -
+        // revert immediately then, as we have no way to do it later.
+        if std::thread::Builder::new()
+            .name("minidisplay revert timer".into())
+            .spawn(revert)
+            .is_err()
+        {
+            let _ = inner.previous.restore();
+            *inner.lock() = PendingState::Reverted;
+        }
+
+        Self { inner }
+    }
+
+    /// Confirms the applied display configuration changes, cancelling the revert timer.
+    ///
+    /// Returns an error if the changes were already reverted.
+    pub fn confirm(self) -> Result<(), ()> {
+        self.resolve(PendingState::Confirmed)
+    }
+
+    /// Reverts the applied display configuration changes immediately, cancelling the revert timer.
+    ///
+    /// Returns an error if the changes were already reverted, or if reverting them failed.
+    pub fn revert(self) -> Result<(), ()> {
+        self.resolve(PendingState::Reverted)
+    }
+
+    /// Returns `true` if the changes are not yet confirmed or reverted.
+    pub fn is_pending(&self) -> bool {
+        *self.inner.lock() == PendingState::Pending
+    }
+
+    fn resolve(self, new_state: PendingState) -> Result<(), ()> {
+        let mut state = self.inner.lock();
+
+        if *state != PendingState::Pending {
+            return Err(());
+        }
+
+        let res = if new_state == PendingState::Reverted {
+            self.inner.previous.restore()
+        } else {
+            Ok(())
+        };
+
+        *state = new_state;
+        self.inner.condvar.notify_all();
+
+        res
+    }
+}
@@ -0,0 +1,179 @@
+use std::fmt::{Display, Formatter};
+
+/// Display index which remembers the [`display enumeration`] it was obtained from,
+/// so that using it after the displays were re-enumerated and reordered is detected
+/// (the [`display manager`] methods return [`StaleIndex`]) rather than silently referring to a different display.
+///
+/// Obtained via [`Displays::display_index`] (from a raw display index), [`Displays::find`], etc.
+///
+/// The index stays valid as long as the [`index generation`] does not change,
+/// i.e. while the re-enumerations keep the same displays at the same display indices.
+///
+/// NOTE - the display info structures (e.g. [`AdjacencyInfo`]) and the immutable [`snapshots`] use raw display indices,
+/// which never go stale within the same snapshot.
+///
+/// Use a [`DisplayRef`] instead to refer to the same display across the re-enumerations which reorder the displays.
+///
+/// [`display enumeration`]: struct.Displays.html#method.enumerate_displays
+/// [`display manager`]: struct.Displays.html
+/// [`StaleIndex`]: struct.StaleIndex.html
+/// [`Displays::display_index`]: struct.Displays.html#method.display_index
+/// [`Displays::find`]: struct.Displays.html#method.find
+/// [`index generation`]: struct.Displays.html#method.index_generation
+/// [`AdjacencyInfo`]: struct.AdjacencyInfo.html
+/// [`snapshots`]: struct.DisplaysSnapshot.html
+/// [`DisplayRef`]: struct.DisplayRef.html
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct DisplayIndex {
+    index: u32,
+    generation: u64,
+}
+
+impl DisplayIndex {
+    pub(crate) fn new(index: u32, generation: u64) -> Self {
+        Self { index, generation }
+    }
+
+    /// Returns the raw display index, without checking whether it's stale.
+    pub fn index(self) -> u32 {
+        self.index
+    }
+
+    /// Returns the [`index generation`] of the display enumeration the index was obtained from.
+    ///
+    /// [`index generation`]: struct.Displays.html#method.index_generation
+    pub fn generation(self) -> u64 {
+        self.generation
+    }
+}
+
+impl Display for DisplayIndex {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.index)
+    }
+}
+
+/// Error returned when a [`display index`] is used after the displays were re-enumerated and reordered.
+///
+/// [`display index`]: struct.DisplayIndex.html
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct StaleIndex;
+
+impl Display for StaleIndex {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "the display index is stale - the displays were re-enumerated"
+        )
+    }
+}
+
+impl std::error::Error for StaleIndex {}
+
+/// Error returned by the [`display manager`] methods which query / change the display with the provided [`display index`].
+///
+/// [`display manager`]: struct.Displays.html
+/// [`display index`]: struct.DisplayIndex.html
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DisplayError {
+    /// The display index is stale - the displays were re-enumerated and reordered since it was obtained.
+    StaleIndex,
+    /// The query / change failed (e.g. the display or the platform does not support it, or the OS call failed).
+    Failed,
+}
+
+impl Display for DisplayError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            DisplayError::StaleIndex => StaleIndex.fmt(f),
+            DisplayError::Failed => write!(f, "the display query / change failed"),
+        }
+    }
+}
+
+impl std::error::Error for DisplayError {}
+
+impl From<StaleIndex> for DisplayError {
+    fn from(_: StaleIndex) -> Self {
+        DisplayError::StaleIndex
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{triple_surround, FixtureBackend};
+    use crate::{DisplayId, DisplayInfo, Displays, DisplaysSnapshot, Position};
+    use std::sync::Arc;
+
+    #[test]
+    fn stale_index() {
+        // Primary display in the middle, then the left and the right displays.
+        let surround = triple_surround().displays().to_vec();
+        let (primary, left, right) = (&surround[0], &surround[1], &surround[2]);
+        let new = DisplayInfo {
+            id: DisplayId(4),
+            ..right.clone()
+        };
+        let snapshot = |displays: &[&DisplayInfo]| {
+            DisplaysSnapshot::new(displays.iter().map(|&display| display.clone()).collect())
+        };
+
+        let backend = Arc::new(FixtureBackend::new(snapshot(&[primary, left, right])));
+
+        let mut displays = Displays::with_backend(backend.clone());
+        assert_eq!(displays.index_generation(), 0);
+        assert!(displays.display_index(0).is_none());
+        assert_eq!(displays.generation(), 0);
+        assert!(displays.last_enumerated_at().is_none());
+
+        displays.enumerate_displays().unwrap();
+        assert_eq!(displays.index_generation(), 0);
+        assert_eq!(displays.generation(), 1);
+        assert!(displays.last_enumerated_at().is_some());
+
+        let right_index = displays.display_index(2).unwrap();
+        assert_eq!(right_index.index(), 2);
+        assert_eq!(displays.check_index(right_index), Ok(2));
+        assert!(displays.display_index(3).is_none());
+
+        // Same displays - still valid.
+        displays.enumerate_displays().unwrap();
+        assert_eq!(displays.index_generation(), 0);
+        assert_eq!(displays.generation(), 2);
+        assert_eq!(displays.check_index(right_index), Ok(2));
+
+        // New display added at the end - existing indices still refer to the same displays.
+        backend.set(snapshot(&[primary, left, right, &new]));
+        displays.enumerate_displays().unwrap();
+        assert_eq!(displays.index_generation(), 0);
+        assert_eq!(displays.check_index(right_index), Ok(2));
+
+        // Display removed - the following displays are shifted.
+        backend.set(snapshot(&[primary, right, &new]));
+        displays.enumerate_displays().unwrap();
+        assert_eq!(displays.index_generation(), 1);
+        assert_eq!(displays.check_index(right_index), Err(StaleIndex));
+        assert_eq!(displays.display_info(right_index).err(), Some(StaleIndex));
+        assert_eq!(displays.display_ref(right_index), Err(StaleIndex));
+        assert_eq!(displays.neighbors(right_index), Err(StaleIndex));
+        assert_eq!(displays.span_rect(&[right_index]), Err(StaleIndex));
+        assert_eq!(
+            displays.contrast(right_index).err(),
+            Some(DisplayError::StaleIndex)
+        );
+        assert_eq!(
+            displays
+                .begin_config()
+                .set_position(right_index, Position::new(0, 0)),
+            Err(StaleIndex)
+        );
+        assert_eq!(
+            displays
+                .display_info(displays.display_index(1).unwrap())
+                .unwrap()
+                .id,
+            right.id
+        );
+    }
+}
@@ -1,11 +1,19 @@
 #![allow(clippy::too_many_arguments)]
 
 use std::fmt::{Display, Formatter};
+use std::ops::Deref;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
 
-use crate::{Dimensions, Rectangle};
+#[cfg(not(feature = "no-modes"))]
+use crate::ModeQuery;
+
+use crate::{Colorimetry, Dimensions, Margins, Mm, Pt, Px, Rectangle};
 
 /// Describes the display's upscaling mode.
 #[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum UpscaleMode {
     Unknown,
     Center,
@@ -26,6 +34,7 @@ impl Display for UpscaleMode {
 
 /// Describes the display's physical connection type.
 #[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ConnectionType {
     Unknown,
     VGA,
@@ -50,177 +59,2391 @@ impl Display for ConnectionType {
     }
 }
 
-/// Describes a display's supported fullscreen display mode.
-#[derive(Clone, Copy, Debug)]
-pub struct DisplayMode {
-    /// Display mode dimensions.
-    pub dimensions: Dimensions,
-    /// Refresh rate in Hz.
-    pub refresh_rate: u32,
-    /// Refresh rate numerator, such that numerator/denominator gives the refresh rate in Hz.
-    pub refresh_rate_num: u32,
-    /// Refresh rate denominator, such that numerator/denominator gives the refresh rate in Hz.
-    pub refresh_rate_denom: u32,
-    /// Display mode upscale mode.
-    pub upscale_mode: UpscaleMode,
+/// Describes where the display's info was obtained from.
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DisplaySource {
+    /// Full display info, obtained via the display configuration API's.
+    DisplayConfig,
+    /// Degraded display info, obtained via the legacy GDI API's only
+    /// (e.g. inside Windows containers and some VMs, where the display configuration API's fail).
+    ///
+    /// Display connection type is always `Unknown`, the name is less descriptive (if any),
+    /// the preferred display mode is assumed to be the largest supported display mode.
+    Gdi,
+    /// Last-resort pseudo display info, made up from the system's virtual screen metrics
+    /// when all other enumeration methods fail.
+    ///
+    /// Only a single display covering the whole virtual screen is reported,
+    /// with a single display mode of virtual screen dimensions.
+    Fallback,
+    /// Degraded display info of the monitors attached to the system, as reported by the PnP manager,
+    /// when running in a non-interactive services session (session 0 on Windows),
+    /// where the displays' desktop info is not available.
+    ///
+    /// Display rectangles are empty, the name and the only display mode
+    /// (or a zero-sized one, if unknown) are read from the monitor's EDID, if possible,
+    /// connection type is always `Unknown`, no display is primary.
+    ServiceInventory,
+    /// Full display info, obtained via the display configuration API's, of a display created by an indirect display driver
+    /// (e.g. a virtual monitor of a game streaming / remote desktop app, a USB display adapter or a headless display dongle),
+    /// with the driver's name (e.g. `Parsec Virtual Display Adapter`).
+    ///
+    /// Such displays are usually not the ones the user looks at, and may be deprioritized (e.g. in the display pickers).
+    Indirect(String),
 }
 
-/// Describes the display's rectangles w.r.t. the virtual display.
-#[derive(Clone, Copy, Debug)]
-pub struct DisplayRects {
-    /// Display (non-work, a.k.a. full) rectangle w.r.t. the virtual display.
-    pub virtual_rect: Rectangle,
-    /// Display work (e.g. with the taskbar subtracted) rectangle w.r.t. the virtual display.
-    pub work_rect: Rectangle,
+impl DisplaySource {
+    /// Returns the name of the indirect display driver which created the display, if it's an [`indirect`] one.
+    ///
+    /// [`indirect`]: #variant.Indirect
+    pub fn indirect_driver(&self) -> Option<&str> {
+        match self {
+            DisplaySource::Indirect(driver) => Some(driver),
+            _ => None,
+        }
+    }
 }
 
-/// Describes a single enumerated system display.
-#[derive(Clone, Debug)]
-pub struct DisplayInfo {
-    /// Display's friendly name, if any.
-    pub name: Option<String>,
-    /// Whether the display is the system's primary display.
-    pub is_primary: bool,
-    /// The display's rectangles w.r.t. the virtual display.
-    pub rects: DisplayRects,
-    /// The display's physical connection type.
-    pub connection: ConnectionType,
-    /// The display's current display mode.
-    pub current_mode: DisplayMode,
-    /// The display's preferred display mode.
-    pub preferred_mode: DisplayMode,
-    /// The display's supported (fullscreen) display modes.
-    /// At least one display mode is supported by any enumerated display.
-    pub display_modes: Vec<DisplayMode>,
-    /// The dimensions of the smallest (by area) of the display's supported display modes.
-    pub min_dimensions: Dimensions,
-    /// The display's DPI scale value.
-    /// `1.0` is the default and means no scaling.
-    /// Higher values like `1.25`, `1.5`, `2.0` mean higher zoom.
-    pub dpi_scale: f32,
+impl Display for DisplaySource {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use DisplaySource::*;
+
+        match self {
+            DisplayConfig => write!(f, "display config"),
+            Gdi => write!(f, "GDI"),
+            Fallback => write!(f, "fallback"),
+            ServiceInventory => write!(f, "service inventory"),
+            Indirect(driver) => write!(f, "indirect display driver ({})", driver),
+        }
+    }
 }
 
-impl DisplayInfo {
-    pub(crate) fn new(
-        name: Option<String>,
-        is_primary: bool,
-        rects: DisplayRects,
-        connection: ConnectionType,
-        current_mode: DisplayMode,
-        preferred_mode: DisplayMode,
-        display_modes: Vec<DisplayMode>,
-        dpi_scale: f32,
-    ) -> Self {
-        let min_dimensions = DisplayInfo::calc_min_dimensions(&display_modes);
+/// Well-known (lowercase) keywords of the indirect display drivers' descriptions.
+const INDIRECT_DRIVER_KEYWORDS: &[&str] = &[
+    "virtual display",
+    "virtual monitor",
+    "indirect display",
+    "displaylink",
+    "spacedesk",
+    "usb display",
+];
 
-        Self {
-            name,
-            is_primary,
-            rects,
-            connection,
-            current_mode,
-            preferred_mode,
-            display_modes,
-            min_dimensions,
-            dpi_scale,
+/// Well-known (lowercase) hardware ID's of the indirect display drivers' virtual monitors,
+/// as found in the monitors' device paths (e.g. `\\?\DISPLAY#MTT1337#...`).
+const INDIRECT_MONITOR_HARDWARE_IDS: &[&str] = &[
+    // Indirect display driver sample, and the many virtual display drivers based on it.
+    "mtt1337",
+];
+
+/// Returns the name of the indirect display driver which created the display, if it's (likely) an indirect display.
+///
+/// The display is indirect if the OS reports its output technology as an indirect one (`indirect_output`),
+/// or if the `driver_description` or the monitor's `device_path` contain the well-known keywords
+/// (for the older drivers which don't report it).
+///
+/// The driver is named by its `driver_description`, if known,
+/// or the monitor's hardware ID from the `device_path` otherwise.
+#[cfg_attr(not(windows), allow(dead_code))]
+pub(crate) fn indirect_driver(
+    driver_description: Option<&str>,
+    device_path: Option<&str>,
+    indirect_output: bool,
+) -> Option<String> {
+    let driver_description = driver_description.filter(|description| !description.is_empty());
+
+    // `\\?\DISPLAY#<hardware ID>#<instance ID>#{<interface class GUID>}`
+    let hardware_id = device_path
+        .and_then(|device_path| device_path.split('#').nth(1))
+        .filter(|hardware_id| !hardware_id.is_empty());
+
+    let is_indirect = indirect_output
+        || driver_description.is_some_and(|description| {
+            let description = description.to_lowercase();
+            INDIRECT_DRIVER_KEYWORDS
+                .iter()
+                .any(|keyword| description.contains(keyword))
+        })
+        || hardware_id.is_some_and(|hardware_id| {
+            INDIRECT_MONITOR_HARDWARE_IDS.contains(&hardware_id.to_lowercase().as_str())
+        });
+
+    if !is_indirect {
+        return None;
+    }
+
+    Some(
+        driver_description
+            .or(hardware_id)
+            .unwrap_or("<unknown>")
+            .to_string(),
+    )
+}
+
+/// Display's name.
+///
+/// Stores both the platform's raw UTF-16 name and its (lossy) UTF-8 conversion.
+/// Cheap to clone - the name strings are shared between the clones.
+///
+/// Unchanged display names are reused between display enumerations, not reallocated.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct DisplayName {
+    utf16: Arc<[u16]>,
+    utf8: Arc<str>,
+}
+
+impl DisplayName {
+    /// Creates the display name from a raw UTF-16 `name`, up to the first nul terminator, if any.
+    /// Returns `None` if the `name` is empty.
+    pub fn from_utf16(name: &[u16]) -> Option<Self> {
+        let name = name.split(|&c| c == 0).next().unwrap_or(name);
+
+        if name.is_empty() {
+            None
+        } else {
+            Some(Self {
+                utf16: name.into(),
+                utf8: String::from_utf16_lossy(name).into(),
+            })
         }
     }
 
-    /// Returns the [`dimensions`] of the display's [`display mode`] closest to provided `dimensions`
-    /// based on provided `flags`.
+    /// Same as [`from_utf16`], but returns the clone of the matching name from `previous` names, if any,
+    /// instead of allocating a new one.
     ///
-    /// [`dimensions`]: struct.Dimensions.html
-    /// [`display mode`]: struct.DisplayMode.html
-    pub fn closest_dimensions(
-        &self,
-        dimensions: Dimensions,
-        flags: ClosestDimensionsFlags,
-    ) -> Dimensions {
-        closest_dimensions(&self.display_modes, dimensions, flags)
+    /// [`from_utf16`]: #method.from_utf16
+    #[cfg_attr(not(windows), allow(dead_code))]
+    pub(crate) fn from_utf16_reuse(name: &[u16], previous: &[DisplayName]) -> Option<Self> {
+        let trimmed = name.split(|&c| c == 0).next().unwrap_or(name);
+
+        if let Some(previous) = previous
+            .iter()
+            .find(|previous| previous.as_utf16() == trimmed)
+        {
+            Some(previous.clone())
+        } else {
+            Self::from_utf16(trimmed)
+        }
     }
 
-    /// Returns the dimensions of the smallest (by area) display mode from a non-empty array of `display_modes`.
-    fn calc_min_dimensions(display_modes: &[DisplayMode]) -> Dimensions {
-        debug_assert!(!display_modes.is_empty());
+    /// Returns the display name as a (lossy) UTF-8 string.
+    pub fn as_str(&self) -> &str {
+        &self.utf8
+    }
+
+    /// Returns the display name as the platform's raw UTF-16 string (not nul-terminated).
+    pub fn as_utf16(&self) -> &[u16] {
+        &self.utf16
+    }
+}
 
-        let mut min_area = std::u32::MAX;
-        let mut found = None;
+impl Deref for DisplayName {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        self.as_str()
+    }
+}
 
-        for (index, mode) in display_modes.iter().enumerate() {
-            let area = mode.dimensions.area();
+impl AsRef<str> for DisplayName {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
 
-            if area < min_area {
-                min_area = area;
-                found.replace(index);
-            }
+impl Display for DisplayName {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Serialized as the (lossy) UTF-8 string.
+#[cfg(feature = "serde")]
+impl serde::Serialize for DisplayName {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for DisplayName {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = <String as serde::Deserialize>::deserialize(deserializer)?;
+
+        DisplayName::from_utf16(&name.encode_utf16().collect::<Vec<_>>())
+            .ok_or_else(|| serde::de::Error::custom("empty display name"))
+    }
+}
+
+/// All names the platform reported for the display, to choose which one to show
+/// (the display's [`name`] is the first known of the `friendly` and `device_string` names).
+///
+/// [`name`]: struct.DisplayInfo.html#structfield.name
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DisplayNames {
+    /// The monitor's friendly name (e.g. `DELL U2720Q`), as reported by its EDID, if known.
+    ///
+    /// NOTE - on Windows, unknown if the display configuration API's failed to read the monitor's EDID
+    /// (and the `wmi` feature is disabled, or WMI failed as well).
+    pub friendly: Option<String>,
+    /// The monitor's description, as reported by its driver (often generic, e.g. `Generic PnP Monitor`), if known.
+    pub device_string: Option<String>,
+    /// The display's OS device name (e.g. `\\.\DISPLAY1` on Windows).
+    ///
+    /// NOTE - only known for the displays enumerated via GDI (i.e. the [`DisplayConfig`], [`Gdi`] and [`Indirect`] sources).
+    /// `None` for the [`Fallback`] pseudo display and the [`ServiceInventory`] displays, which have no GDI device,
+    /// and for the displays enumerated via a custom [`display backend`], unless it provides the device name.
+    ///
+    /// [`DisplayConfig`]: enum.DisplaySource.html#variant.DisplayConfig
+    /// [`Gdi`]: enum.DisplaySource.html#variant.Gdi
+    /// [`Indirect`]: enum.DisplaySource.html#variant.Indirect
+    /// [`Fallback`]: enum.DisplaySource.html#variant.Fallback
+    /// [`ServiceInventory`]: enum.DisplaySource.html#variant.ServiceInventory
+    /// [`display backend`]: trait.DisplayBackend.html
+    pub gdi_device: Option<String>,
+}
+
+/// Stable display identifier, which persists across display enumerations, process runs and reboots,
+/// as long as the display stays connected to the same port.
+///
+/// On Windows, derived from the display monitor's device interface path (if known).
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DisplayId(pub u64);
+
+impl DisplayId {
+    /// Creates the display identifier from a platform-specific (case-insensitive) UTF-16 identifier string.
+    ///
+    /// Uses the 64-bit FNV-1a hash - it's stable across Rust versions, unlike the standard library hashers.
+    #[cfg_attr(not(windows), allow(dead_code))]
+    pub(crate) fn from_utf16(string: &[u16]) -> Self {
+        const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+        const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+        let hash = string
+            .iter()
+            .map(|&c| {
+                if c < 0x80 {
+                    (c as u8).to_ascii_lowercase() as u16
+                } else {
+                    c
+                }
+            })
+            .flat_map(u16::to_le_bytes)
+            .fold(FNV_OFFSET_BASIS, |hash, byte| {
+                (hash ^ byte as u64).wrapping_mul(FNV_PRIME)
+            });
+
+        Self(hash)
+    }
+}
+
+impl Display for DisplayId {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{:016x}", self.0)
+    }
+}
+
+/// Describes the display's orientation (clockwise rotation of the desktop image).
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Orientation {
+    /// No rotation.
+    #[default]
+    Landscape,
+    /// Rotated by 90 degrees.
+    Portrait,
+    /// Rotated by 180 degrees.
+    LandscapeFlipped,
+    /// Rotated by 270 degrees.
+    PortraitFlipped,
+}
+
+impl Orientation {
+    /// Returns `true` if the orientation swaps the display's width and height.
+    pub fn is_portrait(self) -> bool {
+        match self {
+            Orientation::Landscape | Orientation::LandscapeFlipped => false,
+            Orientation::Portrait | Orientation::PortraitFlipped => true,
         }
+    }
+}
+
+impl Display for Orientation {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use Orientation::*;
 
-        display_modes[found.expect("Failed to calculate the minimum display mode dimensions.")]
-            .dimensions
+        match self {
+            Landscape => write!(f, "landscape"),
+            Portrait => write!(f, "portrait"),
+            LandscapeFlipped => write!(f, "landscape (flipped)"),
+            PortraitFlipped => write!(f, "portrait (flipped)"),
+        }
     }
 }
 
-/// Determines which display mode to pick when looking for one
-/// with closest dimensions to provided value.
-pub enum ClosestDimensionsFlags {
-    /// Pick the display mode with closest (by area) dimensions to provided value,
-    /// both smaller or larger.
-    Closest,
-    /// Pick the display mode with closest (by area) dimensions to provided value,
-    /// and additionally not wider/taller.
-    ClosestSmallerOrEqual,
+/// Video signal timing of the display's current display mode, as reported by the OS / driver.
+///
+/// Totals include the blanking intervals, so the scanout of a single line takes `h_total / pixel_rate_hz` seconds,
+/// and the vertical blanking interval lasts `v_total - <active height>` lines.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SignalTiming {
+    /// Pixel clock, in Hz.
+    pub pixel_rate_hz: u64,
+    /// Total number of pixels per line, including the horizontal blanking interval.
+    pub h_total: u32,
+    /// Total number of lines per frame, including the vertical blanking interval.
+    pub v_total: u32,
 }
 
-/// Returns the [`dimensions`] of the [`display mode`] closest to provided `dimensions`
-/// based on provided `flags`.
+impl SignalTiming {
+    /// Returns the duration of the scanout of a single line (including the horizontal blanking interval).
+    ///
+    /// Returns a zero duration if the pixel clock is unknown (zero).
+    pub fn line_duration(&self) -> Duration {
+        self.duration(self.h_total as u128)
+    }
+
+    /// Returns the duration of the scanout of a whole frame (including the blanking intervals),
+    /// i.e. the vblank interval.
+    ///
+    /// Returns a zero duration if the pixel clock is unknown (zero).
+    pub fn frame_duration(&self) -> Duration {
+        self.duration(self.h_total as u128 * self.v_total as u128)
+    }
+
+    /// Returns the duration of the scanout of `pixels`, rounded to nearest nanosecond.
+    fn duration(&self, pixels: u128) -> Duration {
+        if self.pixel_rate_hz == 0 {
+            return Duration::from_nanos(0);
+        }
+
+        const NANOS_PER_SEC: u128 = 1_000_000_000;
+
+        let pixel_rate = self.pixel_rate_hz as u128;
+        let nanos = (pixels * NANOS_PER_SEC + pixel_rate / 2) / pixel_rate;
+
+        Duration::from_nanos(nanos.min(u64::MAX as u128) as u64)
+    }
+}
+
+/// HDR capabilities of the display, as reported by the HDR static metadata data block of the monitor's EDID.
 ///
-/// [`dimensions`]: struct.Dimensions.html
-/// [`display mode`]: struct.DisplayMode.html
-pub fn closest_dimensions(
-    display_modes: &[DisplayMode],
-    dimensions: Dimensions,
-    flags: ClosestDimensionsFlags,
-) -> Dimensions {
-    debug_assert!(!display_modes.is_empty());
+/// Luminance values are in nits (cd/m²), and are the values the monitor recommends
+/// for the content's mastering metadata (i.e. what tone mapping should target), if specified.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HdrCaps {
+    /// Whether the display supports the SMPTE ST 2084 (PQ) transfer function (i.e. HDR10).
+    pub supports_pq: bool,
+    /// Whether the display supports the Hybrid Log-Gamma transfer function.
+    pub supports_hlg: bool,
+    /// Desired content maximum luminance (i.e. the display's peak luminance), if specified.
+    pub max_luminance: Option<f32>,
+    /// Desired content minimum luminance (i.e. the display's black level), if specified.
+    pub min_luminance: Option<f32>,
+    /// Desired content maximum frame-average light level (MaxFALL), if specified.
+    pub max_fall: Option<f32>,
+}
+
+/// Rough VESA DisplayHDR certification tier the display's reported HDR capabilities correspond to,
+/// as returned by [`DisplayInfo::hdr_tier`] (e.g. to pick the default HDR settings in a game's settings screen).
+///
+/// NOTE - this is a heuristic based on the monitor's self-reported [`metrics`],
+/// not the actual certification (which also covers e.g. the local dimming and the sustained brightness).
+///
+/// [`DisplayInfo::hdr_tier`]: struct.DisplayInfo.html#method.hdr_tier
+/// [`metrics`]: struct.HdrMetrics.html
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum HdrTier {
+    /// At least `400` nits peak luminance (e.g. entry level "HDR" desktop monitors).
+    Hdr400,
+    /// At least `600` nits peak luminance, `0.1` nits black level, 10 bits per channel and a wide (DCI-P3) gamut.
+    Hdr600,
+    /// At least `1000` nits peak luminance, `0.05` nits black level, 10 bits per channel and a wide (DCI-P3) gamut.
+    Hdr1000,
+}
+
+impl Display for HdrTier {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use HdrTier::*;
+
+        match self {
+            Hdr400 => write!(f, "HDR400-class"),
+            Hdr600 => write!(f, "HDR600-class"),
+            Hdr1000 => write!(f, "HDR1000-class"),
+        }
+    }
+}
+
+/// The display's HDR-related numbers the [`HDR tier`] heuristic is based on,
+/// as returned by [`DisplayInfo::hdr_metrics`].
+///
+/// [`HDR tier`]: enum.HdrTier.html
+/// [`DisplayInfo::hdr_metrics`]: struct.DisplayInfo.html#method.hdr_metrics
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct HdrMetrics {
+    /// Whether the display supports the SMPTE ST 2084 (PQ) transfer function (i.e. HDR10).
+    pub supports_pq: bool,
+    /// The display's peak luminance in nits, if known.
+    pub max_luminance: Option<f32>,
+    /// The display's black level in nits, if known.
+    pub min_luminance: Option<f32>,
+    /// The monitor's maximum supported bit depth per color channel, if known.
+    pub max_bits_per_channel: Option<u8>,
+    /// The fraction in `[0.0 .. 1.0]` range of the DCI-P3 gamut covered by the display's gamut, if known.
+    pub dci_p3_coverage: Option<f32>,
+}
+
+impl HdrMetrics {
+    /// Returns the highest [`HDR tier`] whose requirements the metrics meet,
+    /// or `None` if the display does not support HDR10, or its peak luminance is unknown or below `400` nits.
+    ///
+    /// The peak luminance may be up to `5%` below the tier's requirement (the EDID luminance values are coarsely quantized).
+    /// The unknown black level is assumed to meet the requirements;
+    /// the unknown bit depth or gamut are assumed not to (i.e. limit the tier to [`HdrTier::Hdr400`]).
+    ///
+    /// [`HDR tier`]: enum.HdrTier.html
+    /// [`HdrTier::Hdr400`]: enum.HdrTier.html#variant.Hdr400
+    pub fn tier(&self) -> Option<HdrTier> {
+        if !self.supports_pq {
+            return None;
+        }
+
+        let max_luminance = self.max_luminance?;
+
+        let wide_gamut = self.max_bits_per_channel.is_some_and(|bits| bits >= 10)
+            && self
+                .dci_p3_coverage
+                .is_some_and(|coverage| coverage >= HDR_WIDE_GAMUT_DCI_P3_COVERAGE);
+
+        HDR_TIERS
+            .iter()
+            .find(|(_, peak, black, requires_wide_gamut)| {
+                max_luminance >= peak * HDR_LUMINANCE_TOLERANCE
+                    && self.min_luminance.is_none_or(|min| min <= *black)
+                    && (wide_gamut || !requires_wide_gamut)
+            })
+            .map(|(tier, ..)| *tier)
+    }
+}
+
+/// (Tier, minimum peak luminance, maximum black level, requires a wide gamut), highest tier first.
+const HDR_TIERS: [(HdrTier, f32, f32, bool); 3] = [
+    (HdrTier::Hdr1000, 1000.0, 0.05, true),
+    (HdrTier::Hdr600, 600.0, 0.1, true),
+    (HdrTier::Hdr400, 400.0, 0.4, false),
+];
+
+/// Fraction of the tier's peak luminance requirement the reported peak luminance must reach.
+const HDR_LUMINANCE_TOLERANCE: f32 = 0.95;
+
+/// DisplayHDR 600+ require `90%` DCI-P3 coverage.
+const HDR_WIDE_GAMUT_DCI_P3_COVERAGE: f32 = 0.9;
+
+/// Monitor asset / maintenance info (e.g. for the IT inventory agents), as returned by [`Displays::asset_info`].
+///
+/// [`Displays::asset_info`]: struct.Displays.html#method.asset_info
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AssetInfo {
+    /// Monitor manufacturer's three-letter PnP ID (e.g. `DEL`), as reported by its EDID, if valid.
+    pub manufacturer: Option<String>,
+    /// Monitor's product code (e.g. `40F0`), as reported by its EDID.
+    pub product_code: String,
+    /// Monitor's serial number, as reported by its EDID, if any.
+    pub serial_number: Option<String>,
+    /// Monitor's year of manufacture (or the model year), as reported by its EDID, if specified.
+    pub manufacture_year: Option<u16>,
+    /// Monitor's week of manufacture, as reported by its EDID, if specified.
+    pub manufacture_week: Option<u8>,
+    /// Monitor's total power-on time in hours, as read via DDC/CI, if known.
+    ///
+    /// NOTE - never read during the display enumeration (see [`Displays::asset_info`]).
+    ///
+    /// [`Displays::asset_info`]: struct.Displays.html#method.asset_info
+    pub usage_hours: Option<u32>,
+}
 
-    let area = dimensions.area();
+/// A supported display mode which fits the content with an integer scale factor,
+/// as returned by [`DisplayInfo::integer_scaled_modes`].
+///
+/// [`DisplayInfo::integer_scaled_modes`]: struct.DisplayInfo.html#method.integer_scaled_modes
+#[cfg(not(feature = "no-modes"))]
+#[derive(Clone, Copy, Debug)]
+pub struct IntegerScaledMode {
+    /// The supported display mode, in the display's unrotated orientation.
+    pub mode: DisplayMode,
+    /// The largest integer scale factor the content fits the display mode with.
+    pub factor: u32,
+    /// Margins around the centered scaled content (i.e. the letterbox / pillarbox) in pixels,
+    /// in the display's current orientation.
+    pub letterbox: Margins,
+}
 
-    let mut min_difference = std::u32::MAX;
-    let mut found = None;
-    let mut found_smaller = None;
+/// Pixel density category of the display, as returned by [`DisplayInfo::density_class`].
+///
+/// [`DisplayInfo::density_class`]: struct.DisplayInfo.html#method.density_class
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum DensityClass {
+    /// Less than `90` PPI (e.g. large TV's, `1080p` displays larger than `24"`).
+    Low,
+    /// `90` to `140` PPI (e.g. `24" 1080p` or `27" 1440p` desktop displays).
+    Standard,
+    /// `140` to `220` PPI (e.g. `27" 4K` displays, most `1080p` laptop displays).
+    High,
+    /// `220` PPI or more (e.g. high-end laptop displays).
+    Ultra,
+}
 
-    for (index, mode) in display_modes.iter().enumerate() {
-        let mode_area = mode.dimensions.area();
-        let area_difference = if mode_area > area {
-            mode_area - area
+impl DensityClass {
+    fn from_ppi(ppi: f32) -> Self {
+        if ppi < 90.0 {
+            DensityClass::Low
+        } else if ppi < 140.0 {
+            DensityClass::Standard
+        } else if ppi < 220.0 {
+            DensityClass::High
         } else {
-            area - mode_area
-        };
+            DensityClass::Ultra
+        }
+    }
+}
 
-        if area_difference < min_difference {
-            min_difference = area_difference;
-            found.replace(index);
+impl Display for DensityClass {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use DensityClass::*;
 
-            match flags {
-                ClosestDimensionsFlags::Closest => {}
-                ClosestDimensionsFlags::ClosestSmallerOrEqual => {
-                    if (mode.dimensions.width <= dimensions.width)
-                        && (mode.dimensions.height <= dimensions.height)
-                    {
-                        found_smaller.replace(index);
-                    }
-                }
-            }
+        match self {
+            Low => write!(f, "low density"),
+            Standard => write!(f, "standard density"),
+            High => write!(f, "high density"),
+            Ultra => write!(f, "ultra high density"),
         }
     }
+}
 
-    let found = found.expect("Failed to find a display mode with closest dimensions.");
-    let found_smaller = found_smaller.unwrap_or(found);
+/// Pixel density (at typical viewing distance) which needs no UI scaling, in PPI.
+const UNSCALED_PPI: f32 = 110.0;
 
-    let found = match flags {
-        ClosestDimensionsFlags::Closest => found,
-        ClosestDimensionsFlags::ClosestSmallerOrEqual => found_smaller,
-    };
+/// DPI scale percentages the OS may offer for the displays, in ascending order.
+const DPI_SCALE_STEPS: [u32; 12] = [100, 125, 150, 175, 200, 225, 250, 300, 350, 400, 450, 500];
+
+/// DPI scaling options the OS offers for the display (e.g. in the display settings UI), in percent.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DpiOptions {
+    /// Allowed DPI scale percentages, in ascending order (e.g. `[100, 125, 150, 175]`).
+    pub steps: Vec<u32>,
+    /// Recommended DPI scale percentage (e.g. `150`). One of the `steps`.
+    pub recommended: u32,
+    /// Current DPI scale percentage (e.g. `125`). One of the `steps`, unless a custom DPI scale is set.
+    pub current: u32,
+}
+
+impl DpiOptions {
+    /// Builds the DPI scaling options from the (minimum, current, maximum) DPI scale step indices,
+    /// relative to the recommended DPI scale step, as reported by the OS.
+    /// Returns `None` if the relative indices are out of bounds.
+    #[cfg_attr(not(windows), allow(dead_code))]
+    pub(crate) fn from_relative(min: i32, current: i32, max: i32) -> Option<Self> {
+        // The minimum step is always the first one.
+        let recommended = min.checked_neg()?;
+
+        let step = |relative: i32| -> Option<u32> {
+            let index = recommended.checked_add(relative)?;
+
+            if index < 0 {
+                None
+            } else {
+                DPI_SCALE_STEPS.get(index as usize).copied()
+            }
+        };
+
+        let max = step(max)?;
+
+        Some(Self {
+            steps: DPI_SCALE_STEPS
+                .iter()
+                .copied()
+                .take_while(|&step| step <= max)
+                .collect(),
+            recommended: step(0)?,
+            current: step(current)?,
+        })
+    }
+
+    /// Returns the index of the DPI scale `percent` step relative to the recommended DPI scale step,
+    /// or `None` if `percent` is not one of the allowed `steps`.
+    #[cfg_attr(not(all(windows, feature = "unstable-scaling")), allow(dead_code))]
+    pub(crate) fn relative_step(&self, percent: u32) -> Option<i32> {
+        let index = self.steps.iter().position(|&step| step == percent)?;
+        let recommended = self
+            .steps
+            .iter()
+            .position(|&step| step == self.recommended)?;
+
+        Some(index as i32 - recommended as i32)
+    }
+}
+
+/// Describes a display's supported fullscreen display mode.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DisplayMode {
+    /// Display mode dimensions.
+    pub dimensions: Dimensions,
+    /// Refresh rate in Hz.
+    pub refresh_rate: u32,
+    /// Refresh rate numerator, such that numerator/denominator gives the refresh rate in Hz.
+    pub refresh_rate_num: u32,
+    /// Refresh rate denominator, such that numerator/denominator gives the refresh rate in Hz.
+    pub refresh_rate_denom: u32,
+    /// Display mode upscale mode.
+    pub upscale_mode: UpscaleMode,
+}
 
-    display_modes[found].dimensions
+impl DisplayMode {
+    /// Returns the precise refresh rate in Hz, as given by numerator/denominator.
+    pub(crate) fn precise_refresh_rate(&self) -> f64 {
+        if self.refresh_rate_denom == 0 {
+            self.refresh_rate as f64
+        } else {
+            self.refresh_rate_num as f64 / self.refresh_rate_denom as f64
+        }
+    }
+
+    /// Returns the duration of a single frame (i.e. the vblank interval) in this display mode,
+    /// computed from the exact refresh rate numerator / denominator
+    /// (e.g. `16.683ms` for `59.94Hz` (`60000 / 1001`)).
+    ///
+    /// Returns a zero duration if the refresh rate is unknown (zero).
+    pub fn frame_duration(&self) -> Duration {
+        let (num, denom) = if self.refresh_rate_num == 0 || self.refresh_rate_denom == 0 {
+            (self.refresh_rate, 1)
+        } else {
+            (self.refresh_rate_num, self.refresh_rate_denom)
+        };
+
+        if num == 0 {
+            return Duration::from_nanos(0);
+        }
+
+        const NANOS_PER_SEC: u128 = 1_000_000_000;
+
+        // Round to nearest.
+        let num = num as u128;
+        let nanos = (denom as u128 * NANOS_PER_SEC + num / 2) / num;
+
+        Duration::from_nanos(nanos as u64)
+    }
+}
+
+impl Display for DisplayMode {
+    /// Formats the display mode as `<width>x<height>@<refresh rate>Hz`,
+    /// with the precise refresh rate rounded to at most 3 decimal places (e.g. `2560x1440@143.998Hz`).
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        let refresh_rate = format!("{:.3}", self.precise_refresh_rate());
+        let refresh_rate = refresh_rate.trim_end_matches('0').trim_end_matches('.');
+
+        write!(f, "{}@{}Hz", self.dimensions.wxh(), refresh_rate)
+    }
+}
+
+impl FromStr for DisplayMode {
+    type Err = ();
+
+    /// Parses the display mode formatted as `<width>x<height>@<refresh rate>Hz` (e.g. `2560x1440@143.998Hz`).
+    ///
+    /// The dimensions are parsed as [`Dimensions`].
+    /// The refresh rate may be fractional, and the `Hz` suffix is optional and case-insensitive.
+    /// Whitespace around the numbers is ignored (e.g. `1920 x 1080 @ 60 hz`).
+    ///
+    /// The upscale mode of the parsed display mode is unknown.
+    ///
+    /// [`Dimensions`]: struct.Dimensions.html
+    fn from_str(s: &str) -> Result<Self, ()> {
+        let (dimensions, refresh_rate) = s.split_once('@').ok_or(())?;
+
+        let dimensions = dimensions.parse()?;
+        let (refresh_rate_num, refresh_rate_denom) = parse_refresh_rate(refresh_rate)?;
+
+        Ok(Self {
+            dimensions,
+            refresh_rate: refresh_rate_num / refresh_rate_denom,
+            refresh_rate_num,
+            refresh_rate_denom,
+            upscale_mode: UpscaleMode::Unknown,
+        })
+    }
+}
+
+/// Parses the non-zero (possibly fractional) refresh rate in Hz, with an optional case-insensitive `Hz` suffix
+/// (e.g. `59.94Hz`), returning it as a (reduced) numerator / denominator pair.
+fn parse_refresh_rate(refresh_rate: &str) -> Result<(u32, u32), ()> {
+    let refresh_rate = refresh_rate.trim();
+
+    let refresh_rate = match refresh_rate
+        .len()
+        .checked_sub(2)
+        .and_then(|suffix| refresh_rate.get(suffix..))
+    {
+        Some(suffix) if suffix.eq_ignore_ascii_case("hz") => {
+            refresh_rate[..refresh_rate.len() - 2].trim_end()
+        }
+        _ => refresh_rate,
+    };
+
+    let (integer, fraction) = refresh_rate.split_once('.').unwrap_or((refresh_rate, ""));
+
+    if integer.is_empty()
+        || !integer
+            .bytes()
+            .chain(fraction.bytes())
+            .all(|c| c.is_ascii_digit())
+    {
+        return Err(());
+    }
+
+    let mut num: u32 = integer.parse().map_err(|_| ())?;
+    let mut denom: u32 = 1;
+
+    for digit in fraction.bytes() {
+        num = num
+            .checked_mul(10)
+            .and_then(|num| num.checked_add((digit - b'0') as u32))
+            .ok_or(())?;
+        denom = denom.checked_mul(10).ok_or(())?;
+    }
+
+    if num == 0 {
+        return Err(());
+    }
+
+    let gcd = gcd(num, denom);
+
+    Ok((num / gcd, denom / gcd))
+}
+
+fn gcd(mut l: u32, mut r: u32) -> u32 {
+    while r != 0 {
+        let rem = l % r;
+        l = r;
+        r = rem;
+    }
+
+    l
+}
+
+/// Result of [`validating`] a requested display mode against the display's supported display modes.
+///
+/// [`validating`]: struct.DisplayInfo.html#method.validate_mode
+#[cfg(not(feature = "no-modes"))]
+#[derive(Clone, Copy, Debug)]
+pub enum ModeValidation {
+    /// The requested display mode is supported.
+    /// Contains the matching supported display mode,
+    /// whose precise refresh rate may slightly differ from the requested one (e.g. `59.94Hz` vs `60Hz`).
+    Supported(DisplayMode),
+    /// The requested display mode is not supported.
+    /// Contains the nearest supported display mode -
+    /// the one with the closest (by area) dimensions, then with the closest refresh rate.
+    Unsupported(DisplayMode),
+    /// The display has no supported display modes to validate against
+    /// (e.g. a display provided by a custom [`display backend`](trait.DisplayBackend.html)).
+    NoModes,
+}
+
+#[cfg(not(feature = "no-modes"))]
+impl ModeValidation {
+    /// Returns `true` if the requested display mode is supported.
+    pub fn is_supported(&self) -> bool {
+        match self {
+            ModeValidation::Supported(_) => true,
+            ModeValidation::Unsupported(_) | ModeValidation::NoModes => false,
+        }
+    }
+
+    /// Returns the matching supported display mode, or the nearest supported display mode,
+    /// or `None` if the display has no supported display modes.
+    pub fn mode(&self) -> Option<DisplayMode> {
+        match self {
+            ModeValidation::Supported(mode) | ModeValidation::Unsupported(mode) => Some(*mode),
+            ModeValidation::NoModes => None,
+        }
+    }
+}
+
+/// Describes the display's rectangles w.r.t. the virtual display.
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DisplayRects {
+    /// Display (non-work, a.k.a. full) rectangle w.r.t. the virtual display.
+    pub virtual_rect: Rectangle,
+    /// Display work (e.g. with the taskbar subtracted) rectangle w.r.t. the virtual display.
+    pub work_rect: Rectangle,
+}
+
+impl DisplayRects {
+    /// Returns the margins of the display's full (virtual) rectangle excluded from its work rectangle,
+    /// i.e. the display edges reserved by the taskbar / app bars.
+    pub fn reserved_margins(&self) -> Margins {
+        Margins::between(&self.virtual_rect, &self.work_rect)
+    }
+}
+
+impl Display for DisplayRects {
+    /// Formats the display rectangle followed by the work rectangle (e.g. `[0, 0]:[1920, 1080] (work: [0, 0]:[1920, 1040])`).
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{} (work: {})", self.virtual_rect, self.work_rect)
+    }
+}
+
+/// Describes a single enumerated system display.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DisplayInfo {
+    /// Stable display identifier.
+    pub id: DisplayId,
+    /// Display's friendly name, if any.
+    ///
+    /// Falls back to the monitor's (often generic) description if the friendly name is unknown,
+    /// see [`names`](#structfield.names).
+    pub name: Option<DisplayName>,
+    /// All names the platform reported for the display.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub names: DisplayNames,
+    /// Whether the display is the system's primary display.
+    pub is_primary: bool,
+    /// The display's rectangles w.r.t. the virtual display.
+    pub rects: DisplayRects,
+    /// The display's physical connection type.
+    pub connection: ConnectionType,
+    /// The display's current display mode.
+    pub current_mode: DisplayMode,
+    /// The display's current orientation.
+    pub orientation: Orientation,
+    /// The display's preferred display mode.
+    ///
+    /// If the platform failed to report it (e.g. for some TV's and display adapters),
+    /// this is the largest (by area, then by refresh rate) of the display's supported display modes,
+    /// and `preferred_mode_is_fallback` is `true`.
+    pub preferred_mode: DisplayMode,
+    /// Whether the `preferred_mode` was not reported by the platform, but assumed to be the largest supported display mode.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub preferred_mode_is_fallback: bool,
+    /// The display's supported (fullscreen) display modes.
+    /// At least one display mode is supported by any display enumerated by the native backend
+    /// (but the displays provided by a custom [`display backend`](trait.DisplayBackend.html) may have none).
+    ///
+    /// NOTE - not available with the `no-modes` feature.
+    #[cfg(not(feature = "no-modes"))]
+    pub display_modes: Vec<DisplayMode>,
+    /// The dimensions of the smallest (by area) of the display's supported display modes,
+    /// or zero dimensions if the display has no display modes.
+    #[cfg(not(feature = "no-modes"))]
+    pub min_dimensions: Dimensions,
+    /// The display's DPI scale value.
+    /// `1.0` is the default and means no scaling.
+    /// Higher values like `1.25`, `1.5`, `2.0` mean higher zoom.
+    ///
+    /// NOTE - this is the horizontal DPI scale value, see [`dpi_scale_exact`](#method.dpi_scale_exact).
+    pub dpi_scale: f32,
+    /// The display's vertical DPI scale value, if it differs from the (horizontal) `dpi_scale`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub dpi_scale_y: Option<f32>,
+    /// Where the display's info was obtained from.
+    /// Some of the info may be degraded / missing if it's not `DisplaySource::DisplayConfig`.
+    pub source: DisplaySource,
+    /// The display's overscan margins, in pixels, if known.
+    /// Parts of the display's rectangle within these margins are not visible (e.g. on TV's connected via HDMI).
+    ///
+    /// NOTE - currently there's no reliable way to obtain this on Windows, so this is always `None`.
+    pub overscan: Option<Margins>,
+    /// Whether the OS may dynamically change the display's refresh rate (e.g. Windows' Dynamic Refresh Rate),
+    /// in which case the `current_mode`'s refresh rate is not a reliable predictor of the vblank interval.
+    pub current_mode_is_dynamic: bool,
+    /// The maximum refresh rate (numerator / denominator) the display's refresh rate may be dynamically boosted to,
+    /// if `current_mode_is_dynamic` and it's known.
+    pub dynamic_refresh_ceiling: Option<(u32, u32)>,
+    /// DPI scaling options the OS offers for the display (e.g. for the settings UI's), if known.
+    pub dpi_options: Option<DpiOptions>,
+    /// The display's physical image size in millimeters in the display's unrotated orientation,
+    /// as reported by the monitor, if known.
+    ///
+    /// NOTE - may be inaccurate, and is often unknown for TV's and projectors.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub physical_size: Option<Dimensions>,
+    /// Video signal timing of the display's current display mode (e.g. for the scanout latency computations), if known.
+    ///
+    /// NOTE - only known for the displays enumerated via the display configuration API's on Windows.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub signal_timing: Option<SignalTiming>,
+    /// The display's HDR capabilities (e.g. to configure the tone mapping), if it's HDR-capable and they are known.
+    ///
+    /// NOTE - reported regardless of whether HDR output is currently enabled in the OS.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub hdr_caps: Option<HdrCaps>,
+    /// The display's color primaries and white point, as reported by the monitor (e.g. for the color-managed rendering), if known.
+    ///
+    /// See [`Colorimetry::srgb_coverage`](struct.Colorimetry.html#method.srgb_coverage) /
+    /// [`Colorimetry::dci_p3_coverage`](struct.Colorimetry.html#method.dci_p3_coverage).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub colorimetry: Option<Colorimetry>,
+    /// The monitor's maximum supported bit depth per color channel (e.g. `10` for the 10-bit capable monitors),
+    /// as reported by its EDID, if known.
+    ///
+    /// NOTE - only reported by the EDID 1.4+ monitors with digital inputs.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub max_bits_per_channel: Option<u8>,
+    /// The display's current output bit depth per color channel (e.g. `8`, or `10` for a 10-bit output pipeline),
+    /// as reported by the graphics driver, if known.
+    ///
+    /// Compare with [`max_bits_per_channel`] to detect the displays which could, but do not currently use a higher bit depth
+    /// (e.g. due to the link bandwidth limits, or the driver settings).
+    ///
+    /// NOTE - only known for the displays enumerated via the display configuration API's on Windows 10 1709+.
+    ///
+    /// [`max_bits_per_channel`]: #structfield.max_bits_per_channel
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub current_bits_per_channel: Option<u8>,
+    /// The monitor's asset info, as reported by its EDID, if known.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub asset: Option<AssetInfo>,
+}
+
+impl DisplayInfo {
+    /// Creates the display info with provided values (e.g. in a custom [`display backend`]).
+    /// The display's `name`, if any, is also its only known (friendly) name; the other optional info is unknown.
+    ///
+    /// NOTE - the display's `id` must be unique among the enumerated displays, and should be stable across the enumerations,
+    /// as it is what tells the displays apart in the [`display references`], [`display event subscriptions`] and the [`snapshot diffs`].
+    /// Derive it from the backend's own display identifier (e.g. the connector / port), rather than the display's name,
+    /// which the identical monitor models share.
+    ///
+    /// [`display backend`]: trait.DisplayBackend.html
+    /// [`display references`]: struct.DisplayRef.html
+    /// [`display event subscriptions`]: struct.Displays.html#method.on_display
+    /// [`snapshot diffs`]: struct.DisplaysSnapshot.html#method.diff
+    pub fn new(
+        id: DisplayId,
+        name: Option<DisplayName>,
+        is_primary: bool,
+        rects: DisplayRects,
+        connection: ConnectionType,
+        current_mode: DisplayMode,
+        preferred_mode: DisplayMode,
+        #[cfg_attr(feature = "no-modes", allow(unused_variables))] display_modes: Vec<DisplayMode>,
+        dpi_scale: f32,
+        source: DisplaySource,
+    ) -> Self {
+        #[cfg(not(feature = "no-modes"))]
+        let min_dimensions = DisplayInfo::calc_min_dimensions(&display_modes);
+
+        Self {
+            id,
+            names: DisplayNames {
+                friendly: name.as_ref().map(DisplayName::to_string),
+                ..DisplayNames::default()
+            },
+            name,
+            is_primary,
+            rects,
+            connection,
+            current_mode,
+            orientation: Orientation::default(),
+            preferred_mode,
+            preferred_mode_is_fallback: false,
+            #[cfg(not(feature = "no-modes"))]
+            display_modes,
+            #[cfg(not(feature = "no-modes"))]
+            min_dimensions,
+            dpi_scale,
+            dpi_scale_y: None,
+            source,
+            overscan: None,
+            current_mode_is_dynamic: false,
+            dynamic_refresh_ceiling: None,
+            dpi_options: None,
+            physical_size: None,
+            signal_timing: None,
+            hdr_caps: None,
+            colorimetry: None,
+            max_bits_per_channel: None,
+            current_bits_per_channel: None,
+            asset: None,
+        }
+    }
+
+    /// Returns the display's pixel density in pixels per inch,
+    /// computed from its [`physical size`] and the preferred (native) display mode's dimensions, if known.
+    ///
+    /// [`physical size`]: #structfield.physical_size
+    pub fn ppi(&self) -> Option<f32> {
+        let physical_size = self.physical_size?;
+        let dimensions = self.preferred_mode.dimensions;
+
+        let diagonal = |width: u32, height: u32| (width as f32).hypot(height as f32);
+
+        let diagonal_inches = diagonal(physical_size.width, physical_size.height) / 25.4;
+
+        if diagonal_inches > 0.0 {
+            Some(diagonal(dimensions.width, dimensions.height) / diagonal_inches)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the display's [`physical size`] as (width, height) in millimeters, if known.
+    ///
+    /// [`physical size`]: #structfield.physical_size
+    pub fn physical_size_mm(&self) -> Option<(Mm<f32>, Mm<f32>)> {
+        self.physical_size
+            .map(|size| (Mm(size.width as f32), Mm(size.height as f32)))
+    }
+
+    /// Converts the length in pixels to millimeters on the display, if its [`pixel density`] is known.
+    ///
+    /// [`pixel density`]: #method.ppi
+    pub fn px_to_mm(&self, px: Px<i32>) -> Option<Mm<f32>> {
+        self.ppi().map(|ppi| px.to_mm(ppi))
+    }
+
+    /// Converts the physical length in millimeters to pixels on the display, if its [`pixel density`] is known
+    /// (e.g. to draw a ruler, or an object at its real size).
+    ///
+    /// [`pixel density`]: #method.ppi
+    pub fn mm_to_px(&self, mm: Mm<f32>) -> Option<Px<i32>> {
+        self.ppi().map(|ppi| mm.to_px(ppi))
+    }
+
+    /// Converts the physical length in points to pixels on the display, if its [`pixel density`] is known
+    /// (e.g. to size the text at its real print size).
+    ///
+    /// [`pixel density`]: #method.ppi
+    pub fn pt_to_px(&self, pt: Pt<f32>) -> Option<Px<i32>> {
+        self.ppi().map(|ppi| pt.to_px(ppi))
+    }
+
+    /// Returns the display's [`pixel density category`], if its [`pixel density`] is known.
+    ///
+    /// [`pixel density category`]: enum.DensityClass.html
+    /// [`pixel density`]: #method.ppi
+    pub fn density_class(&self) -> Option<DensityClass> {
+        self.ppi().map(DensityClass::from_ppi)
+    }
+
+    /// Returns the display's HDR-related [`metrics`], as reported by the monitor.
+    ///
+    /// [`metrics`]: struct.HdrMetrics.html
+    pub fn hdr_metrics(&self) -> HdrMetrics {
+        HdrMetrics {
+            supports_pq: self.hdr_caps.is_some_and(|hdr_caps| hdr_caps.supports_pq),
+            max_luminance: self.hdr_caps.and_then(|hdr_caps| hdr_caps.max_luminance),
+            min_luminance: self.hdr_caps.and_then(|hdr_caps| hdr_caps.min_luminance),
+            max_bits_per_channel: self.max_bits_per_channel,
+            dci_p3_coverage: self
+                .colorimetry
+                .map(|colorimetry| colorimetry.dci_p3_coverage()),
+        }
+    }
+
+    /// Returns the rough [`HDR tier`] the display's reported HDR capabilities correspond to,
+    /// or `None` if the display is not HDR-capable (or its capabilities are unknown).
+    ///
+    /// See [`HdrMetrics::tier`] for the heuristic, and [`hdr_metrics`] for the numbers it's based on.
+    ///
+    /// [`HDR tier`]: enum.HdrTier.html
+    /// [`HdrMetrics::tier`]: struct.HdrMetrics.html#method.tier
+    /// [`hdr_metrics`]: #method.hdr_metrics
+    pub fn hdr_tier(&self) -> Option<HdrTier> {
+        self.hdr_metrics().tier()
+    }
+
+    /// Returns `true` if the display was created by an indirect display driver
+    /// (e.g. a virtual monitor of a streaming app, a USB display adapter or a headless display dongle).
+    ///
+    /// See [`DisplaySource::Indirect`].
+    ///
+    /// [`DisplaySource::Indirect`]: enum.DisplaySource.html#variant.Indirect
+    pub fn is_indirect(&self) -> bool {
+        self.source.indirect_driver().is_some()
+    }
+
+    /// Returns the recommended UI scale for the display (e.g. `1.5` for a `27" 4K` display), in `0.25` steps,
+    /// for applications which don't use the OS [`DPI scale`] (e.g. immediate mode GUI's).
+    ///
+    /// Computed from the display's [`pixel density`], if known, or the OS [`DPI scale`] otherwise.
+    /// Never less than `1.0`.
+    ///
+    /// [`DPI scale`]: #structfield.dpi_scale
+    /// [`pixel density`]: #method.ppi
+    pub fn recommended_ui_scale(&self) -> f32 {
+        match self.ppi() {
+            Some(ppi) => ((ppi / UNSCALED_PPI * 4.0).round() / 4.0).max(1.0),
+            None => self.dpi_scale.max(1.0),
+        }
+    }
+
+    /// Returns the display's (horizontal, vertical) DPI scale values.
+    ///
+    /// These only differ for the displays with non-square DPI scaling, which are rare, but do exist.
+    pub fn dpi_scale_exact(&self) -> (f32, f32) {
+        (self.dpi_scale, self.dpi_scale_y.unwrap_or(self.dpi_scale))
+    }
+
+    /// Returns the [`query`] over the display's supported [`display modes`],
+    /// used to filter and sort them
+    /// (e.g. `display_info.modes().min_refresh(120).min_resolution(1920, 1080).aspect(16, 9).iter()`).
+    ///
+    /// [`query`]: struct.ModeQuery.html
+    /// [`display modes`]: struct.DisplayMode.html
+    #[cfg(not(feature = "no-modes"))]
+    pub fn modes(&self) -> ModeQuery<'_> {
+        ModeQuery::new(&self.display_modes)
+    }
+
+    /// Returns the [`dimensions`] of the display's [`display mode`] closest (by area) to provided `dimensions`
+    /// based on provided `flags`, or `None` if the display has no display modes.
+    ///
+    /// [`dimensions`]: struct.Dimensions.html
+    /// [`display mode`]: struct.DisplayMode.html
+    #[cfg(not(feature = "no-modes"))]
+    pub fn closest_dimensions(
+        &self,
+        dimensions: Dimensions,
+        flags: ClosestDimensionsFlags,
+    ) -> Option<Dimensions> {
+        closest_dimensions(&self.display_modes, dimensions, flags)
+    }
+
+    /// Returns the [`dimensions`] of the display's [`display mode`] closest to provided `dimensions`
+    /// based on provided `flags`, measuring the distance with provided [`metric`],
+    /// or `None` if the display has no display modes.
+    ///
+    /// [`dimensions`]: struct.Dimensions.html
+    /// [`display mode`]: struct.DisplayMode.html
+    /// [`metric`]: enum.DistanceMetric.html
+    #[cfg(not(feature = "no-modes"))]
+    pub fn closest_dimensions_by(
+        &self,
+        dimensions: Dimensions,
+        flags: ClosestDimensionsFlags,
+        metric: DistanceMetric,
+    ) -> Option<Dimensions> {
+        closest_dimensions_by(&self.display_modes, dimensions, flags, metric)
+    }
+
+    /// Returns the rectangle, w.r.t. the virtual display, a borderless fullscreen window on this display should cover.
+    ///
+    /// If `cover_taskbar` is `true`, this is the display's full (virtual) rectangle, otherwise its work rectangle.
+    ///
+    /// NOTE - display rectangles are enumerated in physical pixels.
+    /// Windows virtualizes the coordinates for DPI-unaware (and system DPI-aware, on displays with non-system DPI) windows,
+    /// so the returned rectangle must be applied to the window from a per-monitor DPI-aware thread
+    /// (or manually scaled by the [`DPI scale`] for DPI-unaware windows).
+    ///
+    /// [`DPI scale`]: #structfield.dpi_scale
+    pub fn borderless_rect(&self, cover_taskbar: bool) -> Rectangle {
+        if cover_taskbar {
+            self.rects.virtual_rect
+        } else {
+            self.rects.work_rect
+        }
+    }
+
+    /// Same as [`borderless_rect`], but the returned rectangle is one pixel taller.
+    ///
+    /// A window exactly covering the display may be treated by the OS as an exclusive fullscreen one
+    /// (e.g. the taskbar and notifications are not shown over it, and the presentation
+    /// switches to the flip model / independent flip with its quirks).
+    /// Making the borderless window one pixel taller than the display is a common workaround.
+    ///
+    /// NOTE - the extra pixel row may overlap the adjacent display below, if any.
+    ///
+    /// [`borderless_rect`]: #method.borderless_rect
+    pub fn borderless_rect_oversized(&self, cover_taskbar: bool) -> Rectangle {
+        let mut rect = self.borderless_rect(cover_taskbar);
+        rect.dimensions.height = rect.dimensions.height.saturating_add(1);
+        rect
+    }
+
+    /// Returns the "safe area" rectangle of the display, w.r.t. the virtual display -
+    /// the part of the display's full (virtual) rectangle guaranteed to be visible,
+    /// used to keep the critical UI elements within.
+    ///
+    /// The display's [`overscan`] margins, if known, are excluded first;
+    /// then `margin_percent` percent of the remaining rectangle's width / height is excluded on each side
+    /// (e.g. `5.0` for the common "action safe" area, `10.0` for the "title safe" area).
+    ///
+    /// [`overscan`]: #structfield.overscan
+    pub fn safe_area(&self, margin_percent: f32) -> Rectangle {
+        let mut rect = self.rects.virtual_rect;
+
+        if let Some(overscan) = self.overscan {
+            rect = rect.shrink(overscan);
+        }
+
+        let margin_percent = margin_percent.clamp(0.0, 50.0) / 100.0;
+        let margin_x = (rect.width() as f32 * margin_percent).round() as u32;
+        let margin_y = (rect.height() as f32 * margin_percent).round() as u32;
+
+        rect.shrink(Margins::new(margin_x, margin_y, margin_x, margin_y))
+    }
+
+    /// Checks whether the requested display `mode` (its dimensions and refresh rate)
+    /// is in the display's list of supported display modes, returning the matching supported display mode,
+    /// or the nearest supported display mode otherwise.
+    ///
+    /// Refresh rates within `0.5Hz` of each other are considered matching (e.g. `59.94Hz` and `60Hz`).
+    ///
+    /// Use this to validate the user-provided display modes before trying to set them.
+    #[cfg(not(feature = "no-modes"))]
+    pub fn validate_mode(&self, mode: &DisplayMode) -> ModeValidation {
+        validate_mode(&self.display_modes, mode)
+    }
+
+    /// Returns the supported display mode with the current display mode's dimensions
+    /// and the refresh rate closest to `refresh_rate` (in Hz, e.g. `119.88`), if any.
+    ///
+    /// NOTE - the returned display mode's dimensions are in the display's unrotated [`orientation`],
+    /// as are all the supported display modes.
+    ///
+    /// [`orientation`]: #structfield.orientation
+    #[cfg(not(feature = "no-modes"))]
+    pub fn closest_refresh_rate(&self, refresh_rate: f64) -> Option<DisplayMode> {
+        let dimensions = if self.orientation.is_portrait() {
+            Dimensions::new(
+                self.current_mode.dimensions.height,
+                self.current_mode.dimensions.width,
+            )
+        } else {
+            self.current_mode.dimensions
+        };
+
+        closest_refresh_rate(&self.display_modes, dimensions, refresh_rate)
+    }
+
+    /// Returns the supported display modes which fit the `content` (e.g. an emulated console's screen)
+    /// with an integer scale factor (i.e. without the blurry / uneven pixel scaling),
+    /// with the largest factor and the remaining letterbox for each.
+    ///
+    /// The display modes are sorted by the fraction of the display covered by the scaled content (most covered first),
+    /// then by the scale factor, then by the refresh rate (highest first).
+    ///
+    /// The `content` dimensions are in the display's current [`orientation`].
+    ///
+    /// [`orientation`]: #structfield.orientation
+    #[cfg(not(feature = "no-modes"))]
+    pub fn integer_scaled_modes(&self, content: Dimensions) -> Vec<IntegerScaledMode> {
+        integer_scaled_modes(&self.display_modes, content, self.orientation.is_portrait())
+    }
+
+    /// Parses the (possibly partial) display `mode` string and returns the matching supported display mode, if any.
+    ///
+    /// Accepts the display mode strings as parsed by [`DisplayMode::from_str`] (e.g. `2560x1440@144Hz`),
+    /// [`validated`] against the display's supported display modes (so e.g. `2560x1440@144` matches `2560x1440@143.998Hz`).
+    /// The refresh rate may be omitted (e.g. `1920x1080`) -
+    /// the supported display mode with these dimensions and the highest refresh rate is returned then.
+    ///
+    /// Use this to parse the display modes from the config files / command line arguments.
+    ///
+    /// [`DisplayMode::from_str`]: struct.DisplayMode.html#method.from_str
+    /// [`validated`]: #method.validate_mode
+    #[cfg(not(feature = "no-modes"))]
+    pub fn find_mode(&self, mode: &str) -> Option<DisplayMode> {
+        find_mode(&self.display_modes, mode)
+    }
+
+    /// Returns the dimensions of the smallest (by area) display mode from the array of `display_modes`,
+    /// or zero dimensions if it's empty.
+    #[cfg(not(feature = "no-modes"))]
+    fn calc_min_dimensions(display_modes: &[DisplayMode]) -> Dimensions {
+        // Exact areas - `Dimensions::area()` saturates.
+        display_modes
+            .iter()
+            .min_by_key(|mode| mode.dimensions.width as u64 * mode.dimensions.height as u64)
+            .map(|mode| mode.dimensions)
+            .unwrap_or_default()
+    }
+}
+
+/// Tolerance for comparing the refresh rates of the display modes, in Hz.
+#[cfg(not(feature = "no-modes"))]
+const REFRESH_RATE_TOLERANCE: f64 = 0.5;
+
+/// Returns the display mode from the array of `display_modes` with `dimensions` and the closest refresh rate to `refresh_rate`, if any.
+#[cfg(not(feature = "no-modes"))]
+fn closest_refresh_rate(
+    display_modes: &[DisplayMode],
+    dimensions: Dimensions,
+    refresh_rate: f64,
+) -> Option<DisplayMode> {
+    display_modes
+        .iter()
+        .filter(|mode| mode.dimensions == dimensions)
+        .min_by(|l, r| {
+            let l = (l.precise_refresh_rate() - refresh_rate).abs();
+            let r = (r.precise_refresh_rate() - refresh_rate).abs();
+            l.partial_cmp(&r).unwrap_or(std::cmp::Ordering::Equal)
+        })
+        .copied()
+}
+
+#[cfg(not(feature = "no-modes"))]
+fn validate_mode(display_modes: &[DisplayMode], mode: &DisplayMode) -> ModeValidation {
+    let refresh_rate = mode.precise_refresh_rate();
+
+    if let Some(supported) = closest_refresh_rate(display_modes, mode.dimensions, refresh_rate) {
+        if (supported.precise_refresh_rate() - refresh_rate).abs() < REFRESH_RATE_TOLERANCE {
+            return ModeValidation::Supported(supported);
+        }
+    }
+
+    // `None` only if there are no display modes.
+    closest_dimensions(
+        display_modes,
+        mode.dimensions,
+        ClosestDimensionsFlags::Closest,
+    )
+    .and_then(|dimensions| closest_refresh_rate(display_modes, dimensions, refresh_rate))
+    .map_or(ModeValidation::NoModes, ModeValidation::Unsupported)
+}
+
+/// See [`DisplayInfo::integer_scaled_modes`](struct.DisplayInfo.html#method.integer_scaled_modes).
+///
+/// `portrait` is `true` if the `display_modes` must be rotated to match the `content`.
+#[cfg(not(feature = "no-modes"))]
+fn integer_scaled_modes(
+    display_modes: &[DisplayMode],
+    content: Dimensions,
+    portrait: bool,
+) -> Vec<IntegerScaledMode> {
+    if content.width == 0 || content.height == 0 {
+        return Vec::new();
+    }
+
+    let mut modes: Vec<_> = display_modes
+        .iter()
+        .filter_map(|&mode| {
+            let dimensions = if portrait {
+                Dimensions::new(mode.dimensions.height, mode.dimensions.width)
+            } else {
+                mode.dimensions
+            };
+
+            let factor = (dimensions.width / content.width).min(dimensions.height / content.height);
+
+            if factor == 0 {
+                return None;
+            }
+
+            let horizontal = dimensions.width - content.width * factor;
+            let vertical = dimensions.height - content.height * factor;
+
+            Some(IntegerScaledMode {
+                mode,
+                factor,
+                letterbox: Margins::new(
+                    horizontal / 2,
+                    vertical / 2,
+                    horizontal - horizontal / 2,
+                    vertical - vertical / 2,
+                ),
+            })
+        })
+        .collect();
+
+    // Compare the covered fractions (`covered_0 / area_0` vs `covered_1 / area_1`) exactly.
+    let coverage = |scaled: &IntegerScaledMode| {
+        (
+            content.area() as u64 * (scaled.factor as u64).pow(2),
+            scaled.mode.dimensions.area() as u64,
+        )
+    };
+
+    modes.sort_by(|l, r| {
+        let ((covered_l, area_l), (covered_r, area_r)) = (coverage(l), coverage(r));
+
+        (covered_r as u128 * area_l as u128)
+            .cmp(&(covered_l as u128 * area_r as u128))
+            .then(r.factor.cmp(&l.factor))
+            .then(
+                (r.mode.refresh_rate_num as u64 * l.mode.refresh_rate_denom.max(1) as u64).cmp(
+                    &(l.mode.refresh_rate_num as u64 * r.mode.refresh_rate_denom.max(1) as u64),
+                ),
+            )
+    });
+
+    modes
+}
+
+#[cfg(not(feature = "no-modes"))]
+fn find_mode(display_modes: &[DisplayMode], mode: &str) -> Option<DisplayMode> {
+    if mode.contains('@') {
+        match validate_mode(display_modes, &mode.parse().ok()?) {
+            ModeValidation::Supported(mode) => Some(mode),
+            ModeValidation::Unsupported(_) | ModeValidation::NoModes => None,
+        }
+    } else {
+        let dimensions: Dimensions = mode.parse().ok()?;
+
+        display_modes
+            .iter()
+            .filter(|mode| mode.dimensions == dimensions)
+            .max_by(|l, r| {
+                l.precise_refresh_rate()
+                    .partial_cmp(&r.precise_refresh_rate())
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .copied()
+    }
+}
+
+/// Determines which display mode to pick when looking for one
+/// with closest dimensions to provided value.
+#[cfg(not(feature = "no-modes"))]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ClosestDimensionsFlags {
+    /// Pick the display mode with closest dimensions to provided value,
+    /// both smaller or larger.
+    Closest,
+    /// Pick the display mode with closest dimensions to provided value,
+    /// and additionally not wider/taller, if any (otherwise the closest one).
+    ClosestSmallerOrEqual,
+}
+
+/// Determines how the distance between the display modes' dimensions and provided value is measured
+/// when looking for the closest ones (see [`closest_dimensions_by`]).
+///
+/// Display modes at the same distance are further compared as documented for each metric;
+/// remaining ties are broken in favour of the display mode listed first.
+///
+/// [`closest_dimensions_by`]: fn.closest_dimensions_by.html
+#[cfg(not(feature = "no-modes"))]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DistanceMetric {
+    /// Smallest difference in area.
+    /// Ties (e.g. `1920x1080` vs `1620x1280`, which have the same area) are broken by the per-axis delta.
+    ByArea,
+    /// Smallest per-axis delta, i.e. the sum of the width and height differences.
+    /// Ties are broken by the difference in area.
+    ByPerAxisDelta,
+    /// Closest aspect ratio, then the smallest difference in area.
+    /// Ties are broken by the per-axis delta.
+    ByAspectThenArea,
+}
+
+/// Returns the [`dimensions`] of the [`display mode`] closest (by area) to provided `dimensions`
+/// based on provided `flags`, or `None` if `display_modes` is empty.
+///
+/// See [`closest_dimensions_by`] to use other distance metrics.
+///
+/// [`dimensions`]: struct.Dimensions.html
+/// [`display mode`]: struct.DisplayMode.html
+/// [`closest_dimensions_by`]: fn.closest_dimensions_by.html
+#[cfg(not(feature = "no-modes"))]
+pub fn closest_dimensions(
+    display_modes: &[DisplayMode],
+    dimensions: Dimensions,
+    flags: ClosestDimensionsFlags,
+) -> Option<Dimensions> {
+    closest_dimensions_by(display_modes, dimensions, flags, DistanceMetric::ByArea)
+}
+
+/// Returns the [`dimensions`] of the [`display mode`] closest to provided `dimensions`
+/// based on provided `flags`, measuring the distance with provided `metric`,
+/// or `None` if `display_modes` is empty.
+///
+/// [`dimensions`]: struct.Dimensions.html
+/// [`display mode`]: struct.DisplayMode.html
+#[cfg(not(feature = "no-modes"))]
+pub fn closest_dimensions_by(
+    display_modes: &[DisplayMode],
+    dimensions: Dimensions,
+    flags: ClosestDimensionsFlags,
+    metric: DistanceMetric,
+) -> Option<Dimensions> {
+    // Exact areas - `Dimensions::area()` saturates.
+    let area = |dimensions: Dimensions| dimensions.width as u64 * dimensions.height as u64;
+    let target_area = area(dimensions);
+
+    let area_difference = |mode: &DisplayMode| area(mode.dimensions).abs_diff(target_area);
+    let axis_delta = |mode: &DisplayMode| {
+        mode.dimensions.width.abs_diff(dimensions.width) as u64
+            + mode.dimensions.height.abs_diff(dimensions.height) as u64
+    };
+    // Aspect ratio difference `|w_0 / h_0 - w_1 / h_1|` as a fraction `|w_0 * h_1 - w_1 * h_0| / (h_0 * h_1)`.
+    let aspect_difference = |mode: &DisplayMode| {
+        let num = (mode.dimensions.width as u128 * dimensions.height as u128)
+            .abs_diff(dimensions.width as u128 * mode.dimensions.height as u128);
+        let denom = (mode.dimensions.height as u128 * dimensions.height as u128).max(1);
+        (num, denom)
+    };
+
+    let compare = |l: &&DisplayMode, r: &&DisplayMode| match metric {
+        DistanceMetric::ByArea => area_difference(l)
+            .cmp(&area_difference(r))
+            .then(axis_delta(l).cmp(&axis_delta(r))),
+        DistanceMetric::ByPerAxisDelta => axis_delta(l)
+            .cmp(&axis_delta(r))
+            .then(area_difference(l).cmp(&area_difference(r))),
+        DistanceMetric::ByAspectThenArea => {
+            let ((num_l, denom_l), (num_r, denom_r)) = (aspect_difference(l), aspect_difference(r));
+
+            num_l
+                .saturating_mul(denom_r)
+                .cmp(&num_r.saturating_mul(denom_l))
+                .then(area_difference(l).cmp(&area_difference(r)))
+                .then(axis_delta(l).cmp(&axis_delta(r)))
+        }
+    };
+
+    let fits = |mode: &&DisplayMode| {
+        mode.dimensions.width <= dimensions.width && mode.dimensions.height <= dimensions.height
+    };
+
+    let found = match flags {
+        ClosestDimensionsFlags::Closest => None,
+        ClosestDimensionsFlags::ClosestSmallerOrEqual => {
+            display_modes.iter().filter(fits).min_by(compare)
+        }
+    };
+
+    found
+        .or_else(|| display_modes.iter().min_by(compare))
+        .map(|mode| mode.dimensions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(not(feature = "no-modes"))]
+    use crate::rectangle::tests::{any_dimensions, CASES};
+    use crate::Position;
+    #[cfg(not(feature = "no-modes"))]
+    use proptest::prelude::*;
+
+    fn mode(
+        width: u32,
+        height: u32,
+        refresh_rate_num: u32,
+        refresh_rate_denom: u32,
+    ) -> DisplayMode {
+        DisplayMode {
+            dimensions: Dimensions::new(width, height),
+            refresh_rate: refresh_rate_num / refresh_rate_denom,
+            refresh_rate_num,
+            refresh_rate_denom,
+            upscale_mode: UpscaleMode::Unknown,
+        }
+    }
+
+    #[cfg(not(feature = "no-modes"))]
+    fn dimensions_and_refresh(mode: DisplayMode) -> (u32, u32, u32) {
+        (
+            mode.dimensions.width,
+            mode.dimensions.height,
+            mode.precise_refresh_rate().round() as u32,
+        )
+    }
+
+    #[test]
+    fn frame_duration() {
+        assert_eq!(
+            mode(1920, 1080, 60, 1).frame_duration().as_nanos(),
+            16_666_667
+        );
+        assert_eq!(
+            mode(1920, 1080, 60000, 1001).frame_duration().as_nanos(),
+            16_683_333
+        );
+        assert_eq!(
+            mode(1920, 1080, 144, 1).frame_duration().as_nanos(),
+            6_944_444
+        );
+
+        let unknown = DisplayMode {
+            refresh_rate_num: 0,
+            refresh_rate_denom: 0,
+            ..mode(1920, 1080, 50, 1)
+        };
+        assert_eq!(unknown.frame_duration(), Duration::from_millis(20));
+
+        let unknown = DisplayMode {
+            refresh_rate: 0,
+            refresh_rate_num: 0,
+            refresh_rate_denom: 0,
+            ..mode(1920, 1080, 50, 1)
+        };
+        assert_eq!(unknown.frame_duration(), Duration::from_nanos(0));
+    }
+
+    #[test]
+    fn dpi_options() {
+        let options = DpiOptions::from_relative(-2, -1, 1).unwrap();
+        assert_eq!(options.steps, vec![100, 125, 150, 175]);
+        assert_eq!(options.recommended, 150);
+        assert_eq!(options.current, 125);
+
+        assert_eq!(options.relative_step(100), Some(-2));
+        assert_eq!(options.relative_step(150), Some(0));
+        assert_eq!(options.relative_step(175), Some(1));
+        assert_eq!(options.relative_step(200), None);
+        assert_eq!(options.relative_step(110), None);
+
+        let options = DpiOptions::from_relative(0, 0, 0).unwrap();
+        assert_eq!(options.steps, vec![100]);
+        assert_eq!(options.recommended, 100);
+        assert_eq!(options.current, 100);
+
+        // Out of bounds.
+        assert!(DpiOptions::from_relative(1, 0, 0).is_none());
+        assert!(DpiOptions::from_relative(-1, -2, 0).is_none());
+        assert!(DpiOptions::from_relative(0, 0, 12).is_none());
+    }
+
+    #[test]
+    fn display_id() {
+        let utf16 = |id: &str| id.encode_utf16().collect::<Vec<_>>();
+
+        let id = DisplayId::from_utf16(&utf16(r"\\?\DISPLAY#DEL40F0#5&1a2b3c4d&0&UID4352"));
+
+        // Case-insensitive.
+        assert_eq!(
+            id,
+            DisplayId::from_utf16(&utf16(r"\\?\display#del40f0#5&1A2B3C4D&0&uid4352"))
+        );
+        assert_ne!(
+            id,
+            DisplayId::from_utf16(&utf16(r"\\?\DISPLAY#DEL40F0#5&1a2b3c4d&0&UID4353"))
+        );
+
+        // Must be stable - identifiers may be persisted.
+        assert_eq!(DisplayId::from_utf16(&[]).0, 0xcbf2_9ce4_8422_2325);
+        assert_eq!(DisplayId::from_utf16(&utf16("A")).0, 0x089b_e207_b544_f1e4);
+        assert_eq!(
+            DisplayId::from_utf16(&utf16("A")).to_string(),
+            "089be207b544f1e4"
+        );
+    }
+
+    #[test]
+    fn display_name() {
+        let utf16 = |name: &str| name.encode_utf16().collect::<Vec<_>>();
+
+        assert!(DisplayName::from_utf16(&[]).is_none());
+        assert!(DisplayName::from_utf16(&[0, 0]).is_none());
+
+        let mut raw = utf16("DELL U2718Q");
+        let name = DisplayName::from_utf16(&raw).unwrap();
+        assert_eq!(name.as_str(), "DELL U2718Q");
+        assert_eq!(name.as_utf16(), &raw[..]);
+
+        // Nul-terminated.
+        raw.extend_from_slice(&[0, 0, 0]);
+        assert_eq!(DisplayName::from_utf16(&raw).unwrap(), name);
+
+        // Reused.
+        let previous = [DisplayName::from_utf16(&utf16("LG TV")).unwrap(), name];
+        let reused = DisplayName::from_utf16_reuse(&raw, &previous).unwrap();
+        assert_eq!(reused.as_str(), "DELL U2718Q");
+        assert!(Arc::ptr_eq(&reused.utf8, &previous[1].utf8));
+
+        // Not reused.
+        let name = DisplayName::from_utf16_reuse(&utf16("DELL P2419H"), &previous).unwrap();
+        assert_eq!(name.as_str(), "DELL P2419H");
+        assert!(previous
+            .iter()
+            .all(|previous| !Arc::ptr_eq(&name.utf8, &previous.utf8)));
+    }
+
+    #[test]
+    fn safe_area() {
+        let mode = mode(1920, 1080, 60, 1);
+        let virtual_rect = Rectangle::new(Position::new(-1920, 0), Dimensions::new(1920, 1080));
+
+        let mut info = DisplayInfo::new(
+            DisplayId::default(),
+            None,
+            false,
+            DisplayRects {
+                virtual_rect,
+                work_rect: virtual_rect,
+            },
+            ConnectionType::HDMI,
+            mode,
+            mode,
+            vec![mode],
+            1.0,
+            DisplaySource::DisplayConfig,
+        );
+
+        assert_eq!(info.safe_area(0.0), virtual_rect);
+        assert_eq!(
+            info.safe_area(5.0),
+            Rectangle::new(Position::new(-1824, 54), Dimensions::new(1728, 972))
+        );
+
+        info.overscan.replace(Margins::new(20, 10, 20, 10));
+
+        assert_eq!(
+            info.safe_area(0.0),
+            Rectangle::new(Position::new(-1900, 10), Dimensions::new(1880, 1060))
+        );
+        assert_eq!(
+            info.safe_area(50.0),
+            Rectangle::new(Position::new(-960, 540), Dimensions::new(0, 0))
+        );
+    }
+
+    #[cfg(not(feature = "no-modes"))]
+    #[test]
+    fn min_dimensions() {
+        let display = |display_modes: Vec<DisplayMode>| {
+            let rect = Rectangle::new(Position::new(0, 0), Dimensions::new(1920, 1080));
+            let current_mode = mode(1920, 1080, 60, 1);
+
+            DisplayInfo::new(
+                DisplayId::default(),
+                None,
+                true,
+                DisplayRects {
+                    virtual_rect: rect,
+                    work_rect: rect,
+                },
+                ConnectionType::Unknown,
+                current_mode,
+                current_mode,
+                display_modes,
+                1.0,
+                DisplaySource::DisplayConfig,
+            )
+        };
+
+        assert_eq!(
+            display(vec![mode(1920, 1080, 60, 1), mode(1280, 720, 60, 1)]).min_dimensions,
+            Dimensions::new(1280, 720)
+        );
+
+        // Areas saturating `u32`.
+        assert_eq!(
+            display(vec![
+                mode(u32::MAX, u32::MAX, 60, 1),
+                mode(u32::MAX, 2, 60, 1)
+            ])
+            .min_dimensions,
+            Dimensions::new(u32::MAX, 2)
+        );
+
+        // No display modes (e.g. a custom display backend).
+        assert_eq!(display(Vec::new()).min_dimensions, Dimensions::default());
+    }
+
+    #[cfg(not(feature = "no-modes"))]
+    #[test]
+    fn validate_mode() {
+        let modes = [
+            mode(1280, 720, 60, 1),
+            mode(1920, 1080, 60, 1),
+            mode(1920, 1080, 144, 1),
+            mode(2560, 1440, 60, 1),
+        ];
+
+        // Exact match.
+        let validation = super::validate_mode(&modes, &mode(1920, 1080, 144, 1));
+        assert!(validation.is_supported());
+        assert_eq!(
+            dimensions_and_refresh(validation.mode().unwrap()),
+            (1920, 1080, 144)
+        );
+
+        // Fractional refresh rate match.
+        let validation = super::validate_mode(&modes, &mode(2560, 1440, 59_940, 1000));
+        assert!(validation.is_supported());
+        assert_eq!(
+            dimensions_and_refresh(validation.mode().unwrap()),
+            (2560, 1440, 60)
+        );
+
+        let validation = super::validate_mode(&modes, &mode(1920, 1080, 143_998, 1000));
+        assert!(validation.is_supported());
+        assert_eq!(
+            dimensions_and_refresh(validation.mode().unwrap()),
+            (1920, 1080, 144)
+        );
+
+        // Unsupported refresh rate.
+        let validation = super::validate_mode(&modes, &mode(1920, 1080, 120, 1));
+        assert!(!validation.is_supported());
+        assert_eq!(
+            dimensions_and_refresh(validation.mode().unwrap()),
+            (1920, 1080, 144)
+        );
+
+        // Unsupported dimensions.
+        let validation = super::validate_mode(&modes, &mode(1366, 768, 60, 1));
+        assert!(!validation.is_supported());
+        assert_eq!(
+            dimensions_and_refresh(validation.mode().unwrap()),
+            (1280, 720, 60)
+        );
+
+        let validation = super::validate_mode(&modes, &mode(2048, 1152, 144, 1));
+        assert!(!validation.is_supported());
+        assert_eq!(
+            dimensions_and_refresh(validation.mode().unwrap()),
+            (1920, 1080, 144)
+        );
+
+        // No display modes.
+        let validation = super::validate_mode(&[], &mode(1920, 1080, 60, 1));
+        assert!(matches!(validation, ModeValidation::NoModes));
+        assert!(!validation.is_supported());
+        assert!(validation.mode().is_none());
+    }
+
+    #[test]
+    fn display_mode_parse_format() {
+        assert_eq!(mode(1920, 1080, 60, 1).to_string(), "1920x1080@60Hz");
+        assert_eq!(
+            mode(1920, 1080, 60000, 1001).to_string(),
+            "1920x1080@59.94Hz"
+        );
+        assert_eq!(
+            mode(2560, 1440, 143_998, 1000).to_string(),
+            "2560x1440@143.998Hz"
+        );
+
+        let parse = |mode: &str| {
+            mode.parse::<DisplayMode>().map(|mode| {
+                (
+                    mode.dimensions.width,
+                    mode.dimensions.height,
+                    mode.refresh_rate,
+                    mode.refresh_rate_num,
+                    mode.refresh_rate_denom,
+                )
+            })
+        };
+
+        assert_eq!(parse("1920x1080@60Hz"), Ok((1920, 1080, 60, 60, 1)));
+        assert_eq!(
+            parse("2560x1440@143.998Hz"),
+            Ok((2560, 1440, 143, 71_999, 500))
+        );
+        assert_eq!(
+            parse(" 1920 X 1080 @ 59.94 hz "),
+            Ok((1920, 1080, 59, 2997, 50))
+        );
+        assert_eq!(parse("1920×1080@60.000"), Ok((1920, 1080, 60, 60, 1)));
+
+        assert_eq!(parse("1920x1080"), Err(()));
+        assert_eq!(parse("1920x@60Hz"), Err(()));
+        assert_eq!(parse("1920x1080x32@60Hz"), Err(()));
+        assert_eq!(parse("1920x1080@Hz"), Err(()));
+        assert_eq!(parse("1920x1080@0Hz"), Err(()));
+        assert_eq!(parse("1920x1080@-60Hz"), Err(()));
+        assert_eq!(parse("1920x1080@60.Hz"), Ok((1920, 1080, 60, 60, 1)));
+        assert_eq!(parse("1920x1080@.5Hz"), Err(()));
+        assert_eq!(parse("1920x1080@59.99999999999Hz"), Err(()));
+
+        // Round trip.
+        for mode in [
+            mode(1920, 1080, 60, 1),
+            mode(1920, 1080, 60000, 1001),
+            mode(2560, 1440, 143_998, 1000),
+        ]
+        .iter()
+        {
+            let parsed: DisplayMode = mode.to_string().parse().unwrap();
+            assert_eq!(parsed.to_string(), mode.to_string());
+        }
+
+        assert_eq!("1920x1080".parse(), Ok(Dimensions::new(1920, 1080)));
+        assert_eq!(Dimensions::new(1920, 1080).wxh().to_string(), "1920x1080");
+        assert_eq!(Dimensions::new(1920, 1080).to_string(), "[1920, 1080]");
+    }
+
+    #[cfg(not(feature = "no-modes"))]
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(CASES / 10))]
+
+        #[test]
+        fn closest_dimensions_properties(
+            modes in prop::collection::vec(
+                any_dimensions()
+                    .prop_map(|dimensions| mode(dimensions.width, dimensions.height, 60, 1)),
+                1..=8,
+            ),
+            dimensions in any_dimensions(),
+        ) {
+            for flags in [
+                ClosestDimensionsFlags::Closest,
+                ClosestDimensionsFlags::ClosestSmallerOrEqual,
+            ] {
+                let closest = super::closest_dimensions(&modes, dimensions, flags).unwrap();
+                assert!(modes.iter().any(|mode| mode.dimensions == closest));
+
+                for metric in [
+                    DistanceMetric::ByArea,
+                    DistanceMetric::ByPerAxisDelta,
+                    DistanceMetric::ByAspectThenArea,
+                ] {
+                    let closest =
+                        super::closest_dimensions_by(&modes, dimensions, flags, metric).unwrap();
+                    assert!(modes.iter().any(|mode| mode.dimensions == closest));
+                }
+            }
+        }
+    }
+
+    #[cfg(not(feature = "no-modes"))]
+    #[test]
+    fn closest_dimensions_by() {
+        let modes = [
+            mode(1620, 1280, 60, 1),
+            mode(1920, 1080, 60, 1),
+            mode(1280, 1024, 60, 1),
+            mode(2560, 1080, 60, 1),
+            mode(1680, 1050, 60, 1),
+            mode(1440, 900, 60, 1),
+        ];
+
+        let closest = |width, height, flags, metric| {
+            let closest =
+                super::closest_dimensions_by(&modes, Dimensions::new(width, height), flags, metric)
+                    .unwrap();
+            (closest.width, closest.height)
+        };
+
+        use ClosestDimensionsFlags::*;
+        use DistanceMetric::*;
+
+        // Same area - broken by the per-axis delta.
+        assert_eq!(closest(1920, 1080, Closest, ByArea), (1920, 1080));
+        assert_eq!(closest(1900, 1090, Closest, ByArea), (1920, 1080));
+        assert_eq!(closest(1700, 1200, Closest, ByArea), (1620, 1280));
+
+        assert_eq!(closest(1700, 1200, Closest, ByPerAxisDelta), (1620, 1280));
+        assert_eq!(closest(1900, 1090, Closest, ByPerAxisDelta), (1920, 1080));
+
+        // 16:10 - by area, the 21:9 mode is closer.
+        assert_eq!(closest(2560, 1600, Closest, ByArea), (2560, 1080));
+        // Same aspect ratio - broken by the area.
+        assert_eq!(closest(2560, 1600, Closest, ByAspectThenArea), (1680, 1050));
+        assert_eq!(closest(1600, 1000, Closest, ByAspectThenArea), (1680, 1050));
+        assert_eq!(
+            closest(1600, 1000, ClosestSmallerOrEqual, ByAspectThenArea),
+            (1440, 900)
+        );
+        assert_eq!(closest(3440, 1440, Closest, ByAspectThenArea), (2560, 1080));
+
+        // No smaller or equal modes - the closest one.
+        assert_eq!(
+            closest(640, 480, ClosestSmallerOrEqual, ByArea),
+            (1440, 900)
+        );
+        assert_eq!(closest(640, 480, Closest, ByArea), (1440, 900));
+        assert_eq!(
+            closest(1920, 1200, ClosestSmallerOrEqual, ByArea),
+            (1920, 1080)
+        );
+
+        // Ties - the first listed.
+        let modes = [mode(1280, 720, 60, 1), mode(1280, 720, 144, 1)];
+        for metric in [ByArea, ByPerAxisDelta, ByAspectThenArea] {
+            assert_eq!(
+                super::closest_dimensions_by(&modes, Dimensions::new(1920, 1080), Closest, metric),
+                Some(Dimensions::new(1280, 720))
+            );
+        }
+
+        // No modes.
+        for flags in [Closest, ClosestSmallerOrEqual] {
+            assert_eq!(
+                super::closest_dimensions_by(&[], Dimensions::new(1920, 1080), flags, ByArea),
+                None
+            );
+        }
+    }
+
+    #[cfg(not(feature = "no-modes"))]
+    #[test]
+    fn find_mode() {
+        let modes = [
+            mode(1920, 1080, 60, 1),
+            mode(1920, 1080, 144, 1),
+            mode(2560, 1440, 143_998, 1000),
+        ];
+
+        let find = |mode: &str| super::find_mode(&modes, mode).map(dimensions_and_refresh);
+
+        assert_eq!(find("1920x1080@60Hz"), Some((1920, 1080, 60)));
+        assert_eq!(find("2560x1440@144"), Some((2560, 1440, 144)));
+        assert_eq!(find("1920x1080"), Some((1920, 1080, 144)));
+
+        assert_eq!(find("1920x1080@120Hz"), None);
+        assert_eq!(find("1280x720"), None);
+        assert_eq!(find("1920x1080@"), None);
+        assert_eq!(find("garbage"), None);
+    }
+
+    #[cfg(not(feature = "no-modes"))]
+    #[test]
+    fn closest_refresh_rate() {
+        let current = mode(2560, 1440, 60, 1);
+        let modes = vec![
+            mode(1920, 1080, 120, 1),
+            current,
+            mode(2560, 1440, 119_880, 1000),
+            mode(2560, 1440, 143_998, 1000),
+        ];
+        let rect = Rectangle::new(Position::new(0, 0), Dimensions::new(2560, 1440));
+
+        let mut info = DisplayInfo::new(
+            DisplayId::default(),
+            None,
+            true,
+            DisplayRects {
+                virtual_rect: rect,
+                work_rect: rect,
+            },
+            ConnectionType::DisplayPort,
+            current,
+            current,
+            modes,
+            1.0,
+            DisplaySource::DisplayConfig,
+        );
+
+        let closest = |info: &DisplayInfo, refresh_rate| {
+            info.closest_refresh_rate(refresh_rate)
+                .map(dimensions_and_refresh)
+        };
+
+        assert_eq!(closest(&info, 120.0), Some((2560, 1440, 120)));
+        assert_eq!(closest(&info, 144.0), Some((2560, 1440, 144)));
+        assert_eq!(closest(&info, 240.0), Some((2560, 1440, 144)));
+        assert_eq!(closest(&info, 50.0), Some((2560, 1440, 60)));
+
+        // Current display mode dimensions are rotated.
+        info.orientation = Orientation::Portrait;
+        info.current_mode = mode(1440, 2560, 60, 1);
+        assert_eq!(closest(&info, 120.0), Some((2560, 1440, 120)));
+    }
+
+    #[test]
+    fn density() {
+        let display = |width, height, physical_size| {
+            let mode = mode(width, height, 60, 1);
+            let rect = Rectangle::new(Position::new(0, 0), Dimensions::new(width, height));
+
+            DisplayInfo {
+                physical_size,
+                ..DisplayInfo::new(
+                    DisplayId::default(),
+                    None,
+                    true,
+                    DisplayRects {
+                        virtual_rect: rect,
+                        work_rect: rect,
+                    },
+                    ConnectionType::DisplayPort,
+                    mode,
+                    mode,
+                    vec![mode],
+                    1.25,
+                    DisplaySource::DisplayConfig,
+                )
+            }
+        };
+
+        let density = |width, height, physical_width, physical_height| {
+            let display = display(
+                width,
+                height,
+                Some(Dimensions::new(physical_width, physical_height)),
+            );
+            (
+                display.ppi().unwrap().round() as u32,
+                display.density_class().unwrap(),
+                display.recommended_ui_scale(),
+            )
+        };
+
+        // 24" 1080p.
+        assert_eq!(
+            density(1920, 1080, 531, 299),
+            (92, DensityClass::Standard, 1.0)
+        );
+        // 27" 4K.
+        assert_eq!(
+            density(3840, 2160, 597, 336),
+            (163, DensityClass::High, 1.5)
+        );
+        // 13" 2560x1600.
+        assert_eq!(
+            density(2560, 1600, 286, 179),
+            (227, DensityClass::Ultra, 2.0)
+        );
+        // 55" 4K TV.
+        assert_eq!(density(3840, 2160, 1210, 680), (81, DensityClass::Low, 1.0));
+
+        // Unknown physical size.
+        let unknown = display(1920, 1080, None);
+        assert_eq!(unknown.ppi(), None);
+        assert_eq!(unknown.density_class(), None);
+        assert_eq!(unknown.recommended_ui_scale(), 1.25);
+
+        assert_eq!(display(1920, 1080, Some(Dimensions::new(0, 0))).ppi(), None);
+
+        // Unit conversions.
+        let display_27_4k = display(3840, 2160, Some(Dimensions::new(597, 336)));
+        assert_eq!(
+            display_27_4k.physical_size_mm(),
+            Some((Mm(597.0), Mm(336.0)))
+        );
+        assert_eq!(display_27_4k.mm_to_px(Mm(25.4)), Some(Px(163)));
+        assert_eq!(display_27_4k.pt_to_px(Pt(72.0)), Some(Px(163)));
+        assert_eq!(
+            display_27_4k
+                .px_to_mm(Px(3840))
+                .map(|mm| mm.0.round() as u32),
+            Some(597)
+        );
+        assert_eq!(unknown.physical_size_mm(), None);
+        assert_eq!(unknown.mm_to_px(Mm(10.0)), None);
+
+        // Square / non-square DPI scaling.
+        assert_eq!(unknown.dpi_scale_exact(), (1.25, 1.25));
+        let non_square = DisplayInfo {
+            dpi_scale_y: Some(1.5),
+            ..unknown
+        };
+        assert_eq!(non_square.dpi_scale_exact(), (1.25, 1.5));
+    }
+
+    #[test]
+    fn hdr_tier() {
+        let mode = mode(3840, 2160, 60, 1);
+        let rect = Rectangle::new(Position::new(0, 0), mode.dimensions);
+        let sdr = DisplayInfo::new(
+            DisplayId::default(),
+            None,
+            true,
+            DisplayRects {
+                virtual_rect: rect,
+                work_rect: rect,
+            },
+            ConnectionType::DisplayPort,
+            mode,
+            mode,
+            vec![mode],
+            1.0,
+            DisplaySource::DisplayConfig,
+        );
+        assert_eq!(sdr.hdr_metrics(), HdrMetrics::default());
+        assert_eq!(sdr.hdr_tier(), None);
+
+        let hdr = |max_luminance, min_luminance, bits, colorimetry| DisplayInfo {
+            hdr_caps: Some(HdrCaps {
+                supports_pq: true,
+                supports_hlg: false,
+                max_luminance,
+                min_luminance,
+                max_fall: None,
+            }),
+            max_bits_per_channel: bits,
+            colorimetry,
+            ..sdr.clone()
+        };
+        let tier = |display: DisplayInfo| display.hdr_tier();
+
+        let p3 = Some(Colorimetry::DCI_P3);
+        let srgb = Some(Colorimetry::SRGB);
+
+        assert_eq!(
+            tier(hdr(Some(1015.0), Some(0.01), Some(10), p3)),
+            Some(HdrTier::Hdr1000)
+        );
+        // Quantized EDID peak luminance.
+        assert_eq!(
+            tier(hdr(Some(590.0), Some(0.05), Some(10), p3)),
+            Some(HdrTier::Hdr600)
+        );
+        // Black level too high for HDR1000.
+        assert_eq!(
+            tier(hdr(Some(1015.0), Some(0.08), Some(10), p3)),
+            Some(HdrTier::Hdr600)
+        );
+        // Unknown black level.
+        assert_eq!(
+            tier(hdr(Some(1015.0), None, Some(10), p3)),
+            Some(HdrTier::Hdr1000)
+        );
+        // Bright, but narrow gamut / 8-bit / unknown bit depth.
+        assert_eq!(
+            tier(hdr(Some(1015.0), None, Some(10), srgb)),
+            Some(HdrTier::Hdr400)
+        );
+        assert_eq!(
+            tier(hdr(Some(1015.0), None, Some(8), p3)),
+            Some(HdrTier::Hdr400)
+        );
+        assert_eq!(
+            tier(hdr(Some(1015.0), None, None, p3)),
+            Some(HdrTier::Hdr400)
+        );
+        // Too dim / unknown peak luminance / no PQ.
+        assert_eq!(tier(hdr(Some(300.0), None, Some(10), p3)), None);
+        assert_eq!(tier(hdr(None, None, Some(10), p3)), None);
+
+        let mut no_pq = hdr(Some(1015.0), None, Some(10), p3);
+        no_pq.hdr_caps.as_mut().unwrap().supports_pq = false;
+        assert_eq!(no_pq.hdr_tier(), None);
+
+        let metrics = hdr(Some(1015.0), Some(0.01), Some(10), p3).hdr_metrics();
+        assert_eq!(metrics.max_luminance, Some(1015.0));
+        assert!(metrics.dci_p3_coverage.unwrap() > 0.99);
+        assert_eq!(HdrTier::Hdr600.to_string(), "HDR600-class");
+        assert!(HdrTier::Hdr1000 > HdrTier::Hdr400);
+    }
+
+    #[test]
+    fn indirect_driver() {
+        let parsec = Some("Parsec Virtual Display Adapter");
+        let nvidia = Some("NVIDIA GeForce RTX 3080");
+        let dell = Some(
+            r"\\?\DISPLAY#DEL40F0#5&1a2b3c4d&0&UID4352#{e6f07b5f-ee97-4a90-b076-33f57bf4eaa7}",
+        );
+        let idd =
+            Some(r"\\?\DISPLAY#MTT1337#1&1a2b3c4d&0&UID256#{e6f07b5f-ee97-4a90-b076-33f57bf4eaa7}");
+
+        // Reported by the OS.
+        assert_eq!(
+            super::indirect_driver(parsec, dell, true).as_deref(),
+            parsec
+        );
+        assert_eq!(
+            super::indirect_driver(None, idd, true).as_deref(),
+            Some("MTT1337")
+        );
+        assert_eq!(
+            super::indirect_driver(None, None, true).as_deref(),
+            Some("<unknown>")
+        );
+
+        // Well-known keywords.
+        assert_eq!(
+            super::indirect_driver(parsec, dell, false).as_deref(),
+            parsec
+        );
+        assert_eq!(
+            super::indirect_driver(Some("DisplayLink USB Device"), None, false).as_deref(),
+            Some("DisplayLink USB Device")
+        );
+        assert_eq!(
+            super::indirect_driver(nvidia, idd, false).as_deref(),
+            nvidia
+        );
+
+        // Regular displays.
+        assert_eq!(super::indirect_driver(nvidia, dell, false), None);
+        assert_eq!(super::indirect_driver(None, None, false), None);
+
+        let source = DisplaySource::Indirect("DisplayLink USB Device".to_string());
+        assert_eq!(source.indirect_driver(), Some("DisplayLink USB Device"));
+        assert_eq!(
+            source.to_string(),
+            "indirect display driver (DisplayLink USB Device)"
+        );
+        assert_eq!(DisplaySource::DisplayConfig.indirect_driver(), None);
+    }
+
+    #[test]
+    fn signal_timing() {
+        // CTA-861 1080p60.
+        let timing = SignalTiming {
+            pixel_rate_hz: 148_500_000,
+            h_total: 2200,
+            v_total: 1125,
+        };
+        assert_eq!(timing.line_duration(), Duration::from_nanos(14_815));
+        assert_eq!(timing.frame_duration(), Duration::from_nanos(16_666_667));
+
+        let unknown = SignalTiming {
+            pixel_rate_hz: 0,
+            ..timing
+        };
+        assert_eq!(unknown.line_duration(), Duration::from_nanos(0));
+        assert_eq!(unknown.frame_duration(), Duration::from_nanos(0));
+    }
+
+    #[cfg(not(feature = "no-modes"))]
+    #[test]
+    fn integer_scaled_modes() {
+        let modes = [
+            mode(1920, 1080, 60, 1),
+            mode(2560, 1440, 60, 1),
+            mode(2560, 1440, 144, 1),
+            mode(1280, 720, 60, 1),
+            mode(640, 480, 60, 1),
+            mode(3840, 2160, 60, 1),
+        ];
+
+        let scaled = |content: Dimensions, portrait: bool| {
+            super::integer_scaled_modes(&modes, content, portrait)
+                .into_iter()
+                .map(|scaled| {
+                    (
+                        dimensions_and_refresh(scaled.mode),
+                        scaled.factor,
+                        scaled.letterbox,
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // NES - 256x240.
+        assert_eq!(
+            scaled(Dimensions::new(256, 240), false),
+            vec![
+                // 512x480 - 80% covered.
+                ((640, 480, 60), 2, Margins::new(64, 0, 64, 0)),
+                // 2304x2160, 1536x1440, 768x720 - 60% covered.
+                ((3840, 2160, 60), 9, Margins::new(768, 0, 768, 0)),
+                ((2560, 1440, 144), 6, Margins::new(512, 0, 512, 0)),
+                ((2560, 1440, 60), 6, Margins::new(512, 0, 512, 0)),
+                ((1280, 720, 60), 3, Margins::new(256, 0, 256, 0)),
+                // 1024x960 - 47% covered.
+                ((1920, 1080, 60), 4, Margins::new(448, 60, 448, 60)),
+            ]
+        );
+
+        // Portrait - rotated NES.
+        assert_eq!(
+            scaled(Dimensions::new(240, 256), true)[0],
+            ((640, 480, 60), 2, Margins::new(0, 64, 0, 64))
+        );
+
+        // Too large.
+        assert!(scaled(Dimensions::new(4000, 3000), false).is_empty());
+        // Empty.
+        assert!(scaled(Dimensions::new(0, 240), false).is_empty());
+    }
+
+    #[test]
+    fn display_rects_display() {
+        let rects = DisplayRects {
+            virtual_rect: Rectangle::new(Position::new(-1920, 0), Dimensions::new(1920, 1080)),
+            work_rect: Rectangle::new(Position::new(-1920, 0), Dimensions::new(1920, 1040)),
+        };
+        assert_eq!(
+            rects.to_string(),
+            "[-1920, 0]:[1920, 1080] (work: [-1920, 0]:[1920, 1040])"
+        );
+    }
 }
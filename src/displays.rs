@@ -1,72 +1,359 @@
+use std::fmt::{Display, Formatter};
 use std::iter::Iterator;
+use std::path::Path;
 use std::slice::Iter;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
 
-use crate::{Dimensions, DisplayInfo, Position, Rectangle};
+#[cfg(not(feature = "no-modes"))]
+use crate::DisplayMode;
 
+use crate::{
+    analyze_layout, brightness_adjustments, find_display, identify_pulse, parse_input_sources,
+    reconcile_display_order, span_rects, AdaptiveBrightness, AdjacencyInfo, AssetInfo,
+    BackendCapabilities, BrightnessAdjustment, BrightnessSetting, ConnectionType, CoordinateSpace,
+    Coordinates, CursorClipGuard, Dimensions, DisplayBackend, DisplayConfigTransaction,
+    DisplayError, DisplayEvent, DisplayEventKind, DisplayId, DisplayIndex, DisplayInfo,
+    DisplayMatcher, DisplayName, DisplayRects, DisplaySource, DisplaySubscription,
+    DisplaysSnapshot, DpiAwareness, EnumerateOptions, Error, GammaPreset, GammaTransition,
+    InputSource, LayoutReport, NativeBackend, Neighbor, NightLightState, Orientation,
+    PhysicalMonitor, PlatformInfo, Projection, Rectangle, Region, SharedEdge, StaleIndex,
+    StitchLayout, Subscribers, VcpValue, VCP_BLACK_LEVEL, VCP_CONTRAST, VCP_INPUT_SOURCE,
+    VCP_USAGE_HOURS,
+};
+
+#[cfg(all(windows, feature = "raw-win"))]
+use crate::RawDisplayConfig;
 #[cfg(windows)]
-use crate::DisplayInfoPlatform;
+use crate::{AdapterLuid, DisplayInfoWin};
 
+#[cfg(windows)]
+use super::win::adaptive_brightness_win as adaptive_brightness_platform;
+#[cfg(windows)]
+use super::win::apply_color_profile_win as apply_color_profile_platform;
+#[cfg(windows)]
+use super::win::brightness_win as brightness_platform;
+#[cfg(windows)]
+use super::win::capabilities_win as capabilities_platform;
+#[cfg(windows)]
+use super::win::enumerate_attached_hardware_win as enumerate_attached_hardware_platform;
+#[cfg(windows)]
+use super::win::enumerate_display_rects_win as enumerate_display_rects_platform;
 #[cfg(windows)]
 use super::win::enumerate_displays_win as enumerate_displays_platform;
+#[cfg(windows)]
+use super::win::gamma_ramp_win as gamma_ramp_platform;
+#[cfg(windows)]
+use super::win::night_light_win as night_light_platform;
+#[cfg(windows)]
+use super::win::physical_monitors_win as physical_monitors_platform;
+#[cfg(windows)]
+use super::win::presentation_mode_active_win as presentation_mode_active_platform;
+#[cfg(windows)]
+use super::win::process_dpi_awareness_win as process_dpi_awareness_platform;
+#[cfg(windows)]
+use super::win::projection_win as projection_platform;
+#[cfg(windows)]
+use super::win::set_brightness_win as set_brightness_platform;
+#[cfg(all(windows, feature = "unstable-scaling"))]
+use super::win::set_dpi_scale_win as set_dpi_scale_platform;
+#[cfg(windows)]
+use super::win::set_gamma_ramp_win as set_gamma_ramp_platform;
+#[cfg(windows)]
+use super::win::set_orientation_win as set_orientation_platform;
+#[cfg(windows)]
+use super::win::set_projection_win as set_projection_platform;
+#[cfg(windows)]
+use super::win::set_thread_display_required_win as set_thread_display_required_platform;
+#[cfg(windows)]
+use super::win::set_vcp_feature_win as set_vcp_feature_platform;
+#[cfg(windows)]
+use super::win::thread_dpi_awareness_win as thread_dpi_awareness_platform;
+#[cfg(windows)]
+use super::win::vcp_feature_win as vcp_feature_platform;
+
+#[cfg(all(not(windows), feature = "unstable-scaling"))]
+use super::stub::set_dpi_scale_stub as set_dpi_scale_platform;
+#[cfg(not(windows))]
+use super::stub::{
+    adaptive_brightness_stub as adaptive_brightness_platform,
+    apply_color_profile_stub as apply_color_profile_platform,
+    brightness_stub as brightness_platform, capabilities_stub as capabilities_platform,
+    enumerate_attached_hardware_stub as enumerate_attached_hardware_platform,
+    enumerate_display_rects_stub as enumerate_display_rects_platform,
+    enumerate_displays_stub as enumerate_displays_platform, gamma_ramp_stub as gamma_ramp_platform,
+    night_light_stub as night_light_platform, physical_monitors_stub as physical_monitors_platform,
+    presentation_mode_active_stub as presentation_mode_active_platform,
+    process_dpi_awareness_stub as process_dpi_awareness_platform,
+    projection_stub as projection_platform, set_brightness_stub as set_brightness_platform,
+    set_gamma_ramp_stub as set_gamma_ramp_platform,
+    set_orientation_stub as set_orientation_platform,
+    set_projection_stub as set_projection_platform,
+    set_thread_display_required_stub as set_thread_display_required_platform,
+    set_vcp_feature_stub as set_vcp_feature_platform,
+    thread_dpi_awareness_stub as thread_dpi_awareness_platform,
+    vcp_feature_stub as vcp_feature_platform,
+};
+
+/// (Best-effort) state of the laptop lid, as returned by [`internal_display`].
+///
+/// [`internal_display`]: struct.Displays.html#method.internal_display
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LidState {
+    /// The lid is open.
+    Open,
+    /// The lid is closed.
+    Closed,
+    /// The lid state cannot be determined.
+    Unknown,
+}
+
+impl Display for LidState {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use LidState::*;
+
+        match self {
+            Open => write!(f, "open"),
+            Closed => write!(f, "closed"),
+            Unknown => write!(f, "<unknown>"),
+        }
+    }
+}
+
+/// Returns `false` if the display is enumerated, but is not attached to the desktop
+/// (i.e. has an empty virtual rectangle, or its platform-specific state is reported inactive).
+///
+/// The displays enumerated from the attached monitor inventory are considered active,
+/// as their desktop rectangles are unknown.
+#[cfg_attr(not(windows), allow(unused_variables))]
+fn is_active(info: &DisplayInfo, platform: &PlatformInfo) -> bool {
+    #[cfg(windows)]
+    if let Some(state) = platform.windows().and_then(DisplayInfoWin::state) {
+        if !state.is_active() {
+            return false;
+        }
+    }
+
+    info.source == DisplaySource::ServiceInventory || !info.rects.virtual_rect.is_empty()
+}
+
+/// Weak reference to an enumerated display, which carries its [`stable identifier`]
+/// and may be [`resolved`] to the display's current index after the displays are re-enumerated.
+///
+/// Use this to hold onto a display (e.g. "the display my window was on") in long-lived subsystems
+/// without the display index dangling when the displays are added / removed / reordered.
+///
+/// [`stable identifier`]: struct.DisplayId.html
+/// [`resolved`]: #method.resolve
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct DisplayRef(DisplayId);
+
+impl DisplayRef {
+    /// Creates a reference to the display with the provided stable `id`.
+    pub fn new(id: DisplayId) -> Self {
+        Self(id)
+    }
+
+    /// Returns the referenced display's stable identifier.
+    pub fn id(self) -> DisplayId {
+        self.0
+    }
+
+    /// Returns the current [`display index`] of the referenced display in the [`enumerated displays`],
+    /// or `None` if the display is no longer enumerated (e.g. it was disconnected or disabled).
+    ///
+    /// [`display index`]: struct.DisplayIndex.html
+    /// [`enumerated displays`]: struct.Displays.html
+    pub fn resolve(self, displays: &Displays) -> Option<DisplayIndex> {
+        displays
+            .iter()
+            .position(|display| display.info.id == self.0)
+            .map(|index| displays.current_index(index as u32))
+    }
+}
+
+/// Enumerates the system's displays once, returning their [`full display info`] in display index order.
+///
+/// A shorthand for the one-shot queries which don't need to keep the [`display manager`] around.
+///
+/// NOTE - `display_index == 0` corresponds to the system's primary display, if any.
+///
+/// [`full display info`]: struct.DisplayInfoFull.html
+/// [`display manager`]: struct.Displays.html
+pub fn enumerate() -> Result<Vec<DisplayInfoFull>, Error> {
+    let mut displays = Displays::new();
+    displays.enumerate_displays()?;
+
+    Ok(displays.iter().map(DisplayInfoFull::from).collect())
+}
+
+/// Enumerates the system's displays once, returning the [`full display info`] of the primary display.
+///
+/// See [`enumerate`].
+///
+/// [`full display info`]: struct.DisplayInfoFull.html
+/// [`enumerate`]: fn.enumerate.html
+pub fn primary_display() -> Result<DisplayInfoFull, Error> {
+    enumerate()?
+        .into_iter()
+        .find(|display| display.info.is_primary)
+        .ok_or(Error::NoPrimaryDisplay)
+}
 
-/// Single display info as returned by `enumerate_displays_platform`.
+/// Single display's generic, platform-specific and adjacency info, as returned by [`enumerate`].
+///
+/// See [`DisplayInfoFullRef`] for the info borrowed from the [`display manager`].
+///
+/// [`enumerate`]: fn.enumerate.html
+/// [`DisplayInfoFullRef`]: struct.DisplayInfoFullRef.html
+/// [`display manager`]: struct.Displays.html
 #[derive(Clone, Debug)]
-pub(crate) struct EnumeratedDisplayInfo {
+pub struct DisplayInfoFull {
     /// Generic display info.
-    pub(crate) info: DisplayInfo,
+    pub info: DisplayInfo,
     /// Platform-specific display info.
-    pub(crate) platform: DisplayInfoPlatform,
+    pub platform: PlatformInfo,
+    /// Calculated display adjacency info.
+    pub adjacency_info: AdjacencyInfo,
 }
 
-/// Describes the display (non-work) rectangle adjacency
-/// to other display rectangles in virtual desctop space.
-/// Contains the index of the adjacent display on each side, if any.
-#[derive(Clone, Copy, Debug)]
-pub struct AdjacencyInfo {
-    /// Another display is adjacent on the left.
-    pub left: Option<u32>,
-    /// Another display is adjacent on the top.
-    pub top: Option<u32>,
-    /// Another display is adjacent on the right.
-    pub right: Option<u32>,
-    /// Another display is adjacent on the bottom.
-    pub bottom: Option<u32>,
+impl DisplayInfoFull {
+    /// Borrows the full display info.
+    pub fn as_ref(&self) -> DisplayInfoFullRef<'_> {
+        DisplayInfoFullRef {
+            info: &self.info,
+            platform: &self.platform,
+            adjacency_info: &self.adjacency_info,
+        }
+    }
 }
 
-impl Default for AdjacencyInfo {
-    fn default() -> Self {
+impl<'d> From<DisplayInfoFullRef<'d>> for DisplayInfoFull {
+    fn from(display: DisplayInfoFullRef<'d>) -> Self {
         Self {
-            left: None,
-            top: None,
-            right: None,
-            bottom: None,
+            info: display.info.clone(),
+            platform: display.platform.clone(),
+            adjacency_info: *display.adjacency_info,
         }
     }
 }
 
-impl AdjacencyInfo {
-    pub fn is_some(self) -> bool {
-        self.left.is_some() || self.top.is_some() || self.right.is_some() || self.bottom.is_some()
+impl Display for DisplayInfoFull {
+    /// See [`DisplayInfoFullRef`](struct.DisplayInfoFullRef.html#impl-Display).
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        self.as_ref().fmt(f)
     }
 }
 
-/// Single display info as stored by the [`display manager`].
+/// Single display's generic, platform-specific and adjacency info, borrowed from the [`display manager`].
 ///
 /// [`display manager`]: struct.Displays.html
-#[derive(Clone)]
-pub struct DisplayInfoFull {
+#[derive(Clone, Copy, Debug)]
+pub struct DisplayInfoFullRef<'d> {
     /// Generic display info.
-    pub info: DisplayInfo,
+    pub info: &'d DisplayInfo,
     /// Platform-specific display info.
-    pub platform: DisplayInfoPlatform,
+    pub platform: &'d PlatformInfo,
     /// Calculated display adjacency info.
-    pub adjacency_info: AdjacencyInfo,
+    pub adjacency_info: &'d AdjacencyInfo,
+}
+
+impl<'d> Display for DisplayInfoFullRef<'d> {
+    /// Formats the display's name, connection, rectangles, modes and DPI scale on the first line,
+    /// followed by the adjacent displays (if any) on the second, indented line.
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        let info = self.info;
+
+        match info.name.as_ref() {
+            Some(name) => write!(f, "{}", name)?,
+            None => write!(f, "<unnamed>")?,
+        }
+
+        if info.is_primary {
+            write!(f, " (primary)")?;
+        }
+
+        write!(
+            f,
+            " ({}) {} (current: {}, preferred: {}) (DPI scale: ",
+            info.connection, info.rects, info.current_mode, info.preferred_mode
+        )?;
+
+        let (dpi_scale_x, dpi_scale_y) = info.dpi_scale_exact();
+
+        if dpi_scale_x == dpi_scale_y {
+            write!(f, "{}%)", (dpi_scale_x * 100.0).round())?;
+        } else {
+            write!(
+                f,
+                "{}%x{}%)",
+                (dpi_scale_x * 100.0).round(),
+                (dpi_scale_y * 100.0).round()
+            )?;
+        }
+
+        if self.adjacency_info.is_some() {
+            write!(f, "\n\tadjacent: {}", self.adjacency_info)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Lightweight single display info as returned by [`enumerate_rects_only`].
+///
+/// [`enumerate_rects_only`]: struct.Displays.html#method.enumerate_rects_only
+#[derive(Clone, Debug)]
+pub struct DisplayRectsFull {
+    /// The display's rectangles w.r.t. the virtual display.
+    pub rects: DisplayRects,
+    /// Whether the display is the system's primary display.
+    pub is_primary: bool,
+    /// Platform-specific display info.
+    pub platform: PlatformInfo,
+}
+
+/// Monitor hardware known to the system, as returned by [`enumerate_attached_hardware`].
+///
+/// [`enumerate_attached_hardware`]: struct.Displays.html#method.enumerate_attached_hardware
+#[derive(Clone, Debug)]
+pub struct MonitorHardware {
+    /// Stable display identifier. Matches the identifier of the enumerated display, if the monitor is active.
+    pub id: DisplayId,
+    /// Whether the monitor is currently attached (connected and powered on).
+    /// Otherwise, the monitor was attached before and is remembered by the system.
+    pub is_attached: bool,
+    /// Monitor's name, as reported by its EDID, if any.
+    pub name: Option<String>,
+    /// Monitor manufacturer's three-letter PnP ID (e.g. `DEL`), as reported by its EDID, if any.
+    pub manufacturer: Option<String>,
+    /// Monitor's product code (e.g. `40F0`), as reported by its EDID, if any.
+    pub product_code: Option<String>,
+    /// Monitor's serial number, as reported by its EDID, if any.
+    pub serial_number: Option<String>,
+    /// Monitor's year of manufacture (or the model year), as reported by its EDID, if any.
+    pub manufacture_year: Option<u16>,
+    /// Monitor's week of manufacture, as reported by its EDID, if any.
+    pub manufacture_week: Option<u8>,
+    /// Monitor's raw EDID data, if known.
+    pub edid: Option<Vec<u8>>,
 }
 
 /// Enumerates and holds the information about the system's displays.
 pub struct Displays {
-    displays: Vec<DisplayInfoFull>,
-    virtual_desktop: Option<Rectangle>,
+    /// Platform-independent info of the enumerated displays.
+    snapshot: Arc<DisplaysSnapshot>,
+    /// Platform-specific info of the enumerated displays, in display index order.
+    platform: Vec<PlatformInfo>,
+    /// Incremented whenever a re-enumeration changes which display any of the previous display indices refers to.
+    index_generation: u64,
+    subscribers: Subscribers,
+    /// Custom display backend, if any. Otherwise the native one is used.
+    backend: Option<Arc<dyn DisplayBackend>>,
+    /// Whether the displays whose monitor device is not active / attached are enumerated.
+    include_inactive: bool,
+    #[cfg(all(windows, feature = "raw-win"))]
+    raw_display_config: Option<RawDisplayConfig>,
 }
 
 impl Default for Displays {
@@ -75,6 +362,35 @@ impl Default for Displays {
     }
 }
 
+impl Display for Displays {
+    /// Formats the number of the enumerated displays followed by each display's info on its own, indented line(s),
+    /// prefixed by its display index.
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        if self.num_displays() == 0 {
+            return write!(f, "No displays found.");
+        }
+
+        write!(f, "Found {} display(s):", self.num_displays())?;
+
+        for (idx, display) in self.iter().enumerate() {
+            let display = display.to_string().replace('\n', "\n\t");
+            write!(f, "\n\t{}: {}", idx, display)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for Displays {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("Displays")
+            .field("snapshot", &self.snapshot)
+            .field("platform", &self.platform)
+            .field("custom_backend", &self.backend.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
 impl Displays {
     /// Creates a new, empty instance of the [`display manager`].
     ///
@@ -84,188 +400,1359 @@ impl Displays {
     /// [`enumerate_displays`]: #method.enumerate_displays
     pub fn new() -> Self {
         Self {
-            displays: Vec::new(),
-            virtual_desktop: None,
+            snapshot: Arc::new(DisplaysSnapshot::default()),
+            platform: Vec::new(),
+            index_generation: 0,
+            subscribers: Subscribers::default(),
+            backend: None,
+            include_inactive: false,
+            #[cfg(all(windows, feature = "raw-win"))]
+            raw_display_config: None,
         }
     }
 
+    /// Creates a new, empty instance of the [`display manager`] which enumerates the displays
+    /// via provided [`display backend`] instead of the native one.
+    ///
+    /// NOTE: call [`enumerate_displays`] to actually populate the display info.
+    ///
+    /// [`display manager`]: struct.Displays.html
+    /// [`display backend`]: trait.DisplayBackend.html
+    /// [`enumerate_displays`]: #method.enumerate_displays
+    pub fn with_backend(backend: Arc<dyn DisplayBackend>) -> Self {
+        Self {
+            backend: Some(backend),
+            ..Self::new()
+        }
+    }
+
+    /// Sets whether the displays whose monitor device is not reported active / attached
+    /// (e.g. while the monitor is turned off) are enumerated by the following calls to [`enumerate_displays`].
+    ///
+    /// `false` by default - only the active displays are enumerated.
+    /// See [`EnumerateOptions::include_inactive`].
+    ///
+    /// [`enumerate_displays`]: #method.enumerate_displays
+    /// [`EnumerateOptions::include_inactive`]: struct.EnumerateOptions.html#structfield.include_inactive
+    pub fn set_include_inactive(&mut self, include_inactive: bool) {
+        self.include_inactive = include_inactive;
+    }
+
     /// Enumerates the system's displays, updating the stored [`display info`] for later use.
     /// Returns the number of enumerated displays.
     ///
+    /// The displays which were enumerated before keep their relative order (and their display indices,
+    /// unless the preceding displays were removed or the primary display changed);
+    /// the new displays are added at the end. The primary display, if any, is always at display index `0`.
+    ///
+    /// NOTE: display enumeration is relatively expensive - avoid calling this every frame.
+    /// Re-enumerate the displays when notified about display configuration changes,
+    /// or use the [`display info cache`] instead.
+    ///
+    /// Returns [`Error::Unsupported`] on unsupported platforms,
+    /// or the [`display backend`]'s error, if any.
+    ///
     /// [`display info`]: struct.DisplayInfo.html
-    pub fn enumerate_displays(&mut self) -> Result<u32, ()> {
-        let displays = enumerate_displays_platform()?;
+    /// [`display info cache`]: struct.CachedDisplays.html
+    /// [`Error::Unsupported`]: enum.Error.html#variant.Unsupported
+    /// [`display backend`]: trait.DisplayBackend.html
+    pub fn enumerate_displays(&mut self) -> Result<u32, Error> {
+        // Reuse the unchanged display names.
+        let previous_names: Vec<DisplayName> = self
+            .snapshot
+            .displays()
+            .iter()
+            .filter_map(|info| info.name.clone())
+            .collect();
+
+        let options = EnumerateOptions {
+            previous_names: &previous_names,
+            include_inactive: self.include_inactive,
+        };
+
+        #[cfg_attr(not(all(windows, feature = "raw-win")), allow(unused_variables))]
+        let (displays, raw_display_config) = match self.backend.as_ref() {
+            Some(backend) => (backend.enumerate(&options)?, None),
+            None => enumerate_displays_platform(&options)?,
+        };
         let num_displays = displays.len() as u32;
 
-        let adjacency_info: Vec<AdjacencyInfo> = (0..displays.len())
-            .map(|index| Self::calc_adjacency_info(&displays, index))
+        let previous_ids: Vec<_> = self
+            .snapshot
+            .displays()
+            .iter()
+            .map(|info| info.id)
             .collect();
 
-        let mut displays = displays
+        // Keep the indices of the displays which are still present.
+        let displays = {
+            let ids: Vec<_> = displays.iter().map(|display| display.info.id).collect();
+            let primary = displays.iter().position(|display| display.info.is_primary);
+
+            let order = reconcile_display_order(&previous_ids, &ids, primary);
+
+            let mut displays: Vec<_> = displays.into_iter().map(Some).collect();
+            order
+                .into_iter()
+                .filter_map(|index| displays[index].take())
+                .collect::<Vec<_>>()
+        };
+
+        let (infos, platform): (Vec<_>, Vec<_>) = displays
             .into_iter()
-            .zip(adjacency_info)
-            .map(|(info, adjacency_info)| DisplayInfoFull {
-                info: info.info,
-                platform: info.platform,
-                adjacency_info,
-            })
-            .collect();
+            .map(|display| (display.info, display.platform))
+            .unzip();
 
-        self.displays.clear();
-        self.displays.append(&mut displays);
-        self.displays.shrink_to_fit();
+        // Previous display indices are stale unless they still refer to the same displays.
+        let indices_unchanged = previous_ids.len() <= infos.len()
+            && previous_ids
+                .iter()
+                .zip(infos.iter())
+                .all(|(id, info)| *id == info.id);
 
-        // Calculate the virtual desctop rectangle.
-        if !self.displays.is_empty() {
-            let mut virtual_desktop_left = 0;
-            let mut virtual_desktop_top = 0;
-            let mut virtual_desktop_right = 0;
-            let mut virtual_desktop_bottom = 0;
+        if !indices_unchanged {
+            self.index_generation += 1;
+        }
 
-            for display in self.displays.iter() {
-                let virtual_rect = display.info.rects.virtual_rect;
+        let snapshot = DisplaysSnapshot::new(infos).stamp(
+            self.snapshot.generation() + 1,
+            self.index_generation,
+            SystemTime::now(),
+        );
 
-                virtual_desktop_left = virtual_desktop_left.min(virtual_rect.left());
-                virtual_desktop_top = virtual_desktop_top.min(virtual_rect.top());
-                virtual_desktop_right = virtual_desktop_right.max(virtual_rect.right());
-                virtual_desktop_bottom = virtual_desktop_bottom.max(virtual_rect.bottom());
-            }
+        self.platform = platform;
 
-            debug_assert!(virtual_desktop_right >= virtual_desktop_left);
-            debug_assert!(virtual_desktop_bottom >= virtual_desktop_top);
-            let virtual_desktop_width = (virtual_desktop_right - virtual_desktop_left) as u32;
-            let virtual_desktop_height = (virtual_desktop_bottom - virtual_desktop_top) as u32;
+        #[cfg(all(windows, feature = "raw-win"))]
+        {
+            self.raw_display_config = raw_display_config;
+        }
 
-            self.virtual_desktop.replace(Rectangle::new(
-                Position::new(virtual_desktop_left, virtual_desktop_top),
-                Dimensions::new(virtual_desktop_width, virtual_desktop_height),
-            ));
-        } else {
-            self.virtual_desktop.take();
+        let previous = std::mem::replace(&mut self.snapshot, Arc::new(snapshot));
+
+        // Only diff the displays if anyone's interested in the changes.
+        if !self.subscribers.is_empty() {
+            let diff = self.snapshot.diff(&previous);
+            self.subscribers.dispatch(diff.events(), self);
         }
 
         Ok(num_displays)
     }
 
+    /// Enumerates the system's displays' [`rectangles`] only,
+    /// without querying any other display info (names, display modes, etc.).
+    ///
+    /// Much cheaper than [`enumerate_displays`] - use this for e.g. per-frame window clamping logic.
+    /// Does not update the stored display info.
+    ///
+    /// NOTE - the display at index `0` is the system's primary display.
+    ///
+    /// Returns [`Error::Unsupported`] on the unsupported platforms.
+    ///
+    /// [`rectangles`]: struct.DisplayRectsFull.html
+    /// [`enumerate_displays`]: #method.enumerate_displays
+    /// [`Error::Unsupported`]: enum.Error.html#variant.Unsupported
+    pub fn enumerate_rects_only() -> Result<Vec<DisplayRectsFull>, Error> {
+        enumerate_display_rects_platform()
+    }
+
+    /// Enumerates all monitors known to the system, including the ones currently turned off
+    /// or disconnected but remembered by the system, with the info read from the monitors' EDID.
+    ///
+    /// Unlike [`enumerate_displays`], does not require an interactive desktop (e.g. works from a service),
+    /// but provides no info about the monitors' desktop configuration (rectangles, display modes, etc.).
+    ///
+    /// Returns [`Error::Unsupported`] on the unsupported platforms.
+    ///
+    /// [`enumerate_displays`]: #method.enumerate_displays
+    /// [`Error::Unsupported`]: enum.Error.html#variant.Unsupported
+    pub fn enumerate_attached_hardware() -> Result<Vec<MonitorHardware>, Error> {
+        enumerate_attached_hardware_platform()
+    }
+
+    /// Returns the features supported by the [`display backend`] the displays are enumerated with
+    /// (the native one, unless created [`with_backend`]).
+    ///
+    /// [`display backend`]: trait.DisplayBackend.html
+    /// [`with_backend`]: #method.with_backend
+    pub fn capabilities(&self) -> BackendCapabilities {
+        self.backend.as_ref().map_or_else(
+            || NativeBackend.capabilities(),
+            |backend| backend.capabilities(),
+        )
+    }
+
     /// Returns the current number of enumerated displays.
     pub fn num_displays(&self) -> u32 {
-        self.displays.len() as u32
+        self.snapshot.num_displays()
     }
 
-    /// Returns the [`full display info`] for the display with the provided `display_index`,
+    /// Returns the generation of the last successful display enumeration,
+    /// incremented on each call to [`enumerate_displays`] (`0` if the displays were never enumerated).
+    ///
+    /// Same as the [`snapshot`]'s [`generation`], and the generation of the [`display events`] it detected.
+    ///
+    /// [`enumerate_displays`]: #method.enumerate_displays
+    /// [`snapshot`]: #method.snapshot
+    /// [`generation`]: struct.DisplaysSnapshot.html#method.generation
+    /// [`display events`]: struct.DisplayEvent.html
+    pub fn generation(&self) -> u64 {
+        self.snapshot.generation()
+    }
+
+    /// Returns the time of the last successful display enumeration,
+    /// or `None` if the displays were never enumerated.
+    ///
+    /// See [`DisplaysSnapshot::enumerated_at`].
+    ///
+    /// [`DisplaysSnapshot::enumerated_at`]: struct.DisplaysSnapshot.html#method.enumerated_at
+    pub fn last_enumerated_at(&self) -> Option<SystemTime> {
+        self.snapshot.enumerated_at()
+    }
+
+    /// Returns the current display index generation.
+    ///
+    /// Incremented by [`enumerate_displays`] whenever any of the previously valid display indices
+    /// no longer refers to the same display (e.g. when a display is removed, or the primary display changes).
+    /// Adding new displays (at the end) does not change the generation.
+    ///
+    /// [`enumerate_displays`]: #method.enumerate_displays
+    pub fn index_generation(&self) -> u64 {
+        self.index_generation
+    }
+
+    /// Returns the [`display index`] for the display with the provided `display_index`,
+    /// tagged with the current [`index generation`],
     /// or `None` if `display_index` is out of bounds.
     ///
-    /// NOTE - `display_index == 0` corresponds to the system's primary display, if any.
+    /// [`display index`]: struct.DisplayIndex.html
+    /// [`index generation`]: #method.index_generation
+    pub fn display_index(&self, display_index: u32) -> Option<DisplayIndex> {
+        (display_index < self.num_displays()).then(|| self.current_index(display_index))
+    }
+
+    /// Returns the raw display index of the [`display index`] (e.g. to use with the current [`snapshot`]),
+    /// or an error if the displays were re-enumerated and reordered since it was obtained.
+    ///
+    /// [`display index`]: struct.DisplayIndex.html
+    /// [`snapshot`]: #method.snapshot
+    pub fn check_index(&self, display_index: DisplayIndex) -> Result<u32, StaleIndex> {
+        if display_index.generation() == self.index_generation {
+            Ok(display_index.index())
+        } else {
+            Err(StaleIndex)
+        }
+    }
+
+    /// Returns the [`full display info`] for the display with the provided [`display index`],
+    /// or an error if the displays were re-enumerated and reordered since it was obtained.
+    ///
+    /// NOTE - display index `0` corresponds to the system's primary display, if any.
     ///
     /// [`full display info`]: struct.DisplayInfoFull.html
-    pub fn display_info_full(&self, display_index: u32) -> Option<&DisplayInfoFull> {
-        self.display_info_inner(display_index)
+    /// [`display index`]: struct.DisplayIndex.html
+    pub fn display_info_full(
+        &self,
+        display_index: DisplayIndex,
+    ) -> Result<DisplayInfoFullRef<'_>, StaleIndex> {
+        Ok(DisplayInfoFullRef {
+            info: self.display_info(display_index)?,
+            platform: self.display_info_platform(display_index)?,
+            adjacency_info: self.adjacency_info(display_index)?,
+        })
     }
 
-    /// Returns the [`display info`] for the display with the provided `display_index`,
-    /// or `None` if `display_index` is out of bounds.
+    /// Returns the [`display info`] for the display with the provided [`display index`],
+    /// or an error if the displays were re-enumerated and reordered since it was obtained.
     ///
-    /// NOTE - `display_index == 0` corresponds to the system's primary display, if any.
+    /// NOTE - display index `0` corresponds to the system's primary display, if any.
     ///
     /// [`display info`]: struct.DisplayInfo.html
-    pub fn display_info(&self, display_index: u32) -> Option<&DisplayInfo> {
-        self.display_info_inner(display_index)
-            .map(|display_info| &display_info.info)
+    /// [`display index`]: struct.DisplayIndex.html
+    pub fn display_info(&self, display_index: DisplayIndex) -> Result<&DisplayInfo, StaleIndex> {
+        // Valid indices of the current generation are always in bounds.
+        self.display_info_inner(self.check_index(display_index)?)
+            .ok_or(StaleIndex)
     }
 
-    /// Returns the [`platform-specific info`] for the display with the provided `display_index`,
-    /// or `None` if `display_index` is out of bounds.
+    /// Returns the [`platform-specific info`] for the display with the provided [`display index`],
+    /// or an error if the displays were re-enumerated and reordered since it was obtained.
     ///
-    /// NOTE - `display_index == 0` corresponds to the system's primary display, if any.
+    /// NOTE - display index `0` corresponds to the system's primary display, if any.
     ///
-    /// [`platform-specific info`]: struct.DisplayInfoPlatform.html
-    pub fn display_info_platform(&self, display_index: u32) -> Option<&DisplayInfoPlatform> {
-        self.display_info_inner(display_index)
-            .map(|display_info| &display_info.platform)
+    /// [`platform-specific info`]: enum.PlatformInfo.html
+    /// [`display index`]: struct.DisplayIndex.html
+    pub fn display_info_platform(
+        &self,
+        display_index: DisplayIndex,
+    ) -> Result<&PlatformInfo, StaleIndex> {
+        self.display_info_platform_inner(self.check_index(display_index)?)
+            .ok_or(StaleIndex)
     }
 
-    /// Returns the [`adjacency info`] for the display with the provided `display_index`,
-    /// or `None` if `display_index` is out of bounds.
+    /// Returns the [`Windows-specific info`] for the display with the provided [`display index`],
+    /// or `None` if the display has no Windows-specific info.
+    ///
+    /// Returns an error if the displays were re-enumerated and reordered since the display index was obtained.
+    ///
+    /// NOTE - display index `0` corresponds to the system's primary display, if any.
+    ///
+    /// [`Windows-specific info`]: struct.DisplayInfoWin.html
+    /// [`display index`]: struct.DisplayIndex.html
+    #[cfg(windows)]
+    pub fn display_info_win(
+        &self,
+        display_index: DisplayIndex,
+    ) -> Result<Option<&DisplayInfoWin>, StaleIndex> {
+        self.display_info_platform(display_index)
+            .map(PlatformInfo::windows)
+    }
+
+    /// Returns the identifier of the graphics adapter a (high-performance) application should render on
+    /// to present to the display with the provided [`display index`] without a cross-adapter copy,
+    /// or `None` if the adapter is unknown.
+    ///
+    /// Returns an error if the displays were re-enumerated and reordered since the display index was obtained.
+    ///
+    /// See [`DisplayInfoWin::render_adapter_luid`].
+    ///
+    /// [`display index`]: struct.DisplayIndex.html
+    /// [`DisplayInfoWin::render_adapter_luid`]: struct.DisplayInfoWin.html#method.render_adapter_luid
+    #[cfg(windows)]
+    pub fn render_adapter(
+        &self,
+        display_index: DisplayIndex,
+    ) -> Result<Option<AdapterLuid>, StaleIndex> {
+        self.display_info_win(display_index)
+            .map(|display| display.and_then(DisplayInfoWin::render_adapter_luid))
+    }
+
+    /// Returns `true` if the displays with the provided [`display indices`] are driven by the same graphics adapter.
     ///
-    /// NOTE - `display_index == 0` corresponds to the system's primary display, if any.
+    /// Returns `false` if either display's adapter is unknown,
+    /// or an error if the displays were re-enumerated and reordered since either display index was obtained.
+    ///
+    /// See [`DisplayInfoWin::same_adapter`].
+    ///
+    /// [`display indices`]: struct.DisplayIndex.html
+    /// [`DisplayInfoWin::same_adapter`]: struct.DisplayInfoWin.html#method.same_adapter
+    #[cfg(windows)]
+    pub fn same_adapter(
+        &self,
+        display_index: DisplayIndex,
+        other_display_index: DisplayIndex,
+    ) -> Result<bool, StaleIndex> {
+        match (
+            self.display_info_win(display_index)?,
+            self.display_info_win(other_display_index)?,
+        ) {
+            (Some(display), Some(other_display)) => Ok(display.same_adapter(other_display)),
+            _ => Ok(false),
+        }
+    }
+
+    /// Returns the index of the first enumerated display matched by the [`matcher`], if any
+    /// (e.g. `displays.find(&ByNameContains::new("DELL*").and(ByConnection(ConnectionType::DisplayPort)))`).
+    ///
+    /// [`matcher`]: trait.DisplayMatcher.html
+    pub fn find<M: DisplayMatcher + ?Sized>(&self, matcher: &M) -> Option<DisplayIndex> {
+        find_display(self.snapshot.displays().iter(), matcher)
+            .map(|display_index| self.current_index(display_index))
+    }
+
+    /// Returns a [`weak reference`] to the display with the provided [`display index`],
+    /// or an error if the displays were re-enumerated and reordered since it was obtained.
+    ///
+    /// [`weak reference`]: struct.DisplayRef.html
+    /// [`display index`]: struct.DisplayIndex.html
+    pub fn display_ref(&self, display_index: DisplayIndex) -> Result<DisplayRef, StaleIndex> {
+        self.display_info(display_index)
+            .map(|info| DisplayRef::new(info.id))
+    }
+
+    /// Registers the `callback` to be called when the display with the provided stable `id`
+    /// changes in the way described by `kind`, as detected when the displays are [`re-enumerated`]
+    /// (with the event and the re-enumerated displays).
+    ///
+    /// The callback is unregistered when the returned [`subscription`] is dropped.
+    ///
+    /// NOTE - the changes are detected by comparing the display info before and after [`enumerate_displays`] is called on this instance,
+    /// so the application must still re-enumerate the displays when notified about display configuration changes by the OS
+    /// (e.g. on `WM_DISPLAYCHANGE` / `WM_DPICHANGED`, and `WM_SETTINGCHANGE` with `SPI_SETWORKAREA` on Windows).
+    /// The callbacks are called from within [`enumerate_displays`].
+    ///
+    /// [`re-enumerated`]: #method.enumerate_displays
+    /// [`enumerate_displays`]: #method.enumerate_displays
+    /// [`subscription`]: struct.DisplaySubscription.html
+    pub fn on_display<F>(
+        &self,
+        id: DisplayId,
+        kind: DisplayEventKind,
+        callback: F,
+    ) -> DisplaySubscription
+    where
+        F: FnMut(&DisplayEvent, &Displays) + Send + 'static,
+    {
+        self.subscribers.subscribe(id, kind, callback)
+    }
+
+    /// Returns the [`adjacency info`] for the display with the provided [`display index`],
+    /// or an error if the displays were re-enumerated and reordered since it was obtained.
+    ///
+    /// NOTE - display index `0` corresponds to the system's primary display, if any.
     ///
     /// [`adjacency info`]: struct.AdjacencyInfo.html
-    pub fn adjacency_info(&self, display_index: u32) -> Option<&AdjacencyInfo> {
-        self.display_info_inner(display_index)
-            .map(|display_info| &display_info.adjacency_info)
+    /// [`display index`]: struct.DisplayIndex.html
+    pub fn adjacency_info(
+        &self,
+        display_index: DisplayIndex,
+    ) -> Result<&AdjacencyInfo, StaleIndex> {
+        self.snapshot
+            .adjacency_info(self.check_index(display_index)?)
+            .ok_or(StaleIndex)
     }
 
-    /// Returns an iterator over [`full display info`](struct.DisplayInfoFull.html) of all enumerated displays.
-    pub fn iter(&self) -> DisplayInfoIter<'_> {
-        DisplayInfoIter(self.displays.iter())
+    /// Returns the edges the display with the provided [`display index`] shares with all adjacent displays
+    /// (i.e. the spans over which the cursor may cross between them),
+    /// or an error if the displays were re-enumerated and reordered since it was obtained.
+    ///
+    /// See [`SharedEdge`](struct.SharedEdge.html).
+    ///
+    /// [`display index`]: struct.DisplayIndex.html
+    pub fn shared_edges(&self, display_index: DisplayIndex) -> Result<Vec<SharedEdge>, StaleIndex> {
+        self.snapshot
+            .shared_edges(self.check_index(display_index)?)
+            .ok_or(StaleIndex)
     }
 
-    /// Returns the combined virtual desktop [`rectangle`] for all enumerated displays.
+    /// Returns the nearest display in each direction from the display with the provided [`display index`]
+    /// (e.g. to move a window one display to the left), even if the displays don't touch,
+    /// or an error if the displays were re-enumerated and reordered since it was obtained.
     ///
-    /// [`rectangle`]: struct.Rectangle.html
-    pub fn virtual_desktop(&self) -> Option<Rectangle> {
-        self.virtual_desktop
+    /// See [`Neighbor`](struct.Neighbor.html).
+    ///
+    /// [`display index`]: struct.DisplayIndex.html
+    pub fn neighbors(&self, display_index: DisplayIndex) -> Result<Vec<Neighbor>, StaleIndex> {
+        self.snapshot
+            .neighbors(self.check_index(display_index)?)
+            .ok_or(StaleIndex)
     }
 
-    fn display_info_inner(&self, display_index: u32) -> Option<&DisplayInfoFull> {
-        let display_index = display_index as usize;
+    /// Returns the index of the (first) internal display (e.g. a laptop's built-in panel), if any is enumerated,
+    /// and the (best-effort) [`state`] of the laptop lid.
+    ///
+    /// The lid state is inferred from the display topology.
+    /// The OS deactivates the internal display when the lid is closed and external displays are connected,
+    /// so an inactive internal display means the lid is closed,
+    /// and an active internal display alongside other active displays means the lid is open.
+    /// If the internal display is the only active display, it may stay active with the lid closed
+    /// (depending on the system's power settings), so the lid state is unknown.
+    ///
+    /// NOTE - the inactive internal display is only enumerated if the inactive displays are
+    /// (see [`set_include_inactive`]). Otherwise returns `None` with the lid closed,
+    /// same as if no internal display is present.
+    ///
+    /// [`state`]: enum.LidState.html
+    /// [`set_include_inactive`]: #method.set_include_inactive
+    pub fn internal_display(&self) -> Option<(DisplayIndex, LidState)> {
+        let displays = || self.snapshot.displays().iter().zip(self.platform.iter());
+
+        let (index, (info, platform)) = displays()
+            .enumerate()
+            .find(|(_, (info, _))| info.connection == ConnectionType::Internal)?;
 
-        if display_index >= self.displays.len() {
-            None
+        let lid_state = if !is_active(info, platform) {
+            LidState::Closed
+        } else if displays()
+            .filter(|(info, platform)| is_active(info, platform))
+            .count()
+            > 1
+        {
+            LidState::Open
         } else {
-            Some(&self.displays[display_index])
+            LidState::Unknown
+        };
+
+        Some((self.current_index(index as u32), lid_state))
+    }
+
+    /// Begins a new display configuration [`transaction`] over the enumerated displays,
+    /// used to change the displays' modes, positions, enabled state, and the primary display in one go.
+    ///
+    /// [`transaction`]: struct.DisplayConfigTransaction.html
+    pub fn begin_config(&self) -> DisplayConfigTransaction<'_> {
+        DisplayConfigTransaction::new(self)
+    }
+
+    /// Returns the current user's blue light reduction ([`Night Light`] on Windows) state,
+    /// or `None` if it is not supported or was never configured.
+    ///
+    /// Colors captured / measured while the blue light reduction is active may be shifted.
+    ///
+    /// [`Night Light`]: struct.NightLightState.html
+    pub fn night_light() -> Option<NightLightState> {
+        night_light_platform()
+    }
+
+    /// Returns `true` if the system is in presentation mode
+    /// (i.e. the user turned it on, or an exclusive full screen application is running),
+    /// during which the screen saver and the notifications are suppressed.
+    pub fn presentation_mode_active() -> Result<bool, ()> {
+        presentation_mode_active_platform()
+    }
+
+    /// Keeps the displays turned on (and the screen saver from starting) while the calling thread runs,
+    /// if `display_required` is `true`, e.g. while playing back a video.
+    ///
+    /// Call again with `display_required == false` to let the displays turn off on idle again.
+    ///
+    /// NOTE - the requirement applies to all displays, and is reset when the calling thread exits.
+    pub fn set_thread_display_required(display_required: bool) -> Result<(), ()> {
+        set_thread_display_required_platform(display_required)
+    }
+
+    /// Returns the [`DPI awareness`] of the current process.
+    ///
+    /// See [`coordinates_are_virtualized`].
+    ///
+    /// [`DPI awareness`]: enum.DpiAwareness.html
+    /// [`coordinates_are_virtualized`]: #method.coordinates_are_virtualized
+    pub fn process_dpi_awareness() -> Result<DpiAwareness, ()> {
+        process_dpi_awareness_platform()
+    }
+
+    /// Returns `true` if the OS virtualizes the coordinates for the calling thread (i.e. it's not per-monitor DPI-aware),
+    /// so the coordinates it exchanges with the OS (e.g. window rectangles) don't match the display rectangles,
+    /// which are always reported in physical pixels.
+    ///
+    /// Applications should warn about this, or make the thread per-monitor DPI-aware
+    /// (e.g. via the application manifest), or scale the display rectangles by the displays' [`DPI scale`].
+    ///
+    /// Returns `false` if the calling thread's DPI awareness is unknown.
+    ///
+    /// [`DPI scale`]: struct.DisplayInfo.html#structfield.dpi_scale
+    pub fn coordinates_are_virtualized() -> bool {
+        matches!(
+            thread_dpi_awareness_platform(),
+            Ok(DpiAwareness::Unaware) | Ok(DpiAwareness::System)
+        )
+    }
+
+    /// Returns the system's current [`projection`] mode.
+    ///
+    /// [`projection`]: enum.Projection.html
+    pub fn projection() -> Result<Projection, ()> {
+        projection_platform()
+    }
+
+    /// Sets the system's [`projection`] mode, like the `Win+P` OS UI does on Windows.
+    ///
+    /// NOTE - the displays must be re-enumerated after the projection mode is changed.
+    ///
+    /// [`projection`]: enum.Projection.html
+    pub fn set_projection(projection: Projection) -> Result<(), ()> {
+        set_projection_platform(projection)
+    }
+
+    /// Changes the [`orientation`] of the display with the provided `display_index`.
+    ///
+    /// The display's desktop dimensions are swapped if the orientation changes between landscape and portrait.
+    ///
+    /// Returns an error if the `display_index` is stale,
+    /// if the display does not support the (rotated) current display mode, or if changing the orientation failed.
+    /// With the `no-modes` feature the (rotated) current display mode is only validated by the OS.
+    ///
+    /// NOTE - the displays must be re-enumerated after the orientation is changed.
+    ///
+    /// [`orientation`]: enum.Orientation.html
+    pub fn set_orientation(
+        &self,
+        display_index: DisplayIndex,
+        orientation: Orientation,
+    ) -> Result<(), DisplayError> {
+        let display = self.display_info_full(display_index)?;
+        let info = &display.info;
+
+        let current_mode = info.current_mode;
+        let swap = orientation.is_portrait() != info.orientation.is_portrait();
+
+        let dimensions = if swap {
+            Dimensions::new(
+                current_mode.dimensions.height,
+                current_mode.dimensions.width,
+            )
+        } else {
+            current_mode.dimensions
+        };
+
+        // Supported display modes are reported in the display's unrotated orientation.
+        #[cfg(not(feature = "no-modes"))]
+        let unrotated_dimensions = if orientation.is_portrait() {
+            Dimensions::new(dimensions.height, dimensions.width)
+        } else {
+            dimensions
+        };
+
+        #[cfg(not(feature = "no-modes"))]
+        if !info
+            .validate_mode(&DisplayMode {
+                dimensions: unrotated_dimensions,
+                ..current_mode
+            })
+            .is_supported()
+        {
+            return Err(DisplayError::Failed);
         }
+
+        set_orientation_platform(
+            display.platform.native().ok_or(DisplayError::Failed)?,
+            orientation,
+            dimensions,
+        )
+        .map_err(|()| DisplayError::Failed)
     }
 
-    fn calc_adjacency_info(
-        displays: &[EnumeratedDisplayInfo],
-        display_index: usize,
-    ) -> AdjacencyInfo {
-        debug_assert!(display_index < displays.len());
-        let rectangle = &displays[display_index].info.rects.virtual_rect;
+    /// Switches the display with the provided `display_index` to the supported refresh rate closest to `refresh_rate`
+    /// (in Hz, e.g. `120.0` or `59.94`), keeping its current dimensions.
+    ///
+    /// Returns the display mode the display was switched to.
+    ///
+    /// Returns an error if the `display_index` is stale,
+    /// if the display supports no refresh rates with its current dimensions, or if changing the refresh rate failed.
+    ///
+    /// NOTE - the displays must be re-enumerated after the refresh rate is changed.
+    ///
+    /// NOTE - not available with the `no-modes` feature.
+    #[cfg(not(feature = "no-modes"))]
+    pub fn set_refresh_rate(
+        &self,
+        display_index: DisplayIndex,
+        refresh_rate: f64,
+    ) -> Result<DisplayMode, DisplayError> {
+        let info = self.display_info(display_index)?;
 
-        let mut adjacency = AdjacencyInfo::default();
+        // Keep the current (rotated) dimensions.
+        let mode = DisplayMode {
+            dimensions: info.current_mode.dimensions,
+            ..info
+                .closest_refresh_rate(refresh_rate)
+                .ok_or(DisplayError::Failed)?
+        };
 
-        for (i, display_info) in displays.iter().enumerate() {
-            if i == display_index {
-                continue;
-            }
+        let mut config = self.begin_config();
+        config.set_validated_mode(self.check_index(display_index)?, mode);
+        config.apply().map_err(|()| DisplayError::Failed)?;
 
-            let i = i as u32;
-            let other_rectangle = &display_info.info.rects.virtual_rect;
+        Ok(mode)
+    }
 
-            // Adjacent to the left?
-            if other_rectangle.right() == rectangle.left() {
-                adjacency.left.replace(i);
+    /// Sets the DPI scale `percent` (e.g. `100` for no scaling) for the display with the provided `display_index`.
+    /// `percent` must be one of the display's allowed DPI [`scaling options`], if known.
+    ///
+    /// Returns an error if the `display_index` is stale, if `percent` is not allowed,
+    /// or if setting the DPI scale failed.
+    ///
+    /// NOTE - relies on the undocumented OS API's.
+    /// NOTE - the displays must be re-enumerated after the DPI scale is changed.
+    ///
+    /// [`scaling options`]: struct.DisplayInfo.html#structfield.dpi_options
+    #[cfg(feature = "unstable-scaling")]
+    pub fn set_dpi_scale(
+        &self,
+        display_index: DisplayIndex,
+        percent: u32,
+    ) -> Result<(), DisplayError> {
+        let platform = self.display_info_platform(display_index)?;
+        set_dpi_scale_platform(platform.native().ok_or(DisplayError::Failed)?, percent)
+            .map_err(|()| DisplayError::Failed)
+    }
+
+    /// Computes the brightness adjustments of the other displays to match the brightness
+    /// of the display with the provided `reference_index`, without applying them (i.e. a dry run of [`match_brightness`]).
+    ///
+    /// Prefers the monitors' (hardware) DDC/CI brightness, and falls back to scaling the displays' gamma ramps
+    /// (in linear light) for the displays which don't support DDC/CI.
+    /// Displays with neither are skipped.
+    ///
+    /// Returns an error if the `reference_index` is stale, or if the reference display's brightness is unknown.
+    ///
+    /// NOTE - assumes the DDC/CI brightness levels are linear in luminance and all displays have the same peak luminance,
+    /// which is only approximately true.
+    /// NOTE - reading the DDC/CI brightness is slow (tens of milliseconds per monitor).
+    ///
+    /// [`match_brightness`]: #method.match_brightness
+    pub fn brightness_adjustments(
+        &self,
+        reference_index: DisplayIndex,
+    ) -> Result<Vec<BrightnessAdjustment>, DisplayError> {
+        let brightness: Vec<_> = self
+            .platform
+            .iter()
+            .map(|platform| platform.native().and_then(brightness_platform))
+            .collect();
+
+        let reference_index = self.check_index(reference_index)?;
+
+        brightness_adjustments(&brightness, reference_index as usize, self.index_generation)
+            .map_err(|()| DisplayError::Failed)
+    }
+
+    /// Adjusts the brightness of the other displays to match the brightness of the display with the provided `reference_index`
+    /// (see [`brightness_adjustments`]).
+    ///
+    /// Returns the applied adjustments.
+    ///
+    /// Returns an error if the `reference_index` is stale, if the reference display's brightness is unknown,
+    /// or if any of the adjustments failed (the rest are still applied).
+    ///
+    /// [`brightness_adjustments`]: #method.brightness_adjustments
+    pub fn match_brightness(
+        &self,
+        reference_index: DisplayIndex,
+    ) -> Result<Vec<BrightnessAdjustment>, DisplayError> {
+        let adjustments = self.brightness_adjustments(reference_index)?;
+
+        let mut result = Ok(());
+
+        for adjustment in adjustments.iter() {
+            let platform = self.display_info_platform(adjustment.display_index)?;
+
+            if platform
+                .native()
+                .ok_or(())
+                .and_then(|platform| set_brightness_platform(platform, adjustment.setting))
+                .is_err()
+            {
+                result = Err(DisplayError::Failed);
             }
+        }
 
-            // Adjacent to the right?
-            if other_rectangle.left() == rectangle.right() {
-                adjacency.right.replace(i);
+        result.map(|_| adjustments)
+    }
+
+    /// Transitions the gamma ramp of the display with the provided `display_index` to the [`preset`]
+    /// (e.g. a warm color temperature for reading at night) over the `duration`,
+    /// smoothly interpolating from its current gamma ramp on a background thread.
+    ///
+    /// The preset is applied right away if the `duration` is zero or the display's current gamma ramp is unknown.
+    ///
+    /// Returns an error if the `display_index` is stale, or if the transition could not be started.
+    /// Errors applying the gamma ramps are reported by the returned [`transition`].
+    ///
+    /// NOTE - overrides the gamma ramp set by the [`brightness adjustments`] and vice versa;
+    /// the OS / driver may reject the very warm color temperatures.
+    ///
+    /// [`preset`]: enum.GammaPreset.html
+    /// [`transition`]: struct.GammaTransition.html
+    /// [`brightness adjustments`]: #method.match_brightness
+    pub fn apply_gamma_preset(
+        &self,
+        display_index: DisplayIndex,
+        preset: GammaPreset,
+        duration: Duration,
+    ) -> Result<GammaTransition, DisplayError> {
+        let platform = self.display_info_platform(display_index)?;
+
+        let to = preset.ramp();
+        let from = platform
+            .native()
+            .ok_or(DisplayError::Failed)
+            .map(gamma_ramp_platform)?;
+
+        let platform = platform.clone();
+
+        GammaTransition::start(from, to, duration, move |ramp| {
+            platform
+                .native()
+                .ok_or(())
+                .and_then(|platform| set_gamma_ramp_platform(platform, ramp))
+        })
+        .map_err(|()| DisplayError::Failed)
+    }
+
+    /// Returns the [`state`] of the adaptive brightness of the display with the provided `display_index`,
+    /// so that the brightness controls may warn the user that the manually set brightness may be overridden.
+    ///
+    /// Returns `None` if the display is not an [`internal`] one
+    /// (the adaptive brightness only applies to the laptop's built-in panel),
+    /// or if the platform does not expose the adaptive brightness settings,
+    /// and an error if the `display_index` is stale.
+    ///
+    /// NOTE - on Windows, the adaptive brightness setting of the active power plan is reported,
+    /// which may be enabled even if the system has no ambient light sensor.
+    ///
+    /// [`state`]: struct.AdaptiveBrightness.html
+    /// [`internal`]: enum.ConnectionType.html#variant.Internal
+    pub fn adaptive_brightness(
+        &self,
+        display_index: DisplayIndex,
+    ) -> Result<Option<AdaptiveBrightness>, StaleIndex> {
+        let info = self.display_info(display_index)?;
+
+        if info.connection != ConnectionType::Internal {
+            return Ok(None);
+        }
+
+        Ok(adaptive_brightness_platform())
+    }
+
+    /// Helps the user physically identify the monitor of the display with the provided `display_index`
+    /// by briefly pulsing its (hardware) brightness via DDC/CI a few times, then restoring it.
+    ///
+    /// An alternative to highlighting the display with a window, e.g. when the caller cannot create windows.
+    ///
+    /// Blocks the calling thread for the duration of the pulse (a couple of seconds).
+    ///
+    /// Returns an error if the `display_index` is stale, if the monitor does not support DDC/CI,
+    /// or if setting its brightness failed (the original brightness is still restored, if possible).
+    ///
+    /// NOTE - DDC/CI is slow (tens of milliseconds per request), and some monitors fade the brightness changes in,
+    /// so the pulse may look smoothed out.
+    pub fn identify_via_ddc(&self, display_index: DisplayIndex) -> Result<(), DisplayError> {
+        const PULSE_DURATION: std::time::Duration = std::time::Duration::from_millis(400);
+
+        let platform = self
+            .display_info_platform(display_index)?
+            .native()
+            .ok_or(DisplayError::Failed)?;
+
+        let level = brightness_platform(platform)
+            .and_then(|brightness| brightness.ddc)
+            .ok_or(DisplayError::Failed)?;
+
+        for pulse in identify_pulse(level) {
+            if set_brightness_platform(platform, BrightnessSetting::Ddc(pulse)).is_err() {
+                // Try to restore the original brightness.
+                let _ = set_brightness_platform(platform, BrightnessSetting::Ddc(level.current));
+                return Err(DisplayError::Failed);
             }
 
-            // Adjacent to the top?
-            if other_rectangle.bottom() == rectangle.top() {
-                adjacency.top.replace(i);
+            std::thread::sleep(PULSE_DURATION);
+        }
+
+        Ok(())
+    }
+
+    /// Installs the ICC color profile at `path` (e.g. as measured by a calibration tool)
+    /// and makes it the current user's default color profile of the monitor of the display with the provided `display_index`.
+    ///
+    /// The profile is copied to the system color directory, unless it's already there,
+    /// then associated with the monitor, which is matched by the same device path its [`stable identifier`] is derived from
+    /// (use [`DisplaysSnapshot::find`] to get the display index of a known monitor).
+    /// The current user's profile associations are enabled for the monitor, if they were not.
+    ///
+    /// Returns an error if the `display_index` is stale, if the monitor cannot be identified,
+    /// or if any step failed, in which case the changes made so far are rolled back
+    /// (the profile is uninstalled, disassociated and the previous default profile is restored).
+    ///
+    /// NOTE - installing the profile may require the administrator privileges.
+    ///
+    /// [`stable identifier`]: struct.DisplayId.html
+    /// [`DisplaysSnapshot::find`]: struct.DisplaysSnapshot.html#method.find
+    pub fn apply_color_profile<P: AsRef<Path>>(
+        &self,
+        display_index: DisplayIndex,
+        path: P,
+    ) -> Result<(), DisplayError> {
+        let platform = self.display_info_platform(display_index)?;
+        apply_color_profile_platform(
+            platform.native().ok_or(DisplayError::Failed)?,
+            path.as_ref(),
+        )
+        .map_err(|()| DisplayError::Failed)
+    }
+
+    /// Returns the physical monitors behind the display with the provided `display_index`.
+    ///
+    /// A display usually has a single physical monitor, but may have more (e.g. in the clone mode, or behind some docks),
+    /// in which case the DDC/CI methods (e.g. [`contrast`]) read from the first physical monitor which supports the control,
+    /// and write to all of them. Use [`physical_monitor_vcp_feature`] / [`set_physical_monitor_vcp_feature`]
+    /// to target a specific physical monitor instead.
+    ///
+    /// Returns an error if the `display_index` is stale, or if the display's physical monitors cannot be queried.
+    ///
+    /// [`contrast`]: #method.contrast
+    /// [`physical_monitor_vcp_feature`]: #method.physical_monitor_vcp_feature
+    /// [`set_physical_monitor_vcp_feature`]: #method.set_physical_monitor_vcp_feature
+    pub fn physical_monitors(
+        &self,
+        display_index: DisplayIndex,
+    ) -> Result<Vec<PhysicalMonitor>, DisplayError> {
+        let platform = self.display_info_platform(display_index)?;
+        physical_monitors_platform(platform.native().ok_or(DisplayError::Failed)?)
+            .map_err(|()| DisplayError::Failed)
+    }
+
+    /// Returns the current and maximum values of the MCCS VCP feature with the `code`
+    /// of the physical monitor with the provided index (see [`physical_monitors`])
+    /// behind the display with the provided `display_index`, as read via DDC/CI.
+    ///
+    /// Returns an error if the `display_index` is stale, if `physical_monitor` is out of bounds,
+    /// or if the physical monitor does not support DDC/CI or the VCP feature.
+    ///
+    /// [`physical_monitors`]: #method.physical_monitors
+    pub fn physical_monitor_vcp_feature(
+        &self,
+        display_index: DisplayIndex,
+        physical_monitor: u32,
+        code: u8,
+    ) -> Result<VcpValue, DisplayError> {
+        let platform = self.display_info_platform(display_index)?;
+        vcp_feature_platform(
+            platform.native().ok_or(DisplayError::Failed)?,
+            Some(physical_monitor),
+            code,
+        )
+        .map_err(|()| DisplayError::Failed)
+    }
+
+    /// Sets the `value` of the MCCS VCP feature with the `code`
+    /// of the physical monitor with the provided index (see [`physical_monitors`])
+    /// behind the display with the provided `display_index` via DDC/CI.
+    ///
+    /// Returns an error if the `display_index` is stale, if `physical_monitor` is out of bounds,
+    /// or if the physical monitor does not support DDC/CI or the VCP feature.
+    ///
+    /// [`physical_monitors`]: #method.physical_monitors
+    pub fn set_physical_monitor_vcp_feature(
+        &self,
+        display_index: DisplayIndex,
+        physical_monitor: u32,
+        code: u8,
+        value: u32,
+    ) -> Result<(), DisplayError> {
+        let platform = self.display_info_platform(display_index)?;
+        set_vcp_feature_platform(
+            platform.native().ok_or(DisplayError::Failed)?,
+            Some(physical_monitor),
+            code,
+            value,
+        )
+        .map_err(|()| DisplayError::Failed)
+    }
+
+    /// Returns the contrast of the monitor of the display with the provided `display_index`, as read via DDC/CI.
+    ///
+    /// Returns an error if the `display_index` is stale, or if the monitor does not support DDC/CI or the contrast control.
+    ///
+    /// NOTE - DDC/CI is slow (tens of milliseconds per request).
+    pub fn contrast(&self, display_index: DisplayIndex) -> Result<VcpValue, DisplayError> {
+        let platform = self.display_info_platform(display_index)?;
+        vcp_feature_platform(
+            platform.native().ok_or(DisplayError::Failed)?,
+            None,
+            VCP_CONTRAST,
+        )
+        .map_err(|()| DisplayError::Failed)
+    }
+
+    /// Sets the `contrast` (in `[0 .. max]` range, see [`contrast`]) of the monitor of the display with the provided `display_index`
+    /// via DDC/CI.
+    ///
+    /// Returns an error if the `display_index` is stale, or if the monitor does not support DDC/CI or the contrast control.
+    ///
+    /// [`contrast`]: #method.contrast
+    pub fn set_contrast(
+        &self,
+        display_index: DisplayIndex,
+        contrast: u32,
+    ) -> Result<(), DisplayError> {
+        let platform = self.display_info_platform(display_index)?;
+        set_vcp_feature_platform(
+            platform.native().ok_or(DisplayError::Failed)?,
+            None,
+            VCP_CONTRAST,
+            contrast,
+        )
+        .map_err(|()| DisplayError::Failed)
+    }
+
+    /// Returns the video black level of the monitor of the display with the provided `display_index`, as read via DDC/CI.
+    ///
+    /// Monitors have separate red / green / blue black level controls - this returns the first supported one
+    /// (they are usually the same).
+    ///
+    /// Returns an error if the `display_index` is stale, or if the monitor does not support DDC/CI or the black level controls.
+    pub fn black_level(&self, display_index: DisplayIndex) -> Result<VcpValue, DisplayError> {
+        let platform = self
+            .display_info_platform(display_index)?
+            .native()
+            .ok_or(DisplayError::Failed)?;
+
+        VCP_BLACK_LEVEL
+            .iter()
+            .find_map(|&code| vcp_feature_platform(platform, None, code).ok())
+            .ok_or(DisplayError::Failed)
+    }
+
+    /// Sets the video `black_level` (in `[0 .. max]` range, see [`black_level`]) of all color channels
+    /// of the monitor of the display with the provided `display_index` via DDC/CI.
+    ///
+    /// Returns an error if the `display_index` is stale, or if the monitor does not support DDC/CI or any of the black level controls.
+    ///
+    /// [`black_level`]: #method.black_level
+    pub fn set_black_level(
+        &self,
+        display_index: DisplayIndex,
+        black_level: u32,
+    ) -> Result<(), DisplayError> {
+        let platform = self
+            .display_info_platform(display_index)?
+            .native()
+            .ok_or(DisplayError::Failed)?;
+
+        let mut result = Err(DisplayError::Failed);
+
+        for &code in VCP_BLACK_LEVEL.iter() {
+            if set_vcp_feature_platform(platform, None, code, black_level).is_ok() {
+                result = Ok(());
             }
+        }
+
+        result
+    }
+
+    /// Returns the currently selected video input source of the monitor of the display with the provided `display_index`,
+    /// as read via DDC/CI.
+    ///
+    /// Returns an error if the `display_index` is stale, or if the monitor does not support DDC/CI or the input source control.
+    pub fn input_source(&self, display_index: DisplayIndex) -> Result<InputSource, DisplayError> {
+        let platform = self.display_info_platform(display_index)?;
+
+        vcp_feature_platform(
+            platform.native().ok_or(DisplayError::Failed)?,
+            None,
+            VCP_INPUT_SOURCE,
+        )
+        .map(|value| InputSource::from_vcp(value.current))
+        .map_err(|()| DisplayError::Failed)
+    }
+
+    /// Switches the monitor of the display with the provided `display_index` to the video `input_source` via DDC/CI.
+    ///
+    /// Returns an error if the `display_index` is stale, or if the monitor does not support DDC/CI or the input source control.
+    ///
+    /// NOTE - the display will (likely) disappear if the monitor is switched to another device's input,
+    /// and the displays must be re-enumerated.
+    pub fn set_input_source(
+        &self,
+        display_index: DisplayIndex,
+        input_source: InputSource,
+    ) -> Result<(), DisplayError> {
+        let platform = self.display_info_platform(display_index)?;
+        set_vcp_feature_platform(
+            platform.native().ok_or(DisplayError::Failed)?,
+            None,
+            VCP_INPUT_SOURCE,
+            input_source.to_vcp(),
+        )
+        .map_err(|()| DisplayError::Failed)
+    }
+
+    /// Returns the asset info of the monitor of the display with the provided `display_index`
+    /// (i.e. the display's [`asset info`] with the monitor's usage hours read via DDC/CI, if supported),
+    /// or `None` if the monitor's asset info is unknown.
+    ///
+    /// Returns an error if the `display_index` is stale.
+    ///
+    /// NOTE - DDC/CI is slow (tens of milliseconds per request).
+    ///
+    /// [`asset info`]: struct.DisplayInfo.html#structfield.asset
+    pub fn asset_info(&self, display_index: DisplayIndex) -> Result<Option<AssetInfo>, StaleIndex> {
+        let display = self.display_info_full(display_index)?;
+
+        Ok(display.info.asset.clone().map(|asset| AssetInfo {
+            usage_hours: display
+                .platform
+                .native()
+                .and_then(|platform| vcp_feature_platform(platform, None, VCP_USAGE_HOURS).ok())
+                .map(|value| value.current),
+            ..asset
+        }))
+    }
+
+    /// Returns the video input sources of the monitor of the display with the provided `display_index`,
+    /// as listed in the monitor's DDC/CI capabilities string.
+    ///
+    /// Returns an error if the `display_index` is stale, if the monitor does not support DDC/CI,
+    /// or if its capabilities string does not list the input sources.
+    ///
+    /// NOTE - reading the capabilities string is very slow (up to a few seconds).
+    /// NOTE - some monitors list the inputs they don't have (e.g. all inputs of the whole monitor series).
+    pub fn input_sources(
+        &self,
+        display_index: DisplayIndex,
+    ) -> Result<Vec<InputSource>, DisplayError> {
+        let platform = self.display_info_platform(display_index)?;
+        let capabilities = capabilities_platform(platform.native().ok_or(DisplayError::Failed)?)
+            .map_err(|()| DisplayError::Failed)?;
 
-            // Adjacent to the bottom?
-            if other_rectangle.top() == rectangle.bottom() {
-                adjacency.bottom.replace(i);
+        parse_input_sources(&capabilities).ok_or(DisplayError::Failed)
+    }
+
+    /// Switches the monitor of the display with the provided `display_index` to the video `input_source` via DDC/CI
+    /// (e.g. to hand the monitor over to another computer, like a KVM switch),
+    /// unless it's already selected.
+    ///
+    /// Unlike [`set_input_source`], fails without switching if the monitor's [`input sources`] are known
+    /// and don't include the `input_source`, which some monitors react badly to (e.g. go blank until power cycled).
+    ///
+    /// Returns an error if the `display_index` is stale, if the monitor does not support DDC/CI or the input source control,
+    /// or if the monitor does not have the `input_source`.
+    ///
+    /// NOTE - the display will (likely) disappear if the monitor is switched to another device's input,
+    /// and the displays must be re-enumerated.
+    ///
+    /// [`set_input_source`]: #method.set_input_source
+    /// [`input sources`]: #method.input_sources
+    pub fn switch_input(
+        &self,
+        display_index: DisplayIndex,
+        input_source: InputSource,
+    ) -> Result<(), DisplayError> {
+        if self.input_source(display_index) == Ok(input_source) {
+            return Ok(());
+        }
+
+        if let Ok(input_sources) = self.input_sources(display_index) {
+            if !input_sources.contains(&input_source) {
+                return Err(DisplayError::Failed);
             }
         }
 
-        adjacency
+        self.set_input_source(display_index, input_source)
+    }
+
+    /// Confines the mouse cursor to the virtual rectangle of the display with the provided `display_index`
+    /// until the returned [`guard`] is dropped, restoring the previous cursor clip rectangle then.
+    ///
+    /// Returns an error if the `display_index` is stale, or if confining the cursor failed.
+    ///
+    /// [`guard`]: struct.CursorClipGuard.html
+    pub fn confine_cursor(
+        &self,
+        display_index: DisplayIndex,
+    ) -> Result<CursorClipGuard, DisplayError> {
+        let display = self.display_info(display_index)?;
+        CursorClipGuard::new(&display.rects.virtual_rect).map_err(|()| DisplayError::Failed)
+    }
+
+    /// Returns the combined virtual desktop [`rectangle`] of the displays with the provided `display_indices`
+    /// if they form a contiguous rectangle (i.e. are adjacent to each other without gaps, e.g. two side-by-side displays with equal heights),
+    /// like for spanning a borderless window across them.
+    ///
+    /// Returns `None` if the displays do not form a contiguous rectangle,
+    /// or an error if any of the `display_indices` is stale.
+    ///
+    /// See [`span_rects`].
+    ///
+    /// [`rectangle`]: struct.Rectangle.html
+    /// [`span_rects`]: fn.span_rects.html
+    pub fn span_rect(
+        &self,
+        display_indices: &[DisplayIndex],
+    ) -> Result<Option<Rectangle>, StaleIndex> {
+        let rects = display_indices
+            .iter()
+            .map(|&display_index| {
+                self.display_info(display_index)
+                    .map(|display| display.rects.virtual_rect)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(span_rects(&rects))
+    }
+
+    /// Analyzes the layout of the enumerated displays in the virtual desktop,
+    /// grouping them by their (portrait / landscape) shape and detecting misalignments
+    /// which cause janky cursor transitions between the displays
+    /// (vertical offsets between side-by-side displays, DPI scale mismatches across adjacent edges, etc.),
+    /// e.g. for setup assistants to suggest fixes.
+    ///
+    /// See [`LayoutReport`].
+    ///
+    /// [`LayoutReport`]: struct.LayoutReport.html
+    pub fn layout_report(&self) -> LayoutReport {
+        let displays: Vec<_> = self
+            .snapshot
+            .displays()
+            .iter()
+            .map(|info| (info.rects.virtual_rect, info.dpi_scale))
+            .collect();
+
+        analyze_layout(&displays)
+    }
+
+    /// Returns an iterator over [`full display info`](struct.DisplayInfoFullRef.html) of all enumerated displays.
+    pub fn iter(&self) -> DisplayInfoIter<'_> {
+        DisplayInfoIter {
+            info: self.snapshot.displays().iter(),
+            platform: self.platform.iter(),
+            adjacency_info: self.snapshot.adjacency_info.iter(),
+        }
+    }
+
+    /// Returns the [`raw display configuration`] the displays were resolved with during the last enumeration,
+    /// for advanced users to do anything the crate does not wrap yet without re-querying the display configuration.
+    ///
+    /// Returns `None` if the displays were not enumerated yet, or were enumerated without the display configuration API's
+    /// (see [`DisplaySource`]), or via a custom [`display backend`].
+    ///
+    /// [`display backend`]: trait.DisplayBackend.html
+    ///
+    /// [`raw display configuration`]: struct.RawDisplayConfig.html
+    /// [`DisplaySource`]: enum.DisplaySource.html
+    #[cfg(all(windows, feature = "raw-win"))]
+    pub fn raw_display_config(&self) -> Option<&RawDisplayConfig> {
+        self.raw_display_config.as_ref()
+    }
+
+    /// Returns the combined virtual desktop [`rectangle`] for all enumerated displays.
+    ///
+    /// [`rectangle`]: struct.Rectangle.html
+    pub fn virtual_desktop(&self) -> Option<Rectangle> {
+        self.snapshot.virtual_desktop()
+    }
+
+    /// Converts the `value` (a [`position`] or a [`rectangle`]) from the [`coordinate space`] `from` to `to`,
+    /// e.g. to pass the display rectangles to an engine which uses the virtual desktop origin.
+    ///
+    /// See [`DisplaysSnapshot::convert`].
+    ///
+    /// [`position`]: struct.Position.html
+    /// [`rectangle`]: struct.Rectangle.html
+    /// [`coordinate space`]: enum.CoordinateSpace.html
+    /// [`DisplaysSnapshot::convert`]: struct.DisplaysSnapshot.html#method.convert
+    pub fn convert<T: Coordinates>(
+        &self,
+        value: T,
+        from: CoordinateSpace,
+        to: CoordinateSpace,
+    ) -> T {
+        self.snapshot.convert(value, from, to)
+    }
+
+    /// Draws the schematic of the enumerated displays' layout as ASCII art scaled to `width` characters.
+    ///
+    /// See [`DisplaysSnapshot::render_layout_ascii`].
+    ///
+    /// [`DisplaysSnapshot::render_layout_ascii`]: struct.DisplaysSnapshot.html#method.render_layout_ascii
+    pub fn render_layout_ascii(&self, width: u32) -> String {
+        self.snapshot.render_layout_ascii(width)
+    }
+
+    /// Draws the schematic of the enumerated displays' layout as an SVG image scaled to `width` pixels.
+    ///
+    /// See [`DisplaysSnapshot::render_layout_svg`].
+    ///
+    /// [`DisplaysSnapshot::render_layout_svg`]: struct.DisplaysSnapshot.html#method.render_layout_svg
+    #[cfg(feature = "svg")]
+    pub fn render_layout_svg(&self, width: u32) -> String {
+        self.snapshot.render_layout_svg(width)
+    }
+
+    /// Returns the [`region`] covered by the virtual rectangles of all enumerated displays.
+    ///
+    /// See [`DisplaysSnapshot::virtual_desktop_region`].
+    ///
+    /// [`region`]: struct.Region.html
+    /// [`DisplaysSnapshot::virtual_desktop_region`]: struct.DisplaysSnapshot.html#method.virtual_desktop_region
+    pub fn virtual_desktop_region(&self) -> Region {
+        self.snapshot.virtual_desktop_region()
+    }
+
+    /// Splits the `rect` (e.g. of a window spanning multiple displays) by the display boundaries
+    /// into the parts covered by each enumerated display, e.g. to render each part with its display's DPI scale.
+    ///
+    /// See [`DisplaysSnapshot::split_rect`].
+    ///
+    /// [`DisplaysSnapshot::split_rect`]: struct.DisplaysSnapshot.html#method.split_rect
+    pub fn split_rect(&self, rect: &Rectangle) -> Vec<(DisplayIndex, Rectangle)> {
+        self.snapshot
+            .split_rect(rect)
+            .into_iter()
+            .map(|(display_index, part)| (self.current_index(display_index), part))
+            .collect()
+    }
+
+    /// Returns the [`layout`] of the full desktop image stitched from the enumerated displays' captures.
+    ///
+    /// See [`DisplaysSnapshot::stitch_layout`].
+    ///
+    /// [`layout`]: struct.StitchLayout.html
+    /// [`DisplaysSnapshot::stitch_layout`]: struct.DisplaysSnapshot.html#method.stitch_layout
+    pub fn stitch_layout(&self) -> StitchLayout {
+        self.snapshot.stitch_layout()
+    }
+
+    /// Returns the [`region`] covered by the work rectangles of all enumerated displays,
+    /// e.g. to subtract the application's own always-on-top toolbars from it.
+    ///
+    /// [`region`]: struct.Region.html
+    pub fn work_region(&self) -> Region {
+        self.snapshot.work_region()
+    }
+
+    /// Returns the [`snapshot`] of the displays' platform-independent info, as of the last display enumeration.
+    ///
+    /// The snapshot is immutable and is not affected by the following display enumerations -
+    /// keep it around to e.g. share it with other threads, or [`diff`] it with a later one.
+    ///
+    /// [`snapshot`]: struct.DisplaysSnapshot.html
+    /// [`diff`]: struct.DisplaysSnapshot.html#method.diff
+    pub fn snapshot(&self) -> Arc<DisplaysSnapshot> {
+        self.snapshot.clone()
+    }
+
+    /// Calls `f` with the [`snapshot`] of the displays' platform-independent info, as of the last display enumeration,
+    /// and returns its result.
+    ///
+    /// All queries made through the snapshot within `f` (e.g. [`display_info`] and [`adjacency_info`])
+    /// see the same display enumeration. Provided for parity with [`CachedDisplays::with_snapshot`] /
+    /// [`PollingWatcher::with_snapshot`], where the displays may be re-enumerated concurrently.
+    ///
+    /// [`snapshot`]: struct.DisplaysSnapshot.html
+    /// [`display_info`]: struct.DisplaysSnapshot.html#method.display_info
+    /// [`adjacency_info`]: struct.DisplaysSnapshot.html#method.adjacency_info
+    /// [`CachedDisplays::with_snapshot`]: struct.CachedDisplays.html#method.with_snapshot
+    /// [`PollingWatcher::with_snapshot`]: struct.PollingWatcher.html#method.with_snapshot
+    pub fn with_snapshot<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&DisplaysSnapshot) -> R,
+    {
+        f(&self.snapshot)
+    }
+
+    /// Returns the [`display index`] of the current generation for the in-bounds raw `display_index`.
+    ///
+    /// [`display index`]: struct.DisplayIndex.html
+    fn current_index(&self, display_index: u32) -> DisplayIndex {
+        DisplayIndex::new(display_index, self.index_generation)
+    }
+
+    pub(crate) fn display_info_inner(&self, display_index: u32) -> Option<&DisplayInfo> {
+        self.snapshot.display_info(display_index)
+    }
+
+    pub(crate) fn display_info_platform_inner(&self, display_index: u32) -> Option<&PlatformInfo> {
+        self.platform.get(display_index as usize)
     }
 }
 
-/// Returns [`full dispaly info`](struct.DisplayInfoFull.html) for consecutive enumerated displays.
-pub struct DisplayInfoIter<'d>(Iter<'d, DisplayInfoFull>);
+/// Returns [`full dispaly info`](struct.DisplayInfoFullRef.html) for consecutive enumerated displays.
+pub struct DisplayInfoIter<'d> {
+    info: Iter<'d, DisplayInfo>,
+    platform: Iter<'d, PlatformInfo>,
+    adjacency_info: Iter<'d, AdjacencyInfo>,
+}
 
 impl<'d> Iterator for DisplayInfoIter<'d> {
-    type Item = &'d DisplayInfoFull;
+    type Item = DisplayInfoFullRef<'d>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.0.next()
+        Some(DisplayInfoFullRef {
+            info: self.info.next()?,
+            platform: self.platform.next()?,
+            adjacency_info: self.adjacency_info.next()?,
+        })
     }
 }
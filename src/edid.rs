@@ -0,0 +1,732 @@
+// The EDID parser is only used by the platform code.
+#![cfg_attr(not(windows), allow(dead_code))]
+
+use crate::{
+    AssetInfo, Chromaticity, Colorimetry, ConnectionType, Dimensions, DisplayId, DisplayInfo,
+    DisplayMode, DisplayName, DisplayRects, DisplaySource, HdrCaps, UpscaleMode,
+};
+
+/// Size of the EDID base block, in bytes.
+const EDID_BLOCK_SIZE: usize = 128;
+/// Fixed EDID header pattern.
+const EDID_HEADER: [u8; 8] = [0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00];
+/// Offsets of the four 18-byte descriptors in the EDID base block.
+const DESCRIPTOR_OFFSETS: [usize; 4] = [54, 72, 90, 108];
+const DESCRIPTOR_SIZE: usize = 18;
+/// Display descriptor tag of the monitor name descriptor.
+const MONITOR_NAME_TAG: u8 = 0xfc;
+/// Display descriptor tag of the monitor serial number descriptor.
+const SERIAL_NUMBER_TAG: u8 = 0xff;
+/// Tag of the CTA-861 extension block.
+const CTA_EXTENSION_TAG: u8 = 0x02;
+/// CTA-861 data block tag which means the tag is extended (in the first payload byte).
+const CTA_EXTENDED_TAG: u8 = 0x07;
+/// CTA-861 extended data block tag of the HDR static metadata data block.
+const HDR_STATIC_METADATA_TAG: u8 = 0x06;
+/// HDR static metadata data block EOTF flag for the SMPTE ST 2084 (PQ) transfer function.
+const EOTF_PQ: u8 = 0x04;
+/// HDR static metadata data block EOTF flag for the Hybrid Log-Gamma transfer function.
+const EOTF_HLG: u8 = 0x08;
+
+/// Display info parsed from the monitor's EDID (Extended Display Identification Data) base block
+/// and its CTA-861 extension blocks, if any.
+#[derive(Clone, PartialEq, Debug)]
+pub(crate) struct Edid {
+    /// Monitor's name, as reported by the monitor name descriptor, if any.
+    pub(crate) name: Option<String>,
+    /// Monitor manufacturer's three-letter PnP ID (e.g. `DEL`), if valid.
+    pub(crate) manufacturer: Option<String>,
+    /// Monitor's product code, as a four-digit hexadecimal string (e.g. `40F0`).
+    pub(crate) product_code: String,
+    /// Monitor's serial number, as reported by the serial number descriptor, if any,
+    /// or the numeric serial number, if non-zero.
+    pub(crate) serial_number: Option<String>,
+    /// Monitor's year of manufacture (or the model year), if specified.
+    pub(crate) manufacture_year: Option<u16>,
+    /// Monitor's week of manufacture, if specified.
+    pub(crate) manufacture_week: Option<u8>,
+    /// Monitor's preferred (native) timing, as reported by the first detailed timing descriptor, if any.
+    pub(crate) preferred_timing: Option<EdidTiming>,
+    /// Monitor's (unrotated) physical image size in millimeters, if specified.
+    pub(crate) physical_size: Option<Dimensions>,
+    /// Monitor's HDR capabilities, as reported by the CTA-861 HDR static metadata data block, if it's HDR-capable.
+    pub(crate) hdr_caps: Option<HdrCaps>,
+    /// Monitor's color primaries and white point, if specified.
+    pub(crate) colorimetry: Option<Colorimetry>,
+    /// Monitor's maximum supported bit depth per color channel, if specified (EDID 1.4+ digital inputs only).
+    pub(crate) max_bits_per_channel: Option<u8>,
+}
+
+/// Display timing parsed from an EDID detailed timing descriptor.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) struct EdidTiming {
+    pub(crate) dimensions: Dimensions,
+    /// Refresh rate numerator, such that numerator/denominator gives the refresh rate in Hz.
+    pub(crate) refresh_rate_num: u32,
+    /// Refresh rate denominator, such that numerator/denominator gives the refresh rate in Hz.
+    pub(crate) refresh_rate_denom: u32,
+}
+
+impl Edid {
+    /// Parses the EDID base block at the start of the raw `edid` data, and the extension blocks following it, if any.
+    ///
+    /// Returns `None` if the data is too short, or the header or the checksum of the base block are invalid.
+    /// Extension blocks which are missing or have invalid checksums are ignored.
+    pub(crate) fn parse(edid: &[u8]) -> Option<Self> {
+        let block = edid.get(..EDID_BLOCK_SIZE)?;
+
+        if block[..EDID_HEADER.len()] != EDID_HEADER {
+            return None;
+        }
+
+        if block.iter().fold(0u8, |sum, &byte| sum.wrapping_add(byte)) != 0 {
+            return None;
+        }
+
+        let descriptors = DESCRIPTOR_OFFSETS
+            .iter()
+            .map(|&offset| &block[offset..offset + DESCRIPTOR_SIZE]);
+
+        let mut name = None;
+        let mut serial_number = None;
+        let mut preferred_timing = None;
+        let mut physical_size = None;
+
+        for (index, descriptor) in descriptors.enumerate() {
+            // Display descriptors have zero pixel clock.
+            let is_display_descriptor = descriptor[0] == 0 && descriptor[1] == 0;
+
+            if !is_display_descriptor {
+                // The first detailed timing descriptor is the preferred timing.
+                if index == 0 {
+                    preferred_timing = parse_detailed_timing(descriptor);
+                    physical_size = parse_image_size(descriptor);
+                }
+            } else if descriptor[3] == MONITOR_NAME_TAG && name.is_none() {
+                name = parse_descriptor_string(&descriptor[5..]);
+            } else if descriptor[3] == SERIAL_NUMBER_TAG && serial_number.is_none() {
+                serial_number = parse_descriptor_string(&descriptor[5..]);
+            }
+        }
+
+        let serial_number = serial_number.or_else(|| {
+            let serial_number = u32::from_le_bytes([block[12], block[13], block[14], block[15]]);
+
+            if serial_number != 0 {
+                Some(serial_number.to_string())
+            } else {
+                None
+            }
+        });
+
+        // `0xff` means the year is the model year.
+        let manufacture_week = match block[16] {
+            0 | 0xff => None,
+            week => Some(week),
+        };
+
+        let manufacture_year = if block[17] != 0 {
+            Some(1990 + block[17] as u16)
+        } else {
+            None
+        };
+
+        // Fall back to the (less precise) screen size in centimeters.
+        // Either may be zero (e.g. for projectors), in which case the other one is the aspect ratio.
+        let physical_size = physical_size.or_else(|| {
+            if block[21] != 0 && block[22] != 0 {
+                Some(Dimensions::new(
+                    block[21] as u32 * 10,
+                    block[22] as u32 * 10,
+                ))
+            } else {
+                None
+            }
+        });
+
+        Some(Self {
+            name,
+            manufacturer: parse_manufacturer([block[8], block[9]]),
+            product_code: format!("{:04X}", u16::from_le_bytes([block[10], block[11]])),
+            serial_number,
+            manufacture_year,
+            manufacture_week,
+            preferred_timing,
+            physical_size,
+            hdr_caps: parse_hdr_caps(edid, block[126]),
+            colorimetry: parse_colorimetry(&block[25..35]),
+            max_bits_per_channel: parse_bits_per_channel(block[19], block[20]),
+        })
+    }
+}
+
+impl Edid {
+    /// Returns the monitor's asset info, as reported by the EDID (i.e. without the usage hours).
+    pub(crate) fn asset_info(&self) -> AssetInfo {
+        AssetInfo {
+            manufacturer: self.manufacturer.clone(),
+            product_code: self.product_code.clone(),
+            serial_number: self.serial_number.clone(),
+            manufacture_year: self.manufacture_year,
+            manufacture_week: self.manufacture_week,
+            usage_hours: None,
+        }
+    }
+}
+
+/// Returns the degraded info of a monitor enumerated in the services session (see [`DisplaySource::ServiceInventory`]),
+/// with the `id`, the `name` and the rest of the info read from the monitor's `edid`, if known.
+///
+/// The monitor has no (empty) rectangles, and exactly one display mode -
+/// its preferred one as reported by the EDID, or a zero-sized one if unknown.
+///
+/// [`DisplaySource::ServiceInventory`]: enum.DisplaySource.html#variant.ServiceInventory
+pub(crate) fn inventory_display_info(
+    id: DisplayId,
+    name: Option<DisplayName>,
+    edid: Option<&Edid>,
+) -> DisplayInfo {
+    let display_mode = edid
+        .and_then(|edid| edid.preferred_timing)
+        .map(|timing| DisplayMode {
+            dimensions: timing.dimensions,
+            refresh_rate: (timing.refresh_rate_num as f64 / timing.refresh_rate_denom as f64)
+                .round() as u32,
+            refresh_rate_num: timing.refresh_rate_num,
+            refresh_rate_denom: timing.refresh_rate_denom,
+            upscale_mode: UpscaleMode::Unknown,
+        })
+        // Unknown.
+        .unwrap_or(DisplayMode {
+            dimensions: Dimensions::default(),
+            refresh_rate: 0,
+            refresh_rate_num: 0,
+            refresh_rate_denom: 1,
+            upscale_mode: UpscaleMode::Unknown,
+        });
+
+    DisplayInfo {
+        physical_size: edid.and_then(|edid| edid.physical_size),
+        hdr_caps: edid.and_then(|edid| edid.hdr_caps),
+        colorimetry: edid.and_then(|edid| edid.colorimetry),
+        asset: edid.map(Edid::asset_info),
+        ..DisplayInfo::new(
+            id,
+            name,
+            false,
+            DisplayRects::default(),
+            ConnectionType::Unknown,
+            display_mode,
+            display_mode,
+            vec![display_mode],
+            1.0,
+            DisplaySource::ServiceInventory,
+        )
+    }
+}
+
+/// Parses the color primaries and the white point from the 10-byte chromaticity `data` of the base block,
+/// stored as 10-bit fractions (two low bits of each packed into the first two bytes).
+///
+/// Returns `None` if not specified (all zeros).
+fn parse_colorimetry(data: &[u8]) -> Option<Colorimetry> {
+    if data.iter().all(|&byte| byte == 0) {
+        return None;
+    }
+
+    let coordinate = |index: usize| {
+        let low_bits = (data[index / 4] >> (6 - (index % 4) * 2)) & 0x3;
+        ((data[2 + index] as u32) << 2 | low_bits as u32) as f32 / 1024.0
+    };
+    let chromaticity = |index: usize| Chromaticity::new(coordinate(index), coordinate(index + 1));
+
+    Some(Colorimetry {
+        red: chromaticity(0),
+        green: chromaticity(2),
+        blue: chromaticity(4),
+        white: chromaticity(6),
+    })
+}
+
+/// Parses the maximum bit depth per color channel from the EDID `revision` (1.x)
+/// and the base block's video input definition byte.
+///
+/// Returns `None` for the analog inputs, the EDID revisions before 1.4 (which don't specify it),
+/// and the undefined / reserved values.
+fn parse_bits_per_channel(revision: u8, video_input: u8) -> Option<u8> {
+    let is_digital = video_input & 0x80 != 0;
+
+    if !is_digital || revision < 4 {
+        return None;
+    }
+
+    match (video_input >> 4) & 0x7 {
+        value @ 1..=6 => Some(4 + value * 2),
+        _ => None,
+    }
+}
+
+/// Parses the HDR capabilities from the first HDR static metadata data block
+/// in the CTA-861 extension blocks of the raw `edid` data (`extension_count` of them, as reported by the base block),
+/// if any, and if the monitor supports any HDR transfer function.
+fn parse_hdr_caps(edid: &[u8], extension_count: u8) -> Option<HdrCaps> {
+    let metadata = edid
+        .chunks_exact(EDID_BLOCK_SIZE)
+        .skip(1)
+        .take(extension_count as usize)
+        .filter(|block| block[0] == CTA_EXTENSION_TAG)
+        .filter(|block| block.iter().fold(0u8, |sum, &byte| sum.wrapping_add(byte)) == 0)
+        .find_map(find_hdr_static_metadata)?;
+
+    let eotfs = *metadata.first()?;
+
+    if eotfs & (EOTF_PQ | EOTF_HLG) == 0 {
+        return None;
+    }
+
+    // Coded values - zero (or missing) means not specified.
+    let coded = |index: usize| metadata.get(index).copied().filter(|&value| value != 0);
+    // `50 * 2 ^ (value / 32)` nits.
+    let luminance = |value: u8| 50.0 * (value as f32 / 32.0).exp2();
+
+    let max_luminance = coded(2).map(luminance);
+    let max_fall = coded(3).map(luminance);
+    // `max_luminance * (value / 255) ^ 2 / 100` nits.
+    let min_luminance = max_luminance
+        .zip(coded(4))
+        .map(|(max_luminance, value)| max_luminance * (value as f32 / 255.0).powi(2) / 100.0);
+
+    Some(HdrCaps {
+        supports_pq: eotfs & EOTF_PQ != 0,
+        supports_hlg: eotfs & EOTF_HLG != 0,
+        max_luminance,
+        min_luminance,
+        max_fall,
+    })
+}
+
+/// Returns the payload (following the extended tag) of the HDR static metadata data block
+/// in the data block collection of the CTA-861 extension `block`, if any.
+fn find_hdr_static_metadata(block: &[u8]) -> Option<&[u8]> {
+    // Offset of the detailed timing descriptors, which follow the data block collection.
+    let end = (block[2] as usize).clamp(4, EDID_BLOCK_SIZE - 1);
+    let mut data_blocks = &block[4..end];
+
+    while let Some((&header, rest)) = data_blocks.split_first() {
+        let (tag, len) = (header >> 5, (header & 0x1f) as usize);
+        let payload = rest.get(..len)?;
+
+        if tag == CTA_EXTENDED_TAG && payload.first() == Some(&HDR_STATIC_METADATA_TAG) {
+            return Some(&payload[1..]);
+        }
+
+        data_blocks = &rest[len..];
+    }
+
+    None
+}
+
+/// Parses the big-endian three-letter PnP manufacturer ID, packed as three 5-bit letters (`1` is `A`).
+fn parse_manufacturer(data: [u8; 2]) -> Option<String> {
+    let packed = u16::from_be_bytes(data);
+
+    [10, 5, 0]
+        .iter()
+        .map(|&shift| match (packed >> shift) & 0x1f {
+            letter @ 1..=26 => Some((b'A' + letter as u8 - 1) as char),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Parses the descriptor's ASCII string, terminated by a line feed and padded with spaces.
+fn parse_descriptor_string(data: &[u8]) -> Option<String> {
+    let data = data.split(|&c| c == b'\n').next().unwrap_or(data);
+
+    let string: String = data
+        .iter()
+        .map(|&c| if c.is_ascii() { c as char } else { '?' })
+        .collect();
+    let string = string.trim();
+
+    if string.is_empty() {
+        None
+    } else {
+        Some(string.to_owned())
+    }
+}
+
+/// Parses the 18-byte detailed timing `descriptor`.
+///
+/// Returns `None` for interlaced timings, or if the timing is malformed.
+fn parse_detailed_timing(descriptor: &[u8]) -> Option<EdidTiming> {
+    // In 10 kHz units.
+    let pixel_clock = u16::from_le_bytes([descriptor[0], descriptor[1]]) as u64;
+
+    let horizontal_active = descriptor[2] as u32 | ((descriptor[4] as u32 >> 4) << 8);
+    let horizontal_blanking = descriptor[3] as u32 | ((descriptor[4] as u32 & 0xf) << 8);
+    let vertical_active = descriptor[5] as u32 | ((descriptor[7] as u32 >> 4) << 8);
+    let vertical_blanking = descriptor[6] as u32 | ((descriptor[7] as u32 & 0xf) << 8);
+
+    let interlaced = descriptor[17] & 0x80 != 0;
+
+    if interlaced || horizontal_active == 0 || vertical_active == 0 {
+        return None;
+    }
+
+    let pixels_per_frame = (horizontal_active + horizontal_blanking) as u64
+        * (vertical_active + vertical_blanking) as u64;
+
+    // Reduce the fraction to fit the refresh rate numerator / denominator.
+    let num = pixel_clock * 10_000;
+    let denom = pixels_per_frame;
+
+    let divisor = gcd64(num, denom);
+    let (num, denom) = (num / divisor, denom / divisor);
+
+    if num == 0 || num > u32::MAX as u64 || denom > u32::MAX as u64 {
+        return None;
+    }
+
+    Some(EdidTiming {
+        dimensions: Dimensions::new(horizontal_active, vertical_active),
+        refresh_rate_num: num as u32,
+        refresh_rate_denom: denom as u32,
+    })
+}
+
+/// Parses the image size in millimeters from the 18-byte detailed timing `descriptor`, if specified.
+fn parse_image_size(descriptor: &[u8]) -> Option<Dimensions> {
+    let width = descriptor[12] as u32 | ((descriptor[14] as u32 >> 4) << 8);
+    let height = descriptor[13] as u32 | ((descriptor[14] as u32 & 0xf) << 8);
+
+    if width != 0 && height != 0 {
+        Some(Dimensions::new(width, height))
+    } else {
+        None
+    }
+}
+
+fn gcd64(mut l: u64, mut r: u64) -> u64 {
+    while r != 0 {
+        let rem = l % r;
+        l = r;
+        r = rem;
+    }
+
+    l
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::FixtureBackend;
+    use crate::{Displays, DisplaysSnapshot};
+    use std::sync::Arc;
+
+    /// Returns a valid EDID base block with the provided descriptors.
+    fn edid(descriptors: [[u8; DESCRIPTOR_SIZE]; 4]) -> Vec<u8> {
+        let mut edid = vec![0; EDID_BLOCK_SIZE];
+        edid[..EDID_HEADER.len()].copy_from_slice(&EDID_HEADER);
+
+        // `DEL`, product code `40F0`, serial number `12345678`, week 12 of 2020.
+        edid[8..18].copy_from_slice(&[0x10, 0xac, 0xf0, 0x40, 0x4e, 0x61, 0xbc, 0x00, 12, 30]);
+
+        for (&offset, descriptor) in DESCRIPTOR_OFFSETS.iter().zip(descriptors.iter()) {
+            edid[offset..offset + DESCRIPTOR_SIZE].copy_from_slice(descriptor);
+        }
+
+        let sum = edid.iter().fold(0u8, |sum, &byte| sum.wrapping_add(byte));
+        edid[EDID_BLOCK_SIZE - 1] = 0u8.wrapping_sub(sum);
+
+        edid
+    }
+
+    fn string_descriptor(tag: u8, name: &[u8]) -> [u8; DESCRIPTOR_SIZE] {
+        let mut descriptor = [0x20; DESCRIPTOR_SIZE];
+        descriptor[..5].copy_from_slice(&[0, 0, 0, tag, 0]);
+        descriptor[5..5 + name.len()].copy_from_slice(name);
+
+        if name.len() < DESCRIPTOR_SIZE - 5 {
+            descriptor[5 + name.len()] = b'\n';
+        }
+
+        descriptor
+    }
+
+    const DUMMY_DESCRIPTOR: [u8; DESCRIPTOR_SIZE] =
+        [0, 0, 0, 0x10, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+
+    /// 1920x1080 @ 60Hz, 148.5 MHz pixel clock, 2200x1125 total.
+    const TIMING_1080P60: [u8; DESCRIPTOR_SIZE] = [
+        0x02, 0x3a, 0x80, 0x18, 0x71, 0x38, 0x2d, 0x40, 0x58, 0x2c, 0x45, 0x00, 0x50, 0x2d, 0x21,
+        0x00, 0x00, 0x1e,
+    ];
+
+    #[test]
+    fn parse() {
+        let parsed = Edid::parse(&edid([
+            TIMING_1080P60,
+            string_descriptor(MONITOR_NAME_TAG, b"DELL U2720Q"),
+            DUMMY_DESCRIPTOR,
+            DUMMY_DESCRIPTOR,
+        ]))
+        .unwrap();
+
+        assert_eq!(parsed.name.as_deref(), Some("DELL U2720Q"));
+        assert_eq!(parsed.manufacturer.as_deref(), Some("DEL"));
+        assert_eq!(parsed.product_code, "40F0");
+        assert_eq!(parsed.serial_number.as_deref(), Some("12345678"));
+        assert_eq!(parsed.manufacture_year, Some(2020));
+        assert_eq!(parsed.manufacture_week, Some(12));
+        assert_eq!(
+            parsed.preferred_timing,
+            Some(EdidTiming {
+                dimensions: Dimensions::new(1920, 1080),
+                refresh_rate_num: 60,
+                refresh_rate_denom: 1,
+            })
+        );
+        assert_eq!(parsed.physical_size, Some(Dimensions::new(592, 301)));
+        assert_eq!(parsed.colorimetry, None);
+        assert_eq!(
+            parsed.asset_info(),
+            AssetInfo {
+                manufacturer: Some("DEL".to_owned()),
+                product_code: "40F0".to_owned(),
+                serial_number: Some("12345678".to_owned()),
+                manufacture_year: Some(2020),
+                manufacture_week: Some(12),
+                usage_hours: None,
+            }
+        );
+
+        // No preferred timing, name fills the whole descriptor, serial number descriptor.
+        let parsed = Edid::parse(&edid([
+            DUMMY_DESCRIPTOR,
+            string_descriptor(SERIAL_NUMBER_TAG, b"7C3J123"),
+            string_descriptor(MONITOR_NAME_TAG, b"0123456789ABC"),
+            DUMMY_DESCRIPTOR,
+        ]))
+        .unwrap();
+
+        assert_eq!(parsed.name.as_deref(), Some("0123456789ABC"));
+        assert_eq!(parsed.serial_number.as_deref(), Some("7C3J123"));
+        assert_eq!(parsed.preferred_timing, None);
+        assert_eq!(parsed.physical_size, None);
+
+        // Screen size in centimeters.
+        let mut sized = edid([
+            DUMMY_DESCRIPTOR,
+            DUMMY_DESCRIPTOR,
+            DUMMY_DESCRIPTOR,
+            DUMMY_DESCRIPTOR,
+        ]);
+        sized[21] = 60;
+        sized[22] = 34;
+        sized[EDID_BLOCK_SIZE - 1] = sized[EDID_BLOCK_SIZE - 1].wrapping_sub(60 + 34);
+        assert_eq!(
+            Edid::parse(&sized).unwrap().physical_size,
+            Some(Dimensions::new(600, 340))
+        );
+
+        // sRGB chromaticity, as encoded by the typical monitors.
+        let mut colorimetry = edid([
+            DUMMY_DESCRIPTOR,
+            DUMMY_DESCRIPTOR,
+            DUMMY_DESCRIPTOR,
+            DUMMY_DESCRIPTOR,
+        ]);
+        let chromaticity = [0xee, 0x91, 0xa3, 0x54, 0x4c, 0x99, 0x26, 0x0f, 0x50, 0x54];
+        colorimetry[25..35].copy_from_slice(&chromaticity);
+        let sum = chromaticity
+            .iter()
+            .fold(0u8, |sum, &byte| sum.wrapping_add(byte));
+        colorimetry[EDID_BLOCK_SIZE - 1] = colorimetry[EDID_BLOCK_SIZE - 1].wrapping_sub(sum);
+
+        let colorimetry = Edid::parse(&colorimetry).unwrap().colorimetry.unwrap();
+        let round = |chromaticity: Chromaticity| {
+            (
+                (chromaticity.x * 1000.0).round() as u32,
+                (chromaticity.y * 1000.0).round() as u32,
+            )
+        };
+        assert_eq!(round(colorimetry.red), (640, 330));
+        assert_eq!(round(colorimetry.green), (300, 600));
+        assert_eq!(round(colorimetry.blue), (150, 60));
+        assert_eq!(round(colorimetry.white), (313, 329));
+
+        // Bit depth - EDID 1.4, digital input, 10 bits per color channel.
+        let mut bit_depth = edid([
+            DUMMY_DESCRIPTOR,
+            DUMMY_DESCRIPTOR,
+            DUMMY_DESCRIPTOR,
+            DUMMY_DESCRIPTOR,
+        ]);
+        bit_depth[18..21].copy_from_slice(&[1, 4, 0xb5]);
+        bit_depth[EDID_BLOCK_SIZE - 1] = bit_depth[EDID_BLOCK_SIZE - 1].wrapping_sub(1 + 4 + 0xb5);
+        assert_eq!(
+            Edid::parse(&bit_depth).unwrap().max_bits_per_channel,
+            Some(10)
+        );
+        assert_eq!(parsed.max_bits_per_channel, None);
+
+        assert_eq!(parse_bits_per_channel(4, 0xa5), Some(8));
+        assert_eq!(parse_bits_per_channel(4, 0xe5), Some(16));
+        // Undefined / reserved.
+        assert_eq!(parse_bits_per_channel(4, 0x85), None);
+        assert_eq!(parse_bits_per_channel(4, 0xf5), None);
+        // Analog input.
+        assert_eq!(parse_bits_per_channel(4, 0x35), None);
+        // EDID 1.3.
+        assert_eq!(parse_bits_per_channel(3, 0xb5), None);
+
+        // Too short.
+        assert_eq!(Edid::parse(&[0; 64]), None);
+
+        // Invalid header.
+        let mut invalid = edid([
+            TIMING_1080P60,
+            DUMMY_DESCRIPTOR,
+            DUMMY_DESCRIPTOR,
+            DUMMY_DESCRIPTOR,
+        ]);
+        invalid[0] = 0xff;
+        assert_eq!(Edid::parse(&invalid), None);
+
+        // Invalid checksum.
+        let mut invalid = edid([
+            TIMING_1080P60,
+            DUMMY_DESCRIPTOR,
+            DUMMY_DESCRIPTOR,
+            DUMMY_DESCRIPTOR,
+        ]);
+        invalid[20] ^= 1;
+        assert_eq!(Edid::parse(&invalid), None);
+    }
+
+    #[test]
+    fn hdr_caps() {
+        let with_extension = |data_blocks: &[u8]| {
+            let mut edid = edid([
+                TIMING_1080P60,
+                DUMMY_DESCRIPTOR,
+                DUMMY_DESCRIPTOR,
+                DUMMY_DESCRIPTOR,
+            ]);
+            edid[126] = 1;
+            edid[EDID_BLOCK_SIZE - 1] = edid[EDID_BLOCK_SIZE - 1].wrapping_sub(1);
+
+            let mut extension = vec![0; EDID_BLOCK_SIZE];
+            extension[..4].copy_from_slice(&[CTA_EXTENSION_TAG, 3, 4 + data_blocks.len() as u8, 0]);
+            extension[4..4 + data_blocks.len()].copy_from_slice(data_blocks);
+
+            let sum = extension
+                .iter()
+                .fold(0u8, |sum, &byte| sum.wrapping_add(byte));
+            extension[EDID_BLOCK_SIZE - 1] = 0u8.wrapping_sub(sum);
+
+            edid.extend(extension);
+            edid
+        };
+
+        // Video data block, then the HDR static metadata data block - SDR, PQ, HLG,
+        // ~1181 nits max, ~1015 nits max frame-average, ~0.11 nits min.
+        let edid = with_extension(&[0x42, 0x10, 0x04, 0xe6, 0x06, 0x0d, 0x01, 0x92, 0x8b, 0x19]);
+        let hdr_caps = Edid::parse(&edid).unwrap().hdr_caps.unwrap();
+
+        assert!(hdr_caps.supports_pq);
+        assert!(hdr_caps.supports_hlg);
+        assert_eq!(hdr_caps.max_luminance.unwrap().round(), 1181.0);
+        assert_eq!(hdr_caps.max_fall.unwrap().round(), 1015.0);
+        assert_eq!((hdr_caps.min_luminance.unwrap() * 100.0).round(), 11.0);
+
+        // Luminance not specified.
+        let hdr_caps = Edid::parse(&with_extension(&[0xe3, 0x06, 0x05, 0x01]))
+            .unwrap()
+            .hdr_caps
+            .unwrap();
+        assert!(hdr_caps.supports_pq);
+        assert!(!hdr_caps.supports_hlg);
+        assert_eq!(hdr_caps.max_luminance, None);
+        assert_eq!(hdr_caps.min_luminance, None);
+        assert_eq!(hdr_caps.max_fall, None);
+
+        // SDR only.
+        assert_eq!(
+            Edid::parse(&with_extension(&[0xe3, 0x06, 0x01, 0x01]))
+                .unwrap()
+                .hdr_caps,
+            None
+        );
+
+        // No HDR static metadata data block.
+        assert_eq!(
+            Edid::parse(&with_extension(&[0x42, 0x10, 0x04]))
+                .unwrap()
+                .hdr_caps,
+            None
+        );
+
+        // Invalid extension block checksum.
+        let mut invalid = with_extension(&[0xe3, 0x06, 0x05, 0x01]);
+        invalid[EDID_BLOCK_SIZE + 4] ^= 1;
+        assert_eq!(Edid::parse(&invalid).unwrap().hdr_caps, None);
+
+        // Missing extension block.
+        assert_eq!(
+            Edid::parse(&invalid[..EDID_BLOCK_SIZE]).unwrap().hdr_caps,
+            None
+        );
+    }
+
+    #[test]
+    fn inventory_display_info() {
+        // Unknown EDID - a single zero-sized display mode.
+        let unknown = super::inventory_display_info(DisplayId(1), None, None);
+        assert_eq!(unknown.source, DisplaySource::ServiceInventory);
+        assert!(unknown.rects.virtual_rect.is_empty());
+        assert_eq!(unknown.current_mode.dimensions, Dimensions::default());
+        assert!(unknown.asset.is_none());
+        #[cfg(not(feature = "no-modes"))]
+        {
+            assert_eq!(unknown.display_modes.len(), 1);
+            assert_eq!(unknown.display_modes[0].dimensions, Dimensions::default());
+            assert_eq!(unknown.min_dimensions, Dimensions::default());
+            assert!(unknown.validate_mode(&unknown.current_mode).is_supported());
+            assert_eq!(
+                unknown.closest_dimensions(
+                    Dimensions::new(1920, 1080),
+                    crate::ClosestDimensionsFlags::Closest
+                ),
+                Some(Dimensions::default())
+            );
+        }
+
+        let parsed = Edid::parse(&edid([
+            TIMING_1080P60,
+            string_descriptor(MONITOR_NAME_TAG, b"DELL U2720Q"),
+            DUMMY_DESCRIPTOR,
+            DUMMY_DESCRIPTOR,
+        ]))
+        .unwrap();
+        let known = super::inventory_display_info(DisplayId(2), None, Some(&parsed));
+        assert_eq!(known.current_mode.dimensions, Dimensions::new(1920, 1080));
+        assert_eq!(known.current_mode.refresh_rate, 60);
+        assert_eq!(
+            known.preferred_mode.dimensions,
+            known.current_mode.dimensions
+        );
+        assert_eq!(known.physical_size, Some(Dimensions::new(592, 301)));
+        assert_eq!(known.asset, Some(parsed.asset_info()));
+
+        // Enumerated via a display backend.
+        let mut displays =
+            Displays::with_backend(Arc::new(FixtureBackend::new(DisplaysSnapshot::new(vec![
+                unknown, known,
+            ]))));
+        assert_eq!(displays.enumerate_displays(), Ok(2));
+        assert!(displays.stitch_layout().is_empty());
+        assert_eq!(displays.snapshot().virtual_desktop(), None);
+    }
+}
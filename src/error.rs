@@ -0,0 +1,29 @@
+use std::fmt::{Display, Formatter};
+
+/// Error returned by the one-shot display query functions (see [`enumerate`]).
+///
+/// [`enumerate`]: fn.enumerate.html
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Error {
+    /// The system's displays could not be enumerated.
+    EnumerationFailed,
+    /// No primary display was enumerated
+    /// (e.g. the process runs in a services session, where the displays have no virtual desktop rectangles).
+    NoPrimaryDisplay,
+    /// The displays cannot be enumerated on the current platform.
+    Unsupported,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use Error::*;
+
+        match self {
+            EnumerationFailed => write!(f, "failed to enumerate the displays"),
+            NoPrimaryDisplay => write!(f, "no primary display was enumerated"),
+            Unsupported => write!(f, "the current platform is not supported"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
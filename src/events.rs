@@ -0,0 +1,394 @@
+use std::sync::{Arc, Mutex, MutexGuard, Weak};
+
+use crate::{
+    DisplayId, DisplayIndex, DisplayInfo, DisplayMode, DisplayRects, Displays, Orientation,
+};
+
+/// Kind of a per-display change detected when the displays are re-enumerated.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum DisplayEventKind {
+    /// The display was added (connected / enabled).
+    Added,
+    /// The display was removed (disconnected / disabled).
+    Removed,
+    /// The display's current display mode (dimensions / refresh rate) changed.
+    ModeChanged,
+    /// The display's orientation changed.
+    OrientationChanged,
+    /// The display's DPI scale value changed.
+    DpiChanged,
+    /// The display's virtual rectangle changed,
+    /// e.g. the display was moved in the virtual desktop.
+    RectsChanged,
+    /// The display's work rectangle changed, e.g. the taskbar was moved / resized / (un)set to auto-hide,
+    /// or the display was moved in the virtual desktop.
+    ///
+    /// NOTE - the work rectangles may change without a display configuration change notification -
+    /// re-enumerate the displays on `WM_SETTINGCHANGE` with `SPI_SETWORKAREA` on Windows to detect this.
+    WorkAreaChanged,
+}
+
+/// Per-display change detected when the displays are re-enumerated,
+/// as passed to the [`display event callbacks`].
+///
+/// [`display event callbacks`]: struct.Displays.html#method.on_display
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DisplayEvent {
+    /// Stable identifier of the changed display.
+    pub id: DisplayId,
+    /// What changed.
+    pub kind: DisplayEventKind,
+    /// Index of the changed display in the re-enumerated displays,
+    /// or `None` if the display was removed.
+    pub display_index: Option<DisplayIndex>,
+    /// [`Generation`] of the display enumeration which detected the change.
+    ///
+    /// Compare with the generation of the display info snapshot at hand to ignore the outdated events.
+    ///
+    /// [`Generation`]: struct.DisplaysSnapshot.html#method.generation
+    pub generation: u64,
+}
+
+/// RAII display event subscription token, as returned by [`Displays::on_display`].
+///
+/// The display event callback is unregistered when this is dropped.
+///
+/// [`Displays::on_display`]: struct.Displays.html#method.on_display
+#[must_use = "the display event callback is unregistered when the subscription is dropped"]
+pub struct DisplaySubscription {
+    subscribers: Weak<Mutex<SubscribersInner>>,
+    token: u64,
+}
+
+impl Drop for DisplaySubscription {
+    fn drop(&mut self) {
+        if let Some(subscribers) = self.subscribers.upgrade() {
+            lock(&subscribers)
+                .subscribers
+                .retain(|subscriber| subscriber.token != self.token);
+        }
+    }
+}
+
+type DisplayCallback = dyn FnMut(&DisplayEvent, &Displays) + Send;
+
+struct Subscriber {
+    token: u64,
+    id: DisplayId,
+    kind: DisplayEventKind,
+    callback: Arc<Mutex<Box<DisplayCallback>>>,
+}
+
+#[derive(Default)]
+struct SubscribersInner {
+    next_token: u64,
+    subscribers: Vec<Subscriber>,
+}
+
+/// Display event callbacks registered with the [`display manager`].
+///
+/// [`display manager`]: struct.Displays.html
+#[derive(Default)]
+pub(crate) struct Subscribers(Arc<Mutex<SubscribersInner>>);
+
+impl Subscribers {
+    pub(crate) fn subscribe<F>(
+        &self,
+        id: DisplayId,
+        kind: DisplayEventKind,
+        callback: F,
+    ) -> DisplaySubscription
+    where
+        F: FnMut(&DisplayEvent, &Displays) + Send + 'static,
+    {
+        let mut inner = lock(&self.0);
+
+        let token = inner.next_token;
+        inner.next_token += 1;
+
+        inner.subscribers.push(Subscriber {
+            token,
+            id,
+            kind,
+            callback: Arc::new(Mutex::new(Box::new(callback))),
+        });
+
+        DisplaySubscription {
+            subscribers: Arc::downgrade(&self.0),
+            token,
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        lock(&self.0).subscribers.is_empty()
+    }
+
+    /// Calls the callbacks subscribed to the `events`.
+    pub(crate) fn dispatch(&self, events: &[DisplayEvent], displays: &Displays) {
+        for event in events {
+            // Don't hold the lock while calling the callbacks - they may (un)subscribe.
+            let callbacks: Vec<_> = lock(&self.0)
+                .subscribers
+                .iter()
+                .filter(|subscriber| subscriber.id == event.id && subscriber.kind == event.kind)
+                .map(|subscriber| subscriber.callback.clone())
+                .collect();
+
+            for callback in callbacks {
+                (lock(&callback))(event, displays);
+            }
+        }
+    }
+}
+
+fn lock<T: ?Sized>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Display state compared across display enumerations to detect the changes.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct DisplayState {
+    id: DisplayId,
+    mode: DisplayMode,
+    orientation: Orientation,
+    dpi_scale: f32,
+    rects: DisplayRects,
+}
+
+impl DisplayState {
+    pub(crate) fn new(info: &DisplayInfo) -> Self {
+        Self {
+            id: info.id,
+            mode: info.current_mode,
+            orientation: info.orientation,
+            dpi_scale: info.dpi_scale,
+            rects: info.rects,
+        }
+    }
+}
+
+/// Returns the per-display changes between the `previous` and `current` display states
+/// (removed displays first, then the added / changed displays in `current` order),
+/// detected by the display enumeration with the `generation` and the display `index_generation`.
+pub(crate) fn diff_displays(
+    previous: &[DisplayState],
+    current: &[DisplayState],
+    generation: u64,
+    index_generation: u64,
+) -> Vec<DisplayEvent> {
+    let mut events = Vec::new();
+
+    for state in previous {
+        if !current.iter().any(|current| current.id == state.id) {
+            events.push(DisplayEvent {
+                id: state.id,
+                kind: DisplayEventKind::Removed,
+                display_index: None,
+                generation,
+            });
+        }
+    }
+
+    for (display_index, state) in current.iter().enumerate() {
+        let event = |kind| DisplayEvent {
+            id: state.id,
+            kind,
+            display_index: Some(DisplayIndex::new(display_index as u32, index_generation)),
+            generation,
+        };
+
+        let previous = match previous.iter().find(|previous| previous.id == state.id) {
+            Some(previous) => previous,
+            None => {
+                events.push(event(DisplayEventKind::Added));
+                continue;
+            }
+        };
+
+        if !same_mode(&previous.mode, &state.mode) {
+            events.push(event(DisplayEventKind::ModeChanged));
+        }
+
+        if previous.orientation != state.orientation {
+            events.push(event(DisplayEventKind::OrientationChanged));
+        }
+
+        if previous.dpi_scale != state.dpi_scale {
+            events.push(event(DisplayEventKind::DpiChanged));
+        }
+
+        if previous.rects.virtual_rect != state.rects.virtual_rect {
+            events.push(event(DisplayEventKind::RectsChanged));
+        }
+
+        if previous.rects.work_rect != state.rects.work_rect {
+            events.push(event(DisplayEventKind::WorkAreaChanged));
+        }
+    }
+
+    events
+}
+
+/// Returns the order of the `current` displays (as indices into `current`)
+/// which preserves the `previous` order of the displays which are still present,
+/// followed by the new displays, in `current` order.
+///
+/// The `primary` display (index into `current`), if any, always comes first.
+pub(crate) fn reconcile_display_order(
+    previous: &[DisplayId],
+    current: &[DisplayId],
+    primary: Option<usize>,
+) -> Vec<usize> {
+    let mut order = Vec::with_capacity(current.len());
+
+    if let Some(primary) = primary {
+        order.push(primary);
+    }
+
+    for id in previous {
+        // Displays may share the same identifier (e.g. if unnamed) - match them in order.
+        if let Some(index) =
+            (0..current.len()).find(|index| current[*index] == *id && !order.contains(index))
+        {
+            order.push(index);
+        }
+    }
+
+    for index in 0..current.len() {
+        if !order.contains(&index) {
+            order.push(index);
+        }
+    }
+
+    order
+}
+
+/// Compares the display modes' dimensions and precise refresh rates.
+pub(crate) fn same_mode(l: &DisplayMode, r: &DisplayMode) -> bool {
+    l.dimensions == r.dimensions
+        && (l.refresh_rate_num as u64 * r.refresh_rate_denom as u64)
+            == (r.refresh_rate_num as u64 * l.refresh_rate_denom as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Dimensions, Position, Rectangle, UpscaleMode};
+
+    fn state(id: u64, left: i32, width: u32, height: u32, refresh_rate: u32) -> DisplayState {
+        let rect = Rectangle::new(Position::new(left, 0), Dimensions::new(width, height));
+
+        DisplayState {
+            id: DisplayId(id),
+            mode: DisplayMode {
+                dimensions: Dimensions::new(width, height),
+                refresh_rate,
+                refresh_rate_num: refresh_rate,
+                refresh_rate_denom: 1,
+                upscale_mode: UpscaleMode::Unknown,
+            },
+            orientation: Orientation::Landscape,
+            dpi_scale: 1.0,
+            rects: DisplayRects {
+                virtual_rect: rect,
+                work_rect: rect,
+            },
+        }
+    }
+
+    fn event(id: u64, kind: DisplayEventKind, display_index: Option<u32>) -> DisplayEvent {
+        DisplayEvent {
+            id: DisplayId(id),
+            kind,
+            display_index: display_index.map(|display_index| DisplayIndex::new(display_index, 0)),
+            generation: 0,
+        }
+    }
+
+    #[test]
+    fn diff_displays() {
+        let previous = [state(0, 0, 1920, 1080, 60), state(1, 1920, 1920, 1080, 60)];
+
+        // No changes.
+        assert!(super::diff_displays(&previous, &previous, 0, 0).is_empty());
+
+        // Reordered.
+        assert!(super::diff_displays(&previous, &[previous[1], previous[0]], 0, 0).is_empty());
+
+        // Removed / added.
+        assert_eq!(
+            super::diff_displays(
+                &previous,
+                &[previous[1], state(2, 1920, 2560, 1440, 144)],
+                0,
+                0
+            ),
+            vec![
+                event(0, DisplayEventKind::Removed, None),
+                event(2, DisplayEventKind::Added, Some(1)),
+            ]
+        );
+
+        // Mode changed (which also changes the rectangles), DPI scale changed.
+        let mut dpi_changed = previous[1];
+        dpi_changed.dpi_scale = 1.5;
+
+        assert_eq!(
+            super::diff_displays(&previous, &[state(0, 0, 1280, 720, 60), dpi_changed], 0, 0),
+            vec![
+                event(0, DisplayEventKind::ModeChanged, Some(0)),
+                event(0, DisplayEventKind::RectsChanged, Some(0)),
+                event(0, DisplayEventKind::WorkAreaChanged, Some(0)),
+                event(1, DisplayEventKind::DpiChanged, Some(1)),
+            ]
+        );
+
+        // Refresh rate changed, orientation changed.
+        let mut rotated = previous[1];
+        rotated.orientation = Orientation::PortraitFlipped;
+
+        assert_eq!(
+            super::diff_displays(&previous, &[state(0, 0, 1920, 1080, 120), rotated], 0, 0),
+            vec![
+                event(0, DisplayEventKind::ModeChanged, Some(0)),
+                event(1, DisplayEventKind::OrientationChanged, Some(1)),
+            ]
+        );
+
+        // Taskbar moved.
+        let mut work_area_changed = previous[0];
+        work_area_changed.rects.work_rect =
+            Rectangle::new(Position::new(0, 0), Dimensions::new(1920, 1040));
+
+        assert_eq!(
+            super::diff_displays(&previous, &[work_area_changed, previous[1]], 0, 0),
+            vec![event(0, DisplayEventKind::WorkAreaChanged, Some(0))]
+        );
+    }
+
+    #[test]
+    fn reconcile_display_order() {
+        let ids = |ids: &[u64]| ids.iter().map(|&id| DisplayId(id)).collect::<Vec<_>>();
+        let reconcile = |previous: &[u64], current: &[u64], primary| {
+            super::reconcile_display_order(&ids(previous), &ids(current), primary)
+        };
+
+        // Unchanged / shuffled.
+        assert_eq!(reconcile(&[0, 1, 2], &[0, 1, 2], Some(0)), vec![0, 1, 2]);
+        assert_eq!(reconcile(&[0, 1, 2], &[0, 2, 1], Some(0)), vec![0, 2, 1]);
+
+        // Added displays are appended, removed displays are skipped.
+        assert_eq!(reconcile(&[0, 1, 2], &[0, 3, 2], Some(0)), vec![0, 2, 1]);
+        assert_eq!(reconcile(&[0, 2], &[0, 3, 1, 2], Some(0)), vec![0, 3, 1, 2]);
+
+        // Primary display changed.
+        assert_eq!(reconcile(&[0, 1, 2], &[2, 0, 1], Some(0)), vec![0, 1, 2]);
+        assert_eq!(reconcile(&[0, 1, 2], &[1, 0, 2], None), vec![1, 0, 2]);
+
+        // Duplicate identifiers.
+        assert_eq!(reconcile(&[0, 7, 7], &[0, 7, 7], Some(0)), vec![0, 1, 2]);
+        assert_eq!(reconcile(&[], &[0, 7, 7], None), vec![0, 1, 2]);
+    }
+}
@@ -0,0 +1,503 @@
+//! Deterministic display configurations modeled after the common real-world layouts,
+//! to test the layout logic against without the actual hardware.
+//!
+//! Each fixture is a [`snapshot`] of the displays' platform-independent info.
+//! Use the [`FixtureBackend`] to enumerate the fixture's displays with the [`display manager`].
+//!
+//! The display rectangles are in physical pixels (i.e. as seen by a per-monitor DPI aware process),
+//! and the display at index `0` is the primary display.
+//!
+//! Requires the `test-util` feature.
+//!
+//! [`snapshot`]: ../struct.DisplaysSnapshot.html
+//! [`FixtureBackend`]: struct.FixtureBackend.html
+//! [`display manager`]: ../struct.Displays.html
+
+use std::sync::{Mutex, MutexGuard};
+
+use crate::{
+    ConnectionType, Dimensions, DisplayBackend, DisplayId, DisplayInfo, DisplayMode, DisplayName,
+    DisplayRects, DisplaySource, DisplaysSnapshot, EnumerateOptions, EnumeratedDisplayInfo, Error,
+    Margins, Orientation, PlatformInfo, Position, Rectangle, UpscaleMode,
+};
+
+/// A single `24" 1080p` display.
+pub fn single_1080p() -> DisplaysSnapshot {
+    DisplaysSnapshot::new(vec![display(
+        1,
+        "DELL P2419H",
+        ConnectionType::DisplayPort,
+        Position::new(0, 0),
+        mode(1920, 1080, 60),
+        (531, 299),
+        1.0,
+    )])
+}
+
+/// A primary `27" 4K` display at `150%` DPI scale,
+/// with a `24" 1080p` display at `100%` DPI scale on its right, aligned to the bottom.
+pub fn dual_mixed_dpi() -> DisplaysSnapshot {
+    DisplaysSnapshot::new(vec![
+        display(
+            1,
+            "LG 27UL850",
+            ConnectionType::DisplayPort,
+            Position::new(0, 0),
+            mode(3840, 2160, 60),
+            (597, 336),
+            1.5,
+        ),
+        display(
+            2,
+            "DELL P2419H",
+            ConnectionType::HDMI,
+            Position::new(3840, 1080),
+            mode(1920, 1080, 60),
+            (531, 299),
+            1.0,
+        ),
+    ])
+}
+
+/// Three identical `27" 1440p 144Hz` displays side by side (e.g. for the surround gaming),
+/// with the primary display in the middle.
+pub fn triple_surround() -> DisplaysSnapshot {
+    let surround = |id, left| {
+        display(
+            id,
+            "ASUS PG279Q",
+            ConnectionType::DisplayPort,
+            Position::new(left, 0),
+            mode(2560, 1440, 144),
+            (597, 336),
+            1.0,
+        )
+    };
+
+    DisplaysSnapshot::new(vec![surround(1, 0), surround(2, -2560), surround(3, 2560)])
+}
+
+/// A primary `27" 1440p` landscape display, with a `24" 1080p` display rotated to portrait on its left,
+/// vertically centered.
+pub fn portrait_landscape() -> DisplaysSnapshot {
+    let mut portrait = display(
+        2,
+        "DELL P2419H",
+        ConnectionType::DisplayPort,
+        Position::new(-1080, -240),
+        mode(1080, 1920, 60),
+        (531, 299),
+        1.0,
+    );
+    // Supported display modes are in the display's unrotated orientation.
+    portrait.orientation = Orientation::Portrait;
+    portrait.preferred_mode = mode(1920, 1080, 60);
+    #[cfg(not(feature = "no-modes"))]
+    {
+        portrait.display_modes = display_modes(portrait.preferred_mode);
+    }
+
+    DisplaysSnapshot::new(vec![
+        display(
+            1,
+            "DELL U2719D",
+            ConnectionType::DisplayPort,
+            Position::new(0, 0),
+            mode(2560, 1440, 60),
+            (597, 336),
+            1.0,
+        ),
+        portrait,
+    ])
+}
+
+/// A laptop's `15" 1080p` internal display at `125%` DPI scale, cloned to a projector
+/// (i.e. both displays show the same image, at the same virtual desktop rectangle).
+///
+/// NOTE - the native Windows backend reports the cloned displays as a single display
+/// (see [`Displays::physical_monitors`](../struct.Displays.html#method.physical_monitors));
+/// this models the backends which report each cloned target separately.
+pub fn clone_mode() -> DisplaysSnapshot {
+    let mut projector = display(
+        2,
+        "EPSON PJ",
+        ConnectionType::HDMI,
+        Position::new(0, 0),
+        mode(1920, 1080, 60),
+        (0, 0),
+        1.25,
+    );
+    projector.physical_size = None;
+
+    DisplaysSnapshot::new(vec![
+        display(
+            1,
+            "Internal Display",
+            ConnectionType::Internal,
+            Position::new(0, 0),
+            mode(1920, 1080, 60),
+            (344, 194),
+            1.25,
+        ),
+        projector,
+    ])
+}
+
+/// A laptop's `14" 1200p` internal display at `125%` DPI scale,
+/// with a `55" 4K` TV connected via HDMI on its right, aligned to the top, which overscans the image by ~2.5%.
+pub fn tv_with_overscan() -> DisplaysSnapshot {
+    let mut tv = display(
+        2,
+        "SONY TV",
+        ConnectionType::HDMI,
+        Position::new(1920, 0),
+        mode(3840, 2160, 60),
+        (1210, 680),
+        2.0,
+    );
+    tv.overscan = Some(Margins::new(96, 54, 96, 54));
+
+    DisplaysSnapshot::new(vec![
+        display(
+            1,
+            "Internal Display",
+            ConnectionType::Internal,
+            Position::new(0, 0),
+            mode(1920, 1200, 60),
+            (302, 189),
+            1.25,
+        ),
+        tv,
+    ])
+}
+
+/// A laptop with its lid closed, docked to a primary `27" 1440p` display.
+///
+/// The laptop's `14" 1200p` internal display is enumerated, but inactive, i.e. not attached to the desktop
+/// (see [`Displays::set_include_inactive`](../struct.Displays.html#method.set_include_inactive)).
+pub fn laptop_lid_closed() -> DisplaysSnapshot {
+    let mut internal = display(
+        2,
+        "Internal Display",
+        ConnectionType::Internal,
+        Position::new(0, 0),
+        mode(1920, 1200, 60),
+        (302, 189),
+        1.25,
+    );
+    internal.rects = DisplayRects::default();
+
+    DisplaysSnapshot::new(vec![
+        display(
+            1,
+            "DELL U2719D",
+            ConnectionType::DisplayPort,
+            Position::new(0, 0),
+            mode(2560, 1440, 60),
+            (597, 336),
+            1.0,
+        ),
+        internal,
+    ])
+}
+
+/// Returns all fixtures, with their names (e.g. to run the same test against all of them).
+pub fn all() -> Vec<(&'static str, DisplaysSnapshot)> {
+    vec![
+        ("single_1080p", single_1080p()),
+        ("dual_mixed_dpi", dual_mixed_dpi()),
+        ("triple_surround", triple_surround()),
+        ("portrait_landscape", portrait_landscape()),
+        ("clone_mode", clone_mode()),
+        ("tv_with_overscan", tv_with_overscan()),
+        ("laptop_lid_closed", laptop_lid_closed()),
+    ]
+}
+
+/// [`Display backend`] which enumerates the displays of a fixture (or any other [`snapshot`]),
+/// to use with [`Displays::with_backend`].
+///
+/// The enumerated displays have no [`platform-specific info`].
+///
+/// The enumerated snapshot may be replaced with [`set`] (e.g. to simulate the display configuration changes)
+/// while the backend is in use.
+///
+/// [`Display backend`]: ../trait.DisplayBackend.html
+/// [`snapshot`]: ../struct.DisplaysSnapshot.html
+/// [`Displays::with_backend`]: ../struct.Displays.html#method.with_backend
+/// [`platform-specific info`]: ../enum.PlatformInfo.html
+/// [`set`]: #method.set
+#[derive(Debug)]
+pub struct FixtureBackend(Mutex<DisplaysSnapshot>);
+
+impl FixtureBackend {
+    /// Creates a new backend which enumerates the displays of the `snapshot`.
+    pub fn new(snapshot: DisplaysSnapshot) -> Self {
+        Self(Mutex::new(snapshot))
+    }
+
+    /// Replaces the snapshot whose displays are enumerated on next display enumeration.
+    pub fn set(&self, snapshot: DisplaysSnapshot) {
+        *self.lock() = snapshot;
+    }
+
+    fn lock(&self) -> MutexGuard<'_, DisplaysSnapshot> {
+        self.0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl DisplayBackend for FixtureBackend {
+    fn enumerate(
+        &self,
+        _options: &EnumerateOptions<'_>,
+    ) -> Result<Vec<EnumeratedDisplayInfo>, Error> {
+        Ok(self
+            .lock()
+            .displays()
+            .iter()
+            .map(|info| EnumeratedDisplayInfo {
+                info: info.clone(),
+                platform: PlatformInfo::None,
+            })
+            .collect())
+    }
+}
+
+fn mode(width: u32, height: u32, refresh_rate: u32) -> DisplayMode {
+    DisplayMode {
+        dimensions: Dimensions::new(width, height),
+        refresh_rate,
+        refresh_rate_num: refresh_rate,
+        refresh_rate_denom: 1,
+        upscale_mode: UpscaleMode::Unknown,
+    }
+}
+
+/// Returns the typical display modes supported by a display with the `native` display mode.
+fn display_modes(native: DisplayMode) -> Vec<DisplayMode> {
+    let mut modes: Vec<_> = [(1280, 720), (1600, 900), (1920, 1080), (2560, 1440)]
+        .iter()
+        .filter(|&&(width, height)| {
+            width < native.dimensions.width && height < native.dimensions.height
+        })
+        .map(|&(width, height)| mode(width, height, 60))
+        .collect();
+
+    modes.push(native);
+
+    modes
+}
+
+/// Builds a bare test display at the virtual desktop rectangle, for the unit tests
+/// which need a specific layout rather than one of the fixtures.
+///
+/// By default the display has a default ID, no name, an unknown connection type,
+/// a single `60Hz` display mode, a `100%` DPI scale and no taskbar,
+/// and is the primary display if it's at the virtual desktop origin.
+#[cfg(test)]
+pub(crate) struct TestDisplay<'n> {
+    id: u64,
+    name: Option<&'n str>,
+    connection: ConnectionType,
+    rect: Rectangle,
+    taskbar: u32,
+    dpi_scale: f32,
+}
+
+#[cfg(test)]
+impl<'n> TestDisplay<'n> {
+    pub(crate) fn new(left: i32, top: i32, width: u32, height: u32) -> Self {
+        Self {
+            id: 0,
+            name: None,
+            connection: ConnectionType::Unknown,
+            rect: Rectangle::new(Position::new(left, top), Dimensions::new(width, height)),
+            taskbar: 0,
+            dpi_scale: 1.0,
+        }
+    }
+
+    pub(crate) fn id(mut self, id: u64) -> Self {
+        self.id = id;
+        self
+    }
+
+    pub(crate) fn name(mut self, name: &'n str) -> Self {
+        self.name = Some(name);
+        self
+    }
+
+    pub(crate) fn connection(mut self, connection: ConnectionType) -> Self {
+        self.connection = connection;
+        self
+    }
+
+    /// Reserves `height` pixels at the bottom of the display for the taskbar.
+    pub(crate) fn taskbar(mut self, height: u32) -> Self {
+        self.taskbar = height;
+        self
+    }
+
+    pub(crate) fn dpi_scale(mut self, dpi_scale: f32) -> Self {
+        self.dpi_scale = dpi_scale;
+        self
+    }
+
+    pub(crate) fn build(self) -> DisplayInfo {
+        let mode = mode(self.rect.dimensions.width, self.rect.dimensions.height, 60);
+
+        DisplayInfo::new(
+            DisplayId(self.id),
+            self.name
+                .and_then(|name| DisplayName::from_utf16(&name.encode_utf16().collect::<Vec<_>>())),
+            self.rect.position == Position::new(0, 0),
+            DisplayRects {
+                virtual_rect: self.rect,
+                work_rect: Rectangle::new(
+                    self.rect.position,
+                    Dimensions::new(
+                        self.rect.dimensions.width,
+                        self.rect.dimensions.height - self.taskbar,
+                    ),
+                ),
+            },
+            self.connection,
+            mode,
+            mode,
+            vec![mode],
+            self.dpi_scale,
+            DisplaySource::DisplayConfig,
+        )
+    }
+}
+
+fn display(
+    id: u64,
+    name: &str,
+    connection: ConnectionType,
+    position: Position,
+    current_mode: DisplayMode,
+    physical_size: (u32, u32),
+    dpi_scale: f32,
+) -> DisplayInfo {
+    let rect = Rectangle::new(position, current_mode.dimensions);
+
+    DisplayInfo {
+        physical_size: Some(Dimensions::new(physical_size.0, physical_size.1)),
+        ..DisplayInfo::new(
+            DisplayId(id),
+            DisplayName::from_utf16(&name.encode_utf16().collect::<Vec<_>>()),
+            position == Position::new(0, 0) && id == 1,
+            DisplayRects {
+                virtual_rect: rect,
+                // Taskbar at the bottom of the primary display.
+                work_rect: if id == 1 {
+                    Rectangle::new(
+                        position,
+                        Dimensions::new(
+                            rect.dimensions.width,
+                            rect.dimensions.height - (40.0 * dpi_scale) as u32,
+                        ),
+                    )
+                } else {
+                    rect
+                },
+            },
+            connection,
+            current_mode,
+            current_mode,
+            display_modes(current_mode),
+            dpi_scale,
+            DisplaySource::DisplayConfig,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Displays, LidState};
+    use std::sync::Arc;
+
+    #[test]
+    fn fixtures() {
+        for (name, fixture) in all() {
+            assert!(fixture.num_displays() > 0, "{}", name);
+
+            let primary = fixture.display_info(0).unwrap();
+            assert!(primary.is_primary, "{}", name);
+            assert_eq!(primary.rects.virtual_rect.position, Position::new(0, 0));
+
+            for display in fixture.displays().iter().skip(1) {
+                assert!(!display.is_primary, "{}", name);
+            }
+
+            // Enumerates the same displays via the backend.
+            let mut displays =
+                Displays::with_backend(Arc::new(FixtureBackend::new(fixture.clone())));
+            assert_eq!(displays.enumerate_displays(), Ok(fixture.num_displays()));
+            assert_eq!(
+                displays.snapshot().virtual_desktop(),
+                fixture.virtual_desktop()
+            );
+        }
+
+        let adjacent = |fixture: &DisplaysSnapshot, index| *fixture.adjacency_info(index).unwrap();
+
+        assert_eq!(adjacent(&dual_mixed_dpi(), 0).right, Some(1));
+        assert_eq!(adjacent(&triple_surround(), 0).left, Some(1));
+        assert_eq!(adjacent(&triple_surround(), 0).right, Some(2));
+        assert_eq!(adjacent(&portrait_landscape(), 0).left, Some(1));
+        assert!(!adjacent(&clone_mode(), 0).is_some());
+        assert_eq!(adjacent(&tv_with_overscan(), 1).left, Some(0));
+
+        let portrait = portrait_landscape();
+        let portrait = portrait.display_info(1).unwrap();
+        assert_eq!(
+            portrait.rects.virtual_rect.dimensions,
+            Dimensions::new(1080, 1920)
+        );
+        #[cfg(not(feature = "no-modes"))]
+        assert!(portrait
+            .validate_mode(&portrait.preferred_mode)
+            .is_supported());
+
+        let tv = tv_with_overscan();
+        assert!(tv.display_info(1).unwrap().overscan.is_some());
+        assert_eq!(
+            tv.display_info(0).unwrap().rects.reserved_margins().bottom,
+            50
+        );
+    }
+
+    #[test]
+    fn lid_state() {
+        let internal_display = |fixture| {
+            let mut displays = Displays::with_backend(Arc::new(FixtureBackend::new(fixture)));
+            displays.enumerate_displays().unwrap();
+            displays
+                .internal_display()
+                .map(|(display_index, lid_state)| (display_index.index(), lid_state))
+        };
+
+        assert_eq!(internal_display(single_1080p()), None);
+        assert_eq!(
+            internal_display(tv_with_overscan()),
+            Some((0, LidState::Open))
+        );
+        assert_eq!(
+            internal_display(laptop_lid_closed()),
+            Some((1, LidState::Closed))
+        );
+
+        // The internal display may stay active with the lid closed if it's the only display.
+        let internal_only =
+            DisplaysSnapshot::new(vec![tv_with_overscan().display_info(0).unwrap().clone()]);
+        assert_eq!(
+            internal_display(internal_only),
+            Some((0, LidState::Unknown))
+        );
+    }
+}
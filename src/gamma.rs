@@ -0,0 +1,297 @@
+use std::fmt::{Display, Formatter};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use crate::brightness::{gamma_ramp, GAMMA_RAMP_SIZE};
+
+/// Color temperature of the display with no blue light reduction, in Kelvin.
+const NEUTRAL_COLOR_TEMPERATURE: u32 = 6500;
+/// Lowest supported color temperature, in Kelvin.
+const MIN_COLOR_TEMPERATURE: u32 = 1000;
+/// Interval between the gamma ramps applied during a [`transition`](struct.GammaTransition.html) (~60Hz).
+const TRANSITION_STEP: Duration = Duration::from_millis(16);
+
+/// Gamma ramp preset applied to a display by [`Displays::apply_gamma_preset`].
+///
+/// [`Displays::apply_gamma_preset`]: struct.Displays.html#method.apply_gamma_preset
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GammaPreset {
+    /// Identity gamma ramp - no color or brightness adjustment.
+    Neutral,
+    /// Blue light reduction ("reader mode") - the display's white point is shifted to the color temperature, in Kelvin
+    /// (clamped to `[1000 .. 6500]` range; `6500` is neutral, lower is warmer).
+    Warm(u32),
+}
+
+impl GammaPreset {
+    /// Returns the (per-channel) gamma ramp for the preset.
+    pub(crate) fn ramp(self) -> Vec<u16> {
+        use GammaPreset::*;
+
+        match self {
+            Neutral => gamma_ramp(1.0),
+            Warm(color_temperature) => {
+                let white_point = white_point(color_temperature);
+
+                white_point
+                    .iter()
+                    .flat_map(|&scale| {
+                        (0..GAMMA_RAMP_SIZE)
+                            .map(move |index| (index as f32 * 257.0 * scale).round() as u16)
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+impl Display for GammaPreset {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use GammaPreset::*;
+
+        match self {
+            Neutral => "neutral".fmt(f),
+            Warm(color_temperature) => write!(f, "warm ({}K)", color_temperature),
+        }
+    }
+}
+
+/// Returns the RGB multipliers in `[0.0 .. 1.0]` range which shift the display's (neutral) white point
+/// to the `color_temperature`, in Kelvin.
+///
+/// Uses Tanner Helland's approximation of the black body color, normalized to the neutral color temperature.
+fn white_point(color_temperature: u32) -> [f32; 3] {
+    let black_body = |color_temperature: u32| {
+        let t = color_temperature as f64 / 100.0;
+
+        let red = if t <= 66.0 {
+            255.0
+        } else {
+            329.698_727_446 * (t - 60.0).powf(-0.133_204_759_2)
+        };
+        let green = if t <= 66.0 {
+            99.470_802_586_1 * t.ln() - 161.119_568_166_1
+        } else {
+            288.122_169_528_3 * (t - 60.0).powf(-0.075_514_849_2)
+        };
+        let blue = if t >= 66.0 {
+            255.0
+        } else if t <= 19.0 {
+            0.0
+        } else {
+            138.517_731_223_1 * (t - 10.0).ln() - 305.044_792_730_7
+        };
+
+        [red, green, blue]
+    };
+
+    let color_temperature =
+        color_temperature.clamp(MIN_COLOR_TEMPERATURE, NEUTRAL_COLOR_TEMPERATURE);
+
+    let white = black_body(color_temperature);
+    let neutral = black_body(NEUTRAL_COLOR_TEMPERATURE);
+
+    let mut white_point = [0.0; 3];
+
+    for (channel, (white, neutral)) in white_point.iter_mut().zip(white.iter().zip(neutral.iter()))
+    {
+        *channel = (white / neutral).clamp(0.0, 1.0) as f32;
+    }
+
+    white_point
+}
+
+/// Returns the gamma ramp `t` of the way (in `[0.0 .. 1.0]` range) from the `from` ramp to the `to` ramp.
+fn interpolate_ramps(from: &[u16], to: &[u16], t: f32) -> Vec<u16> {
+    let t = t.clamp(0.0, 1.0);
+
+    from.iter()
+        .zip(to.iter())
+        .map(|(&from, &to)| (from as f32 + (to as f32 - from as f32) * t).round() as u16)
+        .collect()
+}
+
+/// Animated transition of a display's gamma ramp to a [`preset`], started by [`Displays::apply_gamma_preset`].
+///
+/// The intermediate gamma ramps are applied on a background thread.
+/// Dropping the transition lets it run to completion; use [`cancel`] to stop it midway.
+///
+/// [`preset`]: enum.GammaPreset.html
+/// [`Displays::apply_gamma_preset`]: struct.Displays.html#method.apply_gamma_preset
+/// [`cancel`]: #method.cancel
+pub struct GammaTransition {
+    stop: Arc<(Mutex<bool>, Condvar)>,
+    thread: Option<JoinHandle<Result<(), ()>>>,
+}
+
+impl GammaTransition {
+    /// Starts the transition from the `from` gamma ramp to the `to` gamma ramp over the `duration`,
+    /// applying the intermediate ramps with `set_ramp` on a background thread.
+    ///
+    /// Applies the `to` ramp right away if `from` is `None` (i.e. the current ramp is unknown) or the `duration` is zero.
+    pub(crate) fn start<F>(
+        from: Option<Vec<u16>>,
+        to: Vec<u16>,
+        duration: Duration,
+        mut set_ramp: F,
+    ) -> Result<Self, ()>
+    where
+        F: FnMut(&[u16]) -> Result<(), ()> + Send + 'static,
+    {
+        let stop = Arc::new((Mutex::new(false), Condvar::new()));
+
+        let thread = {
+            let stop = stop.clone();
+
+            std::thread::Builder::new()
+                .name("minidisplay gamma transition".to_string())
+                .spawn(move || {
+                    let from = match from {
+                        Some(from) if !duration.is_zero() && from.len() == to.len() => from,
+                        _ => return set_ramp(&to),
+                    };
+
+                    let (stopped, condvar) = &*stop;
+                    let started_at = Instant::now();
+
+                    loop {
+                        let t = started_at.elapsed().as_secs_f32() / duration.as_secs_f32();
+
+                        if t >= 1.0 {
+                            return set_ramp(&to);
+                        }
+
+                        set_ramp(&interpolate_ramps(&from, &to, t))?;
+
+                        let guard = lock(stopped);
+                        let (guard, _) = condvar
+                            .wait_timeout_while(guard, TRANSITION_STEP, |stopped| !*stopped)
+                            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+                        if *guard {
+                            return Ok(());
+                        }
+                    }
+                })
+                .map_err(|_| ())?
+        };
+
+        Ok(Self {
+            stop,
+            thread: Some(thread),
+        })
+    }
+
+    /// Returns `true` if the transition has finished (or failed).
+    pub fn is_finished(&self) -> bool {
+        self.thread
+            .as_ref()
+            .is_none_or(|thread| thread.is_finished())
+    }
+
+    /// Blocks until the transition finishes.
+    ///
+    /// Returns an error if applying any of the gamma ramps failed (e.g. the driver rejected it).
+    pub fn wait(mut self) -> Result<(), ()> {
+        self.join()
+    }
+
+    /// Stops the transition, leaving the display's gamma ramp at its current intermediate value.
+    ///
+    /// Returns an error if applying any of the gamma ramps failed before the transition was stopped.
+    pub fn cancel(mut self) -> Result<(), ()> {
+        let (stopped, condvar) = &*self.stop;
+
+        *lock(stopped) = true;
+        condvar.notify_all();
+
+        self.join()
+    }
+
+    fn join(&mut self) -> Result<(), ()> {
+        self.thread
+            .take()
+            .map_or(Ok(()), |thread| thread.join().unwrap_or(Err(())))
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gamma_preset_ramp() {
+        let neutral = GammaPreset::Neutral.ramp();
+        assert_eq!(neutral, gamma_ramp(1.0));
+        assert_eq!(GammaPreset::Warm(6500).ramp(), neutral);
+        // Clamped to the neutral color temperature.
+        assert_eq!(GammaPreset::Warm(10_000).ramp(), neutral);
+
+        let warm = GammaPreset::Warm(3400).ramp();
+        assert_eq!(warm.len(), GAMMA_RAMP_SIZE * 3);
+
+        let (red, green, blue) = (
+            warm[GAMMA_RAMP_SIZE - 1],
+            warm[GAMMA_RAMP_SIZE * 2 - 1],
+            warm[GAMMA_RAMP_SIZE * 3 - 1],
+        );
+        assert_eq!(red, u16::MAX);
+        assert!(green < red);
+        assert!(blue < green);
+    }
+
+    #[test]
+    fn gamma_transition() {
+        let ramps = Arc::new(Mutex::new(Vec::new()));
+
+        let set_ramp = {
+            let ramps = ramps.clone();
+            move |ramp: &[u16]| {
+                lock(&ramps).push(ramp.to_vec());
+                Ok(())
+            }
+        };
+
+        let from = vec![0u16; 4];
+        let to = vec![1000u16; 4];
+
+        // Unknown current ramp - the target is applied right away.
+        GammaTransition::start(None, to.clone(), Duration::from_secs(1), set_ramp.clone())
+            .unwrap()
+            .wait()
+            .unwrap();
+        assert_eq!(*lock(&ramps), vec![to.clone()]);
+
+        lock(&ramps).clear();
+        GammaTransition::start(
+            Some(from.clone()),
+            to.clone(),
+            Duration::from_millis(100),
+            set_ramp,
+        )
+        .unwrap()
+        .wait()
+        .unwrap();
+
+        let ramps = lock(&ramps);
+        assert!(ramps.len() > 2);
+        assert_eq!(ramps.last(), Some(&to));
+        // Monotonic.
+        assert!(ramps.windows(2).all(|pair| pair[0][0] <= pair[1][0]));
+
+        assert_eq!(interpolate_ramps(&from, &to, 0.25), vec![250; 4]);
+        assert_eq!(
+            GammaTransition::start(Some(from), to, Duration::from_secs(1), |_: &[u16]| Err(()))
+                .unwrap()
+                .wait(),
+            Err(())
+        );
+    }
+}
@@ -0,0 +1,241 @@
+use std::sync::atomic::{fence, AtomicU32, AtomicU64, Ordering};
+
+use crate::DisplaysSnapshot;
+
+#[cfg(windows)]
+use crate::win::SharedMemoryWin as SharedMemoryPlatform;
+
+#[cfg(not(windows))]
+use crate::stub::SharedMemoryStub as SharedMemoryPlatform;
+
+/// Identifies the shared memory sections created by the [`SnapshotPublisher`].
+const SECTION_MAGIC: u32 = u32::from_le_bytes(*b"mdss");
+/// Number of attempts the reader makes to read the snapshot while it is being published.
+const READ_ATTEMPTS: u32 = 16;
+
+/// Header at the start of the shared memory section, followed by the serialized snapshot.
+#[repr(C)]
+struct SectionHeader {
+    magic: AtomicU32,
+    /// Maximum size of the serialized snapshot, in bytes.
+    capacity: AtomicU32,
+    /// Incremented before and after each publish - odd while the snapshot is being written.
+    sequence: AtomicU64,
+    /// Size of the serialized snapshot, in bytes.
+    len: AtomicU32,
+}
+
+const HEADER_SIZE: usize = std::mem::size_of::<SectionHeader>();
+
+/// Publishes the [`display info snapshots`] into a named shared memory section,
+/// so that other processes (e.g. crash reporters or injected overlays) may [`read`] them
+/// without enumerating the displays themselves.
+///
+/// Requires the `ipc` feature.
+///
+/// [`display info snapshots`]: struct.DisplaysSnapshot.html
+/// [`read`]: struct.SnapshotReader.html
+pub struct SnapshotPublisher {
+    memory: SharedMemoryPlatform,
+}
+
+impl SnapshotPublisher {
+    /// Default maximum size of the serialized snapshot, in bytes.
+    pub const DEFAULT_CAPACITY: u32 = 256 * 1024;
+
+    /// Creates the shared memory section with the `name` (e.g. `Local\MyAppDisplays` on Windows),
+    /// which fits the serialized snapshots of up to `capacity` bytes.
+    ///
+    /// Returns an error if the section could not be created, or exists already.
+    pub fn new(name: &str, capacity: u32) -> Result<Self, ()> {
+        let memory = SharedMemoryPlatform::create(name, HEADER_SIZE + capacity as usize)?;
+
+        unsafe { init_section(memory.as_ptr(), capacity) };
+
+        Ok(Self { memory })
+    }
+
+    /// Publishes the `snapshot`, replacing the previously published one.
+    /// Returns the new generation (i.e. the number of the snapshots published so far).
+    ///
+    /// Returns an error if the serialized snapshot does not fit the section.
+    pub fn publish(&self, snapshot: &DisplaysSnapshot) -> Result<u64, ()> {
+        let data = serde_json::to_vec(snapshot).map_err(|_| ())?;
+
+        unsafe { write_section(self.memory.as_ptr(), &data) }
+    }
+}
+
+/// Reads the [`display info snapshots`] published by a [`SnapshotPublisher`] in another process.
+///
+/// Requires the `ipc` feature.
+///
+/// [`display info snapshots`]: struct.DisplaysSnapshot.html
+/// [`SnapshotPublisher`]: struct.SnapshotPublisher.html
+pub struct SnapshotReader {
+    memory: SharedMemoryPlatform,
+}
+
+impl SnapshotReader {
+    /// Opens the shared memory section with the `name` created by a [`SnapshotPublisher`].
+    ///
+    /// Returns an error if the section does not exist, or was not created by a [`SnapshotPublisher`].
+    ///
+    /// [`SnapshotPublisher`]: struct.SnapshotPublisher.html
+    pub fn open(name: &str) -> Result<Self, ()> {
+        let memory = SharedMemoryPlatform::open(name)?;
+
+        if memory.len() < HEADER_SIZE {
+            return Err(());
+        }
+
+        if unsafe { header(memory.as_ptr()) }
+            .magic
+            .load(Ordering::Acquire)
+            != SECTION_MAGIC
+        {
+            return Err(());
+        }
+
+        Ok(Self { memory })
+    }
+
+    /// Returns the generation of the currently published snapshot (`0` if none was published yet).
+    ///
+    /// Cheap - use this to check whether the snapshot changed since it was last [`read`].
+    ///
+    /// [`read`]: #method.read
+    pub fn generation(&self) -> u64 {
+        generation(
+            unsafe { header(self.memory.as_ptr()) }
+                .sequence
+                .load(Ordering::Acquire),
+        )
+    }
+
+    /// Returns the currently published snapshot and its generation,
+    /// or `None` if none was published yet, or it could not be read (e.g. it is being published too often).
+    pub fn read(&self) -> Option<(u64, DisplaysSnapshot)> {
+        let (generation, data) = unsafe { read_section(self.memory.as_ptr(), self.memory.len()) }?;
+
+        serde_json::from_slice(&data)
+            .ok()
+            .map(|snapshot| (generation, snapshot))
+    }
+}
+
+fn generation(sequence: u64) -> u64 {
+    sequence / 2
+}
+
+unsafe fn header<'s>(section: *mut u8) -> &'s SectionHeader {
+    &*(section as *const SectionHeader)
+}
+
+/// Initializes the header of the zeroed `section`, which fits `capacity` bytes after the header.
+unsafe fn init_section(section: *mut u8, capacity: u32) {
+    let header = header(section);
+
+    header.capacity.store(capacity, Ordering::Relaxed);
+    header.magic.store(SECTION_MAGIC, Ordering::Release);
+}
+
+/// Writes the `data` to the `section` (which must only be written to by this process).
+/// Returns the new generation.
+unsafe fn write_section(section: *mut u8, data: &[u8]) -> Result<u64, ()> {
+    let header = header(section);
+
+    if data.len() > header.capacity.load(Ordering::Relaxed) as usize {
+        return Err(());
+    }
+
+    // Odd while writing.
+    let sequence = header.sequence.fetch_add(1, Ordering::Relaxed) + 1;
+    fence(Ordering::Release);
+
+    std::ptr::copy_nonoverlapping(data.as_ptr(), section.add(HEADER_SIZE), data.len());
+    header.len.store(data.len() as u32, Ordering::Relaxed);
+
+    header.sequence.store(sequence + 1, Ordering::Release);
+
+    Ok(generation(sequence + 1))
+}
+
+/// Reads the data from the `section` of `section_size` bytes, retrying if it is being written to.
+/// Returns the generation and the data, or `None` if nothing was written yet, or the data could not be read.
+unsafe fn read_section(section: *mut u8, section_size: usize) -> Option<(u64, Vec<u8>)> {
+    let header = header(section);
+    // Don't trust the other process.
+    let capacity =
+        (header.capacity.load(Ordering::Relaxed) as usize).min(section_size - HEADER_SIZE);
+
+    for _ in 0..READ_ATTEMPTS {
+        let sequence = header.sequence.load(Ordering::Acquire);
+
+        if sequence == 0 {
+            return None;
+        }
+
+        if sequence % 2 == 1 {
+            std::thread::yield_now();
+            continue;
+        }
+
+        let len = (header.len.load(Ordering::Relaxed) as usize).min(capacity);
+        let mut data = vec![0u8; len];
+        std::ptr::copy_nonoverlapping(section.add(HEADER_SIZE), data.as_mut_ptr(), len);
+
+        fence(Ordering::Acquire);
+
+        // Not written to while reading.
+        if header.sequence.load(Ordering::Relaxed) == sequence {
+            return Some((generation(sequence), data));
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn section() {
+        const SECTION_SIZE: usize = HEADER_SIZE + 16;
+
+        // `u64`'s to keep the header aligned.
+        let mut buffer = vec![0u64; SECTION_SIZE / 8];
+        let section = buffer.as_mut_ptr() as *mut u8;
+
+        unsafe {
+            init_section(section, 16);
+            assert_eq!(header(section).magic.load(Ordering::Relaxed), SECTION_MAGIC);
+
+            assert_eq!(read_section(section, SECTION_SIZE), None);
+
+            assert_eq!(write_section(section, b"first"), Ok(1));
+            assert_eq!(
+                read_section(section, SECTION_SIZE),
+                Some((1, b"first".to_vec()))
+            );
+
+            assert_eq!(write_section(section, b"second snapshot"), Ok(2));
+            assert_eq!(
+                read_section(section, SECTION_SIZE),
+                Some((2, b"second snapshot".to_vec()))
+            );
+
+            // Too large.
+            assert_eq!(write_section(section, &[0; 17]), Err(()));
+            assert_eq!(
+                read_section(section, SECTION_SIZE),
+                Some((2, b"second snapshot".to_vec()))
+            );
+
+            // Being written to.
+            header(section).sequence.fetch_add(1, Ordering::Relaxed);
+            assert_eq!(read_section(section, SECTION_SIZE), None);
+        }
+    }
+}
@@ -0,0 +1,916 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap};
+use std::fmt::{Display, Formatter};
+
+use crate::Rectangle;
+
+/// Describes the display (non-work) rectangle adjacency
+/// to other display rectangles in virtual desctop space.
+/// Contains the index of the adjacent display on each side, if any,
+/// and the edge segment the displays share.
+///
+/// Displays are adjacent if they share a non-empty edge segment (i.e. not just a corner).
+/// If multiple displays are adjacent on the same side, the one with the longest shared edge is reported
+/// (see [`Displays::shared_edges`] for all of them).
+///
+/// [`Displays::shared_edges`]: struct.Displays.html#method.shared_edges
+#[derive(Clone, Copy, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AdjacencyInfo {
+    /// Another display is adjacent on the left.
+    pub left: Option<u32>,
+    /// Another display is adjacent on the top.
+    pub top: Option<u32>,
+    /// Another display is adjacent on the right.
+    pub right: Option<u32>,
+    /// Another display is adjacent on the bottom.
+    pub bottom: Option<u32>,
+    /// Edge shared with the display adjacent on the left, if any.
+    pub left_edge: Option<SharedEdge>,
+    /// Edge shared with the display adjacent on the top, if any.
+    pub top_edge: Option<SharedEdge>,
+    /// Edge shared with the display adjacent on the right, if any.
+    pub right_edge: Option<SharedEdge>,
+    /// Edge shared with the display adjacent on the bottom, if any.
+    pub bottom_edge: Option<SharedEdge>,
+}
+
+impl AdjacencyInfo {
+    pub fn is_some(self) -> bool {
+        self.left.is_some() || self.top.is_some() || self.right.is_some() || self.bottom.is_some()
+    }
+}
+
+impl Display for AdjacencyInfo {
+    /// Formats the indices of the adjacent displays on each side which has one
+    /// (e.g. `left: 1, bottom: 2`), or `<none>` if no displays are adjacent.
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        if !self.is_some() {
+            return write!(f, "<none>");
+        }
+
+        let sides = [
+            (EdgeSide::Left, self.left),
+            (EdgeSide::Top, self.top),
+            (EdgeSide::Right, self.right),
+            (EdgeSide::Bottom, self.bottom),
+        ];
+
+        let mut first = true;
+
+        for (side, index) in sides.iter() {
+            if let Some(index) = index {
+                if !first {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{}: {}", side, index)?;
+                first = false;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Side of a display's rectangle.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EdgeSide {
+    Left,
+    Top,
+    Right,
+    Bottom,
+}
+
+impl Display for EdgeSide {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use EdgeSide::*;
+
+        match self {
+            Left => write!(f, "left"),
+            Top => write!(f, "top"),
+            Right => write!(f, "right"),
+            Bottom => write!(f, "bottom"),
+        }
+    }
+}
+
+/// Edge segment shared by two adjacent displays in virtual desktop space,
+/// i.e. the span over which the cursor may cross between them.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SharedEdge {
+    /// Index of the adjacent display.
+    pub display_index: u32,
+    /// Side of the display the adjacent display touches.
+    pub side: EdgeSide,
+    /// Start coordinate of the shared segment (inclusive) -
+    /// the vertical coordinate for the left / right sides, horizontal for the top / bottom sides.
+    pub start: i32,
+    /// End coordinate of the shared segment (exclusive).
+    pub end: i32,
+}
+
+impl SharedEdge {
+    /// Returns the length of the shared segment in pixels.
+    pub fn length(&self) -> u32 {
+        (self.end as i64 - self.start as i64) as u32
+    }
+}
+
+/// The nearest display in some direction from another display in virtual desktop space,
+/// as returned by [`Displays::neighbors`].
+///
+/// Unlike the [`adjacent`] displays, neighbors need not touch the display -
+/// there may be a gap between them, and their edges need not overlap
+/// (e.g. a display diagonally below and to the right of another one is its neighbor on the right).
+///
+/// [`Displays::neighbors`]: struct.Displays.html#method.neighbors
+/// [`adjacent`]: struct.AdjacencyInfo.html
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Neighbor {
+    /// Index of the neighboring display.
+    pub index: u32,
+    /// Side of the display the neighboring display is on.
+    pub direction: EdgeSide,
+    /// Distance between the facing edges of the displays in pixels (`0` if the displays are adjacent).
+    pub gap_px: u32,
+    /// Length in pixels of the span over which the facing edges of the displays overlap
+    /// (`0` if they don't, e.g. if the displays are offset diagonally).
+    pub shared_edge_len: u32,
+}
+
+/// A display layout problem which causes janky cursor transitions between the displays,
+/// as reported by [`Displays::layout_report`].
+///
+/// [`Displays::layout_report`]: struct.Displays.html#method.layout_report
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum LayoutIssue {
+    /// Two side-by-side displays are vertically offset, i.e. neither their top nor their bottom edges are aligned,
+    /// so the cursor gets stuck on the shared edge near the top / bottom.
+    VerticalOffset {
+        /// Index of the display on the left.
+        left: u32,
+        /// Index of the display on the right.
+        right: u32,
+        /// Vertical offset of the right display's top edge relative to the left display's top edge, in pixels.
+        offset: i32,
+    },
+    /// Two stacked displays are horizontally offset, i.e. neither their left nor their right edges are aligned,
+    /// so the cursor gets stuck on the shared edge near the left / right.
+    HorizontalOffset {
+        /// Index of the display on the top.
+        top: u32,
+        /// Index of the display on the bottom.
+        bottom: u32,
+        /// Horizontal offset of the bottom display's left edge relative to the top display's left edge, in pixels.
+        offset: i32,
+    },
+    /// Two adjacent displays have different DPI scale values,
+    /// so the cursor / dragged windows jump when crossing the shared edge.
+    DpiMismatch {
+        /// Index of the first display.
+        first: u32,
+        /// Index of the second display.
+        second: u32,
+        /// DPI scale value of the first display.
+        first_dpi_scale: f32,
+        /// DPI scale value of the second display.
+        second_dpi_scale: f32,
+    },
+}
+
+impl Display for LayoutIssue {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use LayoutIssue::*;
+
+        match self {
+            VerticalOffset {
+                left,
+                right,
+                offset,
+            } => write!(
+                f,
+                "display {} is offset vertically by {} pixels from display {} on its left",
+                right, offset, left
+            ),
+            HorizontalOffset {
+                top,
+                bottom,
+                offset,
+            } => write!(
+                f,
+                "display {} is offset horizontally by {} pixels from display {} above it",
+                bottom, offset, top
+            ),
+            DpiMismatch {
+                first,
+                second,
+                first_dpi_scale,
+                second_dpi_scale,
+            } => write!(
+                f,
+                "adjacent displays {} and {} have different DPI scales ({}% and {}%)",
+                first,
+                second,
+                (first_dpi_scale * 100.0).round(),
+                (second_dpi_scale * 100.0).round()
+            ),
+        }
+    }
+}
+
+/// Display layout analysis, as returned by [`Displays::layout_report`].
+///
+/// [`Displays::layout_report`]: struct.Displays.html#method.layout_report
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct LayoutReport {
+    /// Indices of the displays which are (at least as) wide as they are tall in the virtual desktop.
+    pub landscape: Vec<u32>,
+    /// Indices of the displays which are taller than they are wide in the virtual desktop.
+    pub portrait: Vec<u32>,
+    /// Detected layout problems, if any.
+    pub issues: Vec<LayoutIssue>,
+}
+
+impl LayoutReport {
+    /// Returns `true` if no layout problems were detected.
+    pub fn is_aligned(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// Analyzes the layout of the displays, described by their virtual desktop rectangles and DPI scale values.
+pub(crate) fn analyze_layout(displays: &[(Rectangle, f32)]) -> LayoutReport {
+    let mut report = LayoutReport::default();
+
+    for (index, (rect, _)) in displays.iter().enumerate() {
+        if rect.height() > rect.width() {
+            report.portrait.push(index as u32);
+        } else {
+            report.landscape.push(index as u32);
+        }
+    }
+
+    report.issues = map_displays(displays.len(), |first| layout_issues(displays, first))
+        .into_iter()
+        .flatten()
+        .collect();
+
+    report
+}
+
+/// Returns the layout issues between the display at `first` index and the displays after it.
+fn layout_issues(displays: &[(Rectangle, f32)], first: usize) -> Vec<LayoutIssue> {
+    let mut issues = Vec::new();
+
+    let (first_rect, first_dpi_scale) = &displays[first];
+
+    for (second, (second_rect, second_dpi_scale)) in displays.iter().enumerate().skip(first + 1) {
+        let (first, second) = (first as u32, second as u32);
+
+        let side_by_side = if first_rect.right() == second_rect.left() {
+            Some((first, first_rect, second, second_rect))
+        } else if second_rect.right() == first_rect.left() {
+            Some((second, second_rect, first, first_rect))
+        } else {
+            None
+        }
+        .filter(|(_, left, _, right)| {
+            shared_edge(left.top(), left.bottom(), right.top(), right.bottom())
+        });
+
+        let stacked = if first_rect.bottom() == second_rect.top() {
+            Some((first, first_rect, second, second_rect))
+        } else if second_rect.bottom() == first_rect.top() {
+            Some((second, second_rect, first, first_rect))
+        } else {
+            None
+        }
+        .filter(|(_, top, _, bottom)| {
+            shared_edge(top.left(), top.right(), bottom.left(), bottom.right())
+        });
+
+        if let Some((left, left_rect, right, right_rect)) = side_by_side {
+            if left_rect.top() != right_rect.top() && left_rect.bottom() != right_rect.bottom() {
+                issues.push(LayoutIssue::VerticalOffset {
+                    left,
+                    right,
+                    offset: right_rect.top().saturating_sub(left_rect.top()),
+                });
+            }
+        }
+
+        if let Some((top, top_rect, bottom, bottom_rect)) = stacked {
+            if top_rect.left() != bottom_rect.left() && top_rect.right() != bottom_rect.right() {
+                issues.push(LayoutIssue::HorizontalOffset {
+                    top,
+                    bottom,
+                    offset: bottom_rect.left().saturating_sub(top_rect.left()),
+                });
+            }
+        }
+
+        if (side_by_side.is_some() || stacked.is_some()) && first_dpi_scale != second_dpi_scale {
+            issues.push(LayoutIssue::DpiMismatch {
+                first,
+                second,
+                first_dpi_scale: *first_dpi_scale,
+                second_dpi_scale: *second_dpi_scale,
+            });
+        }
+    }
+
+    issues
+}
+
+/// Minimum number of displays to compute the per-display layout info in parallel for -
+/// below that, the overhead of the parallel computation outweighs its benefits.
+#[cfg(feature = "parallel")]
+const PARALLEL_THRESHOLD: usize = 16;
+
+/// Returns the results of `f` called with each display index in `0 .. num_displays`, in display index order.
+///
+/// Calls `f` in parallel with the `parallel` feature, if there are enough displays.
+pub(crate) fn map_displays<T, F>(num_displays: usize, f: F) -> Vec<T>
+where
+    T: Send,
+    F: Fn(usize) -> T + Send + Sync,
+{
+    #[cfg(feature = "parallel")]
+    if num_displays >= PARALLEL_THRESHOLD {
+        use rayon::prelude::*;
+
+        return (0..num_displays).into_par_iter().map(f).collect();
+    }
+
+    (0..num_displays).map(f).collect()
+}
+
+/// Calculates the adjacency info of the display at `display_index`,
+/// given the virtual desktop rectangles of all displays (`None` for displays with no rectangles).
+pub(crate) fn calc_adjacency_info(
+    rects: &[Option<Rectangle>],
+    display_index: usize,
+) -> AdjacencyInfo {
+    let mut adjacency = AdjacencyInfo::default();
+
+    for edge in shared_edges(rects, display_index) {
+        let (neighbour, neighbour_edge) = match edge.side {
+            EdgeSide::Left => (&mut adjacency.left, &mut adjacency.left_edge),
+            EdgeSide::Top => (&mut adjacency.top, &mut adjacency.top_edge),
+            EdgeSide::Right => (&mut adjacency.right, &mut adjacency.right_edge),
+            EdgeSide::Bottom => (&mut adjacency.bottom, &mut adjacency.bottom_edge),
+        };
+
+        // Keep the longest shared edge on each side.
+        let longer = match neighbour_edge {
+            Some(neighbour_edge) => edge.length() > neighbour_edge.length(),
+            None => true,
+        };
+
+        if longer {
+            neighbour.replace(edge.display_index);
+            neighbour_edge.replace(edge);
+        }
+    }
+
+    adjacency
+}
+
+/// Returns the edges the display at `display_index` shares with all adjacent displays,
+/// given the virtual desktop rectangles of all displays (`None` for displays with no rectangles).
+pub(crate) fn shared_edges(rects: &[Option<Rectangle>], display_index: usize) -> Vec<SharedEdge> {
+    debug_assert!(display_index < rects.len());
+
+    let mut edges = Vec::new();
+
+    let rectangle = match &rects[display_index] {
+        Some(rectangle) => rectangle,
+        None => return edges,
+    };
+
+    for (i, other_rectangle) in rects.iter().enumerate() {
+        let other_rectangle = match other_rectangle {
+            Some(other_rectangle) if i != display_index => other_rectangle,
+            _ => continue,
+        };
+
+        let mut push = |side, start: i32, end: i32| {
+            // Touching at the corners only (or empty) - not adjacent.
+            if start < end {
+                edges.push(SharedEdge {
+                    display_index: i as u32,
+                    side,
+                    start,
+                    end,
+                });
+            }
+        };
+
+        let top = rectangle.top().max(other_rectangle.top());
+        let bottom = rectangle.bottom().min(other_rectangle.bottom());
+        let left = rectangle.left().max(other_rectangle.left());
+        let right = rectangle.right().min(other_rectangle.right());
+
+        // Adjacent to the left?
+        if other_rectangle.right() == rectangle.left() {
+            push(EdgeSide::Left, top, bottom);
+        }
+
+        // Adjacent to the right?
+        if other_rectangle.left() == rectangle.right() {
+            push(EdgeSide::Right, top, bottom);
+        }
+
+        // Adjacent to the top?
+        if other_rectangle.bottom() == rectangle.top() {
+            push(EdgeSide::Top, left, right);
+        }
+
+        // Adjacent to the bottom?
+        if other_rectangle.top() == rectangle.bottom() {
+            push(EdgeSide::Bottom, left, right);
+        }
+    }
+
+    edges
+}
+
+/// Returns the nearest display in each direction (left, top, right, bottom, in that order, if any)
+/// from the display at `display_index`,
+/// given the virtual desktop rectangles of all displays (`None` for displays with no rectangles).
+///
+/// A display is in the direction if it lies entirely beyond the display's edge on that side.
+/// Displays are ranked by the gap between their facing edges plus their offset along the edge (if they don't overlap),
+/// then by the longest overlap, then by index.
+pub(crate) fn neighbors(rects: &[Option<Rectangle>], display_index: usize) -> Vec<Neighbor> {
+    debug_assert!(display_index < rects.len());
+
+    let rectangle = match &rects[display_index] {
+        Some(rectangle) => rectangle,
+        None => return Vec::new(),
+    };
+
+    // Distance between the `[start_0 .. end_0)` and `[start_1 .. end_1)` ranges, `0` if they overlap.
+    let offset = |start_0: i64, end_0: i64, start_1: i64, end_1: i64| {
+        (start_1 - end_0).max(start_0 - end_1).max(0)
+    };
+    // Length of the overlap of the `[start_0 .. end_0)` and `[start_1 .. end_1)` ranges, `0` if they don't.
+    let overlap = |start_0: i64, end_0: i64, start_1: i64, end_1: i64| {
+        (end_0.min(end_1) - start_0.max(start_1)).max(0)
+    };
+
+    let (left, top, right, bottom) = (
+        rectangle.left() as i64,
+        rectangle.top() as i64,
+        rectangle.exact_right(),
+        rectangle.exact_bottom(),
+    );
+
+    [
+        EdgeSide::Left,
+        EdgeSide::Top,
+        EdgeSide::Right,
+        EdgeSide::Bottom,
+    ]
+    .iter()
+    .filter_map(|&direction| {
+        rects
+            .iter()
+            .enumerate()
+            .filter_map(|(i, other_rectangle)| {
+                let other_rectangle = match other_rectangle {
+                    Some(other_rectangle) if i != display_index => other_rectangle,
+                    _ => return None,
+                };
+
+                let (other_left, other_top, other_right, other_bottom) = (
+                    other_rectangle.left() as i64,
+                    other_rectangle.top() as i64,
+                    other_rectangle.exact_right(),
+                    other_rectangle.exact_bottom(),
+                );
+
+                let (gap, offset, overlap) = match direction {
+                    EdgeSide::Left => (
+                        left - other_right,
+                        offset(top, bottom, other_top, other_bottom),
+                        overlap(top, bottom, other_top, other_bottom),
+                    ),
+                    EdgeSide::Right => (
+                        other_left - right,
+                        offset(top, bottom, other_top, other_bottom),
+                        overlap(top, bottom, other_top, other_bottom),
+                    ),
+                    EdgeSide::Top => (
+                        top - other_bottom,
+                        offset(left, right, other_left, other_right),
+                        overlap(left, right, other_left, other_right),
+                    ),
+                    EdgeSide::Bottom => (
+                        other_top - bottom,
+                        offset(left, right, other_left, other_right),
+                        overlap(left, right, other_left, other_right),
+                    ),
+                };
+
+                // Not (entirely) in this direction.
+                if gap < 0 {
+                    return None;
+                }
+
+                Some((gap + offset, -overlap, i, gap, overlap))
+            })
+            .min()
+            .map(|(_, _, i, gap, overlap)| Neighbor {
+                index: i as u32,
+                direction,
+                gap_px: gap.min(u32::MAX as i64) as u32,
+                shared_edge_len: overlap.min(u32::MAX as i64) as u32,
+            })
+    })
+    .collect()
+}
+
+/// Returns the indices of a pair of the `rects` which [`overlap`], if any.
+///
+/// Sweeps the rectangles left to right, keeping the ones which intersect the sweep line ordered by their top sides -
+/// `O(n log n)`, unlike checking each pair of the rectangles.
+///
+/// [`overlap`]: struct.Rectangle.html#method.overlaps
+#[cfg_attr(not(windows), allow(dead_code))]
+pub(crate) fn find_overlap(rects: &[Rectangle]) -> Option<(usize, usize)> {
+    let mut sorted: Vec<_> = (0..rects.len())
+        .filter(|&index| !rects[index].is_empty())
+        .collect();
+    sorted.sort_unstable_by_key(|&index| rects[index].left());
+
+    // Rectangles which intersect the sweep line, keyed by their top sides.
+    // Their vertical ranges are disjoint (or they would overlap), so each top side is unique.
+    let mut active = BTreeMap::<i64, (i64, usize)>::new();
+    // (right side, top side) of the active rectangles, to remove them when the sweep line passes their right side.
+    let mut active_rights = BinaryHeap::new();
+
+    for index in sorted {
+        let rect = &rects[index];
+        let (left, top, bottom) = (rect.left() as i64, rect.top() as i64, rect.exact_bottom());
+
+        while let Some(&Reverse((right, top))) = active_rights.peek() {
+            if right > left {
+                break;
+            }
+
+            active_rights.pop();
+            active.remove(&top);
+        }
+
+        // Only the active rectangle with the lowest top side above the `rect`'s bottom side may overlap it.
+        if let Some((_, &(other_bottom, other_index))) = active.range(..bottom).next_back() {
+            if other_bottom > top {
+                return Some((other_index.min(index), other_index.max(index)));
+            }
+        }
+
+        active.insert(top, (bottom, index));
+        active_rights.push(Reverse((rect.exact_right(), top)));
+    }
+
+    None
+}
+
+/// Returns `true` if the `[start_0 .. end_0)` and `[start_1 .. end_1)` edge ranges overlap,
+/// i.e. if the cursor may cross between the two edges.
+fn shared_edge(start_0: i32, end_0: i32, start_1: i32, end_1: i32) -> bool {
+    start_0 < end_1 && start_1 < end_0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rectangle::tests::{any_rect, CASES};
+    use crate::{Dimensions, Position};
+    use proptest::prelude::*;
+
+    fn rect(left: i32, top: i32, width: u32, height: u32) -> Rectangle {
+        Rectangle::new(Position::new(left, top), Dimensions::new(width, height))
+    }
+
+    #[test]
+    fn shared_edges() {
+        // Two displays on the right of a larger one, one below it, one touching at the corner only.
+        let rects = [
+            Some(rect(0, 0, 2560, 1440)),
+            Some(rect(2560, -200, 1920, 1080)),
+            Some(rect(2560, 880, 1920, 1080)),
+            Some(rect(500, 1440, 1920, 1080)),
+            Some(rect(-1920, -1080, 1920, 1080)),
+            None,
+        ];
+
+        assert_eq!(
+            super::shared_edges(&rects, 0),
+            vec![
+                SharedEdge {
+                    display_index: 1,
+                    side: EdgeSide::Right,
+                    start: 0,
+                    end: 880
+                },
+                SharedEdge {
+                    display_index: 2,
+                    side: EdgeSide::Right,
+                    start: 880,
+                    end: 1440
+                },
+                SharedEdge {
+                    display_index: 3,
+                    side: EdgeSide::Bottom,
+                    start: 500,
+                    end: 2420
+                },
+            ]
+        );
+        assert_eq!(
+            super::shared_edges(&rects, 3),
+            vec![SharedEdge {
+                display_index: 0,
+                side: EdgeSide::Top,
+                start: 500,
+                end: 2420
+            }]
+        );
+        assert!(super::shared_edges(&rects, 4).is_empty());
+        assert!(super::shared_edges(&rects, 5).is_empty());
+
+        // The neighbour with the longest shared edge is reported.
+        let adjacency = calc_adjacency_info(&rects, 0);
+        assert_eq!(adjacency.right, Some(1));
+        assert_eq!(adjacency.right_edge.map(|edge| edge.length()), Some(880));
+        assert_eq!(adjacency.bottom, Some(3));
+        assert_eq!(adjacency.left, None);
+        assert_eq!(adjacency.top, None);
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(CASES))]
+
+        #[test]
+        fn adjacency_symmetric(first in any_rect(), mut second in any_rect(), snap in 0..4) {
+            // Snap the second rectangle to one of the edges of the first one every now and then.
+            match snap {
+                0 => second.position.left = first.right(),
+                1 => second.position.top = first.bottom(),
+                _ => {}
+            }
+
+            let rects = [Some(first), Some(second)];
+            let (first_adjacency, second_adjacency) = (
+                calc_adjacency_info(&rects, 0),
+                calc_adjacency_info(&rects, 1),
+            );
+
+            assert_eq!(
+                first_adjacency.right.is_some(),
+                second_adjacency.left.is_some()
+            );
+            assert_eq!(
+                first_adjacency.left.is_some(),
+                second_adjacency.right.is_some()
+            );
+            assert_eq!(
+                first_adjacency.bottom.is_some(),
+                second_adjacency.top.is_some()
+            );
+            assert_eq!(
+                first_adjacency.top.is_some(),
+                second_adjacency.bottom.is_some()
+            );
+        }
+    }
+
+    #[test]
+    fn find_overlap() {
+        // Side by side and stacked, but not overlapping.
+        let rects = [
+            rect(0, 0, 1920, 1080),
+            rect(1920, -360, 2560, 1440),
+            rect(0, 1080, 1920, 1080),
+            rect(-1080, 0, 1080, 1920),
+            rect(100, 100, 0, 100),
+        ];
+        assert_eq!(super::find_overlap(&rects), None);
+        assert_eq!(super::find_overlap(&[]), None);
+
+        let mut overlapping = rects.to_vec();
+        overlapping.push(rect(4000, 1000, 100, 100));
+        assert_eq!(super::find_overlap(&overlapping), Some((1, 5)));
+    }
+
+    /// Small rectangles close to each other to make overlaps likely.
+    fn any_overlapping_rect() -> impl Strategy<Value = Rectangle> {
+        any_rect().prop_map(|rect| {
+            Rectangle::new(
+                Position::new(rect.left() % 64, rect.top() % 64),
+                Dimensions::new(rect.width() % 16, rect.height() % 16),
+            )
+        })
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(CASES / 100))]
+
+        #[test]
+        fn find_overlap_properties(
+            rects in prop::collection::vec(any_overlapping_rect(), 0..16),
+        ) {
+            // Same as checking each pair.
+            let any_overlap = (0..rects.len())
+                .any(|i| (i + 1..rects.len()).any(|j| rects[i].overlaps(&rects[j])));
+
+            match super::find_overlap(&rects) {
+                Some((i, j)) => {
+                    assert!(i < j);
+                    assert!(rects[i].overlaps(&rects[j]));
+                }
+                None => assert!(!any_overlap),
+            }
+        }
+    }
+
+    #[test]
+    fn map_displays() {
+        // Results are in display index order, whether computed in parallel or not.
+        assert_eq!(
+            super::map_displays(64, |index| index * 2),
+            (0..64).map(|index| index * 2).collect::<Vec<_>>()
+        );
+        assert!(super::map_displays(0, |index| index).is_empty());
+    }
+
+    #[test]
+    fn analyze_layout() {
+        // Aligned side by side.
+        let aligned = super::analyze_layout(&[
+            (rect(0, 0, 1920, 1080), 1.0),
+            (rect(1920, 0, 1920, 1080), 1.0),
+        ]);
+        assert!(aligned.is_aligned());
+        assert_eq!(aligned.landscape, vec![0, 1]);
+        assert!(aligned.portrait.is_empty());
+
+        // Different heights, bottom-aligned.
+        let bottom_aligned = super::analyze_layout(&[
+            (rect(0, 0, 2560, 1440), 1.0),
+            (rect(-1920, 360, 1920, 1080), 1.0),
+        ]);
+        assert!(bottom_aligned.is_aligned());
+
+        // Vertically offset, portrait display on the right with a different DPI scale.
+        let offset = super::analyze_layout(&[
+            (rect(0, 0, 1920, 1080), 1.0),
+            (rect(1920, -200, 1080, 1920), 1.25),
+        ]);
+        assert_eq!(offset.landscape, vec![0]);
+        assert_eq!(offset.portrait, vec![1]);
+        assert_eq!(
+            offset.issues,
+            vec![
+                LayoutIssue::VerticalOffset {
+                    left: 0,
+                    right: 1,
+                    offset: -200
+                },
+                LayoutIssue::DpiMismatch {
+                    first: 0,
+                    second: 1,
+                    first_dpi_scale: 1.0,
+                    second_dpi_scale: 1.25
+                },
+            ]
+        );
+
+        // Horizontally offset, stacked.
+        let stacked = super::analyze_layout(&[
+            (rect(0, 0, 1920, 1080), 1.0),
+            (rect(100, -1080, 1920, 1080), 1.0),
+        ]);
+        assert_eq!(
+            stacked.issues,
+            vec![LayoutIssue::HorizontalOffset {
+                top: 1,
+                bottom: 0,
+                offset: -100
+            }]
+        );
+
+        // Touching at the corners only - not adjacent.
+        let corners = super::analyze_layout(&[
+            (rect(0, 0, 1920, 1080), 1.0),
+            (rect(1920, 1080, 1920, 1080), 2.0),
+        ]);
+        assert!(corners.is_aligned());
+    }
+
+    #[test]
+    fn neighbors() {
+        // A display with a gap on the right, a display diagonally below on the left,
+        // and two displays above - a barely overlapping adjacent one, and a farther aligned one.
+        let rects = [
+            Some(rect(0, 0, 1920, 1080)),
+            Some(rect(2020, 100, 1920, 1080)),
+            Some(rect(-1920, 1280, 1920, 1080)),
+            Some(rect(1800, -1080, 1920, 1080)),
+            Some(rect(0, -2260, 1920, 1080)),
+            None,
+        ];
+
+        assert_eq!(
+            super::neighbors(&rects, 0),
+            vec![
+                Neighbor {
+                    index: 2,
+                    direction: EdgeSide::Left,
+                    gap_px: 0,
+                    shared_edge_len: 0,
+                },
+                Neighbor {
+                    index: 3,
+                    direction: EdgeSide::Top,
+                    gap_px: 0,
+                    shared_edge_len: 120,
+                },
+                Neighbor {
+                    index: 1,
+                    direction: EdgeSide::Right,
+                    gap_px: 100,
+                    shared_edge_len: 980,
+                },
+                Neighbor {
+                    index: 2,
+                    direction: EdgeSide::Bottom,
+                    gap_px: 200,
+                    shared_edge_len: 0,
+                },
+            ]
+        );
+
+        assert_eq!(
+            super::neighbors(&rects, 1),
+            vec![
+                Neighbor {
+                    index: 0,
+                    direction: EdgeSide::Left,
+                    gap_px: 100,
+                    shared_edge_len: 980,
+                },
+                Neighbor {
+                    index: 3,
+                    direction: EdgeSide::Top,
+                    gap_px: 100,
+                    shared_edge_len: 1700,
+                },
+                Neighbor {
+                    index: 2,
+                    direction: EdgeSide::Bottom,
+                    gap_px: 100,
+                    shared_edge_len: 0,
+                },
+            ]
+        );
+
+        assert_eq!(
+            super::neighbors(&rects, 2),
+            vec![
+                Neighbor {
+                    index: 0,
+                    direction: EdgeSide::Top,
+                    gap_px: 200,
+                    shared_edge_len: 0,
+                },
+                Neighbor {
+                    index: 0,
+                    direction: EdgeSide::Right,
+                    gap_px: 0,
+                    shared_edge_len: 0,
+                },
+            ]
+        );
+
+        assert!(super::neighbors(&rects, 5).is_empty());
+        assert!(super::neighbors(&rects[..1], 0).is_empty());
+    }
+
+    #[test]
+    fn adjacency_info_display() {
+        assert_eq!(AdjacencyInfo::default().to_string(), "<none>");
+
+        let adjacency_info = AdjacencyInfo {
+            left: Some(1),
+            bottom: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(adjacency_info.to_string(), "left: 1, bottom: 2");
+    }
+}
@@ -3,29 +3,158 @@
 //! A small Rust library that enumerates the system's displays / monitors.
 //!
 //! Implemented for Windows only.
+//! Builds on the other platforms too, where the display enumeration fails with [`Error::Unsupported`]
+//! (see [`Displays::capabilities`]), while the rest of the API (e.g. the rectangle / display mode math) is available.
+//!
+//! [`Error::Unsupported`]: enum.Error.html#variant.Unsupported
+//! [`Displays::capabilities`]: struct.Displays.html#method.capabilities
 //!
 //! ## Dependencies
 //!
 //! [`bitflags`](http://crates.io/crates/bitflags).
 //!
-//! On Windows, [`winapi`](http://crates.io/crates/winapi).
+//! On Windows, [`windows-sys`](http://crates.io/crates/windows-sys).
+//!
+//! Optionally, [`serde`](http://crates.io/crates/serde) (`serde` feature) to (de)serialize the display configuration profiles
+//! and the display info snapshots.
+//!
+//! Optionally, [`serde_json`](http://crates.io/crates/serde_json) (`ipc` feature) to publish the display info snapshots
+//! to the other processes via shared memory.
 
+// Display enumeration reports an `Error`;
+// the other platform queries / changes carry no info beyond the failure itself.
+#![allow(clippy::result_unit_err)]
+
+mod backend;
+mod brightness;
+mod cached_displays;
+mod colorimetry;
+mod coordinates;
+mod cursor;
+mod ddc;
+mod display_config;
+mod display_index;
 mod display_info;
 mod displays;
+mod edid;
+mod error;
+mod events;
+mod gamma;
+#[cfg(feature = "ipc")]
+mod ipc;
+mod layout;
+mod matcher;
+#[cfg(not(feature = "no-modes"))]
+mod mode_query;
+mod night_light;
+mod placement;
+mod platform_info;
+mod polling_watcher;
+mod profiles;
 mod rectangle;
+mod region;
+mod render;
+mod snapshot;
+mod stitch;
+mod units;
+mod window_placement;
+
+#[cfg(any(test, feature = "test-util"))]
+pub mod fixtures;
 
+#[cfg(not(windows))]
+mod stub;
 #[cfg(windows)]
 mod win;
 
+pub(crate) use brightness::{brightness_adjustments, identify_pulse};
+pub(crate) use coordinates::convert;
+pub(crate) use ddc::{
+    parse_input_sources, VCP_BLACK_LEVEL, VCP_CONTRAST, VCP_INPUT_SOURCE, VCP_USAGE_HOURS,
+};
+pub(crate) use display_config::DisplayConfigChange;
+#[cfg(windows)]
+pub(crate) use display_info::indirect_driver;
+pub(crate) use events::{
+    diff_displays, reconcile_display_order, same_mode, DisplayState, Subscribers,
+};
+#[cfg(windows)]
+pub(crate) use layout::find_overlap;
+pub(crate) use layout::{
+    analyze_layout, calc_adjacency_info, map_displays, neighbors, shared_edges,
+};
+pub(crate) use matcher::find_display;
+pub(crate) use polling_watcher::DisplayMetrics;
+#[cfg(windows)]
+pub(crate) use polling_watcher::MonitorMetrics;
+pub(crate) use render::render_layout_ascii;
+#[cfg(feature = "svg")]
+pub(crate) use render::render_layout_svg;
+pub(crate) use stitch::stitch_layout;
+
 #[macro_use]
 extern crate bitflags;
 
+pub use backend::{
+    BackendCapabilities, DisplayBackend, EnumerateOptions, EnumeratedDisplayInfo, NativeBackend,
+};
+pub use brightness::{AdaptiveBrightness, BrightnessAdjustment, BrightnessSetting};
+pub use cached_displays::CachedDisplays;
+pub use colorimetry::{Chromaticity, Colorimetry};
+pub use coordinates::{CoordinateSpace, Coordinates, DpiAwareness};
+pub use cursor::CursorClipGuard;
+pub use ddc::{InputSource, PhysicalMonitor, VcpValue};
+pub use display_config::{
+    DisplayConfigSnapshot, DisplayConfigTransaction, PendingDisplayConfig, Projection,
+};
+pub use display_index::{DisplayError, DisplayIndex, StaleIndex};
+#[cfg(not(feature = "no-modes"))]
 pub use display_info::{
-    closest_dimensions, ClosestDimensionsFlags, ConnectionType, DisplayInfo, DisplayMode,
-    DisplayRects, UpscaleMode,
+    closest_dimensions, closest_dimensions_by, ClosestDimensionsFlags, DistanceMetric,
+    IntegerScaledMode, ModeValidation,
+};
+pub use display_info::{
+    AssetInfo, ConnectionType, DensityClass, DisplayId, DisplayInfo, DisplayMode, DisplayName,
+    DisplayNames, DisplayRects, DisplaySource, DpiOptions, HdrCaps, HdrMetrics, HdrTier,
+    Orientation, SignalTiming, UpscaleMode,
+};
+pub use displays::{
+    enumerate, primary_display, DisplayInfoFull, DisplayInfoFullRef, DisplayInfoIter,
+    DisplayRectsFull, DisplayRef, Displays, LidState, MonitorHardware,
 };
-pub use displays::{AdjacencyInfo, DisplayInfoFull, DisplayInfoIter, Displays};
-pub use rectangle::{ClipRectFlags, Dimensions, Position, Rectangle};
+pub use error::Error;
+pub use events::{DisplayEvent, DisplayEventKind, DisplaySubscription};
+pub use gamma::{GammaPreset, GammaTransition};
+#[cfg(feature = "ipc")]
+pub use ipc::{SnapshotPublisher, SnapshotReader};
+pub use layout::{AdjacencyInfo, EdgeSide, LayoutIssue, LayoutReport, Neighbor, SharedEdge};
+pub use matcher::{
+    And, ByConnection, ByMinResolution, ByNameContains, DisplayMatcher, Or, Primary,
+};
+#[cfg(not(feature = "no-modes"))]
+pub use mode_query::{DisplayModeIter, ModeQuery};
+pub use night_light::NightLightState;
+pub use placement::{Anchor, DisplaySelector, Place};
+pub use platform_info::PlatformInfo;
+pub use polling_watcher::PollingWatcher;
+pub use profiles::{match_profile, score_profile, DisplayProfile, Profile, ProfileMatch};
+pub use rectangle::{
+    fit_dimensions, span_rects, ClipConflict, ClipRectFlags, Dimensions, FitMode, Margins,
+    Position, Rectangle, Strictness,
+};
+pub use region::Region;
+pub use snapshot::{DisplayChange, DisplayChangeKind, DisplaysSnapshot, SnapshotDiff};
+pub use stitch::{StitchLayout, StitchRect};
+pub use units::{Mm, Pt, Px};
+pub use window_placement::{RestorePolicy, WindowPlacement, WindowState};
 
 #[cfg(windows)]
-pub use win::DisplayInfoWin as DisplayInfoPlatform;
+pub use win::{
+    AdapterLuid, DisplayConfigPath, DisplayInfoWin, DisplayStateWin, PhysicalMonitorsWin,
+};
+
+#[cfg(all(windows, feature = "raw-win"))]
+pub use win::RawDisplayConfig;
+
+#[cfg(all(windows, feature = "wmi"))]
+pub use win::{VideoInputType, WmiMonitorInfo};
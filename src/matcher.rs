@@ -0,0 +1,198 @@
+use crate::{ConnectionType, Dimensions, DisplayInfo};
+
+/// Predicate over the [`display info`], used to [`find`] the displays by their properties
+/// (e.g. as specified in the config files) rather than by their (unstable) display indices.
+///
+/// Matchers may be combined with [`and`] / [`or`].
+/// Closures taking the [`display info`] and returning a `bool` are matchers, too.
+///
+/// [`display info`]: struct.DisplayInfo.html
+/// [`find`]: struct.Displays.html#method.find
+/// [`and`]: #method.and
+/// [`or`]: #method.or
+pub trait DisplayMatcher {
+    /// Returns `true` if the `display` matches.
+    fn matches(&self, display: &DisplayInfo) -> bool;
+
+    /// Returns the matcher which matches the displays matched by both this and the `other` matcher.
+    fn and<M: DisplayMatcher>(self, other: M) -> And<Self, M>
+    where
+        Self: Sized,
+    {
+        And(self, other)
+    }
+
+    /// Returns the matcher which matches the displays matched by either this or the `other` matcher.
+    fn or<M: DisplayMatcher>(self, other: M) -> Or<Self, M>
+    where
+        Self: Sized,
+    {
+        Or(self, other)
+    }
+}
+
+impl<F: Fn(&DisplayInfo) -> bool> DisplayMatcher for F {
+    fn matches(&self, display: &DisplayInfo) -> bool {
+        self(display)
+    }
+}
+
+/// Matches the displays whose name contains the (case-insensitive) pattern.
+///
+/// The pattern may contain `*` wildcards, matching any (possibly empty) substring
+/// (e.g. `DELL*DP` matches `Dell U2720DP`, but not `DP DELL`).
+/// The displays with no name never match.
+#[derive(Clone, Debug)]
+pub struct ByNameContains {
+    /// Lowercase non-empty pattern parts between the wildcards.
+    parts: Vec<String>,
+}
+
+impl ByNameContains {
+    pub fn new(pattern: &str) -> Self {
+        Self {
+            parts: pattern
+                .split('*')
+                .filter(|part| !part.is_empty())
+                .map(str::to_lowercase)
+                .collect(),
+        }
+    }
+}
+
+impl DisplayMatcher for ByNameContains {
+    fn matches(&self, display: &DisplayInfo) -> bool {
+        let name = match display.name.as_ref() {
+            Some(name) => name.as_str().to_lowercase(),
+            None => return false,
+        };
+
+        let mut rest = name.as_str();
+
+        for part in self.parts.iter() {
+            match rest.find(part.as_str()) {
+                Some(index) => rest = &rest[index + part.len()..],
+                None => return false,
+            }
+        }
+
+        true
+    }
+}
+
+/// Matches the displays with the connection type.
+#[derive(Clone, Copy, Debug)]
+pub struct ByConnection(pub ConnectionType);
+
+impl DisplayMatcher for ByConnection {
+    fn matches(&self, display: &DisplayInfo) -> bool {
+        display.connection == self.0
+    }
+}
+
+/// Matches the displays whose current display mode is at least this wide and this high.
+#[derive(Clone, Copy, Debug)]
+pub struct ByMinResolution(pub Dimensions);
+
+impl DisplayMatcher for ByMinResolution {
+    fn matches(&self, display: &DisplayInfo) -> bool {
+        let dimensions = display.current_mode.dimensions;
+
+        dimensions.width >= self.0.width && dimensions.height >= self.0.height
+    }
+}
+
+/// Matches the system's primary display.
+#[derive(Clone, Copy, Debug)]
+pub struct Primary;
+
+impl DisplayMatcher for Primary {
+    fn matches(&self, display: &DisplayInfo) -> bool {
+        display.is_primary
+    }
+}
+
+/// Matches the displays matched by both matchers (see [`DisplayMatcher::and`]).
+///
+/// [`DisplayMatcher::and`]: trait.DisplayMatcher.html#method.and
+#[derive(Clone, Copy, Debug)]
+pub struct And<L, R>(pub L, pub R);
+
+impl<L: DisplayMatcher, R: DisplayMatcher> DisplayMatcher for And<L, R> {
+    fn matches(&self, display: &DisplayInfo) -> bool {
+        self.0.matches(display) && self.1.matches(display)
+    }
+}
+
+/// Matches the displays matched by either matcher (see [`DisplayMatcher::or`]).
+///
+/// [`DisplayMatcher::or`]: trait.DisplayMatcher.html#method.or
+#[derive(Clone, Copy, Debug)]
+pub struct Or<L, R>(pub L, pub R);
+
+impl<L: DisplayMatcher, R: DisplayMatcher> DisplayMatcher for Or<L, R> {
+    fn matches(&self, display: &DisplayInfo) -> bool {
+        self.0.matches(display) || self.1.matches(display)
+    }
+}
+
+/// Returns the index of the first of the `displays` matched by the `matcher`, if any.
+pub(crate) fn find_display<'d, I, M>(displays: I, matcher: &M) -> Option<u32>
+where
+    I: Iterator<Item = &'d DisplayInfo>,
+    M: DisplayMatcher + ?Sized,
+{
+    displays
+        .enumerate()
+        .find(|(_, display)| matcher.matches(display))
+        .map(|(display_index, _)| display_index as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::TestDisplay;
+
+    #[test]
+    fn find_display() {
+        let displays = [
+            TestDisplay::new(0, 0, 1920, 1200)
+                .name("Built-in Display")
+                .connection(ConnectionType::Internal)
+                .build(),
+            TestDisplay::new(1920, 0, 2560, 1440)
+                .name("DELL U2720Q")
+                .connection(ConnectionType::HDMI)
+                .build(),
+            TestDisplay::new(4480, 0, 3840, 2160)
+                .name("Dell U2720DP")
+                .connection(ConnectionType::DisplayPort)
+                .build(),
+            TestDisplay::new(8320, 0, 1024, 768).build(),
+        ];
+
+        let find = |matcher: &dyn DisplayMatcher| super::find_display(displays.iter(), matcher);
+
+        assert_eq!(find(&ByNameContains::new("DELL*DP")), Some(2));
+        assert_eq!(find(&ByNameContains::new("dell")), Some(1));
+        assert_eq!(find(&ByNameContains::new("*")), Some(0));
+        assert_eq!(find(&ByNameContains::new("DP*DELL")), None);
+        assert_eq!(find(&ByConnection(ConnectionType::DisplayPort)), Some(2));
+        assert_eq!(find(&ByMinResolution(Dimensions::new(2560, 1440))), Some(1));
+        assert_eq!(find(&Primary), Some(0));
+
+        assert_eq!(
+            find(&ByNameContains::new("dell").and(ByMinResolution(Dimensions::new(3840, 2160)))),
+            Some(2)
+        );
+        assert_eq!(
+            find(&ByConnection(ConnectionType::VGA).or(ByConnection(ConnectionType::HDMI))),
+            Some(1)
+        );
+        assert_eq!(find(&Primary.and(ByConnection(ConnectionType::HDMI))), None);
+        assert_eq!(
+            find(&|display: &DisplayInfo| display.current_mode.dimensions.width < 1280),
+            Some(3)
+        );
+    }
+}
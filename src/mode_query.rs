@@ -0,0 +1,235 @@
+use std::cmp::Ordering;
+
+use crate::{Dimensions, DisplayMode};
+
+/// Filters and sorts the display's supported [`display modes`].
+///
+/// Returned by [`DisplayInfo::modes`].
+///
+/// [`display modes`]: struct.DisplayMode.html
+/// [`DisplayInfo::modes`]: struct.DisplayInfo.html#method.modes
+#[derive(Clone, Debug)]
+pub struct ModeQuery<'m> {
+    display_modes: &'m [DisplayMode],
+    min_refresh_rate: Option<u32>,
+    min_dimensions: Option<Dimensions>,
+    aspect_ratio: Option<(u32, u32)>,
+}
+
+impl<'m> ModeQuery<'m> {
+    /// Creates a new query over the `display_modes`, initially matching all of them.
+    pub fn new(display_modes: &'m [DisplayMode]) -> Self {
+        Self {
+            display_modes,
+            min_refresh_rate: None,
+            min_dimensions: None,
+            aspect_ratio: None,
+        }
+    }
+
+    /// Only match the display modes with refresh rate (rounded to the nearest integer Hz value)
+    /// greater or equal to `refresh_rate`,
+    /// so that e.g. `119.88Hz` display modes match `min_refresh(120)`.
+    pub fn min_refresh(mut self, refresh_rate: u32) -> Self {
+        self.min_refresh_rate.replace(refresh_rate);
+        self
+    }
+
+    /// Only match the display modes at least `width` pixels wide and `height` pixels tall.
+    pub fn min_resolution(mut self, width: u32, height: u32) -> Self {
+        self.min_dimensions.replace(Dimensions::new(width, height));
+        self
+    }
+
+    /// Only match the display modes with `width` to `height` aspect ratio (e.g. `16`, `9`).
+    ///
+    /// Aspect ratios are compared with a 1% tolerance, so that e.g. `1366x768` display modes match `aspect(16, 9)`.
+    pub fn aspect(mut self, width: u32, height: u32) -> Self {
+        self.aspect_ratio.replace((width, height));
+        self
+    }
+
+    /// Returns the iterator over the matching display modes,
+    /// sorted by dimensions (by area), then by refresh rate, largest / highest first.
+    pub fn iter(&self) -> DisplayModeIter<'m> {
+        let mut display_modes: Vec<_> = self
+            .display_modes
+            .iter()
+            .filter(|mode| self.matches(mode))
+            .collect();
+
+        display_modes.sort_by(|l, r| {
+            r.dimensions
+                .area()
+                .cmp(&l.dimensions.area())
+                .then_with(|| cmp_refresh_rate(r, l))
+        });
+
+        DisplayModeIter(display_modes.into_iter())
+    }
+
+    fn matches(&self, mode: &DisplayMode) -> bool {
+        if let Some(min_refresh_rate) = self.min_refresh_rate {
+            if refresh_rate_rounded(mode) < min_refresh_rate {
+                return false;
+            }
+        }
+
+        if let Some(min_dimensions) = self.min_dimensions {
+            if (mode.dimensions.width < min_dimensions.width)
+                || (mode.dimensions.height < min_dimensions.height)
+            {
+                return false;
+            }
+        }
+
+        if let Some((width, height)) = self.aspect_ratio {
+            // Compare `mode_width / mode_height` to `width / height`.
+            let l = mode.dimensions.width as u64 * height as u64;
+            let r = mode.dimensions.height as u64 * width as u64;
+            if l.abs_diff(r) * 100 > r {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+impl<'m> IntoIterator for ModeQuery<'m> {
+    type Item = &'m DisplayMode;
+    type IntoIter = DisplayModeIter<'m>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Returns the [`display modes`](struct.DisplayMode.html) matched by the [`query`](struct.ModeQuery.html).
+pub struct DisplayModeIter<'m>(std::vec::IntoIter<&'m DisplayMode>);
+
+impl<'m> Iterator for DisplayModeIter<'m> {
+    type Item = &'m DisplayMode;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+}
+
+fn refresh_rate_rounded(mode: &DisplayMode) -> u32 {
+    mode.precise_refresh_rate().round() as u32
+}
+
+/// Compares the precise refresh rates of the display modes.
+fn cmp_refresh_rate(l: &DisplayMode, r: &DisplayMode) -> Ordering {
+    (l.refresh_rate_num as u64 * r.refresh_rate_denom as u64)
+        .cmp(&(r.refresh_rate_num as u64 * l.refresh_rate_denom as u64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::UpscaleMode;
+
+    fn mode(
+        width: u32,
+        height: u32,
+        refresh_rate_num: u32,
+        refresh_rate_denom: u32,
+    ) -> DisplayMode {
+        DisplayMode {
+            dimensions: Dimensions::new(width, height),
+            refresh_rate: refresh_rate_num / refresh_rate_denom,
+            refresh_rate_num,
+            refresh_rate_denom,
+            upscale_mode: UpscaleMode::Unknown,
+        }
+    }
+
+    fn dimensions_and_refresh(query: ModeQuery) -> Vec<(u32, u32, u32)> {
+        query
+            .iter()
+            .map(|mode| {
+                (
+                    mode.dimensions.width,
+                    mode.dimensions.height,
+                    refresh_rate_rounded(mode),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn mode_query() {
+        let modes = [
+            mode(1280, 1024, 60, 1),
+            mode(1920, 1080, 60, 1),
+            mode(1920, 1080, 120_000, 1001),
+            mode(1920, 1080, 144, 1),
+            mode(2560, 1440, 60, 1),
+            mode(1366, 768, 60, 1),
+            mode(800, 600, 60, 1),
+        ];
+
+        assert_eq!(
+            dimensions_and_refresh(ModeQuery::new(&modes)),
+            vec![
+                (2560, 1440, 60),
+                (1920, 1080, 144),
+                (1920, 1080, 120),
+                (1920, 1080, 60),
+                (1280, 1024, 60),
+                (1366, 768, 60),
+                (800, 600, 60),
+            ]
+        );
+
+        assert_eq!(
+            dimensions_and_refresh(ModeQuery::new(&modes).min_refresh(120)),
+            vec![(1920, 1080, 144), (1920, 1080, 120)]
+        );
+
+        assert_eq!(
+            dimensions_and_refresh(ModeQuery::new(&modes).min_resolution(1920, 1080)),
+            vec![
+                (2560, 1440, 60),
+                (1920, 1080, 144),
+                (1920, 1080, 120),
+                (1920, 1080, 60),
+            ]
+        );
+
+        assert_eq!(
+            dimensions_and_refresh(ModeQuery::new(&modes).aspect(16, 9)),
+            vec![
+                (2560, 1440, 60),
+                (1920, 1080, 144),
+                (1920, 1080, 120),
+                (1920, 1080, 60),
+                (1366, 768, 60),
+            ]
+        );
+
+        assert_eq!(
+            dimensions_and_refresh(ModeQuery::new(&modes).aspect(4, 3)),
+            vec![(800, 600, 60)]
+        );
+
+        assert_eq!(
+            dimensions_and_refresh(
+                ModeQuery::new(&modes)
+                    .min_refresh(60)
+                    .min_resolution(1280, 720)
+                    .aspect(16, 9)
+                    .min_refresh(100)
+            ),
+            vec![(1920, 1080, 144), (1920, 1080, 120)]
+        );
+
+        assert!(ModeQuery::new(&modes)
+            .min_refresh(240)
+            .iter()
+            .next()
+            .is_none());
+    }
+}
@@ -0,0 +1,157 @@
+// The parsers are only used by the platform code.
+#![cfg_attr(not(windows), allow(dead_code))]
+
+/// Color temperature of the display with no blue light reduction, in Kelvin.
+const NEUTRAL_COLOR_TEMPERATURE: u32 = 6500;
+/// Color temperature of the display with the maximum blue light reduction, in Kelvin.
+const MIN_COLOR_TEMPERATURE: u32 = 1200;
+
+/// Offset of the state byte in the blue light reduction state data.
+const STATE_OFFSET: usize = 18;
+/// Value of the state byte when the blue light reduction is active.
+const STATE_ACTIVE: u8 = 0x15;
+/// Tag which precedes the (night time) color temperature in the blue light reduction settings data.
+const COLOR_TEMPERATURE_TAG: [u8; 2] = [0xcf, 0x28];
+
+/// State of the system's blue light reduction (Night Light on Windows),
+/// as returned by [`Displays::night_light`].
+///
+/// When active, the colors of all displays (and of the screen captures on some systems) are shifted towards red.
+///
+/// [`Displays::night_light`]: struct.Displays.html#method.night_light
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct NightLightState {
+    /// Whether the blue light reduction is currently active
+    /// (either turned on manually, or by the schedule).
+    pub is_active: bool,
+    /// Color temperature of the displays while the blue light reduction is active, in Kelvin,
+    /// if known.
+    pub color_temperature: Option<u32>,
+}
+
+impl NightLightState {
+    /// Returns the blue light reduction strength in `[0.0 .. 1.0]` range
+    /// (`0.0` is the neutral color temperature, `1.0` is the warmest one),
+    /// as displayed by the OS UI, if the color temperature is known.
+    ///
+    /// NOTE - returns the configured strength even if the blue light reduction is not [`active`](#structfield.is_active).
+    pub fn strength(&self) -> Option<f32> {
+        self.color_temperature.map(|color_temperature| {
+            let color_temperature =
+                color_temperature.clamp(MIN_COLOR_TEMPERATURE, NEUTRAL_COLOR_TEMPERATURE);
+
+            (NEUTRAL_COLOR_TEMPERATURE - color_temperature) as f32
+                / (NEUTRAL_COLOR_TEMPERATURE - MIN_COLOR_TEMPERATURE) as f32
+        })
+    }
+}
+
+/// Parses the blue light reduction `state` and (optional) `settings` data, as stored by the OS.
+///
+/// Returns `None` if the state data is malformed.
+pub(crate) fn parse_night_light(state: &[u8], settings: Option<&[u8]>) -> Option<NightLightState> {
+    let is_active = *state.get(STATE_OFFSET)? == STATE_ACTIVE;
+
+    Some(NightLightState {
+        is_active,
+        color_temperature: settings.and_then(parse_color_temperature),
+    })
+}
+
+/// Parses the color temperature from the blue light reduction `settings` data,
+/// stored as a (zigzag-encoded) variable length integer after the color temperature tag.
+fn parse_color_temperature(settings: &[u8]) -> Option<u32> {
+    let offset = settings
+        .windows(COLOR_TEMPERATURE_TAG.len())
+        .position(|window| window == COLOR_TEMPERATURE_TAG)?
+        + COLOR_TEMPERATURE_TAG.len();
+
+    let mut value = 0u64;
+
+    for (index, &byte) in settings[offset..].iter().enumerate().take(5) {
+        value |= ((byte & 0x7f) as u64) << (7 * index);
+
+        if byte & 0x80 == 0 {
+            let color_temperature = (value >> 1) as u32;
+
+            return if color_temperature > 0 {
+                Some(color_temperature)
+            } else {
+                None
+            };
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the blue light reduction state data with the state byte set to `state`.
+    fn state(state: u8) -> Vec<u8> {
+        let mut data = vec![
+            0x43, 0x42, 0x01, 0x00, 0x0a, 0x02, 0x01, 0x00, 0x2a, 0x06, 0x9c, 0xd5, 0xa3, 0xa4,
+            0x06, 0x2a, 0x2b, 0x0e, 0x13, 0x43, 0x42, 0x01, 0x00, 0xd0, 0x0a, 0x02, 0xc6, 0x14,
+            0x00, 0x00, 0x00, 0x00,
+        ];
+        data[STATE_OFFSET] = state;
+        data
+    }
+
+    /// Returns the blue light reduction settings data with the color temperature set to `color_temperature`.
+    fn settings(color_temperature: u32) -> Vec<u8> {
+        let mut data = vec![
+            0x43, 0x42, 0x01, 0x00, 0x0a, 0x02, 0x01, 0x00, 0x2a, 0x06, 0x9c, 0xd5, 0xa3, 0xa4,
+            0x06, 0x2a, 0x2b, 0x0e, 0x1d, 0x43, 0x42, 0x01, 0x00, 0xca, 0x14, 0x0e, 0x15, 0x00,
+        ];
+        data.extend_from_slice(&COLOR_TEMPERATURE_TAG);
+
+        let mut value = color_temperature << 1;
+
+        loop {
+            if value < 0x80 {
+                data.push(value as u8);
+                break;
+            }
+
+            data.push((value & 0x7f) as u8 | 0x80);
+            value >>= 7;
+        }
+
+        data.extend_from_slice(&[0xca, 0x32, 0x00, 0x00, 0x00, 0x00]);
+        data
+    }
+
+    #[test]
+    fn parse_night_light() {
+        let active = super::parse_night_light(&state(STATE_ACTIVE), Some(&settings(3400))).unwrap();
+        assert!(active.is_active);
+        assert_eq!(active.color_temperature, Some(3400));
+        assert!((active.strength().unwrap() - 3100.0 / 5300.0).abs() < 1e-6);
+
+        let inactive = super::parse_night_light(&state(0x13), Some(&settings(6500))).unwrap();
+        assert!(!inactive.is_active);
+        assert_eq!(inactive.strength(), Some(0.0));
+
+        let warmest = super::parse_night_light(&state(0x13), Some(&settings(1200))).unwrap();
+        assert_eq!(warmest.strength(), Some(1.0));
+
+        // No / malformed settings.
+        let unknown = super::parse_night_light(&state(STATE_ACTIVE), None).unwrap();
+        assert_eq!(unknown.color_temperature, None);
+        assert_eq!(unknown.strength(), None);
+
+        let truncated = &settings(3400)[..30];
+        assert_eq!(
+            super::parse_night_light(&state(STATE_ACTIVE), Some(truncated))
+                .unwrap()
+                .color_temperature,
+            None
+        );
+
+        // Malformed state.
+        assert_eq!(super::parse_night_light(&[0x43, 0x42], None), None);
+    }
+}
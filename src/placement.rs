@@ -0,0 +1,309 @@
+use crate::{
+    ByNameContains, ClipRectFlags, Dimensions, DisplayId, DisplayInfo, DisplayMatcher, Displays,
+    DisplaysSnapshot, Position, Rectangle,
+};
+
+/// Selects the display a [`placement rule`] places the window on.
+///
+/// [`placement rule`]: struct.Place.html
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DisplaySelector {
+    /// The system's primary display.
+    Primary,
+    /// The display with the index.
+    Index(u32),
+    /// The display with the stable identifier.
+    Id(DisplayId),
+    /// The first display whose name contains the (case-insensitive) pattern,
+    /// which may contain `*` wildcards (see [`ByNameContains`]).
+    ///
+    /// [`ByNameContains`]: struct.ByNameContains.html
+    Name(String),
+    /// The display which contains the point in the virtual desktop (e.g. the cursor position).
+    ContainingPoint(Position),
+}
+
+impl DisplaySelector {
+    /// Returns the first of the `displays` selected by the selector, if any.
+    fn select<'d>(
+        &self,
+        mut displays: impl Iterator<Item = &'d DisplayInfo>,
+    ) -> Option<&'d DisplayInfo> {
+        use DisplaySelector::*;
+
+        match self {
+            Primary => displays.find(|display| display.is_primary),
+            Index(index) => displays.nth(*index as usize),
+            Id(id) => displays.find(|display| display.id == *id),
+            Name(pattern) => {
+                let matcher = ByNameContains::new(pattern);
+                displays.find(|display| matcher.matches(display))
+            }
+            ContainingPoint(point) => {
+                displays.find(|display| display.rects.virtual_rect.contains_point(*point))
+            }
+        }
+    }
+}
+
+/// Which point of the display's rectangle a [`placement rule`] aligns the window's rectangle to.
+///
+/// [`placement rule`]: struct.Place.html
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Anchor {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    #[default]
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+impl Anchor {
+    /// Returns the anchor's horizontal and vertical alignment,
+    /// `0` for the left / top side, `1` for the center and `2` for the right / bottom side.
+    fn alignment(self) -> (i64, i64) {
+        use Anchor::*;
+
+        match self {
+            TopLeft => (0, 0),
+            Top => (1, 0),
+            TopRight => (2, 0),
+            Left => (0, 1),
+            Center => (1, 1),
+            Right => (2, 1),
+            BottomLeft => (0, 2),
+            Bottom => (1, 2),
+            BottomRight => (2, 2),
+        }
+    }
+}
+
+/// Declarative window placement rule (e.g. as specified in the config files),
+/// which is [`resolved`] to the window's rectangle in the virtual desktop against the enumerated displays.
+///
+/// ```
+/// # use minidisplay::{Anchor, DisplaySelector, Place};
+/// // 1280x720 window at the center of the primary display's work area, moved up by 40 pixels.
+/// let place = Place::on(DisplaySelector::Primary)
+///     .size(1280, 720)
+///     .anchor(Anchor::Center)
+///     .offset(0, -40);
+/// ```
+///
+/// The window's rectangle is placed within the selected display's work rectangle (or its full rectangle,
+/// see [`cover_taskbar`]), then moved / shrunk to fit within it (see [`Rectangle::clip`]).
+///
+/// Enable the `serde` feature to (de)serialize the rules.
+///
+/// [`resolved`]: #method.resolve
+/// [`cover_taskbar`]: #method.cover_taskbar
+/// [`Rectangle::clip`]: struct.Rectangle.html#method.clip
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Place {
+    display: DisplaySelector,
+    /// `None` to fill the display's rectangle.
+    #[cfg_attr(feature = "serde", serde(default))]
+    size: Option<Dimensions>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    anchor: Anchor,
+    #[cfg_attr(feature = "serde", serde(default))]
+    offset: Position,
+    #[cfg_attr(feature = "serde", serde(default))]
+    cover_taskbar: bool,
+}
+
+impl Place {
+    /// Creates the placement rule which fills the work rectangle of the `display` selected by the selector.
+    pub fn on(display: DisplaySelector) -> Self {
+        Self {
+            display,
+            size: None,
+            anchor: Anchor::default(),
+            offset: Position::default(),
+            cover_taskbar: false,
+        }
+    }
+
+    /// Sets the window's dimensions, in physical pixels.
+    pub fn size(mut self, width: u32, height: u32) -> Self {
+        self.size.replace(Dimensions::new(width, height));
+        self
+    }
+
+    /// Sets the point of the display's rectangle to align the window to ([`Anchor::Center`] by default).
+    ///
+    /// [`Anchor::Center`]: enum.Anchor.html#variant.Center
+    pub fn anchor(mut self, anchor: Anchor) -> Self {
+        self.anchor = anchor;
+        self
+    }
+
+    /// Sets the window's offset from its anchored position, in physical pixels.
+    pub fn offset(mut self, x: i32, y: i32) -> Self {
+        self.offset = Position::new(x, y);
+        self
+    }
+
+    /// Places the window within the display's full rectangle, rather than its work rectangle
+    /// (i.e. allows it to cover the taskbar).
+    pub fn cover_taskbar(mut self) -> Self {
+        self.cover_taskbar = true;
+        self
+    }
+
+    /// Resolves the rule to the window's rectangle in the virtual desktop against the [`enumerated displays`].
+    ///
+    /// Returns an error if no display is selected,
+    /// or if the selected display has no rectangle (i.e. was enumerated from a services session).
+    ///
+    /// [`enumerated displays`]: struct.Displays.html
+    pub fn resolve(&self, displays: &Displays) -> Result<Rectangle, ()> {
+        displays.with_snapshot(|snapshot| self.resolve_snapshot(snapshot))
+    }
+
+    /// Same as [`resolve`], but against the displays in the [`snapshot`].
+    ///
+    /// [`resolve`]: #method.resolve
+    /// [`snapshot`]: struct.DisplaysSnapshot.html
+    pub fn resolve_snapshot(&self, snapshot: &DisplaysSnapshot) -> Result<Rectangle, ()> {
+        self.resolve_impl(snapshot.displays().iter())
+    }
+
+    fn resolve_impl<'d>(
+        &self,
+        displays: impl Iterator<Item = &'d DisplayInfo>,
+    ) -> Result<Rectangle, ()> {
+        let display = self.display.select(displays).ok_or(())?;
+
+        let bounds = if self.cover_taskbar {
+            display.rects.virtual_rect
+        } else {
+            display.rects.work_rect
+        };
+
+        let dimensions = self.size.unwrap_or(bounds.dimensions);
+
+        // Use the exact `i64` coordinates - the window may lie outside of the `i32` range before it's clipped.
+        let (horizontal, vertical) = self.anchor.alignment();
+        let left = bounds.left() as i64
+            + (bounds.width() as i64 - dimensions.width as i64) * horizontal / 2
+            + self.offset.left as i64;
+        let top = bounds.top() as i64
+            + (bounds.height() as i64 - dimensions.height as i64) * vertical / 2
+            + self.offset.top as i64;
+
+        let saturate_i32 = |value: i64| value.clamp(i32::MIN as i64, i32::MAX as i64) as i32;
+
+        Rectangle::new(
+            Position::new(saturate_i32(left), saturate_i32(top)),
+            dimensions,
+        )
+        .clip(&bounds, ClipRectFlags::KEEP_NONE)
+        .ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::TestDisplay;
+
+    fn rect(left: i32, top: i32, width: u32, height: u32) -> Rectangle {
+        Rectangle::new(Position::new(left, top), Dimensions::new(width, height))
+    }
+
+    #[test]
+    fn place() {
+        let snapshot = DisplaysSnapshot::new(vec![
+            TestDisplay::new(0, 0, 1920, 1080)
+                .id(1)
+                .name("DELL U2720Q")
+                .taskbar(40)
+                .build(),
+            TestDisplay::new(1920, -200, 2560, 1440)
+                .id(2)
+                .name("LG 27GL850")
+                .taskbar(40)
+                .build(),
+        ]);
+
+        let resolve = |place: Place| place.resolve_snapshot(&snapshot);
+
+        // Fills the work rectangle by default.
+        assert_eq!(
+            resolve(Place::on(DisplaySelector::Primary)),
+            Ok(rect(0, 0, 1920, 1040))
+        );
+        assert_eq!(
+            resolve(Place::on(DisplaySelector::Primary).cover_taskbar()),
+            Ok(rect(0, 0, 1920, 1080))
+        );
+
+        assert_eq!(
+            resolve(
+                Place::on(DisplaySelector::Primary)
+                    .size(1280, 720)
+                    .anchor(Anchor::Center)
+                    .offset(0, -40)
+            ),
+            Ok(rect(320, 120, 1280, 720))
+        );
+        assert_eq!(
+            resolve(
+                Place::on(DisplaySelector::Name("lg*850".to_string()))
+                    .size(800, 600)
+                    .anchor(Anchor::BottomRight)
+            ),
+            Ok(rect(3680, 600, 800, 600))
+        );
+        assert_eq!(
+            resolve(
+                Place::on(DisplaySelector::ContainingPoint(Position::new(2000, 0)))
+                    .size(800, 600)
+                    .anchor(Anchor::TopLeft)
+                    .offset(10, 10)
+            ),
+            Ok(rect(1930, -190, 800, 600))
+        );
+        assert_eq!(
+            resolve(Place::on(DisplaySelector::Id(snapshot.displays()[1].id)).anchor(Anchor::Left)),
+            Ok(rect(1920, -200, 2560, 1400))
+        );
+
+        // Moved / shrunk to fit within the display.
+        assert_eq!(
+            resolve(
+                Place::on(DisplaySelector::Index(0))
+                    .size(800, 600)
+                    .anchor(Anchor::TopRight)
+                    .offset(100, -100)
+            ),
+            Ok(rect(1120, 0, 800, 600))
+        );
+        assert_eq!(
+            resolve(Place::on(DisplaySelector::Index(0)).size(4000, 600)),
+            Ok(rect(0, 220, 1920, 600))
+        );
+
+        // No display selected.
+        assert_eq!(resolve(Place::on(DisplaySelector::Index(2))), Err(()));
+        assert_eq!(
+            resolve(Place::on(DisplaySelector::Name("ASUS".to_string()))),
+            Err(())
+        );
+        assert_eq!(
+            resolve(Place::on(DisplaySelector::ContainingPoint(Position::new(
+                -1, 0
+            )))),
+            Err(())
+        );
+    }
+}
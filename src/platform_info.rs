@@ -0,0 +1,56 @@
+#[cfg(windows)]
+use crate::DisplayInfoWin;
+
+#[cfg(not(windows))]
+use crate::stub::DisplayInfoStub as DisplayInfoPlatform;
+#[cfg(windows)]
+use crate::DisplayInfoWin as DisplayInfoPlatform;
+
+/// Platform-specific display info.
+///
+/// Present on all targets, so that the code naming it builds on all targets too;
+/// use the per-platform accessors (e.g. [`windows`]) to access the platform-specific info.
+///
+/// [`windows`]: #method.windows
+#[derive(Clone, Default, Debug)]
+#[non_exhaustive]
+#[allow(clippy::large_enum_variant)]
+pub enum PlatformInfo {
+    /// Windows-specific display info.
+    #[cfg(windows)]
+    Windows(DisplayInfoWin),
+    /// No platform-specific display info
+    /// (e.g. for the displays provided by a custom [`display backend`](trait.DisplayBackend.html)).
+    #[default]
+    None,
+}
+
+impl PlatformInfo {
+    /// Returns the Windows-specific display info, if any.
+    #[cfg(windows)]
+    pub fn windows(&self) -> Option<&DisplayInfoWin> {
+        match self {
+            PlatformInfo::Windows(info) => Some(info),
+            PlatformInfo::None => None,
+        }
+    }
+
+    /// Returns the current platform's display info, if any.
+    pub(crate) fn native(&self) -> Option<&DisplayInfoPlatform> {
+        #[cfg(windows)]
+        {
+            self.windows()
+        }
+        #[cfg(not(windows))]
+        match self {
+            PlatformInfo::None => None,
+        }
+    }
+}
+
+#[cfg(windows)]
+impl From<DisplayInfoWin> for PlatformInfo {
+    fn from(info: DisplayInfoWin) -> Self {
+        PlatformInfo::Windows(info)
+    }
+}
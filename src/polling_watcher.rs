@@ -0,0 +1,260 @@
+use std::sync::mpsc;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use crate::{DisplayBackend, DisplayEvent, Displays, DisplaysSnapshot, Error, Rectangle};
+
+#[cfg(windows)]
+use super::win::display_metrics_win as display_metrics_platform;
+
+#[cfg(not(windows))]
+use super::stub::display_metrics_stub as display_metrics_platform;
+
+/// Cheap to query system display metrics, compared before the (much more expensive) full display enumeration.
+#[derive(Clone, PartialEq, Debug)]
+pub(crate) struct DisplayMetrics {
+    /// Number of the display monitors.
+    pub(crate) num_monitors: u32,
+    /// Virtual screen rectangle, which contains all display monitors.
+    pub(crate) virtual_screen: Rectangle,
+    /// Per-monitor metrics, in the system's monitor enumeration order.
+    pub(crate) monitors: Vec<MonitorMetrics>,
+}
+
+/// Cheap to query display monitor metrics, part of the system display metrics.
+#[cfg_attr(not(windows), allow(dead_code))]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(crate) struct MonitorMetrics {
+    /// The monitor's rectangle w.r.t. the virtual screen.
+    pub(crate) virtual_rect: Rectangle,
+    /// The monitor's work rectangle w.r.t. the virtual screen.
+    pub(crate) work_rect: Rectangle,
+    /// The monitor's horizontal and vertical DPI scale, if known.
+    pub(crate) dpi_scale: Option<(f32, f32)>,
+}
+
+/// Watches the system's displays for changes without a window / message loop
+/// (e.g. for the command line tools and daemons), by re-enumerating them on a background thread
+/// and calling the provided callback with the [`display events`].
+///
+/// The displays are polled every `interval`. To keep the polling cheap, each poll first compares
+/// the number of the display monitors, the virtual screen rectangle, and each monitor's rectangles and DPI scale
+/// (`GetSystemMetrics()`, `EnumDisplayMonitors()` and `GetDpiForMonitor()` on Windows),
+/// and only re-enumerates the displays if they changed, or if the last full enumeration is older than `max_age`.
+///
+/// NOTE - the changes which affect none of the above (refresh rate / HDR / display name changes, etc.)
+/// are only detected on the full enumerations, i.e. at most `max_age` later.
+///
+/// The background thread is stopped when this is dropped.
+///
+/// [`display events`]: struct.DisplayEvent.html
+#[must_use = "the display change polling is stopped when the watcher is dropped"]
+pub struct PollingWatcher {
+    stop: Arc<(Mutex<bool>, Condvar)>,
+    /// Snapshot of the last display enumeration.
+    snapshot: Arc<Mutex<Arc<DisplaysSnapshot>>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl PollingWatcher {
+    /// Enumerates the displays and starts polling them for changes every `interval`
+    /// on a background thread, calling the `callback` with each detected [`display event`]
+    /// and the re-enumerated displays.
+    ///
+    /// The displays are fully re-enumerated at least every `max_age`.
+    /// Pass `Duration::MAX` to only re-enumerate the displays when the cheap pre-check detects a change.
+    ///
+    /// Returns an error if the initial display enumeration fails.
+    /// Failed re-enumerations are skipped and retried on next poll.
+    ///
+    /// [`display event`]: struct.DisplayEvent.html
+    pub fn new<F>(interval: Duration, max_age: Duration, callback: F) -> Result<Self, Error>
+    where
+        F: FnMut(&DisplayEvent, &Displays) + Send + 'static,
+    {
+        Self::start(interval, max_age, None, callback)
+    }
+
+    /// Enumerates the displays via provided [`display backend`] and starts polling them for changes.
+    ///
+    /// See [`new`].
+    ///
+    /// NOTE - the system display metrics say nothing about the backend's displays,
+    /// so the cheap pre-check is skipped and the displays are fully re-enumerated on each poll.
+    ///
+    /// [`display backend`]: trait.DisplayBackend.html
+    /// [`new`]: #method.new
+    pub fn with_backend<F>(
+        interval: Duration,
+        max_age: Duration,
+        backend: Arc<dyn DisplayBackend>,
+        callback: F,
+    ) -> Result<Self, Error>
+    where
+        F: FnMut(&DisplayEvent, &Displays) + Send + 'static,
+    {
+        Self::start(interval, max_age, Some(backend), callback)
+    }
+
+    fn start<F>(
+        interval: Duration,
+        max_age: Duration,
+        backend: Option<Arc<dyn DisplayBackend>>,
+        callback: F,
+    ) -> Result<Self, Error>
+    where
+        F: FnMut(&DisplayEvent, &Displays) + Send + 'static,
+    {
+        let stop = Arc::new((Mutex::new(false), Condvar::new()));
+        let snapshot = Arc::new(Mutex::new(Arc::new(DisplaysSnapshot::default())));
+        let (result_sender, result_receiver) = mpsc::channel();
+
+        let thread = {
+            let stop = stop.clone();
+            let snapshot = snapshot.clone();
+
+            std::thread::Builder::new()
+                .name("minidisplay polling watcher".to_string())
+                .spawn(move || {
+                    let (mut displays, display_metrics): (_, fn() -> _) = match backend {
+                        Some(backend) => (Displays::with_backend(backend), || None),
+                        None => (Displays::new(), display_metrics_platform),
+                    };
+
+                    let result = displays.enumerate_displays().map(|_| ());
+                    let failed = result.is_err();
+
+                    if !failed {
+                        *lock(&snapshot) = displays.snapshot();
+                    }
+
+                    let _ = result_sender.send(result);
+
+                    if !failed {
+                        poll(
+                            displays,
+                            display_metrics,
+                            interval,
+                            max_age,
+                            &stop,
+                            &snapshot,
+                            callback,
+                        );
+                    }
+                })
+                .map_err(|_| Error::EnumerationFailed)?
+        };
+
+        let result = result_receiver
+            .recv()
+            .unwrap_or(Err(Error::EnumerationFailed));
+
+        let watcher = Self {
+            stop,
+            snapshot,
+            thread: Some(thread),
+        };
+
+        result.map(|_| watcher)
+    }
+
+    /// Returns the [`snapshot`] of the displays' platform-independent info, as of the last display enumeration.
+    ///
+    /// [`snapshot`]: struct.DisplaysSnapshot.html
+    pub fn snapshot(&self) -> Arc<DisplaysSnapshot> {
+        lock(&self.snapshot).clone()
+    }
+
+    /// Calls `f` with the [`snapshot`] of the displays' platform-independent info, as of the last display enumeration,
+    /// and returns its result.
+    ///
+    /// All queries made through the snapshot within `f` see the same display enumeration,
+    /// even if the displays are re-enumerated by the background thread in the meantime.
+    ///
+    /// [`snapshot`]: struct.DisplaysSnapshot.html
+    pub fn with_snapshot<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&DisplaysSnapshot) -> R,
+    {
+        // Don't hold the lock while calling `f`.
+        f(&self.snapshot())
+    }
+}
+
+impl Drop for PollingWatcher {
+    fn drop(&mut self) {
+        let (stopped, condvar) = &*self.stop;
+
+        *lock(stopped) = true;
+        condvar.notify_all();
+
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Polls the `displays` for changes every `interval` until `stop`ped.
+/// The displays are only re-enumerated if the `display_metrics` changed (or are unknown),
+/// or the last enumeration is older than `max_age`.
+fn poll<F>(
+    mut displays: Displays,
+    display_metrics: fn() -> Option<DisplayMetrics>,
+    interval: Duration,
+    max_age: Duration,
+    stop: &(Mutex<bool>, Condvar),
+    snapshot: &Mutex<Arc<DisplaysSnapshot>>,
+    mut callback: F,
+) where
+    F: FnMut(&DisplayEvent, &Displays),
+{
+    let (stopped, condvar) = stop;
+
+    let mut metrics = display_metrics();
+    let mut enumerated_at = Instant::now();
+
+    loop {
+        {
+            let guard = lock(stopped);
+            let (guard, _) = condvar
+                .wait_timeout_while(guard, interval, |stopped| !*stopped)
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+            if *guard {
+                return;
+            }
+        }
+
+        let current_metrics = display_metrics();
+
+        // Always re-enumerate if the metrics are unknown.
+        if current_metrics.is_some()
+            && current_metrics == metrics
+            && enumerated_at.elapsed() < max_age
+        {
+            continue;
+        }
+
+        let previous = displays.snapshot();
+
+        if displays.enumerate_displays().is_err() {
+            continue;
+        }
+
+        metrics = current_metrics;
+        enumerated_at = Instant::now();
+
+        *lock(snapshot) = displays.snapshot();
+
+        for event in displays.snapshot().diff(&previous).events() {
+            callback(event, &displays);
+        }
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
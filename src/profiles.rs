@@ -0,0 +1,434 @@
+use crate::{
+    Dimensions, DisplayConfigSnapshot, DisplayId, DisplayMode, DisplayRef, Displays, Orientation,
+    Position,
+};
+
+/// A single display's configuration, as stored in a display configuration [`profile`].
+///
+/// [`profile`]: struct.Profile.html
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DisplayProfile {
+    /// Stable display identifier.
+    pub id: DisplayId,
+    /// Display's friendly name, if any. Informational only.
+    pub name: Option<String>,
+    /// The display's desktop dimensions.
+    pub dimensions: Dimensions,
+    /// Refresh rate numerator, such that numerator/denominator gives the refresh rate in Hz.
+    pub refresh_rate_num: u32,
+    /// Refresh rate denominator, such that numerator/denominator gives the refresh rate in Hz.
+    pub refresh_rate_denom: u32,
+    /// The display's position in the virtual desktop.
+    pub position: Position,
+    /// The display's orientation.
+    pub orientation: Orientation,
+    /// Whether the display is the system's primary display.
+    pub is_primary: bool,
+    /// The display's DPI scale value.
+    ///
+    /// NOTE - saved, but not applied.
+    pub dpi_scale: f32,
+}
+
+/// Named display configuration profile (e.g. "home docking station" vs "office"),
+/// which stores the whole display layout - per-display modes, positions, orientations, the primary display, DPI scaling -
+/// keyed by the [`stable display identifiers`], and may be re-applied when the same set of displays is detected.
+///
+/// Enable the `serde` feature to (de)serialize the profiles.
+///
+/// [`stable display identifiers`]: struct.DisplayId.html
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Profile {
+    /// Profile's user-facing name.
+    pub name: String,
+    /// Stored per-display configuration.
+    pub displays: Vec<DisplayProfile>,
+}
+
+impl Profile {
+    /// Captures the current display configuration of the [`enumerated displays`] as a profile with the provided `name`.
+    ///
+    /// [`enumerated displays`]: struct.Displays.html
+    pub fn capture<N: Into<String>>(name: N, displays: &Displays) -> Self {
+        Self {
+            name: name.into(),
+            displays: displays
+                .iter()
+                .map(|display| {
+                    let info = &display.info;
+
+                    DisplayProfile {
+                        id: info.id,
+                        name: info.name.as_ref().map(|name| name.as_str().to_owned()),
+                        dimensions: info.current_mode.dimensions,
+                        refresh_rate_num: info.current_mode.refresh_rate_num,
+                        refresh_rate_denom: info.current_mode.refresh_rate_denom,
+                        position: info.rects.virtual_rect.position,
+                        orientation: info.orientation,
+                        is_primary: info.is_primary,
+                        dpi_scale: info.dpi_scale,
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns `true` if the set of [`enumerated displays`] is exactly the set of displays stored in the profile.
+    ///
+    /// [`enumerated displays`]: struct.Displays.html
+    pub fn matches(&self, displays: &Displays) -> bool {
+        displays.num_displays() as usize == self.displays.len()
+            && self
+                .displays
+                .iter()
+                .all(|display| DisplayRef::new(display.id).resolve(displays).is_some())
+    }
+
+    /// Applies the stored display configuration to the [`enumerated displays`].
+    ///
+    /// Returns an error if the profile does not [`match`] the displays exactly, or if applying the configuration failed.
+    /// Otherwise returns the [`display configuration`] captured before the profile was applied.
+    ///
+    /// NOTE - the DPI scale is not applied.
+    ///
+    /// [`enumerated displays`]: struct.Displays.html
+    /// [`match`]: #method.matches
+    /// [`display configuration`]: struct.DisplayConfigSnapshot.html
+    pub fn apply(&self, displays: &Displays) -> Result<DisplayConfigSnapshot, ()> {
+        if !self.matches(displays) {
+            return Err(());
+        }
+
+        self.apply_matched(displays, &score_profile(self, displays))
+    }
+
+    /// Applies the stored display configuration to the [`enumerated displays`] matched to the profile's displays
+    /// as described by the (possibly partial) [`profile match`], as returned by [`match_profile`] / [`score_profile`].
+    ///
+    /// Stored displays not matched to any of the enumerated displays are skipped,
+    /// as are the enumerated displays not matched to any of the stored displays.
+    ///
+    /// Returns the [`display configuration`] captured before the profile was applied.
+    ///
+    /// NOTE - the DPI scale is not applied.
+    ///
+    /// [`enumerated displays`]: struct.Displays.html
+    /// [`profile match`]: struct.ProfileMatch.html
+    /// [`match_profile`]: fn.match_profile.html
+    /// [`score_profile`]: fn.score_profile.html
+    /// [`display configuration`]: struct.DisplayConfigSnapshot.html
+    pub fn apply_matched(
+        &self,
+        displays: &Displays,
+        profile_match: &ProfileMatch,
+    ) -> Result<DisplayConfigSnapshot, ()> {
+        if profile_match.display_indices.len() != self.displays.len() {
+            return Err(());
+        }
+
+        let mut transaction = displays.begin_config();
+
+        for (display, display_index) in self
+            .displays
+            .iter()
+            .zip(profile_match.display_indices.iter())
+            .filter_map(|(display, display_index)| {
+                display_index.map(|display_index| (display, display_index))
+            })
+        {
+            let display_index = displays.display_index(display_index).ok_or(())?;
+            let info = displays.display_info(display_index).map_err(|_| ())?;
+
+            let current_mode = &info.current_mode;
+
+            if current_mode.dimensions != display.dimensions
+                || current_mode.refresh_rate_num != display.refresh_rate_num
+                || current_mode.refresh_rate_denom != display.refresh_rate_denom
+            {
+                let refresh_rate = display
+                    .refresh_rate_num
+                    .checked_div(display.refresh_rate_denom)
+                    .unwrap_or(display.refresh_rate_num);
+
+                transaction
+                    .set_mode(
+                        display_index,
+                        DisplayMode {
+                            dimensions: display.dimensions,
+                            refresh_rate,
+                            refresh_rate_num: display.refresh_rate_num,
+                            refresh_rate_denom: display.refresh_rate_denom,
+                            upscale_mode: current_mode.upscale_mode,
+                        },
+                    )
+                    .map_err(|_| ())?;
+            }
+
+            if info.orientation != display.orientation {
+                transaction
+                    .set_orientation(display_index, display.orientation)
+                    .map_err(|_| ())?;
+            }
+
+            if info.rects.virtual_rect.position != display.position {
+                transaction
+                    .set_position(display_index, display.position)
+                    .map_err(|_| ())?;
+            }
+
+            if display.is_primary && !info.is_primary {
+                transaction.set_primary(display_index).map_err(|_| ())?;
+            }
+        }
+
+        if transaction.is_empty() {
+            DisplayConfigSnapshot::capture()
+        } else {
+            transaction.apply()
+        }
+    }
+}
+
+/// Describes how well a stored display configuration [`profile`] matches the currently [`enumerated displays`].
+///
+/// [`profile`]: struct.Profile.html
+/// [`enumerated displays`]: struct.Displays.html
+#[derive(Clone, PartialEq, Debug)]
+pub struct ProfileMatch {
+    /// Index of the matched profile in the slice of profiles passed to [`match_profile`].
+    /// `0` if returned by [`score_profile`].
+    ///
+    /// [`match_profile`]: fn.match_profile.html
+    /// [`score_profile`]: fn.score_profile.html
+    pub profile_index: usize,
+    /// Match score in `[0.0 .. 1.0]` range.
+    /// `1.0` means all stored displays are present, and no other displays are.
+    /// `0.0` means no stored displays are present.
+    pub score: f32,
+    /// For each of the profile's stored displays, the index of the enumerated display it was matched to, if any.
+    pub display_indices: Vec<Option<u32>>,
+}
+
+impl ProfileMatch {
+    /// Returns `true` if all of the profile's stored displays are present (by their stable identifiers),
+    /// and no other displays are.
+    pub fn is_exact(&self) -> bool {
+        self.score >= 1.0
+    }
+}
+
+/// Score of a stored display matched to an enumerated display by its stable identifier.
+const ID_MATCH_SCORE: f32 = 1.0;
+/// Score of a stored display matched to an enumerated display by its name
+/// (i.e. the same display model, but e.g. connected to a different port).
+const NAME_MATCH_SCORE: f32 = 0.5;
+
+/// Scores how well the stored display configuration `profile` matches the currently [`enumerated displays`].
+///
+/// The profile's stored displays are first matched to the enumerated displays by their stable identifiers,
+/// then the remaining ones - by their names (i.e. the same display model, but e.g. connected to a different port).
+/// The score is then normalized by the number of the stored or enumerated displays, whichever is larger,
+/// so the missing and extra displays both lower the score.
+///
+/// [`enumerated displays`]: struct.Displays.html
+pub fn score_profile(profile: &Profile, displays: &Displays) -> ProfileMatch {
+    displays.with_snapshot(|snapshot| {
+        let current: Vec<_> = snapshot
+            .displays()
+            .iter()
+            .map(|info| (info.id, info.name.as_ref().map(|name| name.as_str())))
+            .collect();
+
+        score_displays(&profile.displays, &current)
+    })
+}
+
+/// Finds the stored display configuration profile best matching the currently [`enumerated displays`],
+/// as [`scored`] by the stored displays present.
+///
+/// Returns `None` if none of the profiles' stored displays are present.
+///
+/// [`enumerated displays`]: struct.Displays.html
+/// [`scored`]: fn.score_profile.html
+pub fn match_profile(profiles: &[Profile], displays: &Displays) -> Option<ProfileMatch> {
+    best_match(
+        profiles
+            .iter()
+            .enumerate()
+            .map(|(profile_index, profile)| ProfileMatch {
+                profile_index,
+                ..score_profile(profile, displays)
+            }),
+    )
+}
+
+/// Returns the match with the highest score, if any is greater than `0.0`.
+/// Prefers the earlier matches if the scores are equal.
+fn best_match<I: Iterator<Item = ProfileMatch>>(matches: I) -> Option<ProfileMatch> {
+    matches
+        .filter(|profile_match| profile_match.score > 0.0)
+        .fold(
+            None,
+            |best: Option<ProfileMatch>, profile_match| match best {
+                Some(best) if best.score >= profile_match.score => Some(best),
+                _ => Some(profile_match),
+            },
+        )
+}
+
+/// Matches the `stored` displays to the `current` displays' (identifier, name) pairs.
+fn score_displays(
+    stored: &[DisplayProfile],
+    current: &[(DisplayId, Option<&str>)],
+) -> ProfileMatch {
+    let mut display_indices = vec![None; stored.len()];
+    let mut claimed = vec![false; current.len()];
+    let mut score = 0.0;
+
+    // Match by identifier first.
+    for (stored, display_index) in stored.iter().zip(display_indices.iter_mut()) {
+        if let Some(index) = current
+            .iter()
+            .zip(claimed.iter())
+            .position(|((id, _), &claimed)| !claimed && *id == stored.id)
+        {
+            claimed[index] = true;
+            display_index.replace(index as u32);
+            score += ID_MATCH_SCORE;
+        }
+    }
+
+    // Then by name.
+    for (stored, display_index) in stored.iter().zip(display_indices.iter_mut()) {
+        if display_index.is_some() {
+            continue;
+        }
+
+        let name = if let Some(name) = stored.name.as_deref() {
+            name
+        } else {
+            continue;
+        };
+
+        if let Some(index) = current
+            .iter()
+            .zip(claimed.iter())
+            .position(|((_, current_name), &claimed)| !claimed && *current_name == Some(name))
+        {
+            claimed[index] = true;
+            display_index.replace(index as u32);
+            score += NAME_MATCH_SCORE;
+        }
+    }
+
+    let num_displays = stored.len().max(current.len());
+
+    let score = if num_displays == 0 {
+        0.0
+    } else {
+        score / num_displays as f32
+    };
+
+    ProfileMatch {
+        profile_index: 0,
+        score,
+        display_indices,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn display(id: u64, name: &str) -> DisplayProfile {
+        DisplayProfile {
+            id: DisplayId(id),
+            name: Some(name.to_owned()),
+            dimensions: Dimensions::new(1920, 1080),
+            refresh_rate_num: 60,
+            refresh_rate_denom: 1,
+            position: Position::new(0, 0),
+            orientation: Orientation::Landscape,
+            is_primary: false,
+            dpi_scale: 1.0,
+        }
+    }
+
+    #[test]
+    fn score_displays() {
+        let stored = [display(0, "DELL U2720Q"), display(1, "DELL U2720Q")];
+
+        // Exact match.
+        let exact = super::score_displays(
+            &stored,
+            &[
+                (DisplayId(1), Some("DELL U2720Q")),
+                (DisplayId(0), Some("DELL U2720Q")),
+            ],
+        );
+        assert!(exact.is_exact());
+        assert_eq!(exact.display_indices, vec![Some(1), Some(0)]);
+
+        // Same models, different ports.
+        let by_name = super::score_displays(
+            &stored,
+            &[
+                (DisplayId(2), Some("DELL U2720Q")),
+                (DisplayId(3), Some("DELL U2720Q")),
+            ],
+        );
+        assert!(!by_name.is_exact());
+        assert_eq!(by_name.score, NAME_MATCH_SCORE);
+        assert_eq!(by_name.display_indices, vec![Some(0), Some(1)]);
+
+        // Subset of the stored displays present.
+        let subset = super::score_displays(&stored, &[(DisplayId(1), Some("DELL U2720Q"))]);
+        assert_eq!(subset.score, 0.5);
+        assert_eq!(subset.display_indices, vec![None, Some(0)]);
+
+        // Extra displays present.
+        let extra = super::score_displays(
+            &stored,
+            &[
+                (DisplayId(0), Some("DELL U2720Q")),
+                (DisplayId(1), Some("DELL U2720Q")),
+                (DisplayId(2), Some("LG 27GL850")),
+                (DisplayId(3), None),
+            ],
+        );
+        assert_eq!(extra.score, 0.5);
+
+        // No stored displays present.
+        let none = super::score_displays(&stored, &[(DisplayId(2), Some("LG 27GL850"))]);
+        assert_eq!(none.score, 0.0);
+        assert_eq!(none.display_indices, vec![None, None]);
+    }
+
+    #[test]
+    fn best_match() {
+        let profile_match = |profile_index, score| ProfileMatch {
+            profile_index,
+            score,
+            display_indices: Vec::new(),
+        };
+
+        assert!(super::best_match(std::iter::empty()).is_none());
+        assert!(super::best_match(vec![profile_match(0, 0.0)].into_iter()).is_none());
+        assert_eq!(
+            super::best_match(
+                vec![
+                    profile_match(0, 0.25),
+                    profile_match(1, 0.75),
+                    profile_match(2, 0.75),
+                    profile_match(3, 0.5)
+                ]
+                .into_iter()
+            )
+            .unwrap()
+            .profile_index,
+            1
+        );
+    }
+}
@@ -1,7 +1,9 @@
 #![allow(non_upper_case_globals)]
 
+use std::convert::TryFrom;
 use std::fmt::{Display, Formatter};
 use std::ops::{Add, Sub};
+use std::str::FromStr;
 
 /// 2D position of a point in display space.
 /// Left-to-right, top-to-bottom.
@@ -11,36 +13,33 @@ use std::ops::{Add, Sub};
 ///  |
 ///  |
 /// \/
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Position {
     pub left: i32,
     pub top: i32,
 }
 
-impl Default for Position {
-    fn default() -> Self {
-        Self { left: 0, top: 0 }
-    }
-}
-
+/// Saturates at the `i32` range.
 impl Add for Position {
     type Output = Self;
 
     fn add(self, other: Self) -> Self::Output {
         Self {
-            left: self.left + other.left,
-            top: self.top + other.top,
+            left: self.left.saturating_add(other.left),
+            top: self.top.saturating_add(other.top),
         }
     }
 }
 
+/// Saturates at the `i32` range.
 impl Sub for Position {
     type Output = Self;
 
     fn sub(self, other: Self) -> Self::Output {
         Self {
-            left: self.left - other.left,
-            top: self.top - other.top,
+            left: self.left.saturating_sub(other.left),
+            top: self.top.saturating_sub(other.top),
         }
     }
 }
@@ -59,6 +58,7 @@ impl Display for Position {
 
 /// 2D dimensions of a rectangle in display space.
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Dimensions {
     pub width: u32,
     pub height: u32,
@@ -70,13 +70,14 @@ impl Default for Dimensions {
     }
 }
 
+/// Saturates at `u32::MAX`.
 impl Add for Dimensions {
     type Output = Self;
 
     fn add(self, other: Self) -> Self::Output {
         Self {
-            width: self.width + other.width,
-            height: self.height + other.height,
+            width: self.width.saturating_add(other.width),
+            height: self.height.saturating_add(other.height),
         }
     }
 }
@@ -101,8 +102,17 @@ impl Dimensions {
         Self { width, height }
     }
 
+    /// Saturates at `u32::MAX`.
     pub fn area(self) -> u32 {
-        self.width * self.height
+        self.width.saturating_mul(self.height)
+    }
+
+    /// Returns the formatter of the dimensions as `<width>x<height>` (e.g. `1920x1080`),
+    /// as parsed by [`from_str`].
+    ///
+    /// [`from_str`]: #method.from_str
+    pub fn wxh(self) -> impl Display {
+        DimensionsWxH(self)
     }
 }
 
@@ -112,6 +122,41 @@ impl Display for Dimensions {
     }
 }
 
+/// Formats the dimensions as `<width>x<height>` (see [`Dimensions::wxh`]).
+///
+/// [`Dimensions::wxh`]: struct.Dimensions.html#method.wxh
+struct DimensionsWxH(Dimensions);
+
+impl Display for DimensionsWxH {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{}x{}", self.0.width, self.0.height)
+    }
+}
+
+impl FromStr for Dimensions {
+    type Err = ();
+
+    /// Parses the dimensions formatted as `<width>x<height>` (e.g. `1920x1080`).
+    ///
+    /// `X` and `×` are accepted as the separator too, and whitespace around the numbers is ignored
+    /// (e.g. `1920 × 1080`).
+    fn from_str(s: &str) -> Result<Self, ()> {
+        let mut parts = s.split(['x', 'X', '×']);
+
+        let mut parse =
+            || -> Result<u32, ()> { parts.next().ok_or(())?.trim().parse().map_err(|_| ()) };
+
+        let width = parse()?;
+        let height = parse()?;
+
+        if parts.next().is_some() {
+            return Err(());
+        }
+
+        Ok(Self::new(width, height))
+    }
+}
+
 /// 2D rectangle in display space.
 /// Left-to-right, top-to-bottom.
 /// Origin depends on context.
@@ -120,42 +165,183 @@ impl Display for Dimensions {
 ///  |
 ///  |
 /// \/
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Rectangle {
     pub position: Position,
     pub dimensions: Dimensions,
 }
 
-impl Default for Rectangle {
-    fn default() -> Self {
+impl Display for Rectangle {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{}:{}", self.position, self.dimensions)
+    }
+}
+
+/// Per-edge margins of a rectangle in display space.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Margins {
+    pub left: u32,
+    pub top: u32,
+    pub right: u32,
+    pub bottom: u32,
+}
+
+impl Margins {
+    pub fn new(left: u32, top: u32, right: u32, bottom: u32) -> Self {
         Self {
-            position: Position::default(),
-            dimensions: Dimensions::default(),
+            left,
+            top,
+            right,
+            bottom,
         }
     }
+
+    /// Creates the margins with the same `margin` on each edge.
+    pub fn uniform(margin: u32) -> Self {
+        Self::new(margin, margin, margin, margin)
+    }
+
+    /// Returns the margins between the edges of the `outer` rectangle and the `inner` rectangle within it
+    /// (e.g. the edges of the display's full rectangle reserved by the taskbar, as excluded from its work rectangle).
+    ///
+    /// Edges of the `inner` rectangle outside of the `outer` rectangle have zero margins.
+    pub fn between(outer: &Rectangle, inner: &Rectangle) -> Self {
+        let margin = |outer: i32, inner: i32| (inner as i64 - outer as i64).max(0) as u32;
+
+        Self::new(
+            margin(outer.left(), inner.left()),
+            margin(outer.top(), inner.top()),
+            margin(inner.right(), outer.right()),
+            margin(inner.bottom(), outer.bottom()),
+        )
+    }
+
+    /// Returns `true` if all margins are zero.
+    pub fn is_zero(&self) -> bool {
+        self.left == 0 && self.top == 0 && self.right == 0 && self.bottom == 0
+    }
 }
 
-impl Display for Rectangle {
+impl Display for Margins {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        write!(f, "{}:{}", self.position, self.dimensions)
+        write!(
+            f,
+            "[{}, {}, {}, {}]",
+            self.left, self.top, self.right, self.bottom
+        )
     }
 }
 
 bitflags! {
     /// Flags which specify the sides of the rectangle to (attempt to) not move to avoid clipping it.
+    ///
+    /// May be parsed from / formatted as a `|`-separated list of flag names (e.g. `KEEP_LEFT | KEEP_TOP`).
+    /// The old flag names (e.g. `KeepLeft|KeepTop`) are accepted when parsing too.
+    #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
     pub struct ClipRectFlags: u32 {
         /// Move the rectangle as appropriate to avoid clipping it.
-        const KeepNone = 0;
+        const KEEP_NONE = 0;
         /// Do not move the left side of the rectangle.
-        const KeepLeft = 1;
+        const KEEP_LEFT = 1;
         /// Do not move the right side of the rectangle.
-        const KeepRight = 1 << 1;
+        const KEEP_RIGHT = 1 << 1;
         /// Do not move the top side of the rectangle.
-        const KeepTop = 1 << 2;
+        const KEEP_TOP = 1 << 2;
         /// Do not move the bottom side of the rectangle.
-        const KeepBottom = 1 << 3;
+        const KEEP_BOTTOM = 1 << 3;
         /// Do not move the rectangle at all, just clip it.
-        const KeepAll = Self::KeepLeft.bits | Self::KeepRight.bits | Self::KeepTop.bits | Self::KeepBottom.bits;
+        const KEEP_ALL = Self::KEEP_LEFT.bits() | Self::KEEP_RIGHT.bits() | Self::KEEP_TOP.bits() | Self::KEEP_BOTTOM.bits();
+    }
+}
+
+/// Old flag names, kept for backwards compatibility.
+impl ClipRectFlags {
+    /// Old name of [`KEEP_NONE`](#associatedconstant.KEEP_NONE).
+    pub const KeepNone: Self = Self::KEEP_NONE;
+    /// Old name of [`KEEP_LEFT`](#associatedconstant.KEEP_LEFT).
+    pub const KeepLeft: Self = Self::KEEP_LEFT;
+    /// Old name of [`KEEP_RIGHT`](#associatedconstant.KEEP_RIGHT).
+    pub const KeepRight: Self = Self::KEEP_RIGHT;
+    /// Old name of [`KEEP_TOP`](#associatedconstant.KEEP_TOP).
+    pub const KeepTop: Self = Self::KEEP_TOP;
+    /// Old name of [`KEEP_BOTTOM`](#associatedconstant.KEEP_BOTTOM).
+    pub const KeepBottom: Self = Self::KEEP_BOTTOM;
+    /// Old name of [`KEEP_ALL`](#associatedconstant.KEEP_ALL).
+    pub const KeepAll: Self = Self::KEEP_ALL;
+
+    const OLD_NAMES: [(&'static str, Self); 6] = [
+        ("KeepNone", Self::KEEP_NONE),
+        ("KeepLeft", Self::KEEP_LEFT),
+        ("KeepRight", Self::KEEP_RIGHT),
+        ("KeepTop", Self::KEEP_TOP),
+        ("KeepBottom", Self::KEEP_BOTTOM),
+        ("KeepAll", Self::KEEP_ALL),
+    ];
+}
+
+impl ClipRectFlags {
+    /// Returns the flags which keep the specified sides of the rectangle.
+    pub const fn from_sides(left: bool, right: bool, top: bool, bottom: bool) -> Self {
+        let mut bits = 0;
+
+        if left {
+            bits |= Self::KEEP_LEFT.bits();
+        }
+        if right {
+            bits |= Self::KEEP_RIGHT.bits();
+        }
+        if top {
+            bits |= Self::KEEP_TOP.bits();
+        }
+        if bottom {
+            bits |= Self::KEEP_BOTTOM.bits();
+        }
+
+        Self::from_bits_retain(bits)
+    }
+
+    /// Returns the flags which keep the left and right sides of the rectangle.
+    pub const fn horizontal() -> Self {
+        Self::from_sides(true, true, false, false)
+    }
+
+    /// Returns the flags which keep the top and bottom sides of the rectangle.
+    pub const fn vertical() -> Self {
+        Self::from_sides(false, false, true, true)
+    }
+}
+
+impl Display for ClipRectFlags {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        if self.is_empty() {
+            write!(f, "KEEP_NONE")
+        } else {
+            bitflags::parser::to_writer(self, f)
+        }
+    }
+}
+
+impl FromStr for ClipRectFlags {
+    type Err = ();
+
+    /// Parses the `|`-separated list of flag names (e.g. `KEEP_LEFT | KEEP_TOP`, or `KeepLeft|KeepTop`).
+    /// Whitespace around the flag names is ignored.
+    fn from_str(s: &str) -> Result<Self, ()> {
+        s.split('|').try_fold(Self::empty(), |flags, name| {
+            let name = name.trim();
+
+            Self::from_name(name)
+                .or_else(|| {
+                    Self::OLD_NAMES
+                        .iter()
+                        .find(|(old_name, _)| *old_name == name)
+                        .map(|(_, flag)| *flag)
+                })
+                .map(|flag| flags | flag)
+                .ok_or(())
+        })
     }
 }
 
@@ -171,16 +357,24 @@ impl Rectangle {
         self.position.left
     }
 
+    /// Saturates at `i32::MAX` for [`degenerate`] rectangles (see [`try_right`]).
+    ///
+    /// [`degenerate`]: #method.is_degenerate
+    /// [`try_right`]: #method.try_right
     pub fn right(&self) -> i32 {
-        self.position.left + self.dimensions.width as i32
+        saturate_i32(self.exact_right())
     }
 
     pub fn top(&self) -> i32 {
         self.position.top
     }
 
+    /// Saturates at `i32::MAX` for [`degenerate`] rectangles (see [`try_bottom`]).
+    ///
+    /// [`degenerate`]: #method.is_degenerate
+    /// [`try_bottom`]: #method.try_bottom
     pub fn bottom(&self) -> i32 {
-        self.position.top + self.dimensions.height as i32
+        saturate_i32(self.exact_bottom())
     }
 
     pub fn width(&self) -> u32 {
@@ -191,20 +385,191 @@ impl Rectangle {
         self.dimensions.height
     }
 
+    /// Returns the right side coordinate,
+    /// or `None` if it lies outside of the `i32` range (i.e. the rectangle is [`degenerate`]).
+    ///
+    /// [`degenerate`]: #method.is_degenerate
+    pub fn try_right(&self) -> Option<i32> {
+        i32::try_from(self.exact_right()).ok()
+    }
+
+    /// Returns the bottom side coordinate,
+    /// or `None` if it lies outside of the `i32` range (i.e. the rectangle is [`degenerate`]).
+    ///
+    /// [`degenerate`]: #method.is_degenerate
+    pub fn try_bottom(&self) -> Option<i32> {
+        i32::try_from(self.exact_bottom()).ok()
+    }
+
+    /// Returns `true` if the rectangle has zero width and / or height.
+    ///
+    /// Empty rectangles cover no pixels: they never [`overlap`] other rectangles,
+    /// are [`contained`] in any rectangle, are ignored when computing the [`bounding`] rectangle,
+    /// and are never returned by [`clip`].
+    ///
+    /// [`overlap`]: #method.overlaps
+    /// [`contained`]: #method.contains
+    /// [`bounding`]: #method.bounding
+    /// [`clip`]: #method.clip
+    pub fn is_empty(&self) -> bool {
+        self.width() == 0 || self.height() == 0
+    }
+
+    /// Returns the rectangle with both dimensions zeroed if it is [`empty`],
+    /// so that all empty rectangles at the same position compare equal;
+    /// or the rectangle itself otherwise.
+    ///
+    /// [`empty`]: #method.is_empty
+    pub fn normalized(&self) -> Rectangle {
+        if self.is_empty() {
+            Rectangle::new(self.position, Dimensions::default())
+        } else {
+            *self
+        }
+    }
+
+    /// Returns `true` if the rectangle extends past the `i32` coordinate space
+    /// (e.g. due to corrupt driver data),
+    /// so its [`right`] / [`bottom`] side coordinates saturate.
+    ///
+    /// Other methods still handle degenerate rectangles without overflowing, using the exact side coordinates.
+    ///
+    /// [`right`]: #method.right
+    /// [`bottom`]: #method.bottom
+    pub fn is_degenerate(&self) -> bool {
+        self.try_right().is_none() || self.try_bottom().is_none()
+    }
+
+    /// Right side coordinate, which may lie outside of the `i32` range.
+    pub(crate) fn exact_right(&self) -> i64 {
+        self.position.left as i64 + self.dimensions.width as i64
+    }
+
+    /// Bottom side coordinate, which may lie outside of the `i32` range.
+    pub(crate) fn exact_bottom(&self) -> i64 {
+        self.position.top as i64 + self.dimensions.height as i64
+    }
+
+    /// Returns the rectangle expanded outwards by the `margins` on each edge
+    /// (e.g. to make a drag / drop target larger than the rectangle it represents).
+    pub fn expand(&self, margins: Margins) -> Rectangle {
+        Rectangle::new(
+            Position::new(
+                saturate_i32(self.left() as i64 - margins.left as i64),
+                saturate_i32(self.top() as i64 - margins.top as i64),
+            ),
+            Dimensions::new(
+                self.width()
+                    .saturating_add(margins.left)
+                    .saturating_add(margins.right),
+                self.height()
+                    .saturating_add(margins.top)
+                    .saturating_add(margins.bottom),
+            ),
+        )
+    }
+
+    /// Returns the rectangle shrunk inwards by the `margins` on each edge, down to zero dimensions.
+    pub fn shrink(&self, margins: Margins) -> Rectangle {
+        let width = self
+            .width()
+            .saturating_sub(margins.left.saturating_add(margins.right));
+        let height = self
+            .height()
+            .saturating_sub(margins.top.saturating_add(margins.bottom));
+
+        Rectangle::new(
+            Position::new(
+                saturate_i32(self.left() as i64 + margins.left.min(self.width()) as i64),
+                saturate_i32(self.top() as i64 + margins.top.min(self.height()) as i64),
+            ),
+            Dimensions::new(width, height),
+        )
+    }
+
     /// Returns `true` if the rectangle overlaps the `other` rectangle.
+    ///
+    /// [`Empty`](#method.is_empty) rectangles never overlap.
     pub fn overlaps(&self, other: &Rectangle) -> bool {
-        (self.left() < other.right())
-            && (self.right() > other.left())
-            && (self.top() < other.bottom())
-            && (self.bottom() > other.top())
+        !self.is_empty()
+            && !other.is_empty()
+            && ((self.left() as i64) < other.exact_right())
+            && (self.exact_right() > other.left() as i64)
+            && ((self.top() as i64) < other.exact_bottom())
+            && (self.exact_bottom() > other.top() as i64)
     }
 
     /// Returns `true` if the rectangle completely contains the `other` rectangle.
+    ///
+    /// [`Empty`](#method.is_empty) rectangles are contained in any rectangle,
+    /// and contain only empty rectangles.
     pub fn contains(&self, other: &Rectangle) -> bool {
+        if other.is_empty() {
+            return true;
+        }
+
         (self.left() <= other.left())
-            && (self.right() >= other.right())
+            && (self.exact_right() >= other.exact_right())
             && (self.top() <= other.top())
-            && (self.bottom() >= other.bottom())
+            && (self.exact_bottom() >= other.exact_bottom())
+    }
+
+    /// Returns `true` if the `point` lies within the rectangle
+    /// (the left / top edges are inclusive, the right / bottom edges are exclusive).
+    pub fn contains_point(&self, point: Position) -> bool {
+        (self.left() <= point.left)
+            && ((point.left as i64) < self.exact_right())
+            && (self.top() <= point.top)
+            && ((point.top as i64) < self.exact_bottom())
+    }
+
+    /// Returns the intersection of the rectangle with the `other` rectangle,
+    /// or `None` if they do not [`overlap`](#method.overlaps).
+    pub fn intersection(&self, other: &Rectangle) -> Option<Rectangle> {
+        if !self.overlaps(other) {
+            return None;
+        }
+
+        let left = self.left().max(other.left());
+        let top = self.top().max(other.top());
+        let right = self.exact_right().min(other.exact_right());
+        let bottom = self.exact_bottom().min(other.exact_bottom());
+
+        Some(Rectangle::new(
+            Position::new(left, top),
+            Dimensions::new((right - left as i64) as u32, (bottom - top as i64) as u32),
+        ))
+    }
+
+    /// Returns the smallest rectangle which contains both this and the `other` rectangle.
+    ///
+    /// [`Empty`](#method.is_empty) rectangles are ignored;
+    /// the bounding rectangle of two empty rectangles is this rectangle, [`normalized`].
+    ///
+    /// Dimensions saturate at `u32::MAX`.
+    ///
+    /// [`normalized`]: #method.normalized
+    pub fn bounding(&self, other: &Rectangle) -> Rectangle {
+        if other.is_empty() {
+            return self.normalized();
+        } else if self.is_empty() {
+            return *other;
+        }
+
+        let left = self.left().min(other.left());
+        let top = self.top().min(other.top());
+        let right = self.exact_right().max(other.exact_right());
+        let bottom = self.exact_bottom().max(other.exact_bottom());
+
+        let saturate_u32 = |value: i64| value.min(u32::MAX as i64) as u32;
+
+        Rectangle::new(
+            Position::new(left, top),
+            Dimensions::new(
+                saturate_u32(right - left as i64),
+                saturate_u32(bottom - top as i64),
+            ),
+        )
     }
 
     /// Tries to clip the rectangle to the provided bounds.
@@ -212,112 +577,392 @@ impl Rectangle {
     /// `clip_flags` control which sides of the rectangle to try to keep in place.
     ///
     /// Returns the clipped rectangle, if any;
-    /// or `None` if the rectangle does not intersect the bounds and may not be moved.
+    /// or `None` if the rectangle does not intersect the bounds and may not be moved,
+    /// or if the rectangle or the bounds are [`empty`](#method.is_empty).
+    ///
+    /// Kept sides outside of the bounds are clipped anyway
+    /// (see [`Strictness::BestEffort`](enum.Strictness.html#variant.BestEffort)).
     pub fn clip(&self, bounds: &Rectangle, clip_flags: ClipRectFlags) -> Option<Rectangle> {
-        // Clip to bottom and right sides, finding top and left coordinates.
-        let mut right = self.right();
-        let mut bottom = self.bottom();
+        self.clip_impl(bounds, clip_flags)
+    }
 
-        let furthest_right = bounds.right();
-        right = at_most(right, furthest_right);
+    /// Tries to clip the rectangle to the provided bounds.
+    ///
+    /// `clip_flags` control which sides of the rectangle to try to keep in place.
+    /// A kept side conflicts with the bounds if it lies outside of them, i.e. if it must be moved anyway;
+    /// `strictness` determines how such conflicts are resolved.
+    ///
+    /// Returns the clipped rectangle, if any;
+    /// or `None` if the rectangle does not intersect the bounds and may not be moved;
+    /// or an error with the conflicting sides, if `strictness` is [`FailOnConflict`].
+    ///
+    /// [`FailOnConflict`]: enum.Strictness.html#variant.FailOnConflict
+    pub fn clip_with(
+        &self,
+        bounds: &Rectangle,
+        clip_flags: ClipRectFlags,
+        strictness: Strictness,
+    ) -> Result<Option<Rectangle>, ClipConflict> {
+        let kept = |side| clip_flags.contains(side);
+
+        let (left, right) = (self.left() as i64, self.exact_right());
+        let (top, bottom) = (self.top() as i64, self.exact_bottom());
+
+        let (bounds_left, bounds_right) = (bounds.left() as i64, bounds.exact_right());
+        let (bounds_top, bounds_bottom) = (bounds.top() as i64, bounds.exact_bottom());
+
+        let conflicts = ClipRectFlags::from_sides(
+            kept(ClipRectFlags::KEEP_LEFT) && (left < bounds_left || left >= bounds_right),
+            kept(ClipRectFlags::KEEP_RIGHT) && (right > bounds_right || right <= bounds_left),
+            kept(ClipRectFlags::KEEP_TOP) && (top < bounds_top || top >= bounds_bottom),
+            kept(ClipRectFlags::KEEP_BOTTOM) && (bottom > bounds_bottom || bottom <= bounds_top),
+        );
+
+        match strictness {
+            Strictness::BestEffort => Ok(self.clip_impl(bounds, clip_flags)),
+            Strictness::FailOnConflict => {
+                if conflicts.is_empty() {
+                    Ok(self.clip_impl(bounds, clip_flags))
+                } else {
+                    Err(ClipConflict { sides: conflicts })
+                }
+            }
+            // Conflicting sides are moved (and the rectangle shrunk) to fit the bounds.
+            Strictness::ShrinkToFit => Ok(self.clip_impl(bounds, clip_flags - conflicts)),
+        }
+    }
+
+    fn clip_impl(&self, bounds: &Rectangle, clip_flags: ClipRectFlags) -> Option<Rectangle> {
+        // Use the exact `i64` coordinates - the sides may lie outside of the `i32` range.
+        let (width, height) = (self.width() as i64, self.height() as i64);
 
-        let furthest_bottom = bounds.bottom();
-        bottom = at_most(bottom, furthest_bottom);
+        // Clip to bottom and right sides, finding top and left coordinates.
+        let furthest_right = bounds.exact_right();
+        let right = at_most(self.exact_right(), furthest_right);
 
-        let mut left = if clip_flags.contains(ClipRectFlags::KeepLeft) {
-            self.left()
+        let furthest_bottom = bounds.exact_bottom();
+        let bottom = at_most(self.exact_bottom(), furthest_bottom);
+
+        let mut left = if clip_flags.contains(ClipRectFlags::KEEP_LEFT) {
+            self.left() as i64
         } else {
-            right - self.width() as i32
+            right - width
         };
-        debug_assert!(left <= self.left());
-        left = at_least(left, bounds.left());
+        debug_assert!(left <= self.left() as i64);
+        left = at_least(left, bounds.left() as i64);
 
-        let mut top = if clip_flags.contains(ClipRectFlags::KeepTop) {
-            self.top()
+        let mut top = if clip_flags.contains(ClipRectFlags::KEEP_TOP) {
+            self.top() as i64
         } else {
-            bottom - self.height() as i32
+            bottom - height
         };
-        debug_assert!(top <= self.top());
-        top = at_least(top, bounds.top());
+        debug_assert!(top <= self.top() as i64);
+        top = at_least(top, bounds.top() as i64);
 
         // Then clip to top and left, finding the bottom and right coordinates.
-        let right = if clip_flags.contains(ClipRectFlags::KeepRight) {
+        let right = if clip_flags.contains(ClipRectFlags::KEEP_RIGHT) {
             right
         } else {
-            at_most(left + self.width() as i32, furthest_right)
+            at_most(left + width, furthest_right)
         };
 
-        let bottom = if clip_flags.contains(ClipRectFlags::KeepBottom) {
+        let bottom = if clip_flags.contains(ClipRectFlags::KEEP_BOTTOM) {
             bottom
         } else {
-            at_most(top + self.height() as i32, furthest_bottom)
-        };
-
-        let width = if right > left {
-            (right - left) as u32
-        } else {
-            return None;
+            at_most(top + height, furthest_bottom)
         };
 
-        let height = if bottom > top {
-            (bottom - top) as u32
-        } else {
+        if right <= left || bottom <= top {
             return None;
-        };
+        }
 
+        // Within the bounds, so the clipped rectangle fits the `i32` / `u32` range.
         Some(Rectangle::new(
-            Position::new(left, top),
-            Dimensions::new(width, height),
+            Position::new(left as i32, top as i32),
+            Dimensions::new((right - left) as u32, (bottom - top) as u32),
         ))
     }
 
     /// Clamps the rectangle's dimensions to the provided minimum.
     /// `clip_flags` control which sides of the rectangle to keep in place.
     /// Returns the clamped rectangle.
+    ///
+    /// If both opposite sides are kept but the rectangle is too small, the left / top side is kept
+    /// (see [`Strictness::BestEffort`](enum.Strictness.html#variant.BestEffort)).
     pub fn clamp(&self, min_dimensions: Dimensions, clip_flags: ClipRectFlags) -> Rectangle {
-        let left = self.left();
-        let top = self.top();
+        match self.clamp_with(min_dimensions, clip_flags, Strictness::BestEffort) {
+            Ok(clamped) => clamped,
+            Err(_) => unreachable!(),
+        }
+    }
+
+    /// Clamps the rectangle's dimensions to the provided minimum.
+    ///
+    /// `clip_flags` control which sides of the rectangle to keep in place.
+    /// Both opposite sides being kept conflict with the minimum dimension if the rectangle is smaller
+    /// along that axis; `strictness` determines how such conflicts are resolved.
+    ///
+    /// Returns the clamped rectangle;
+    /// or an error with the conflicting sides, if `strictness` is [`FailOnConflict`].
+    ///
+    /// [`FailOnConflict`]: enum.Strictness.html#variant.FailOnConflict
+    pub fn clamp_with(
+        &self,
+        min_dimensions: Dimensions,
+        clip_flags: ClipRectFlags,
+        strictness: Strictness,
+    ) -> Result<Rectangle, ClipConflict> {
+        let horizontal_conflict =
+            clip_flags.contains(ClipRectFlags::horizontal()) && self.width() < min_dimensions.width;
+        let vertical_conflict =
+            clip_flags.contains(ClipRectFlags::vertical()) && self.height() < min_dimensions.height;
+
+        let conflicts = ClipRectFlags::from_sides(
+            horizontal_conflict,
+            horizontal_conflict,
+            vertical_conflict,
+            vertical_conflict,
+        );
+
+        if conflicts.is_empty() {
+            return Ok(self.clamp_impl(min_dimensions, clip_flags));
+        }
+
+        match strictness {
+            // Keep the left / top sides.
+            Strictness::BestEffort => Ok(self.clamp_impl(
+                min_dimensions,
+                clip_flags - (conflicts & (ClipRectFlags::KEEP_RIGHT | ClipRectFlags::KEEP_BOTTOM)),
+            )),
+            Strictness::FailOnConflict => Err(ClipConflict { sides: conflicts }),
+            // Do not grow the rectangle along the conflicting axes.
+            Strictness::ShrinkToFit => {
+                let min_dimensions = Dimensions::new(
+                    if horizontal_conflict {
+                        self.width()
+                    } else {
+                        min_dimensions.width
+                    },
+                    if vertical_conflict {
+                        self.height()
+                    } else {
+                        min_dimensions.height
+                    },
+                );
+
+                Ok(self.clamp_impl(min_dimensions, clip_flags))
+            }
+        }
+    }
 
-        let width = at_least(self.width(), min_dimensions.width);
-        let height = at_least(self.height(), min_dimensions.height);
+    fn clamp_impl(&self, min_dimensions: Dimensions, clip_flags: ClipRectFlags) -> Rectangle {
+        // Use the exact `i64` coordinates - the sides may lie outside of the `i32` range.
+        let width = at_least(self.width(), min_dimensions.width) as i64;
+        let height = at_least(self.height(), min_dimensions.height) as i64;
 
-        let right = if clip_flags.contains(ClipRectFlags::KeepRight) {
-            self.right()
+        let right = if clip_flags.contains(ClipRectFlags::KEEP_RIGHT) {
+            self.exact_right()
         } else {
-            left + width as i32
+            self.left() as i64 + width
         };
 
-        let bottom = if clip_flags.contains(ClipRectFlags::KeepBottom) {
-            self.bottom()
+        let bottom = if clip_flags.contains(ClipRectFlags::KEEP_BOTTOM) {
+            self.exact_bottom()
         } else {
-            top + height as i32
+            self.top() as i64 + height
         };
 
-        let left = if clip_flags.contains(ClipRectFlags::KeepLeft) {
-            self.left()
+        let left = if clip_flags.contains(ClipRectFlags::KEEP_LEFT) {
+            self.left() as i64
         } else {
-            right - width as i32
+            right - width
         };
 
-        let top = if clip_flags.contains(ClipRectFlags::KeepTop) {
-            self.top()
+        let top = if clip_flags.contains(ClipRectFlags::KEEP_TOP) {
+            self.top() as i64
         } else {
-            bottom - height as i32
+            bottom - height
         };
 
-        debug_assert!(right >= (left + min_dimensions.width as i32));
-        debug_assert!(bottom >= (top + min_dimensions.height as i32));
-
-        let width = (right - left) as u32;
-        let height = (bottom - top) as u32;
+        debug_assert!(right >= (left + min_dimensions.width as i64));
+        debug_assert!(bottom >= (top + min_dimensions.height as i64));
 
+        // Dimensions take precedence over the kept sides -
+        // the rectangle is moved back into the `i32` range, if necessary.
         Rectangle {
-            position: Position::new(left, top),
-            dimensions: Dimensions::new(width, height),
+            position: Position::new(saturate_i32(left), saturate_i32(top)),
+            dimensions: Dimensions::new((right - left) as u32, (bottom - top) as u32),
+        }
+    }
+
+    /// Returns the rectangle of the `content` scaled according to the `fit_mode`
+    /// and centered within this rectangle.
+    ///
+    /// See [`fit_dimensions`](fn.fit_dimensions.html).
+    pub fn fit(&self, content: Dimensions, fit_mode: FitMode) -> Rectangle {
+        let fitted = fit_dimensions(content, self.dimensions, fit_mode);
+        Rectangle::new(self.position + fitted.position, fitted.dimensions)
+    }
+}
+
+/// Determines how [`Rectangle::clip_with`] / [`Rectangle::clamp_with`] resolve the conflicts
+/// between the [`ClipRectFlags`] and the bounds / minimum dimensions,
+/// e.g. `KEEP_LEFT | KEEP_RIGHT` on a rectangle wider than the bounds.
+///
+/// [`Rectangle::clip_with`]: struct.Rectangle.html#method.clip_with
+/// [`Rectangle::clamp_with`]: struct.Rectangle.html#method.clamp_with
+/// [`ClipRectFlags`]: struct.ClipRectFlags.html
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Strictness {
+    /// Resolve the conflicts silently, as [`Rectangle::clip`] / [`Rectangle::clamp`] do:
+    /// kept sides outside of the bounds are clipped anyway (which may leave nothing of the rectangle);
+    /// if both opposite sides are kept but the rectangle is smaller than the minimum dimensions,
+    /// the left / top side is kept.
+    ///
+    /// [`Rectangle::clip`]: struct.Rectangle.html#method.clip
+    /// [`Rectangle::clamp`]: struct.Rectangle.html#method.clamp
+    BestEffort,
+    /// Fail with a [`ClipConflict`] error if any kept side may not be kept.
+    ///
+    /// [`ClipConflict`]: struct.ClipConflict.html
+    FailOnConflict,
+    /// Let the rectangle shrink to fit: conflicting sides are no longer kept when clipping,
+    /// so the rectangle is moved into the bounds and clipped to them;
+    /// and the rectangle is not grown along the conflicting axes when clamping.
+    ShrinkToFit,
+}
+
+impl Display for Strictness {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use Strictness::*;
+
+        match self {
+            BestEffort => write!(f, "best effort"),
+            FailOnConflict => write!(f, "fail on conflict"),
+            ShrinkToFit => write!(f, "shrink to fit"),
+        }
+    }
+}
+
+/// Error returned by [`Rectangle::clip_with`] / [`Rectangle::clamp_with`]
+/// with [`Strictness::FailOnConflict`] if some kept sides of the rectangle may not be kept.
+///
+/// [`Rectangle::clip_with`]: struct.Rectangle.html#method.clip_with
+/// [`Rectangle::clamp_with`]: struct.Rectangle.html#method.clamp_with
+/// [`Strictness::FailOnConflict`]: enum.Strictness.html#variant.FailOnConflict
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct ClipConflict {
+    /// Kept sides of the rectangle which conflict with the bounds / minimum dimensions.
+    pub sides: ClipRectFlags,
+}
+
+impl Display for ClipConflict {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "conflicting kept sides: {}", self.sides)
+    }
+}
+
+/// Determines how the content is scaled to fit the target dimensions.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FitMode {
+    /// Scale the content, preserving its aspect ratio, to fit completely within the target
+    /// (letterboxing / pillarboxing it as necessary).
+    Contain,
+    /// Scale the content, preserving its aspect ratio, to cover the target completely
+    /// (cropping it as necessary).
+    Cover,
+    /// Stretch the content to the target dimensions, ignoring its aspect ratio.
+    Stretch,
+}
+
+/// Returns the rectangle of the `content` scaled according to the `fit_mode`
+/// and centered within the `target` dimensions.
+///
+/// Returned rectangle position is relative to the top left corner of the `target`
+/// (and is negative when the content is cropped for `FitMode::Cover`).
+pub fn fit_dimensions(content: Dimensions, target: Dimensions, fit_mode: FitMode) -> Rectangle {
+    let dimensions = if content.width == 0 || content.height == 0 {
+        Dimensions::default()
+    } else {
+        let (content_width, content_height) = (content.width as u64, content.height as u64);
+        let (target_width, target_height) = (target.width as u64, target.height as u64);
+
+        // Rounds to the nearest integer.
+        let scale = |value: u64, num: u64, denom: u64| {
+            ((value * num + denom / 2) / denom).min(u32::MAX as u64) as u32
+        };
+
+        // Whether the content is relatively wider than the target.
+        let wider = target_width * content_height <= target_height * content_width;
+
+        // Whether the scaled content width matches the target width (otherwise the height does).
+        let fit_width = match fit_mode {
+            FitMode::Contain => wider,
+            FitMode::Cover => !wider,
+            FitMode::Stretch => return Rectangle::new(Position::default(), target),
+        };
+
+        if fit_width {
+            Dimensions::new(
+                target.width,
+                scale(content_height, target_width, content_width),
+            )
+        } else {
+            Dimensions::new(
+                scale(content_width, target_height, content_height),
+                target.height,
+            )
+        }
+    };
+
+    Rectangle::new(
+        Position::new(
+            ((target.width as i64 - dimensions.width as i64) / 2) as i32,
+            ((target.height as i64 - dimensions.height as i64) / 2) as i32,
+        ),
+        dimensions,
+    )
+}
+
+/// Returns the bounding rectangle of the `rects` if they exactly cover it,
+/// i.e. if they are adjacent to each other (but do not overlap) and together form a contiguous rectangle
+/// without any gaps; otherwise returns `None`.
+///
+/// Returns `None` if `rects` is empty or contains empty rectangles.
+pub fn span_rects(rects: &[Rectangle]) -> Option<Rectangle> {
+    let (first, rest) = rects.split_first()?;
+
+    let mut area = 0u64;
+
+    for (index, rect) in rects.iter().enumerate() {
+        if rect.is_empty() {
+            return None;
         }
+
+        if rects[index + 1..].iter().any(|other| rect.overlaps(other)) {
+            return None;
+        }
+
+        area += rect.width() as u64 * rect.height() as u64;
+    }
+
+    let bounding = rest
+        .iter()
+        .fold(*first, |bounding, rect| bounding.bounding(rect));
+
+    // Non-overlapping rectangles within the bounding rectangle cover it completely
+    // iff their combined area matches its area.
+    if area == bounding.width() as u64 * bounding.height() as u64 {
+        Some(bounding)
+    } else {
+        None
     }
 }
 
+fn saturate_i32(value: i64) -> i32 {
+    value.clamp(i32::MIN as i64, i32::MAX as i64) as i32
+}
+
 fn at_least<T: std::cmp::Ord>(val: T, min: T) -> T {
     val.max(min)
 }
@@ -327,8 +972,57 @@ fn at_most<T: std::cmp::Ord>(val: T, max: T) -> T {
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
+    use proptest::prelude::*;
+
+    /// Number of random inputs per property test.
+    pub(crate) const CASES: u32 = 10_000;
+
+    /// `i32` values biased towards the adversarial extremes.
+    pub(crate) fn any_i32() -> impl Strategy<Value = i32> {
+        prop_oneof![
+            prop::sample::select(vec![
+                i32::MIN,
+                i32::MIN + 1,
+                -1,
+                0,
+                1,
+                i32::MAX - 1,
+                i32::MAX
+            ]),
+            -4..=4,
+            any::<i32>(),
+        ]
+    }
+
+    /// `u32` values biased towards the adversarial extremes.
+    pub(crate) fn any_u32() -> impl Strategy<Value = u32> {
+        prop_oneof![
+            prop::sample::select(vec![0, 1, i32::MAX as u32, i32::MAX as u32 + 1, u32::MAX]),
+            0..=8u32,
+            any::<u32>(),
+        ]
+    }
+
+    pub(crate) fn any_dimensions() -> impl Strategy<Value = Dimensions> {
+        (any_u32(), any_u32()).prop_map(|(width, height)| Dimensions::new(width, height))
+    }
+
+    pub(crate) fn any_rect() -> impl Strategy<Value = Rectangle> {
+        (any_i32(), any_i32(), any_dimensions()).prop_map(|(left, top, dimensions)| {
+            Rectangle::new(Position::new(left, top), dimensions)
+        })
+    }
+
+    fn any_margins() -> impl Strategy<Value = Margins> {
+        (any_u32(), any_u32(), any_u32(), any_u32())
+            .prop_map(|(left, top, right, bottom)| Margins::new(left, top, right, bottom))
+    }
+
+    fn any_clip_flags() -> impl Strategy<Value = ClipRectFlags> {
+        any::<u32>().prop_map(ClipRectFlags::from_bits_truncate)
+    }
 
     #[test]
     fn position_add_sub() {
@@ -362,26 +1056,26 @@ mod tests {
         let rect_0 = Rectangle::new(Position::new(-1, -2), Dimensions::new(4, 3));
 
         assert!(rect_0.overlaps(&rect_0));
-        assert!(rect_0.clip(&rect_0, ClipRectFlags::KeepNone).is_some());
+        assert!(rect_0.clip(&rect_0, ClipRectFlags::KEEP_NONE).is_some());
 
         let rect_1 = Rectangle::new(Position::new(1, -1), Dimensions::new(1, 4));
 
         assert!(rect_0.overlaps(&rect_1));
         assert_eq!(
-            rect_0.clip(&rect_1, ClipRectFlags::KeepNone),
+            rect_0.clip(&rect_1, ClipRectFlags::KEEP_NONE),
             Some(Rectangle::new(Position::new(1, -1), Dimensions::new(1, 3)))
         );
         assert_eq!(
-            rect_0.clip(&rect_1, ClipRectFlags::KeepAll),
+            rect_0.clip(&rect_1, ClipRectFlags::KEEP_ALL),
             Some(Rectangle::new(Position::new(1, -1), Dimensions::new(1, 2)))
         );
         assert!(rect_1.overlaps(&rect_0));
         assert_eq!(
-            rect_1.clip(&rect_0, ClipRectFlags::KeepNone),
+            rect_1.clip(&rect_0, ClipRectFlags::KEEP_NONE),
             Some(Rectangle::new(Position::new(1, -2), Dimensions::new(1, 3)))
         );
         assert_eq!(
-            rect_1.clip(&rect_0, ClipRectFlags::KeepAll),
+            rect_1.clip(&rect_0, ClipRectFlags::KEEP_ALL),
             Some(Rectangle::new(Position::new(1, -1), Dimensions::new(1, 2)))
         );
 
@@ -389,29 +1083,29 @@ mod tests {
 
         assert!(!rect_0.overlaps(&rect_2));
         assert_eq!(
-            rect_0.clip(&rect_2, ClipRectFlags::KeepNone),
+            rect_0.clip(&rect_2, ClipRectFlags::KEEP_NONE),
             Some(Rectangle::new(Position::new(-2, 0), Dimensions::new(1, 2)))
         );
-        assert!(rect_0.clip(&rect_2, ClipRectFlags::KeepAll).is_none());
+        assert!(rect_0.clip(&rect_2, ClipRectFlags::KEEP_ALL).is_none());
         assert!(!rect_2.overlaps(&rect_0));
         assert_eq!(
-            rect_2.clip(&rect_0, ClipRectFlags::KeepNone),
+            rect_2.clip(&rect_0, ClipRectFlags::KEEP_NONE),
             Some(Rectangle::new(Position::new(-1, -1), Dimensions::new(1, 2)))
         );
-        assert!(rect_2.clip(&rect_0, ClipRectFlags::KeepAll).is_none());
+        assert!(rect_2.clip(&rect_0, ClipRectFlags::KEEP_ALL).is_none());
 
         assert!(!rect_1.overlaps(&rect_2));
         assert_eq!(
-            rect_1.clip(&rect_2, ClipRectFlags::KeepNone),
+            rect_1.clip(&rect_2, ClipRectFlags::KEEP_NONE),
             Some(Rectangle::new(Position::new(-2, 0), Dimensions::new(1, 2)))
         );
-        assert!(rect_1.clip(&rect_2, ClipRectFlags::KeepAll).is_none());
+        assert!(rect_1.clip(&rect_2, ClipRectFlags::KEEP_ALL).is_none());
         assert!(!rect_2.overlaps(&rect_1));
         assert_eq!(
-            rect_2.clip(&rect_1, ClipRectFlags::KeepNone),
+            rect_2.clip(&rect_1, ClipRectFlags::KEEP_NONE),
             Some(Rectangle::new(Position::new(1, 0), Dimensions::new(1, 2)))
         );
-        assert!(rect_2.clip(&rect_1, ClipRectFlags::KeepAll).is_none());
+        assert!(rect_2.clip(&rect_1, ClipRectFlags::KEEP_ALL).is_none());
     }
 
     #[test]
@@ -470,7 +1164,7 @@ mod tests {
         //
         let rect = Rectangle::new(Position::new(-2, -1), Dimensions::new(4, 2));
         assert_eq!(
-            rect.clip(&bounds, ClipRectFlags::KeepRight),
+            rect.clip(&bounds, ClipRectFlags::KEEP_RIGHT),
             Some(Rectangle::new(Position::new(-1, -1), Dimensions::new(3, 2)))
         );
         // Resizing on the left -> clipped on the left, moved to the bottom.
@@ -486,7 +1180,7 @@ mod tests {
         //
         let rect = Rectangle::new(Position::new(-2, -3), Dimensions::new(4, 2));
         assert_eq!(
-            rect.clip(&bounds, ClipRectFlags::KeepRight),
+            rect.clip(&bounds, ClipRectFlags::KEEP_RIGHT),
             Some(Rectangle::new(Position::new(-1, -2), Dimensions::new(3, 2)))
         );
 
@@ -503,7 +1197,7 @@ mod tests {
         //
         let rect = Rectangle::new(Position::new(1, -1), Dimensions::new(3, 2));
         assert_eq!(
-            rect.clip(&bounds, ClipRectFlags::KeepLeft),
+            rect.clip(&bounds, ClipRectFlags::KEEP_LEFT),
             Some(Rectangle::new(Position::new(1, -1), Dimensions::new(2, 2)))
         );
         // Resizing on the right -> clipped on the right, moved to the bottom.
@@ -519,7 +1213,7 @@ mod tests {
         //
         let rect = Rectangle::new(Position::new(1, -3), Dimensions::new(3, 2));
         assert_eq!(
-            rect.clip(&bounds, ClipRectFlags::KeepLeft),
+            rect.clip(&bounds, ClipRectFlags::KEEP_LEFT),
             Some(Rectangle::new(Position::new(1, -2), Dimensions::new(2, 2)))
         );
 
@@ -536,7 +1230,7 @@ mod tests {
         //
         let rect = Rectangle::new(Position::new(-1, -3), Dimensions::new(2, 3));
         assert_eq!(
-            rect.clip(&bounds, ClipRectFlags::KeepBottom),
+            rect.clip(&bounds, ClipRectFlags::KEEP_BOTTOM),
             Some(Rectangle::new(Position::new(-1, -2), Dimensions::new(2, 2)))
         );
         // Resizing on the top -> clipped on the top, moved to the right.
@@ -552,7 +1246,7 @@ mod tests {
         //
         let rect = Rectangle::new(Position::new(-2, -3), Dimensions::new(2, 3));
         assert_eq!(
-            rect.clip(&bounds, ClipRectFlags::KeepBottom),
+            rect.clip(&bounds, ClipRectFlags::KEEP_BOTTOM),
             Some(Rectangle::new(Position::new(-1, -2), Dimensions::new(2, 2)))
         );
 
@@ -570,7 +1264,7 @@ mod tests {
         //
         let rect = Rectangle::new(Position::new(0, 2), Dimensions::new(2, 3));
         assert_eq!(
-            rect.clip(&bounds, ClipRectFlags::KeepTop),
+            rect.clip(&bounds, ClipRectFlags::KEEP_TOP),
             Some(Rectangle::new(Position::new(0, 2), Dimensions::new(2, 1)))
         );
         // Resizing on the bottom -> clipped on the bottom, moved to the left.
@@ -587,7 +1281,7 @@ mod tests {
         //
         let rect = Rectangle::new(Position::new(2, 2), Dimensions::new(2, 3));
         assert_eq!(
-            rect.clip(&bounds, ClipRectFlags::KeepTop),
+            rect.clip(&bounds, ClipRectFlags::KEEP_TOP),
             Some(Rectangle::new(Position::new(1, 2), Dimensions::new(2, 1)))
         );
 
@@ -606,7 +1300,7 @@ mod tests {
         assert_eq!(
             rect.clip(
                 &bounds,
-                ClipRectFlags::KeepRight | ClipRectFlags::KeepBottom
+                ClipRectFlags::KEEP_RIGHT | ClipRectFlags::KEEP_BOTTOM
             ),
             Some(Rectangle::new(Position::new(-1, -2), Dimensions::new(1, 1)))
         );
@@ -624,7 +1318,7 @@ mod tests {
         //
         let rect = Rectangle::new(Position::new(-2, 1), Dimensions::new(2, 3));
         assert_eq!(
-            rect.clip(&bounds, ClipRectFlags::KeepRight | ClipRectFlags::KeepTop),
+            rect.clip(&bounds, ClipRectFlags::KEEP_RIGHT | ClipRectFlags::KEEP_TOP),
             Some(Rectangle::new(Position::new(-1, 1), Dimensions::new(1, 2)))
         );
 
@@ -641,7 +1335,10 @@ mod tests {
         //
         let rect = Rectangle::new(Position::new(2, -3), Dimensions::new(2, 3));
         assert_eq!(
-            rect.clip(&bounds, ClipRectFlags::KeepLeft | ClipRectFlags::KeepBottom),
+            rect.clip(
+                &bounds,
+                ClipRectFlags::KEEP_LEFT | ClipRectFlags::KEEP_BOTTOM
+            ),
             Some(Rectangle::new(Position::new(2, -2), Dimensions::new(1, 2)))
         );
 
@@ -658,7 +1355,7 @@ mod tests {
         //
         let rect = Rectangle::new(Position::new(1, 2), Dimensions::new(4, 2));
         assert_eq!(
-            rect.clip(&bounds, ClipRectFlags::KeepAll),
+            rect.clip(&bounds, ClipRectFlags::KEEP_ALL),
             Some(Rectangle::new(Position::new(1, 2), Dimensions::new(2, 1)))
         );
 
@@ -675,7 +1372,7 @@ mod tests {
         //
         let rect = Rectangle::new(Position::new(-3, -3), Dimensions::new(3, 4));
         assert_eq!(
-            rect.clip(&bounds, ClipRectFlags::KeepAll),
+            rect.clip(&bounds, ClipRectFlags::KEEP_ALL),
             Some(Rectangle::new(Position::new(-1, -2), Dimensions::new(1, 3)))
         );
 
@@ -692,7 +1389,7 @@ mod tests {
         //
         let rect = Rectangle::new(Position::new(-3, -3), Dimensions::new(1, 1));
         assert!(!rect.overlaps(&bounds));
-        assert_eq!(rect.clip(&bounds, ClipRectFlags::KeepAll), None);
+        assert_eq!(rect.clip(&bounds, ClipRectFlags::KEEP_ALL), None);
 
         // Non-intersecting rectangle to the right and bottom.
         //
@@ -707,7 +1404,320 @@ mod tests {
         //
         let rect = Rectangle::new(Position::new(3, 3), Dimensions::new(1, 1));
         assert!(!rect.overlaps(&bounds));
-        assert_eq!(rect.clip(&bounds, ClipRectFlags::KeepAll), None);
+        assert_eq!(rect.clip(&bounds, ClipRectFlags::KEEP_ALL), None);
+    }
+
+    #[test]
+    fn clip_with() {
+        let bounds = Rectangle::new(Position::new(0, 0), Dimensions::new(4, 4));
+
+        // Too wide, both horizontal sides kept.
+        let rect = Rectangle::new(Position::new(-1, 1), Dimensions::new(6, 2));
+        let horizontal = ClipRectFlags::horizontal();
+
+        let clipped = Rectangle::new(Position::new(0, 1), Dimensions::new(4, 2));
+        assert_eq!(
+            rect.clip_with(&bounds, horizontal, Strictness::BestEffort),
+            Ok(Some(clipped))
+        );
+        assert_eq!(
+            rect.clip_with(&bounds, horizontal, Strictness::FailOnConflict),
+            Err(ClipConflict { sides: horizontal })
+        );
+        assert_eq!(
+            rect.clip_with(&bounds, horizontal, Strictness::ShrinkToFit),
+            Ok(Some(clipped))
+        );
+
+        // No conflicts.
+        let rect = Rectangle::new(Position::new(1, 1), Dimensions::new(6, 2));
+        assert_eq!(
+            rect.clip_with(
+                &bounds,
+                ClipRectFlags::KEEP_LEFT,
+                Strictness::FailOnConflict
+            ),
+            Ok(Some(Rectangle::new(
+                Position::new(1, 1),
+                Dimensions::new(3, 2)
+            )))
+        );
+
+        // Outside of the bounds, all sides kept.
+        let rect = Rectangle::new(Position::new(5, 1), Dimensions::new(2, 2));
+        assert_eq!(
+            rect.clip_with(&bounds, ClipRectFlags::KEEP_ALL, Strictness::BestEffort),
+            Ok(None)
+        );
+        assert_eq!(
+            rect.clip_with(&bounds, ClipRectFlags::KEEP_ALL, Strictness::FailOnConflict),
+            Err(ClipConflict { sides: horizontal })
+        );
+        assert_eq!(
+            rect.clip_with(&bounds, ClipRectFlags::KEEP_ALL, Strictness::ShrinkToFit),
+            Ok(Some(Rectangle::new(
+                Position::new(2, 1),
+                Dimensions::new(2, 2)
+            )))
+        );
+    }
+
+    #[test]
+    fn clamp_with() {
+        let min_dimensions = Dimensions::new(3, 2);
+
+        // Too narrow, both horizontal sides kept.
+        let rect = Rectangle::new(Position::new(-1, -2), Dimensions::new(2, 3));
+        let horizontal = ClipRectFlags::horizontal();
+
+        assert_eq!(
+            rect.clamp_with(min_dimensions, horizontal, Strictness::BestEffort),
+            Ok(Rectangle::new(Position::new(-1, -2), Dimensions::new(3, 3)))
+        );
+        assert_eq!(
+            rect.clamp(min_dimensions, horizontal),
+            Rectangle::new(Position::new(-1, -2), Dimensions::new(3, 3))
+        );
+        assert_eq!(
+            rect.clamp_with(min_dimensions, horizontal, Strictness::FailOnConflict),
+            Err(ClipConflict { sides: horizontal })
+        );
+        assert_eq!(
+            rect.clamp_with(min_dimensions, horizontal, Strictness::ShrinkToFit),
+            Ok(rect)
+        );
+
+        // Too small, all sides kept.
+        let rect = Rectangle::new(Position::new(0, 0), Dimensions::new(1, 1));
+        assert_eq!(
+            rect.clamp_with(
+                min_dimensions,
+                ClipRectFlags::KEEP_ALL,
+                Strictness::FailOnConflict
+            ),
+            Err(ClipConflict {
+                sides: ClipRectFlags::KEEP_ALL
+            })
+        );
+        assert_eq!(
+            rect.clamp_with(
+                min_dimensions,
+                ClipRectFlags::KEEP_ALL,
+                Strictness::BestEffort
+            ),
+            Ok(Rectangle::new(Position::new(0, 0), min_dimensions))
+        );
+
+        // No conflicts.
+        assert_eq!(
+            rect.clamp_with(
+                min_dimensions,
+                ClipRectFlags::KEEP_RIGHT | ClipRectFlags::KEEP_BOTTOM,
+                Strictness::FailOnConflict
+            ),
+            Ok(Rectangle::new(Position::new(-2, -1), min_dimensions))
+        );
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(CASES))]
+
+        #[test]
+        fn clip_properties(
+            rect in any_rect(),
+            bounds in any_rect(),
+            clip_flags in any_clip_flags(),
+        ) {
+            // The clipped rectangle is never empty, within the bounds, and no larger than the original.
+            let within_bounds = |clipped: &Rectangle| {
+                !clipped.is_empty()
+                    && clipped.left() >= bounds.left()
+                    && clipped.exact_right() <= bounds.exact_right()
+                    && clipped.top() >= bounds.top()
+                    && clipped.exact_bottom() <= bounds.exact_bottom()
+                    && clipped.width() <= rect.width()
+                    && clipped.height() <= rect.height()
+            };
+
+            if let Some(clipped) = rect.clip(&bounds, clip_flags) {
+                assert!(
+                    within_bounds(&clipped),
+                    "{} {} {}",
+                    rect,
+                    bounds,
+                    clip_flags
+                );
+            }
+
+            // Kept sides do not move unless they conflict with the bounds.
+            if let Ok(Some(clipped)) =
+                rect.clip_with(&bounds, clip_flags, Strictness::FailOnConflict)
+            {
+                assert!(within_bounds(&clipped));
+                assert!(
+                    !clip_flags.contains(ClipRectFlags::KEEP_LEFT) || clipped.left() == rect.left()
+                );
+                assert!(
+                    !clip_flags.contains(ClipRectFlags::KEEP_TOP) || clipped.top() == rect.top()
+                );
+                assert!(
+                    !clip_flags.contains(ClipRectFlags::KEEP_RIGHT)
+                        || clipped.exact_right() == rect.exact_right()
+                );
+                assert!(
+                    !clip_flags.contains(ClipRectFlags::KEEP_BOTTOM)
+                        || clipped.exact_bottom() == rect.exact_bottom()
+                );
+            }
+
+            // Shrinking to fit always succeeds for non-empty rectangles.
+            let clipped = rect
+                .clip_with(&bounds, clip_flags, Strictness::ShrinkToFit)
+                .unwrap();
+            assert_eq!(
+                clipped.is_some(),
+                !rect.is_empty() && !bounds.is_empty(),
+                "{} {} {}",
+                rect,
+                bounds,
+                clip_flags
+            );
+            assert!(clipped.iter().all(within_bounds));
+        }
+    }
+
+    #[test]
+    fn empty() {
+        let rect = Rectangle::new(Position::new(0, 0), Dimensions::new(4, 4));
+        let zero_width = Rectangle::new(Position::new(1, 1), Dimensions::new(0, 2));
+        let zero_height = Rectangle::new(Position::new(1, 1), Dimensions::new(2, 0));
+        let outside = Rectangle::new(Position::new(8, 8), Dimensions::new(0, 0));
+
+        assert!(!rect.is_empty());
+        assert!(zero_width.is_empty() && zero_height.is_empty() && outside.is_empty());
+
+        assert_eq!(rect.normalized(), rect);
+        assert_eq!(zero_width.normalized(), zero_height.normalized());
+        assert_eq!(
+            zero_width.normalized(),
+            Rectangle::new(Position::new(1, 1), Dimensions::default())
+        );
+
+        for empty in [zero_width, zero_height, outside].iter() {
+            assert!(!rect.overlaps(empty) && !empty.overlaps(&rect));
+            assert!(!empty.overlaps(empty));
+
+            assert!(rect.contains(empty) && empty.contains(empty));
+            assert!(!empty.contains(&rect));
+
+            assert_eq!(rect.bounding(empty), rect);
+            assert_eq!(empty.bounding(&rect), rect);
+            assert_eq!(empty.bounding(empty), empty.normalized());
+
+            assert_eq!(empty.clip(&rect, ClipRectFlags::KEEP_NONE), None);
+            assert_eq!(rect.clip(empty, ClipRectFlags::KEEP_NONE), None);
+        }
+    }
+
+    #[test]
+    fn intersection() {
+        let rect = Rectangle::new(Position::new(0, 0), Dimensions::new(4, 4));
+
+        assert!(rect.contains_point(Position::new(0, 0)));
+        assert!(rect.contains_point(Position::new(3, 3)));
+        assert!(!rect.contains_point(Position::new(4, 3)));
+        assert!(!rect.contains_point(Position::new(-1, 0)));
+
+        assert_eq!(
+            rect.intersection(&Rectangle::new(Position::new(2, -1), Dimensions::new(4, 2))),
+            Some(Rectangle::new(Position::new(2, 0), Dimensions::new(2, 1)))
+        );
+        assert_eq!(rect.intersection(&rect), Some(rect));
+        assert_eq!(
+            rect.intersection(&Rectangle::new(Position::new(4, 0), Dimensions::new(4, 4))),
+            None
+        );
+    }
+
+    #[test]
+    fn degenerate() {
+        let rect = Rectangle::new(Position::new(-1, 1), Dimensions::new(2, 3));
+        assert_eq!(rect.try_right(), Some(1));
+        assert_eq!(rect.try_bottom(), Some(4));
+        assert!(!rect.is_degenerate());
+
+        let rect = Rectangle::new(Position::new(i32::MIN, 0), Dimensions::new(u32::MAX, 1));
+        assert_eq!(rect.try_right(), Some(i32::MAX));
+        assert!(!rect.is_degenerate());
+
+        let rect = Rectangle::new(
+            Position::new(1, i32::MAX),
+            Dimensions::new(i32::MAX as u32, 1),
+        );
+        assert_eq!(rect.try_right(), None);
+        assert_eq!(rect.right(), i32::MAX);
+        assert_eq!(rect.try_bottom(), None);
+        assert_eq!(rect.bottom(), i32::MAX);
+        assert!(rect.is_degenerate());
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(CASES))]
+
+        #[test]
+        fn bounding_properties(
+            first in any_rect(),
+            second in any_rect(),
+            shrink in any_margins(),
+            expand in any_margins(),
+        ) {
+            let bounding = first.bounding(&second);
+
+            if !first.is_empty() || !second.is_empty() {
+                assert_eq!(bounding, second.bounding(&first));
+            }
+            assert_eq!(first.overlaps(&second), second.overlaps(&first));
+
+            if !bounding.is_degenerate()
+                && bounding.width() < u32::MAX
+                && bounding.height() < u32::MAX
+            {
+                assert!(bounding.contains(&first) && bounding.contains(&second));
+            }
+
+            let _ = first.shrink(shrink);
+            let _ = first.expand(expand);
+            let _ = first.fit(second.dimensions, FitMode::Cover);
+        }
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(CASES))]
+
+        #[test]
+        fn clamp_properties(
+            rect in any_rect(),
+            min_dimensions in any_dimensions(),
+            clip_flags in any_clip_flags(),
+        ) {
+            // The clamped rectangle is at least as large as the minimum and the original.
+            let clamped = rect.clamp(min_dimensions, clip_flags);
+            assert!(clamped.width() >= min_dimensions.width.max(rect.width()));
+            assert!(clamped.height() >= min_dimensions.height.max(rect.height()));
+
+            let clamped = rect
+                .clamp_with(min_dimensions, clip_flags, Strictness::ShrinkToFit)
+                .unwrap();
+            assert!(clamped.width() >= rect.width());
+            assert!(clamped.height() >= rect.height());
+
+            if let Ok(clamped) =
+                rect.clamp_with(min_dimensions, clip_flags, Strictness::FailOnConflict)
+            {
+                assert!(clamped.width() >= min_dimensions.width);
+                assert!(clamped.height() >= min_dimensions.height);
+            }
+        }
     }
 
     #[test]
@@ -717,28 +1727,28 @@ mod tests {
         // Resizing on the left.
         let rect = Rectangle::new(Position::new(-1, -2), Dimensions::new(2, 3));
         assert_eq!(
-            rect.clamp(min_dimensions, ClipRectFlags::KeepRight),
+            rect.clamp(min_dimensions, ClipRectFlags::KEEP_RIGHT),
             Rectangle::new(Position::new(-2, -2), Dimensions::new(3, 3))
         );
 
         // Resizing on the right.
         let rect = Rectangle::new(Position::new(-3, -2), Dimensions::new(2, 3));
         assert_eq!(
-            rect.clamp(min_dimensions, ClipRectFlags::KeepLeft),
+            rect.clamp(min_dimensions, ClipRectFlags::KEEP_LEFT),
             Rectangle::new(Position::new(-3, -2), Dimensions::new(3, 3))
         );
 
         // Resizing on the top.
         let rect = Rectangle::new(Position::new(-3, 0), Dimensions::new(4, 1));
         assert_eq!(
-            rect.clamp(min_dimensions, ClipRectFlags::KeepBottom),
+            rect.clamp(min_dimensions, ClipRectFlags::KEEP_BOTTOM),
             Rectangle::new(Position::new(-3, -1), Dimensions::new(4, 2))
         );
 
         // Resizing on the bottom.
         let rect = Rectangle::new(Position::new(-3, -2), Dimensions::new(4, 1));
         assert_eq!(
-            rect.clamp(min_dimensions, ClipRectFlags::KeepTop),
+            rect.clamp(min_dimensions, ClipRectFlags::KEEP_TOP),
             Rectangle::new(Position::new(-3, -2), Dimensions::new(4, 2))
         );
 
@@ -747,7 +1757,7 @@ mod tests {
         assert_eq!(
             rect.clamp(
                 min_dimensions,
-                ClipRectFlags::KeepRight | ClipRectFlags::KeepBottom
+                ClipRectFlags::KEEP_RIGHT | ClipRectFlags::KEEP_BOTTOM
             ),
             Rectangle::new(Position::new(-2, -1), Dimensions::new(3, 2))
         );
@@ -757,7 +1767,7 @@ mod tests {
         assert_eq!(
             rect.clamp(
                 min_dimensions,
-                ClipRectFlags::KeepRight | ClipRectFlags::KeepTop
+                ClipRectFlags::KEEP_RIGHT | ClipRectFlags::KEEP_TOP
             ),
             Rectangle::new(Position::new(-2, -2), Dimensions::new(3, 2))
         );
@@ -767,7 +1777,7 @@ mod tests {
         assert_eq!(
             rect.clamp(
                 min_dimensions,
-                ClipRectFlags::KeepLeft | ClipRectFlags::KeepTop
+                ClipRectFlags::KEEP_LEFT | ClipRectFlags::KEEP_TOP
             ),
             Rectangle::new(Position::new(-3, -2), Dimensions::new(3, 2))
         );
@@ -777,9 +1787,232 @@ mod tests {
         assert_eq!(
             rect.clamp(
                 min_dimensions,
-                ClipRectFlags::KeepLeft | ClipRectFlags::KeepBottom
+                ClipRectFlags::KEEP_LEFT | ClipRectFlags::KEEP_BOTTOM
             ),
             Rectangle::new(Position::new(-3, -1), Dimensions::new(3, 2))
         );
     }
+
+    #[test]
+    fn fit() {
+        let target = Dimensions::new(1920, 1080);
+
+        // Same aspect ratio.
+        let content = Dimensions::new(1280, 720);
+        let fitted = Rectangle::new(Position::new(0, 0), Dimensions::new(1920, 1080));
+        assert_eq!(fit_dimensions(content, target, FitMode::Contain), fitted);
+        assert_eq!(fit_dimensions(content, target, FitMode::Cover), fitted);
+        assert_eq!(fit_dimensions(content, target, FitMode::Stretch), fitted);
+
+        // Narrower content - pillarboxed / cropped at the top and bottom.
+        let content = Dimensions::new(640, 480);
+        assert_eq!(
+            fit_dimensions(content, target, FitMode::Contain),
+            Rectangle::new(Position::new(240, 0), Dimensions::new(1440, 1080))
+        );
+        assert_eq!(
+            fit_dimensions(content, target, FitMode::Cover),
+            Rectangle::new(Position::new(0, -180), Dimensions::new(1920, 1440))
+        );
+        assert_eq!(
+            fit_dimensions(content, target, FitMode::Stretch),
+            Rectangle::new(Position::new(0, 0), target)
+        );
+
+        // Wider content - letterboxed / cropped on the left and right.
+        let content = Dimensions::new(2560, 1080);
+        assert_eq!(
+            fit_dimensions(content, target, FitMode::Contain),
+            Rectangle::new(Position::new(0, 135), Dimensions::new(1920, 810))
+        );
+        assert_eq!(
+            fit_dimensions(content, target, FitMode::Cover),
+            Rectangle::new(Position::new(-320, 0), Dimensions::new(2560, 1080))
+        );
+
+        // Empty content.
+        assert_eq!(
+            fit_dimensions(Dimensions::new(0, 480), target, FitMode::Contain),
+            Rectangle::new(Position::new(960, 540), Dimensions::new(0, 0))
+        );
+
+        // Fit within a rectangle.
+        let rect = Rectangle::new(Position::new(-1920, 100), target);
+        assert_eq!(
+            rect.fit(Dimensions::new(640, 480), FitMode::Contain),
+            Rectangle::new(Position::new(-1680, 100), Dimensions::new(1440, 1080))
+        );
+    }
+
+    #[test]
+    fn expand_shrink() {
+        let rect = Rectangle::new(Position::new(-10, 20), Dimensions::new(100, 50));
+        let margins = Margins::new(1, 2, 3, 4);
+
+        assert_eq!(
+            rect.expand(margins),
+            Rectangle::new(Position::new(-11, 18), Dimensions::new(104, 56))
+        );
+        assert_eq!(
+            rect.shrink(margins),
+            Rectangle::new(Position::new(-9, 22), Dimensions::new(96, 44))
+        );
+        assert_eq!(rect.expand(margins).shrink(margins), rect);
+        assert_eq!(rect.expand(Margins::default()), rect);
+        assert_eq!(rect.shrink(Margins::default()), rect);
+
+        // Shrunk down to zero dimensions.
+        assert_eq!(
+            rect.shrink(Margins::uniform(60)),
+            Rectangle::new(Position::new(50, 70), Dimensions::new(0, 0))
+        );
+        assert_eq!(
+            rect.shrink(Margins::new(u32::MAX, 0, u32::MAX, 0)),
+            Rectangle::new(Position::new(90, 20), Dimensions::new(0, 50))
+        );
+
+        // Saturates.
+        assert_eq!(
+            rect.expand(Margins::uniform(u32::MAX)),
+            Rectangle::new(
+                Position::new(i32::MIN, i32::MIN),
+                Dimensions::new(u32::MAX, u32::MAX)
+            )
+        );
+    }
+
+    #[test]
+    fn margins_between() {
+        let outer = Rectangle::new(Position::new(0, 0), Dimensions::new(1920, 1080));
+        let taskbar_bottom = Rectangle::new(Position::new(0, 0), Dimensions::new(1920, 1040));
+        let taskbar_left = Rectangle::new(Position::new(62, 0), Dimensions::new(1858, 1080));
+
+        assert_eq!(
+            Margins::between(&outer, &taskbar_bottom),
+            Margins::new(0, 0, 0, 40)
+        );
+        assert_eq!(
+            Margins::between(&outer, &taskbar_left),
+            Margins::new(62, 0, 0, 0)
+        );
+        assert!(Margins::between(&outer, &outer).is_zero());
+        assert_eq!(
+            outer.shrink(Margins::between(&outer, &taskbar_left)),
+            taskbar_left
+        );
+
+        // Inner rectangle outside of the outer one.
+        assert!(Margins::between(&taskbar_left, &outer).is_zero());
+    }
+
+    #[test]
+    fn span_rects() {
+        use super::span_rects;
+
+        let left = Rectangle::new(Position::new(-1920, 0), Dimensions::new(1920, 1080));
+        let center = Rectangle::new(Position::new(0, 0), Dimensions::new(1920, 1080));
+        let right = Rectangle::new(Position::new(1920, 0), Dimensions::new(1920, 1080));
+        let below = Rectangle::new(Position::new(0, 1080), Dimensions::new(1920, 1080));
+        let below_right = Rectangle::new(Position::new(1920, 1080), Dimensions::new(1920, 1080));
+
+        assert_eq!(span_rects(&[]), None);
+        assert_eq!(span_rects(&[center]), Some(center));
+
+        // Side by side.
+        assert_eq!(
+            span_rects(&[center, left]),
+            Some(Rectangle::new(
+                Position::new(-1920, 0),
+                Dimensions::new(3840, 1080)
+            ))
+        );
+        assert_eq!(
+            span_rects(&[left, center, right]),
+            Some(Rectangle::new(
+                Position::new(-1920, 0),
+                Dimensions::new(5760, 1080)
+            ))
+        );
+
+        // Stacked.
+        assert_eq!(
+            span_rects(&[center, below]),
+            Some(Rectangle::new(
+                Position::new(0, 0),
+                Dimensions::new(1920, 2160)
+            ))
+        );
+
+        // 2x2 grid.
+        assert_eq!(
+            span_rects(&[center, right, below, below_right]),
+            Some(Rectangle::new(
+                Position::new(0, 0),
+                Dimensions::new(3840, 2160)
+            ))
+        );
+
+        // Gap.
+        assert_eq!(span_rects(&[left, right]), None);
+
+        // L-shape.
+        assert_eq!(span_rects(&[center, right, below]), None);
+
+        // Diagonal.
+        assert_eq!(span_rects(&[center, below_right]), None);
+
+        // Different heights.
+        let right_short = Rectangle::new(Position::new(1920, 0), Dimensions::new(1280, 1024));
+        assert_eq!(span_rects(&[center, right_short]), None);
+
+        // Overlapping / duplicate.
+        assert_eq!(span_rects(&[center, center]), None);
+
+        // Empty.
+        assert_eq!(span_rects(&[center, Rectangle::default()]), None);
+    }
+
+    #[test]
+    fn clip_rect_flags() {
+        assert_eq!(ClipRectFlags::KeepLeft, ClipRectFlags::KEEP_LEFT);
+        assert_eq!(ClipRectFlags::KeepAll, ClipRectFlags::KEEP_ALL);
+
+        assert_eq!(
+            ClipRectFlags::from_sides(true, false, true, false),
+            ClipRectFlags::KEEP_LEFT | ClipRectFlags::KEEP_TOP
+        );
+        assert_eq!(
+            ClipRectFlags::horizontal() | ClipRectFlags::vertical(),
+            ClipRectFlags::KEEP_ALL
+        );
+
+        assert_eq!(
+            "KEEP_LEFT | KEEP_TOP".parse(),
+            Ok(ClipRectFlags::KEEP_LEFT | ClipRectFlags::KEEP_TOP)
+        );
+        assert_eq!(
+            "KeepLeft|KeepTop".parse(),
+            Ok(ClipRectFlags::KEEP_LEFT | ClipRectFlags::KEEP_TOP)
+        );
+        assert_eq!("KeepNone".parse(), Ok(ClipRectFlags::KEEP_NONE));
+        assert_eq!("KEEP_ALL".parse(), Ok(ClipRectFlags::KEEP_ALL));
+        assert_eq!("KeepSideways".parse::<ClipRectFlags>(), Err(()));
+        assert_eq!("".parse::<ClipRectFlags>(), Err(()));
+
+        assert_eq!(ClipRectFlags::KEEP_NONE.to_string(), "KEEP_NONE");
+        assert_eq!(
+            (ClipRectFlags::KEEP_RIGHT | ClipRectFlags::KEEP_BOTTOM).to_string(),
+            "KEEP_RIGHT | KEEP_BOTTOM"
+        );
+
+        for flags in [
+            ClipRectFlags::KEEP_NONE,
+            ClipRectFlags::KEEP_LEFT | ClipRectFlags::KEEP_BOTTOM,
+            ClipRectFlags::KEEP_ALL,
+        ]
+        .iter()
+        {
+            assert_eq!(flags.to_string().parse(), Ok(*flags));
+        }
+    }
 }
@@ -0,0 +1,309 @@
+use crate::{Dimensions, Position, Rectangle};
+
+/// Set of pixels in display space (e.g. the virtual desktop, which is not necessarily rectangular),
+/// represented as a list of disjoint, non-empty rectangles.
+///
+/// NOTE - pixels with coordinates outside of the `i32` range are not part of any region.
+///
+/// NOTE - the same region may be represented by different lists of rectangles,
+/// depending on the order of the operations it was built with.
+#[derive(Clone, Debug, Default)]
+pub struct Region {
+    rects: Vec<Rectangle>,
+}
+
+impl From<Rectangle> for Region {
+    fn from(rect: Rectangle) -> Self {
+        Self::from_rects(&[rect])
+    }
+}
+
+impl Region {
+    /// Creates a new empty region.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates the region which is the union of the (possibly overlapping) `rects`.
+    pub fn from_rects(rects: &[Rectangle]) -> Self {
+        let mut region = Self::new();
+
+        for rect in rects {
+            region.add_rect(rect);
+        }
+
+        region
+    }
+
+    /// Returns the disjoint, non-empty rectangles which make up the region.
+    pub fn rects(&self) -> &[Rectangle] {
+        &self.rects
+    }
+
+    /// Returns `true` if the region contains no pixels.
+    pub fn is_empty(&self) -> bool {
+        self.rects.is_empty()
+    }
+
+    /// Returns the number of pixels in the region.
+    pub fn area(&self) -> u64 {
+        self.rects
+            .iter()
+            .map(|rect| rect.width() as u64 * rect.height() as u64)
+            .sum()
+    }
+
+    /// Returns the bounding rectangle of the region, or `None` if the region is empty.
+    pub fn bounds(&self) -> Option<Rectangle> {
+        let (first, rest) = self.rects.split_first()?;
+
+        Some(
+            rest.iter()
+                .fold(*first, |bounds, rect| bounds.bounding(rect)),
+        )
+    }
+
+    /// Returns `true` if the `point` lies within the region.
+    pub fn contains_point(&self, point: Position) -> bool {
+        self.rects.iter().any(|rect| rect.contains_point(point))
+    }
+
+    /// Returns `true` if the region overlaps the `rect`.
+    pub fn overlaps(&self, rect: &Rectangle) -> bool {
+        self.rects
+            .iter()
+            .any(|region_rect| region_rect.overlaps(rect))
+    }
+
+    /// Returns `true` if the region completely contains the `rect`.
+    pub fn contains(&self, rect: &Rectangle) -> bool {
+        Region::from(*rect).subtract(self).is_empty()
+    }
+
+    /// Returns the union of this and the `other` region.
+    pub fn union(&self, other: &Region) -> Region {
+        let mut union = self.clone();
+
+        for rect in other.rects.iter() {
+            union.add_rect(rect);
+        }
+
+        union
+    }
+
+    /// Returns the pixels of this region which are not in the `other` region.
+    pub fn subtract(&self, other: &Region) -> Region {
+        let mut rects = self.rects.clone();
+
+        for subtracted in other.rects.iter() {
+            rects = rects
+                .iter()
+                .flat_map(|rect| subtract_rect(rect, subtracted))
+                .collect();
+        }
+
+        Region { rects }
+    }
+
+    /// Returns the pixels which are in both this and the `other` region.
+    pub fn intersect(&self, other: &Region) -> Region {
+        // Intersections of disjoint rectangles are disjoint.
+        Region {
+            rects: self
+                .rects
+                .iter()
+                .flat_map(|rect| {
+                    other
+                        .rects
+                        .iter()
+                        .filter_map(move |other_rect| rect.intersection(other_rect))
+                })
+                .collect(),
+        }
+    }
+
+    /// Adds the parts of the `rect` not yet in the region.
+    fn add_rect(&mut self, rect: &Rectangle) {
+        let mut added = vec![truncate(rect)];
+
+        for region_rect in self.rects.iter() {
+            added = added
+                .iter()
+                .flat_map(|added_rect| subtract_rect(added_rect, region_rect))
+                .collect();
+        }
+
+        self.rects
+            .extend(added.into_iter().filter(|rect| !rect.is_empty()));
+    }
+}
+
+/// Returns the part of the `rect` with pixel coordinates within the `i32` range.
+fn truncate(rect: &Rectangle) -> Rectangle {
+    // Up to `2^32` for the `i32::MIN` origin, which does not fit in `u32`.
+    let max_extent =
+        |origin: i32| (i32::MAX as i64 + 1 - origin as i64).min(u32::MAX as i64) as u32;
+
+    Rectangle::new(
+        Position::new(rect.left(), rect.top()),
+        Dimensions::new(
+            rect.width().min(max_extent(rect.left())),
+            rect.height().min(max_extent(rect.top())),
+        ),
+    )
+}
+
+/// Returns the (up to four) disjoint, non-empty parts of the `rect` outside of the `subtracted` rectangle:
+/// the full-width bands above and below it, and the parts to the left and right of it in between.
+fn subtract_rect(rect: &Rectangle, subtracted: &Rectangle) -> Vec<Rectangle> {
+    let intersection = match rect.intersection(subtracted) {
+        Some(intersection) => intersection,
+        None if rect.is_empty() => return Vec::new(),
+        None => return vec![*rect],
+    };
+
+    // Exact coordinates - the sides may lie outside of the `i32` range.
+    let (left, top) = (rect.left() as i64, rect.top() as i64);
+    let (right, bottom) = (rect.exact_right(), rect.exact_bottom());
+
+    let (inner_left, inner_top) = (intersection.left() as i64, intersection.top() as i64);
+    let (inner_right, inner_bottom) = (intersection.exact_right(), intersection.exact_bottom());
+
+    // All parts are within the (truncated) `rect`, so they fit the `i32` / `u32` range.
+    let part = |left: i64, top: i64, right: i64, bottom: i64| {
+        if left < right && top < bottom {
+            Some(Rectangle::new(
+                Position::new(left as i32, top as i32),
+                Dimensions::new((right - left) as u32, (bottom - top) as u32),
+            ))
+        } else {
+            None
+        }
+    };
+
+    [
+        part(left, top, right, inner_top),
+        part(left, inner_bottom, right, bottom),
+        part(left, inner_top, inner_left, inner_bottom),
+        part(inner_right, inner_top, right, inner_bottom),
+    ]
+    .iter()
+    .flatten()
+    .copied()
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rectangle::tests::{any_rect, CASES};
+    use proptest::prelude::*;
+
+    fn rect(left: i32, top: i32, width: u32, height: u32) -> Rectangle {
+        Rectangle::new(Position::new(left, top), Dimensions::new(width, height))
+    }
+
+    fn is_disjoint(region: &Region) -> bool {
+        region.rects().iter().enumerate().all(|(index, rect)| {
+            !rect.is_empty()
+                && region.rects()[index + 1..]
+                    .iter()
+                    .all(|other| !rect.overlaps(other))
+        })
+    }
+
+    #[test]
+    fn region() {
+        // Two side-by-side displays of different heights.
+        let desktop = Region::from_rects(&[rect(0, 0, 2560, 1440), rect(2560, 0, 1920, 1080)]);
+        assert_eq!(desktop.area(), 2560 * 1440 + 1920 * 1080);
+        assert_eq!(desktop.bounds(), Some(rect(0, 0, 4480, 1440)));
+        assert!(desktop.contains_point(Position::new(3000, 500)));
+        assert!(!desktop.contains_point(Position::new(3000, 1200)));
+        assert!(desktop.contains(&rect(2000, 100, 1000, 100)));
+        assert!(!desktop.contains(&rect(2000, 1000, 1000, 100)));
+
+        // Overlapping union.
+        let union = desktop.union(&Region::from(rect(2000, 1000, 1000, 1000)));
+        assert!(is_disjoint(&union));
+        assert_eq!(
+            union.area(),
+            desktop.area() + 1000 * 1000 - 560 * 440 - 440 * 80
+        );
+
+        // Minus a toolbar across both displays.
+        let toolbar = Region::from(rect(2000, 0, 1000, 40));
+        let available = desktop.subtract(&toolbar);
+        assert!(is_disjoint(&available));
+        assert_eq!(available.area(), desktop.area() - 1000 * 40);
+        assert!(!available.contains_point(Position::new(2500, 20)));
+        assert!(available.contains_point(Position::new(2500, 40)));
+        assert!(!available.overlaps(&rect(2100, 0, 10, 10)));
+
+        let intersection = desktop.intersect(&Region::from(rect(2000, 1000, 1000, 1000)));
+        assert!(is_disjoint(&intersection));
+        assert_eq!(intersection.area(), 560 * 440 + 440 * 80);
+
+        assert!(Region::new().is_empty());
+        assert!(Region::from(rect(0, 0, 0, 1)).is_empty());
+        assert_eq!(Region::new().bounds(), None);
+        assert!(desktop.subtract(&desktop).is_empty());
+
+        // Rectangles at the `i32::MIN` origin.
+        let corner = Region::from(rect(i32::MIN, i32::MIN, 100, 100));
+        assert_eq!(corner.area(), 100 * 100);
+        assert!(corner.contains_point(Position::new(i32::MIN, i32::MIN)));
+        assert!(!corner.contains_point(Position::new(i32::MIN + 100, i32::MIN)));
+
+        let whole = Region::from(rect(i32::MIN, i32::MIN, u32::MAX, u32::MAX));
+        assert_eq!(
+            whole.bounds(),
+            Some(rect(i32::MIN, i32::MIN, u32::MAX, u32::MAX))
+        );
+        assert!(whole.contains(&desktop.bounds().unwrap()));
+        assert!(whole
+            .subtract(&corner)
+            .contains_point(Position::new(i32::MAX - 1, 0)));
+    }
+
+    /// Small rectangles near the origin to get overlaps, and some extreme ones.
+    fn any_region_rect() -> impl Strategy<Value = Rectangle> {
+        prop_oneof![
+            1 => any_rect(),
+            // Near the `i32::MIN` origin.
+            1 => (0..4, 0..4, 0..8u32, 0..8u32).prop_map(|(left, top, width, height)| {
+                rect(i32::MIN + left, i32::MIN + top, width, height)
+            }),
+            6 => (-8..8, -8..8, 0..8u32, 0..8u32)
+                .prop_map(|(left, top, width, height)| rect(left, top, width, height)),
+        ]
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(CASES / 100))]
+
+        #[test]
+        fn region_properties(
+            first in prop::collection::vec(any_region_rect(), 4),
+            second in prop::collection::vec(any_region_rect(), 4),
+        ) {
+            let (first, second) = (Region::from_rects(&first), Region::from_rects(&second));
+
+            let union = first.union(&second);
+            let intersection = first.intersect(&second);
+            let difference = first.subtract(&second);
+
+            for region in [&first, &second, &union, &intersection, &difference].iter() {
+                assert!(is_disjoint(region));
+            }
+
+            // |A ∪ B| = |A| + |B| - |A ∩ B|, |A \ B| = |A| - |A ∩ B|.
+            assert_eq!(
+                union.area(),
+                first.area() + second.area() - intersection.area()
+            );
+            assert_eq!(difference.area(), first.area() - intersection.area());
+            assert_eq!(union.area(), second.union(&first).area());
+        }
+    }
+}
@@ -0,0 +1,304 @@
+use crate::{Dimensions, DisplayInfo, DisplaySource, Position, Rectangle};
+
+/// Single display drawn in the layout schematic.
+struct LayoutDisplay {
+    #[cfg_attr(not(feature = "svg"), allow(dead_code))]
+    is_primary: bool,
+    rect: Rectangle,
+    /// Label lines - display index (and the primary marker), name and resolution.
+    labels: [String; 3],
+}
+
+/// Returns the displays with the virtual rectangles (i.e. not enumerated from a services session)
+/// and the rectangle which bounds them, or `None` if there are no such displays.
+fn layout(displays: &[DisplayInfo]) -> Option<(Vec<LayoutDisplay>, Rectangle)> {
+    let displays: Vec<_> = displays
+        .iter()
+        .enumerate()
+        .filter(|(_, display)| display.source != DisplaySource::ServiceInventory)
+        .map(|(index, display)| LayoutDisplay {
+            is_primary: display.is_primary,
+            rect: display.rects.virtual_rect,
+            labels: [
+                if display.is_primary {
+                    format!("{} (primary)", index)
+                } else {
+                    index.to_string()
+                },
+                display
+                    .name
+                    .as_ref()
+                    .map(|name| name.to_string())
+                    .unwrap_or_else(|| "<unnamed>".to_string()),
+                display.rects.virtual_rect.dimensions.wxh().to_string(),
+            ],
+        })
+        .collect();
+
+    let bounds = displays.iter().map(|display| display.rect).reduce(|l, r| {
+        let (left, top) = (l.left().min(r.left()), l.top().min(r.top()));
+        let (right, bottom) = (l.right().max(r.right()), l.bottom().max(r.bottom()));
+
+        Rectangle::new(
+            Position::new(left, top),
+            Dimensions::new(
+                (right as i64 - left as i64) as u32,
+                (bottom as i64 - top as i64) as u32,
+            ),
+        )
+    })?;
+
+    Some((displays, bounds))
+}
+
+/// Draws the schematic of the `displays`' virtual rectangles, scaled to `width` characters,
+/// followed by the legend with the display index, name and resolution of each drawn display.
+///
+/// Display rectangles are drawn with half as many rows as columns per pixel,
+/// to roughly preserve the aspect ratio in the terminal fonts.
+pub(crate) fn render_layout_ascii(displays: &[DisplayInfo], width: u32) -> String {
+    let (displays, bounds) = match layout(displays) {
+        Some(layout) => layout,
+        None => return "<no displays>\n".to_string(),
+    };
+
+    // At least wide enough to draw a box with a single character label.
+    let width = width.max(3) as usize;
+
+    let scale = (width - 1) as f64 / bounds.dimensions.width.max(1) as f64;
+    let column = |x: i32| ((x as i64 - bounds.left() as i64) as f64 * scale).round() as usize;
+    let row = |y: i32| ((y as i64 - bounds.top() as i64) as f64 * scale / 2.0).round() as usize;
+
+    let height = row(bounds.bottom()) + 1;
+    let mut grid = vec![vec![' '; width]; height];
+
+    let boxes: Vec<_> = displays
+        .iter()
+        .map(|display| {
+            (
+                column(display.rect.left()),
+                row(display.rect.top()),
+                column(display.rect.right()),
+                row(display.rect.bottom()),
+            )
+        })
+        .collect();
+
+    for &(left, top, right, bottom) in boxes.iter() {
+        grid[top][left..=right].fill('-');
+        grid[bottom][left..=right].fill('-');
+
+        for line in grid.iter_mut().take(bottom + 1).skip(top) {
+            line[left] = '|';
+            line[right] = '|';
+        }
+    }
+
+    // Draw the corners over the edges of the adjacent displays.
+    for (display, &(left, top, right, bottom)) in displays.iter().zip(boxes.iter()) {
+        for &(x, y) in [(left, top), (right, top), (left, bottom), (right, bottom)].iter() {
+            grid[y][x] = '+';
+        }
+
+        // Write as many label lines as fit into the box, truncated to its width.
+        let inner_width = right.saturating_sub(left + 1);
+
+        for (label, y) in display.labels.iter().zip(top + 1..bottom) {
+            for (c, x) in label.chars().take(inner_width).zip(left + 1..) {
+                grid[y][x] = c;
+            }
+        }
+    }
+
+    let mut result = String::new();
+
+    for line in grid {
+        let line: String = line.into_iter().collect();
+        result.push_str(line.trim_end());
+        result.push('\n');
+    }
+
+    for display in displays.iter() {
+        let [index, name, resolution] = &display.labels;
+        result.push_str(&format!(
+            "{}: {} {} at {}\n",
+            index, name, resolution, display.rect.position
+        ));
+    }
+
+    result
+}
+
+/// Draws the SVG schematic of the `displays`' virtual rectangles, scaled to `width` pixels,
+/// with each display labeled with its index (and the primary marker), name and resolution.
+#[cfg(feature = "svg")]
+pub(crate) fn render_layout_svg(displays: &[DisplayInfo], width: u32) -> String {
+    const MARGIN: f64 = 8.0;
+    const FONT_SIZE: f64 = 12.0;
+
+    let width = width.max(2 * MARGIN as u32 + 1) as f64;
+
+    let (displays, bounds) = match layout(displays) {
+        Some(layout) => layout,
+        None => {
+            return format!(
+                "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\"/>\n",
+                width,
+                2.0 * MARGIN
+            )
+        }
+    };
+
+    let scale = (width - 2.0 * MARGIN) / bounds.dimensions.width.max(1) as f64;
+    let x = |x: i32| MARGIN + (x as i64 - bounds.left() as i64) as f64 * scale;
+    let y = |y: i32| MARGIN + (y as i64 - bounds.top() as i64) as f64 * scale;
+
+    let height = (y(bounds.bottom()) + MARGIN).ceil();
+
+    let mut result = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\" font-family=\"sans-serif\" font-size=\"{2}\">\n",
+        width, height, FONT_SIZE
+    );
+
+    for display in displays.iter() {
+        let (left, top) = (x(display.rect.left()), y(display.rect.top()));
+        let (right, bottom) = (x(display.rect.right()), y(display.rect.bottom()));
+
+        let fill = if display.is_primary {
+            "#c8dcf0"
+        } else {
+            "#e6e6e6"
+        };
+
+        result.push_str(&format!(
+            "  <rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"{}\" stroke=\"#333333\"/>\n",
+            left,
+            top,
+            right - left,
+            bottom - top,
+            fill
+        ));
+
+        // Center the label lines in the display rectangle.
+        let center = (left + right) / 2.0;
+        let first_line =
+            (top + bottom) / 2.0 - FONT_SIZE * (display.labels.len() as f64 - 1.0) / 2.0;
+
+        result.push_str(&format!(
+            "  <text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\" dominant-baseline=\"middle\">",
+            center, first_line
+        ));
+
+        for (i, label) in display.labels.iter().enumerate() {
+            result.push_str(&format!(
+                "<tspan x=\"{:.1}\" y=\"{:.1}\">{}</tspan>",
+                center,
+                first_line + FONT_SIZE * i as f64,
+                escape_xml(label)
+            ));
+        }
+
+        result.push_str("</text>\n");
+    }
+
+    result.push_str("</svg>\n");
+
+    result
+}
+
+#[cfg(feature = "svg")]
+fn escape_xml(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            '\'' => result.push_str("&apos;"),
+            c => result.push(c),
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::TestDisplay;
+
+    #[test]
+    fn ascii() {
+        let displays = [
+            TestDisplay::new(0, 0, 2560, 1440)
+                .name("DELL U2720Q")
+                .build(),
+            TestDisplay::new(2560, -720, 1440, 2560)
+                .name("LG <27GL850>")
+                .build(),
+        ];
+
+        assert_eq!(
+            render_layout_ascii(&displays, 40),
+            "                         +-------------+
+                         |1            |
+                         |LG <27GL850> |
+                         |1440x2560    |
++------------------------+             |
+|0 (primary)             |             |
+|DELL U2720Q             |             |
+|2560x1440               |             |
+|                        |             |
+|                        |             |
+|                        |             |
++------------------------+             |
+                         +-------------+
+0 (primary): DELL U2720Q 2560x1440 at [0, 0]
+1: LG <27GL850> 1440x2560 at [2560, -720]
+"
+        );
+
+        // Labels are truncated to the display rectangles.
+        assert_eq!(
+            render_layout_ascii(&displays[..1], 8),
+            "+------+
+|0 (pri|
++------+
+0 (primary): DELL U2720Q 2560x1440 at [0, 0]
+"
+        );
+
+        // Displays with no rectangles are not drawn.
+        let mut service = TestDisplay::new(0, 0, 1920, 1080).build();
+        service.source = DisplaySource::ServiceInventory;
+        assert_eq!(render_layout_ascii(&[service], 40), "<no displays>\n");
+        assert_eq!(render_layout_ascii(&[], 40), "<no displays>\n");
+    }
+
+    #[cfg(feature = "svg")]
+    #[test]
+    fn svg() {
+        let displays = [
+            TestDisplay::new(0, 0, 2560, 1440)
+                .name("DELL U2720Q")
+                .build(),
+            TestDisplay::new(2560, -720, 1440, 2560)
+                .name("LG <27GL850>")
+                .build(),
+        ];
+
+        let svg = render_layout_svg(&displays, 416);
+
+        assert!(svg
+            .starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"416\" height=\"272\""));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<rect ").count(), 2);
+        assert!(svg.contains(
+            "<rect x=\"8.0\" y=\"80.0\" width=\"256.0\" height=\"144.0\" fill=\"#c8dcf0\""
+        ));
+        assert!(svg.contains(">LG &lt;27GL850&gt;</tspan>"));
+        assert!(!svg.contains("<27GL850>"));
+    }
+}
@@ -0,0 +1,826 @@
+use std::fmt::{Display, Formatter};
+use std::time::SystemTime;
+
+#[cfg(feature = "svg")]
+use crate::render_layout_svg;
+
+use crate::{
+    calc_adjacency_info, convert, diff_displays, map_displays, neighbors, render_layout_ascii,
+    same_mode, shared_edges, stitch_layout, AdjacencyInfo, CoordinateSpace, Coordinates,
+    Dimensions, DisplayEvent, DisplayId, DisplayInfo, DisplayMode, DisplayName, DisplaySource,
+    DisplayState, Neighbor, Orientation, Position, Rectangle, Region, SharedEdge, StitchLayout,
+};
+
+/// Immutable snapshot of the enumerated displays' platform-independent info,
+/// as taken by the [`display manager`] on each display enumeration (see [`Displays::snapshot`]).
+///
+/// Snapshots are cheap to share across threads, may be cached / compared with each other
+/// (see [`diff`]), and persisted.
+///
+/// Enable the `serde` feature to (de)serialize the snapshots.
+/// The serialized schema is versioned (see [`SCHEMA_VERSION`]) -
+/// snapshots serialized with a newer schema version fail to deserialize.
+///
+/// [`display manager`]: struct.Displays.html
+/// [`Displays::snapshot`]: struct.Displays.html#method.snapshot
+/// [`diff`]: #method.diff
+/// [`SCHEMA_VERSION`]: #associatedconstant.SCHEMA_VERSION
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DisplaysSnapshot {
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "deserialize_version"))]
+    version: u32,
+    displays: Vec<DisplayInfo>,
+    pub(crate) adjacency_info: Vec<AdjacencyInfo>,
+    virtual_desktop: Option<Rectangle>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    generation: u64,
+    /// Display index generation of the display manager which took the snapshot -
+    /// only meaningful within the process, so not serialized.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    index_generation: u64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    enumerated_at: Option<SystemTime>,
+}
+
+impl Default for DisplaysSnapshot {
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
+}
+
+impl DisplaysSnapshot {
+    /// Current schema version of the serialized snapshots.
+    pub const SCHEMA_VERSION: u32 = 1;
+
+    /// Creates the snapshot of the enumerated `displays`,
+    /// calculating their adjacency info and the virtual desktop rectangle.
+    pub(crate) fn new(displays: Vec<DisplayInfo>) -> Self {
+        let rects = display_rects(&displays);
+
+        let adjacency_info =
+            map_displays(displays.len(), |index| calc_adjacency_info(&rects, index));
+
+        Self {
+            version: Self::SCHEMA_VERSION,
+            virtual_desktop: virtual_desktop(rects.iter().flatten()),
+            displays,
+            adjacency_info,
+            generation: 0,
+            index_generation: 0,
+            enumerated_at: None,
+        }
+    }
+
+    /// Sets the `generation`, the display `index_generation` and the time of the display enumeration which took the snapshot.
+    pub(crate) fn stamp(
+        mut self,
+        generation: u64,
+        index_generation: u64,
+        enumerated_at: SystemTime,
+    ) -> Self {
+        self.generation = generation;
+        self.index_generation = index_generation;
+        self.enumerated_at.replace(enumerated_at);
+        self
+    }
+
+    /// Returns the schema version the snapshot was created / serialized with.
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Returns the generation of the display enumeration which took the snapshot,
+    /// incremented by the [`display manager`] on each successful display enumeration.
+    ///
+    /// Snapshots with the higher generation are newer; `0` if the snapshot was not taken by a display enumeration.
+    ///
+    /// [`display manager`]: struct.Displays.html
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Returns the time of the display enumeration which took the snapshot,
+    /// or `None` if the snapshot was not taken by a display enumeration.
+    ///
+    /// NOTE - uses the system (wall clock) time, so that the timestamps are comparable across processes
+    /// (e.g. for the snapshots [`published`] to other processes) and may be persisted.
+    ///
+    /// [`published`]: struct.SnapshotPublisher.html
+    pub fn enumerated_at(&self) -> Option<SystemTime> {
+        self.enumerated_at
+    }
+
+    /// Returns the number of displays in the snapshot.
+    pub fn num_displays(&self) -> u32 {
+        self.displays.len() as u32
+    }
+
+    /// Returns the info of all displays in the snapshot, in display index order.
+    ///
+    /// NOTE - `display_index == 0` corresponds to the system's primary display, if any.
+    pub fn displays(&self) -> &[DisplayInfo] {
+        &self.displays
+    }
+
+    /// Returns the [`display info`] for the display with the provided `display_index`,
+    /// or `None` if `display_index` is out of bounds.
+    ///
+    /// [`display info`]: struct.DisplayInfo.html
+    pub fn display_info(&self, display_index: u32) -> Option<&DisplayInfo> {
+        self.displays.get(display_index as usize)
+    }
+
+    /// Returns the [`adjacency info`] for the display with the provided `display_index`,
+    /// or `None` if `display_index` is out of bounds.
+    ///
+    /// [`adjacency info`]: struct.AdjacencyInfo.html
+    pub fn adjacency_info(&self, display_index: u32) -> Option<&AdjacencyInfo> {
+        self.adjacency_info.get(display_index as usize)
+    }
+
+    /// Returns the edges the display with the provided `display_index` shares with all adjacent displays,
+    /// or `None` if `display_index` is out of bounds.
+    ///
+    /// See [`SharedEdge`](struct.SharedEdge.html).
+    pub fn shared_edges(&self, display_index: u32) -> Option<Vec<SharedEdge>> {
+        let display_index = display_index as usize;
+
+        if display_index < self.displays.len() {
+            Some(shared_edges(&display_rects(&self.displays), display_index))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the nearest display in each direction from the display with the provided `display_index`,
+    /// or `None` if `display_index` is out of bounds.
+    ///
+    /// See [`Neighbor`](struct.Neighbor.html).
+    pub fn neighbors(&self, display_index: u32) -> Option<Vec<Neighbor>> {
+        let display_index = display_index as usize;
+
+        if display_index < self.displays.len() {
+            Some(neighbors(&display_rects(&self.displays), display_index))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the index of the display with the [`stable identifier`] `id`, if it is in the snapshot.
+    ///
+    /// [`stable identifier`]: struct.DisplayId.html
+    pub fn find(&self, id: DisplayId) -> Option<u32> {
+        self.displays
+            .iter()
+            .position(|display| display.id == id)
+            .map(|display_index| display_index as u32)
+    }
+
+    /// Returns the combined virtual desktop [`rectangle`] for all displays in the snapshot.
+    ///
+    /// [`rectangle`]: struct.Rectangle.html
+    pub fn virtual_desktop(&self) -> Option<Rectangle> {
+        self.virtual_desktop
+    }
+
+    /// Converts the `value` (a [`position`] or a [`rectangle`]) from the [`coordinate space`] `from` to `to`.
+    ///
+    /// The virtual desktop origin is the top left corner of the snapshot's [`virtual desktop`]
+    /// (the primary display origin if there are no display rectangles).
+    ///
+    /// [`position`]: struct.Position.html
+    /// [`rectangle`]: struct.Rectangle.html
+    /// [`coordinate space`]: enum.CoordinateSpace.html
+    /// [`virtual desktop`]: #method.virtual_desktop
+    pub fn convert<T: Coordinates>(
+        &self,
+        value: T,
+        from: CoordinateSpace,
+        to: CoordinateSpace,
+    ) -> T {
+        let virtual_desktop_origin = self
+            .virtual_desktop
+            .map(|virtual_desktop| virtual_desktop.position)
+            .unwrap_or_default();
+
+        convert(value, from, to, virtual_desktop_origin)
+    }
+
+    /// Returns the [`region`] covered by the virtual rectangles of all displays in the snapshot.
+    ///
+    /// Unlike the [`virtual desktop`] rectangle, does not include the gaps between the displays.
+    ///
+    /// [`region`]: struct.Region.html
+    /// [`virtual desktop`]: #method.virtual_desktop
+    pub fn virtual_desktop_region(&self) -> Region {
+        Region::from_rects(
+            &display_rects(&self.displays)
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    /// Splits the `rect` (e.g. of a window spanning multiple displays) by the display boundaries
+    /// into the parts covered by each display's virtual rectangle, in display index order.
+    ///
+    /// Returns the display index and the part of the `rect` within the display for each display the `rect` overlaps.
+    /// The parts of the `rect` in the gaps between the displays are not returned.
+    pub fn split_rect(&self, rect: &Rectangle) -> Vec<(u32, Rectangle)> {
+        display_rects(&self.displays)
+            .into_iter()
+            .enumerate()
+            .filter_map(|(display_index, display_rect)| {
+                display_rect?
+                    .intersection(rect)
+                    .map(|part| (display_index as u32, part))
+            })
+            .collect()
+    }
+
+    /// Returns the [`layout`] of the full desktop image stitched from the captures of the displays in the snapshot
+    /// (e.g. for the screenshot tools).
+    ///
+    /// The displays with no rectangles (i.e. enumerated from a services session) are skipped.
+    ///
+    /// [`layout`]: struct.StitchLayout.html
+    pub fn stitch_layout(&self) -> StitchLayout {
+        stitch_layout(&self.displays)
+    }
+
+    /// Returns the [`region`] covered by the work rectangles of all displays in the snapshot.
+    ///
+    /// [`region`]: struct.Region.html
+    pub fn work_region(&self) -> Region {
+        let work_rects: Vec<_> = self
+            .displays
+            .iter()
+            .filter(|display| display.source != DisplaySource::ServiceInventory)
+            .map(|display| display.rects.work_rect)
+            .collect();
+
+        Region::from_rects(&work_rects)
+    }
+
+    /// Draws the schematic of the display layout (the displays' virtual rectangles) as ASCII art
+    /// scaled to `width` characters, labeled with the display indices (and the primary display marker),
+    /// names and resolutions, followed by the legend with the same info for each display
+    /// (e.g. to attach to the bug reports).
+    ///
+    /// The displays with no rectangles (i.e. enumerated from a services session) are not drawn.
+    pub fn render_layout_ascii(&self, width: u32) -> String {
+        render_layout_ascii(&self.displays, width)
+    }
+
+    /// Same as [`render_layout_ascii`], but draws the schematic as an SVG image scaled to `width` pixels.
+    ///
+    /// [`render_layout_ascii`]: #method.render_layout_ascii
+    #[cfg(feature = "svg")]
+    pub fn render_layout_svg(&self, width: u32) -> String {
+        render_layout_svg(&self.displays, width)
+    }
+
+    /// Returns the per-display changes from the `previous` snapshot to this one
+    /// (removed displays first, then the added / changed displays in display index order).
+    ///
+    /// See [`SnapshotDiff`](struct.SnapshotDiff.html).
+    pub fn diff(&self, previous: &DisplaysSnapshot) -> SnapshotDiff {
+        let states = |snapshot: &DisplaysSnapshot| -> Vec<_> {
+            snapshot.displays.iter().map(DisplayState::new).collect()
+        };
+
+        SnapshotDiff {
+            changes: diff_snapshots(&previous.displays, &self.displays),
+            events: diff_displays(
+                &states(previous),
+                &states(self),
+                self.generation,
+                self.index_generation,
+            ),
+        }
+    }
+}
+
+/// What changed about a single display between two [`snapshots`], with the previous and the current values.
+///
+/// [`snapshots`]: struct.DisplaysSnapshot.html
+#[derive(Clone, Debug)]
+pub enum DisplayChangeKind {
+    /// The display was added (connected / enabled).
+    Added,
+    /// The display was removed (disconnected / disabled).
+    Removed,
+    /// The display's name changed.
+    Renamed {
+        from: Option<DisplayName>,
+        to: Option<DisplayName>,
+    },
+    /// The display's current display mode (dimensions / refresh rate) changed.
+    ModeChanged { from: DisplayMode, to: DisplayMode },
+    /// The display's orientation changed.
+    OrientationChanged { from: Orientation, to: Orientation },
+    /// The display's DPI scale value changed.
+    DpiChanged { from: f32, to: f32 },
+    /// The display's virtual rectangle was moved in the virtual desktop.
+    Moved { from: Position, to: Position },
+    /// The display's virtual rectangle was resized (e.g. the display mode or orientation changed).
+    Resized { from: Dimensions, to: Dimensions },
+    /// The display's work rectangle changed, e.g. the taskbar was moved / resized / (un)set to auto-hide.
+    ///
+    /// NOTE - not reported if the work rectangle only moved / was resized along with the display's virtual rectangle.
+    WorkAreaChanged { from: Rectangle, to: Rectangle },
+}
+
+impl Display for DisplayChangeKind {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use DisplayChangeKind::*;
+
+        let name = |name: &Option<DisplayName>| {
+            name.as_ref()
+                .map(DisplayName::to_string)
+                .unwrap_or_else(|| "<unnamed>".to_string())
+        };
+
+        match self {
+            Added => write!(f, "added"),
+            Removed => write!(f, "removed"),
+            Renamed { from, to } => write!(f, "renamed from {} to {}", name(from), name(to)),
+            ModeChanged { from, to } => write!(f, "mode changed from {} to {}", from, to),
+            OrientationChanged { from, to } => {
+                write!(f, "orientation changed from {} to {}", from, to)
+            }
+            DpiChanged { from, to } => write!(
+                f,
+                "DPI scale changed from {}% to {}%",
+                (from * 100.0).round(),
+                (to * 100.0).round()
+            ),
+            Moved { from, to } => write!(f, "moved from {} to {}", from, to),
+            Resized { from, to } => write!(f, "resized from {} to {}", from.wxh(), to.wxh()),
+            WorkAreaChanged { from, to } => {
+                write!(f, "work area changed from {} to {}", from, to)
+            }
+        }
+    }
+}
+
+/// Single display's change between two [`snapshots`].
+///
+/// [`snapshots`]: struct.DisplaysSnapshot.html
+#[derive(Clone, Debug)]
+pub struct DisplayChange {
+    /// Stable identifier of the changed display.
+    pub id: DisplayId,
+    /// What changed.
+    pub kind: DisplayChangeKind,
+    /// Index of the changed display in the current snapshot,
+    /// or `None` if the display was removed.
+    pub display_index: Option<u32>,
+}
+
+impl Display for DisplayChange {
+    /// Formats the change as e.g. `display 1 (<display id>) moved from [0, 0] to [1920, 0]`.
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self.display_index {
+            Some(display_index) => {
+                write!(f, "display {} ({}) {}", display_index, self.id, self.kind)
+            }
+            None => write!(f, "display ({}) {}", self.id, self.kind),
+        }
+    }
+}
+
+/// Structured changes between two display [`snapshots`], as returned by [`DisplaysSnapshot::diff`].
+///
+/// Use the [`changes`] for the semantic per-display changes with the previous and the current values
+/// (e.g. to log / assert them), and the [`events`] for the same changes as the [`display events`].
+///
+/// [`snapshots`]: struct.DisplaysSnapshot.html
+/// [`DisplaysSnapshot::diff`]: struct.DisplaysSnapshot.html#method.diff
+/// [`changes`]: #method.changes
+/// [`events`]: #method.events
+/// [`display events`]: struct.DisplayEvent.html
+#[derive(Clone, Default, Debug)]
+pub struct SnapshotDiff {
+    changes: Vec<DisplayChange>,
+    events: Vec<DisplayEvent>,
+}
+
+impl SnapshotDiff {
+    /// Returns `true` if nothing changed between the snapshots.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty() && self.events.is_empty()
+    }
+
+    /// Returns the per-display changes
+    /// (removed displays first, then the added / changed displays in display index order).
+    pub fn changes(&self) -> &[DisplayChange] {
+        &self.changes
+    }
+
+    /// Returns the per-display changes as the [`display events`], as passed to the [`display event callbacks`].
+    ///
+    /// NOTE - display name changes have no corresponding display event.
+    ///
+    /// [`display events`]: struct.DisplayEvent.html
+    /// [`display event callbacks`]: struct.Displays.html#method.on_display
+    pub fn events(&self) -> &[DisplayEvent] {
+        &self.events
+    }
+}
+
+impl Display for SnapshotDiff {
+    /// Formats each change on its own line, or `no changes` if nothing changed.
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        if self.changes.is_empty() {
+            return write!(f, "no changes");
+        }
+
+        for (i, change) in self.changes.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", change)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the semantic per-display changes between the `previous` and `current` displays
+/// (removed displays first, then the added / changed displays in `current` order).
+fn diff_snapshots(previous: &[DisplayInfo], current: &[DisplayInfo]) -> Vec<DisplayChange> {
+    use DisplayChangeKind::*;
+
+    let mut changes = Vec::new();
+
+    for display in previous {
+        if !current.iter().any(|current| current.id == display.id) {
+            changes.push(DisplayChange {
+                id: display.id,
+                kind: Removed,
+                display_index: None,
+            });
+        }
+    }
+
+    for (display_index, display) in current.iter().enumerate() {
+        let mut change = |kind| {
+            changes.push(DisplayChange {
+                id: display.id,
+                kind,
+                display_index: Some(display_index as u32),
+            })
+        };
+
+        let previous = match previous.iter().find(|previous| previous.id == display.id) {
+            Some(previous) => previous,
+            None => {
+                change(Added);
+                continue;
+            }
+        };
+
+        if previous.name != display.name {
+            change(Renamed {
+                from: previous.name.clone(),
+                to: display.name.clone(),
+            });
+        }
+
+        if !same_mode(&previous.current_mode, &display.current_mode) {
+            change(ModeChanged {
+                from: previous.current_mode,
+                to: display.current_mode,
+            });
+        }
+
+        if previous.orientation != display.orientation {
+            change(OrientationChanged {
+                from: previous.orientation,
+                to: display.orientation,
+            });
+        }
+
+        if previous.dpi_scale != display.dpi_scale {
+            change(DpiChanged {
+                from: previous.dpi_scale,
+                to: display.dpi_scale,
+            });
+        }
+
+        let (from, to) = (previous.rects.virtual_rect, display.rects.virtual_rect);
+
+        if from.position != to.position {
+            change(Moved {
+                from: from.position,
+                to: to.position,
+            });
+        }
+
+        if from.dimensions != to.dimensions {
+            change(Resized {
+                from: from.dimensions,
+                to: to.dimensions,
+            });
+        }
+
+        // Only report the work rectangle changes relative to the display's virtual rectangle.
+        if previous.rects.reserved_margins() != display.rects.reserved_margins() {
+            change(WorkAreaChanged {
+                from: previous.rects.work_rect,
+                to: display.rects.work_rect,
+            });
+        }
+    }
+
+    changes
+}
+
+/// Returns the virtual desktop rectangles of the `displays`,
+/// or `None` for the displays with no rectangles (i.e. enumerated from a services session),
+/// which have no neighbours.
+fn display_rects(displays: &[DisplayInfo]) -> Vec<Option<Rectangle>> {
+    displays
+        .iter()
+        .map(|display| {
+            if display.source != DisplaySource::ServiceInventory {
+                Some(display.rects.virtual_rect)
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Returns the virtual desktop rectangle, which contains the origin and all display `rects`,
+/// or `None` if there are no display rectangles.
+fn virtual_desktop<'r, I: Iterator<Item = &'r Rectangle>>(mut rects: I) -> Option<Rectangle> {
+    let first = rects.next()?;
+
+    let (mut left, mut top, mut right, mut bottom) = (0, 0, 0, 0);
+
+    for rect in std::iter::once(first).chain(rects) {
+        left = left.min(rect.left());
+        top = top.min(rect.top());
+        right = right.max(rect.right());
+        bottom = bottom.max(rect.bottom());
+    }
+
+    debug_assert!(right >= left);
+    debug_assert!(bottom >= top);
+
+    Some(Rectangle::new(
+        Position::new(left, top),
+        Dimensions::new(
+            (right as i64 - left as i64) as u32,
+            (bottom as i64 - top as i64) as u32,
+        ),
+    ))
+}
+
+#[cfg(feature = "serde")]
+fn deserialize_version<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<u32, D::Error> {
+    use serde::Deserialize;
+
+    let version = u32::deserialize(deserializer)?;
+
+    if version > DisplaysSnapshot::SCHEMA_VERSION {
+        Err(serde::de::Error::custom(format!(
+            "unsupported display snapshot schema version {} (at most {} is supported)",
+            version,
+            DisplaysSnapshot::SCHEMA_VERSION
+        )))
+    } else {
+        Ok(version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::TestDisplay;
+    use crate::{DisplayEventKind, DisplayIndex, EdgeSide, Orientation};
+
+    fn display(id: u64, left: i32, top: i32, width: u32, height: u32) -> DisplayInfo {
+        TestDisplay::new(left, top, width, height).id(id).build()
+    }
+
+    #[test]
+    fn snapshot() {
+        let snapshot = DisplaysSnapshot::new(vec![
+            display(0, 0, 0, 1920, 1080),
+            display(1, 1920, -360, 2560, 1440),
+        ]);
+
+        assert_eq!(snapshot.version(), DisplaysSnapshot::SCHEMA_VERSION);
+        assert_eq!(snapshot.num_displays(), 2);
+        assert_eq!(snapshot.find(DisplayId(1)), Some(1));
+        assert_eq!(snapshot.find(DisplayId(2)), None);
+        assert_eq!(
+            snapshot.virtual_desktop(),
+            Some(Rectangle::new(
+                Position::new(0, -360),
+                Dimensions::new(4480, 1440)
+            ))
+        );
+        assert_eq!(snapshot.adjacency_info(0).unwrap().right, Some(1));
+        assert_eq!(snapshot.adjacency_info(1).unwrap().left, Some(0));
+        assert!(snapshot.adjacency_info(2).is_none());
+        assert_eq!(
+            snapshot.shared_edges(1),
+            Some(vec![SharedEdge {
+                display_index: 0,
+                side: EdgeSide::Left,
+                start: 0,
+                end: 1080
+            }])
+        );
+        assert!(snapshot.shared_edges(2).is_none());
+        assert_eq!(
+            snapshot.virtual_desktop_region().area(),
+            1920 * 1080 + 2560 * 1440
+        );
+        assert!(!snapshot
+            .virtual_desktop_region()
+            .contains_point(Position::new(0, -1)));
+        assert_eq!(snapshot.work_region().area(), 1920 * 1080 + 2560 * 1440);
+        assert_eq!(
+            snapshot.split_rect(&Rectangle::new(
+                Position::new(1820, -100),
+                Dimensions::new(200, 300)
+            )),
+            vec![
+                (
+                    0,
+                    Rectangle::new(Position::new(1820, 0), Dimensions::new(100, 200))
+                ),
+                (
+                    1,
+                    Rectangle::new(Position::new(1920, -100), Dimensions::new(100, 300))
+                ),
+            ]
+        );
+        assert!(snapshot
+            .split_rect(&Rectangle::new(
+                Position::new(0, -100),
+                Dimensions::new(100, 100)
+            ))
+            .is_empty());
+        assert_eq!(
+            snapshot.convert(
+                Position::new(0, 0),
+                CoordinateSpace::PrimaryOrigin,
+                CoordinateSpace::VirtualDesktopOrigin
+            ),
+            Position::new(0, 360)
+        );
+
+        // No display rectangles.
+        let mut service = display(0, 0, 0, 1920, 1080);
+        service.source = DisplaySource::ServiceInventory;
+        let service = DisplaysSnapshot::new(vec![service]);
+        assert_eq!(service.virtual_desktop(), None);
+        assert!(service.virtual_desktop_region().is_empty());
+        assert!(!service.adjacency_info(0).unwrap().is_some());
+
+        assert_eq!(DisplaysSnapshot::default().virtual_desktop(), None);
+    }
+
+    #[test]
+    fn diff() {
+        let previous = DisplaysSnapshot::new(vec![
+            display(0, 0, 0, 1920, 1080),
+            display(1, 1920, 0, 1920, 1080),
+        ]);
+        let current = DisplaysSnapshot::new(vec![
+            display(0, 0, 0, 1920, 1080),
+            display(2, -1920, 0, 1920, 1080),
+        ]);
+
+        assert!(current.diff(&current).is_empty());
+        assert_eq!(current.diff(&current).to_string(), "no changes");
+        assert_eq!(
+            current.diff(&previous).events(),
+            vec![
+                DisplayEvent {
+                    id: DisplayId(1),
+                    kind: DisplayEventKind::Removed,
+                    display_index: None,
+                    generation: 0,
+                },
+                DisplayEvent {
+                    id: DisplayId(2),
+                    kind: DisplayEventKind::Added,
+                    display_index: Some(DisplayIndex::new(1, 0)),
+                    generation: 0,
+                },
+            ]
+        );
+
+        let mut rotated = previous.displays().to_vec();
+        rotated[1].orientation = Orientation::Portrait;
+        let rotated = DisplaysSnapshot::new(rotated);
+
+        assert_eq!(
+            rotated.diff(&previous).events(),
+            vec![DisplayEvent {
+                id: DisplayId(1),
+                kind: DisplayEventKind::OrientationChanged,
+                display_index: Some(DisplayIndex::new(1, 0)),
+                generation: 0,
+            }]
+        );
+
+        // Events are tagged with the generations of the newer snapshot.
+        let enumerated_at = std::time::UNIX_EPOCH + std::time::Duration::from_secs(1);
+        let stamped = rotated.stamp(7, 3, enumerated_at);
+        assert_eq!(stamped.generation(), 7);
+        assert_eq!(stamped.enumerated_at(), Some(enumerated_at));
+        assert_eq!(previous.generation(), 0);
+        assert_eq!(previous.enumerated_at(), None);
+        assert_eq!(stamped.diff(&previous).events()[0].generation, 7);
+        assert_eq!(
+            stamped.diff(&previous).events()[0].display_index,
+            Some(DisplayIndex::new(1, 3))
+        );
+    }
+
+    #[test]
+    fn diff_changes() {
+        let previous = DisplaysSnapshot::new(vec![
+            display(0, 0, 0, 1920, 1080),
+            display(1, 1920, 0, 1920, 1080),
+        ]);
+
+        let kinds = |diff: &SnapshotDiff| -> Vec<_> {
+            diff.changes()
+                .iter()
+                .map(|change| (change.id.0, change.kind.to_string(), change.display_index))
+                .collect()
+        };
+
+        // Added / removed.
+        let current = DisplaysSnapshot::new(vec![
+            display(0, 0, 0, 1920, 1080),
+            display(2, -1920, 0, 1920, 1080),
+        ]);
+        assert_eq!(
+            kinds(&current.diff(&previous)),
+            vec![
+                (1, "removed".to_string(), None),
+                (2, "added".to_string(), Some(1)),
+            ]
+        );
+
+        // Moved, renamed, DPI scale changed; the work area moved along with the display is not reported.
+        let mut displays = previous.displays().to_vec();
+        displays[1].rects.virtual_rect.position = Position::new(1920, 100);
+        displays[1].rects.work_rect.position = Position::new(1920, 100);
+        displays[1].name =
+            DisplayName::from_utf16(&"DELL U2720Q".encode_utf16().collect::<Vec<_>>());
+        displays[1].dpi_scale = 1.5;
+        let diff = DisplaysSnapshot::new(displays).diff(&previous);
+
+        assert_eq!(
+            kinds(&diff),
+            vec![
+                (
+                    1,
+                    "renamed from <unnamed> to DELL U2720Q".to_string(),
+                    Some(1)
+                ),
+                (
+                    1,
+                    "DPI scale changed from 100% to 150%".to_string(),
+                    Some(1)
+                ),
+                (
+                    1,
+                    "moved from [1920, 0] to [1920, 100]".to_string(),
+                    Some(1)
+                ),
+            ]
+        );
+        // No display event for the name change.
+        assert_eq!(diff.events().len(), 3);
+
+        // Mode changed (which also resizes the display), taskbar added.
+        let mut displays = previous.displays().to_vec();
+        displays[0] = display(0, 0, 0, 1280, 720);
+        displays[0].rects.work_rect.dimensions = Dimensions::new(1280, 680);
+        let diff = DisplaysSnapshot::new(displays).diff(&previous);
+
+        assert!(matches!(
+            diff.changes()[0].kind,
+            DisplayChangeKind::ModeChanged { .. }
+        ));
+        assert_eq!(
+            diff.to_string(),
+            "display 0 (0000000000000000) mode changed from 1920x1080@60Hz to 1280x720@60Hz\n\
+             display 0 (0000000000000000) resized from 1920x1080 to 1280x720\n\
+             display 0 (0000000000000000) work area changed from [0, 0]:[1920, 1080] to [0, 0]:[1280, 680]"
+        );
+    }
+}
@@ -0,0 +1,143 @@
+use crate::{Dimensions, DisplayInfo, DisplaySource, Position, Rectangle, Region};
+
+/// Where a single display's capture goes in the [`stitched image`].
+///
+/// [`stitched image`]: struct.StitchLayout.html
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct StitchRect {
+    /// Index of the captured display.
+    pub display_index: u32,
+    /// Rectangle to copy from the display's capture (i.e. relative to the display's top left corner).
+    pub source: Rectangle,
+    /// Rectangle to copy the `source` rectangle to in the stitched image.
+    /// Always has the same dimensions as the `source` rectangle.
+    pub destination: Rectangle,
+}
+
+/// Layout of the full desktop image (e.g. a screenshot) stitched from the per-display captures,
+/// as returned by [`DisplaysSnapshot::stitch_layout`].
+///
+/// The stitched image is the bounding rectangle of the displays' virtual rectangles, in physical pixels;
+/// the parts of it not covered by any display (e.g. the gaps between the displays of different sizes)
+/// are the `dead_zones`, to be filled by the application (e.g. with transparent or black pixels).
+///
+/// NOTE - the display captures are expected to be in physical pixels, i.e. as captured by a per-monitor DPI aware process.
+///
+/// [`DisplaysSnapshot::stitch_layout`]: struct.DisplaysSnapshot.html#method.stitch_layout
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct StitchLayout {
+    /// The stitched image's dimensions.
+    pub dimensions: Dimensions,
+    /// Position of the stitched image's top left corner in the virtual desktop.
+    pub origin: Position,
+    /// Where each display's capture goes in the stitched image, in display index order.
+    ///
+    /// NOTE - the destination rectangles of the displays which overlap in the virtual desktop (e.g. in the clone mode) overlap, too.
+    pub displays: Vec<StitchRect>,
+    /// Disjoint rectangles of the stitched image not covered by any display.
+    pub dead_zones: Vec<Rectangle>,
+}
+
+impl StitchLayout {
+    /// Returns `true` if the stitched image contains no display captures.
+    pub fn is_empty(&self) -> bool {
+        self.displays.is_empty()
+    }
+}
+
+/// Computes the [`stitch layout`] of the `displays`' captures.
+/// The displays with no rectangles (i.e. enumerated from a services session) are skipped.
+///
+/// [`stitch layout`]: struct.StitchLayout.html
+pub(crate) fn stitch_layout(displays: &[DisplayInfo]) -> StitchLayout {
+    let rects: Vec<_> = displays
+        .iter()
+        .enumerate()
+        .filter(|(_, display)| display.source != DisplaySource::ServiceInventory)
+        .map(|(display_index, display)| (display_index as u32, display.rects.virtual_rect))
+        .filter(|(_, rect)| !rect.is_empty())
+        .collect();
+
+    let region = Region::from_rects(&rects.iter().map(|&(_, rect)| rect).collect::<Vec<_>>());
+
+    let bounds = match region.bounds() {
+        Some(bounds) => bounds,
+        None => return StitchLayout::default(),
+    };
+
+    let to_image =
+        |rect: &Rectangle| Rectangle::new(rect.position - bounds.position, rect.dimensions);
+
+    StitchLayout {
+        dimensions: bounds.dimensions,
+        origin: bounds.position,
+        displays: rects
+            .iter()
+            .map(|&(display_index, rect)| StitchRect {
+                display_index,
+                source: Rectangle::new(Position::default(), rect.dimensions),
+                destination: to_image(&rect),
+            })
+            .collect(),
+        dead_zones: Region::from(bounds)
+            .subtract(&region)
+            .rects()
+            .iter()
+            .map(to_image)
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::TestDisplay;
+
+    fn rect(left: i32, top: i32, width: u32, height: u32) -> Rectangle {
+        Rectangle::new(Position::new(left, top), Dimensions::new(width, height))
+    }
+
+    #[test]
+    fn stitch_layout() {
+        // 1080p primary display, with a taller 1440p display on its left, aligned to the bottom.
+        let layout = super::stitch_layout(&[
+            TestDisplay::new(0, 0, 1920, 1080).build(),
+            TestDisplay::new(-2560, -360, 2560, 1440).build(),
+        ]);
+
+        assert_eq!(layout.dimensions, Dimensions::new(4480, 1440));
+        assert_eq!(layout.origin, Position::new(-2560, -360));
+        assert_eq!(
+            layout.displays,
+            vec![
+                StitchRect {
+                    display_index: 0,
+                    source: rect(0, 0, 1920, 1080),
+                    destination: rect(2560, 360, 1920, 1080),
+                },
+                StitchRect {
+                    display_index: 1,
+                    source: rect(0, 0, 2560, 1440),
+                    destination: rect(0, 0, 2560, 1440),
+                },
+            ]
+        );
+
+        // Above the primary display.
+        assert_eq!(layout.dead_zones, vec![rect(2560, 0, 1920, 360)]);
+
+        // No dead zones.
+        let layout = super::stitch_layout(&[
+            TestDisplay::new(0, 0, 1920, 1080).build(),
+            TestDisplay::new(1920, 0, 1920, 1080).build(),
+        ]);
+        assert_eq!(layout.dimensions, Dimensions::new(3840, 1080));
+        assert!(layout.dead_zones.is_empty());
+
+        // No display rectangles.
+        let mut service = TestDisplay::new(0, 0, 1920, 1080).build();
+        service.source = DisplaySource::ServiceInventory;
+        assert!(super::stitch_layout(&[service]).is_empty());
+        assert_eq!(super::stitch_layout(&[]), StitchLayout::default());
+    }
+}
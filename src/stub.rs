@@ -0,0 +1,187 @@
+//! Stub platform layer for the unsupported platforms.
+//!
+//! Display enumeration fails with `Error::Unsupported`, and all other platform queries fail.
+//! The platform-specific types are uninhabited, as they are never created.
+
+use std::convert::Infallible;
+
+use crate::brightness::{AdaptiveBrightness, BrightnessSetting, DisplayBrightness};
+use crate::{
+    Dimensions, DisplayConfigChange, DisplayMetrics, DisplayRectsFull, DpiAwareness,
+    EnumerateOptions, EnumeratedDisplayInfo, Error, MonitorHardware, NightLightState, Orientation,
+    PhysicalMonitor, Projection, Rectangle, VcpValue,
+};
+
+/// Platform-specific display info - never created, as no displays are enumerated.
+#[derive(Clone, Debug)]
+pub(crate) enum DisplayInfoStub {}
+
+pub(crate) fn enumerate_displays_stub(
+    _options: &EnumerateOptions<'_>,
+) -> Result<(Vec<EnumeratedDisplayInfo>, Option<Infallible>), Error> {
+    Err(Error::Unsupported)
+}
+
+pub(crate) fn enumerate_display_rects_stub() -> Result<Vec<DisplayRectsFull>, Error> {
+    Err(Error::Unsupported)
+}
+
+pub(crate) fn display_metrics_stub() -> Option<DisplayMetrics> {
+    None
+}
+
+pub(crate) fn enumerate_attached_hardware_stub() -> Result<Vec<MonitorHardware>, Error> {
+    Err(Error::Unsupported)
+}
+
+pub(crate) fn night_light_stub() -> Option<NightLightState> {
+    None
+}
+
+pub(crate) fn adaptive_brightness_stub() -> Option<AdaptiveBrightness> {
+    None
+}
+
+pub(crate) fn presentation_mode_active_stub() -> Result<bool, ()> {
+    Err(())
+}
+
+pub(crate) fn set_thread_display_required_stub(_display_required: bool) -> Result<(), ()> {
+    Err(())
+}
+
+pub(crate) fn process_dpi_awareness_stub() -> Result<DpiAwareness, ()> {
+    Err(())
+}
+
+pub(crate) fn thread_dpi_awareness_stub() -> Result<DpiAwareness, ()> {
+    Err(())
+}
+
+pub(crate) fn projection_stub() -> Result<Projection, ()> {
+    Err(())
+}
+
+pub(crate) fn set_projection_stub(_projection: Projection) -> Result<(), ()> {
+    Err(())
+}
+
+pub(crate) fn set_orientation_stub(
+    platform: &DisplayInfoStub,
+    _orientation: Orientation,
+    _dimensions: Dimensions,
+) -> Result<(), ()> {
+    match *platform {}
+}
+
+#[cfg(feature = "unstable-scaling")]
+pub(crate) fn set_dpi_scale_stub(platform: &DisplayInfoStub, _percent: u32) -> Result<(), ()> {
+    match *platform {}
+}
+
+pub(crate) fn brightness_stub(display: &DisplayInfoStub) -> Option<DisplayBrightness> {
+    match *display {}
+}
+
+pub(crate) fn set_brightness_stub(
+    display: &DisplayInfoStub,
+    _setting: BrightnessSetting,
+) -> Result<(), ()> {
+    match *display {}
+}
+
+pub(crate) fn gamma_ramp_stub(display: &DisplayInfoStub) -> Option<Vec<u16>> {
+    match *display {}
+}
+
+pub(crate) fn set_gamma_ramp_stub(display: &DisplayInfoStub, _ramp: &[u16]) -> Result<(), ()> {
+    match *display {}
+}
+
+pub(crate) fn vcp_feature_stub(
+    display: &DisplayInfoStub,
+    _physical_monitor: Option<u32>,
+    _code: u8,
+) -> Result<VcpValue, ()> {
+    match *display {}
+}
+
+pub(crate) fn set_vcp_feature_stub(
+    display: &DisplayInfoStub,
+    _physical_monitor: Option<u32>,
+    _code: u8,
+    _value: u32,
+) -> Result<(), ()> {
+    match *display {}
+}
+
+pub(crate) fn apply_color_profile_stub(
+    display: &DisplayInfoStub,
+    _path: &std::path::Path,
+) -> Result<(), ()> {
+    match *display {}
+}
+
+pub(crate) fn physical_monitors_stub(
+    display: &DisplayInfoStub,
+) -> Result<Vec<PhysicalMonitor>, ()> {
+    match *display {}
+}
+
+pub(crate) fn capabilities_stub(display: &DisplayInfoStub) -> Result<String, ()> {
+    match *display {}
+}
+
+/// Captured display configuration - never created, as the display configuration cannot be queried.
+#[derive(Clone)]
+pub(crate) enum DisplayConfigSnapshotStub {}
+
+impl DisplayConfigSnapshotStub {
+    pub(crate) fn capture() -> Result<Self, ()> {
+        Err(())
+    }
+
+    pub(crate) fn restore(&self) -> Result<(), ()> {
+        match *self {}
+    }
+}
+
+pub(crate) fn apply_display_config_stub(
+    _changes: &[(&DisplayInfoStub, DisplayConfigChange)],
+    _primary: Option<&DisplayInfoStub>,
+    _validate_only: bool,
+) -> Result<DisplayConfigSnapshotStub, ()> {
+    Err(())
+}
+
+/// Cursor clip guard - never created, as the cursor cannot be confined.
+pub(crate) enum CursorClipGuardStub {}
+
+impl CursorClipGuardStub {
+    pub(crate) fn new(_rect: &Rectangle) -> Result<Self, ()> {
+        Err(())
+    }
+}
+
+/// Shared memory section - never created, as shared memory is not supported.
+#[cfg(feature = "ipc")]
+pub(crate) enum SharedMemoryStub {}
+
+#[cfg(feature = "ipc")]
+impl SharedMemoryStub {
+    pub(crate) fn create(_name: &str, _size: usize) -> Result<Self, ()> {
+        Err(())
+    }
+
+    pub(crate) fn open(_name: &str) -> Result<Self, ()> {
+        Err(())
+    }
+
+    pub(crate) fn as_ptr(&self) -> *mut u8 {
+        match *self {}
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        match *self {}
+    }
+}
@@ -0,0 +1,125 @@
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Sub};
+
+/// Millimeters per inch.
+const MM_PER_INCH: f32 = 25.4;
+/// (Typographic) points per inch.
+const PT_PER_INCH: f32 = 72.0;
+
+/// Length in (physical) display pixels.
+///
+/// Converted to / from the physical units with the display's pixel density
+/// (see [`DisplayInfo::ppi`](struct.DisplayInfo.html#method.ppi)).
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Px<T = i32>(pub T);
+
+/// Physical length in millimeters.
+#[derive(Clone, Copy, PartialEq, PartialOrd, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Mm<T = f32>(pub T);
+
+/// Physical length in (typographic) points, i.e. `1/72` of an inch.
+///
+/// NOTE - not to be confused with the OS logical units (e.g. the `1/96` of an inch DIP's on Windows),
+/// which are scaled by the display's DPI scale, not its pixel density.
+#[derive(Clone, Copy, PartialEq, PartialOrd, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Pt<T = f32>(pub T);
+
+impl Px<i32> {
+    /// Converts the length to millimeters on a display with the pixel density of `ppi` pixels per inch.
+    pub fn to_mm(self, ppi: f32) -> Mm<f32> {
+        Mm(self.0 as f32 / ppi * MM_PER_INCH)
+    }
+
+    /// Converts the length to points on a display with the pixel density of `ppi` pixels per inch.
+    pub fn to_pt(self, ppi: f32) -> Pt<f32> {
+        Pt(self.0 as f32 / ppi * PT_PER_INCH)
+    }
+}
+
+impl Mm<f32> {
+    /// Converts the length to the (nearest whole number of) pixels
+    /// on a display with the pixel density of `ppi` pixels per inch.
+    pub fn to_px(self, ppi: f32) -> Px<i32> {
+        Px((self.0 / MM_PER_INCH * ppi).round() as i32)
+    }
+
+    /// Converts the length to points.
+    pub fn to_pt(self) -> Pt<f32> {
+        Pt(self.0 / MM_PER_INCH * PT_PER_INCH)
+    }
+}
+
+impl Pt<f32> {
+    /// Converts the length to the (nearest whole number of) pixels
+    /// on a display with the pixel density of `ppi` pixels per inch.
+    pub fn to_px(self, ppi: f32) -> Px<i32> {
+        Px((self.0 / PT_PER_INCH * ppi).round() as i32)
+    }
+
+    /// Converts the length to millimeters.
+    pub fn to_mm(self) -> Mm<f32> {
+        Mm(self.0 / PT_PER_INCH * MM_PER_INCH)
+    }
+}
+
+macro_rules! impl_unit {
+    ($unit:ident, $suffix:expr) => {
+        impl<T: Add<Output = T>> Add for $unit<T> {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                $unit(self.0 + rhs.0)
+            }
+        }
+
+        impl<T: Sub<Output = T>> Sub for $unit<T> {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                $unit(self.0 - rhs.0)
+            }
+        }
+
+        impl<T: Display> Display for $unit<T> {
+            fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+                write!(f, "{}{}", self.0, $suffix)
+            }
+        }
+    };
+}
+
+impl_unit!(Px, "px");
+impl_unit!(Mm, "mm");
+impl_unit!(Pt, "pt");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn units() {
+        // 96 PPI - 1 inch is 96 pixels, 25.4 millimeters, 72 points.
+        assert_eq!(Px(96).to_mm(96.0), Mm(25.4));
+        assert_eq!(Px(96).to_pt(96.0), Pt(72.0));
+        assert_eq!(Mm(25.4).to_px(96.0), Px(96));
+        assert_eq!(Mm(25.4).to_pt(), Pt(72.0));
+        assert_eq!(Pt(72.0).to_px(96.0), Px(96));
+        assert_eq!(Pt(72.0).to_mm(), Mm(25.4));
+
+        // Same physical length is more pixels on a denser display.
+        assert_eq!(Pt(12.0).to_px(96.0), Px(16));
+        assert_eq!(Pt(12.0).to_px(192.0), Px(32));
+        assert_eq!(Mm(10.0).to_px(163.0), Px(64));
+
+        assert_eq!(Px(10) + Px(5), Px(15));
+        assert_eq!(Px(10) - Px(15), Px(-5));
+        assert_eq!(Mm(1.5) + Mm(2.0), Mm(3.5));
+
+        assert_eq!(Px(-12).to_string(), "-12px");
+        assert_eq!(Mm(3.5).to_string(), "3.5mm");
+        assert_eq!(Pt(10.0).to_string(), "10pt");
+    }
+}
@@ -0,0 +1,98 @@
+use super::ddc::PhysicalMonitorsWin;
+use super::display_info::DisplayInfoWin;
+use crate::brightness::{
+    gamma_ramp, gamma_ramp_scale, BrightnessSetting, DisplayBrightness, GAMMA_RAMP_SIZE,
+};
+
+use windows_sys::Win32::{
+    Graphics::Gdi::{CreateDCW, DeleteDC, HDC},
+    UI::ColorSystem::{GetDeviceGammaRamp, SetDeviceGammaRamp},
+};
+
+/// Reads the display's DDC/CI brightness level and gamma ramp.
+/// Returns `None` if neither is available.
+pub(crate) fn brightness_win(display: &DisplayInfoWin) -> Option<DisplayBrightness> {
+    let ddc = PhysicalMonitorsWin::new(display).and_then(|monitors| monitors.brightness());
+
+    let gamma_ramp_scale = gamma_ramp_win(display).and_then(|ramp| gamma_ramp_scale(&ramp));
+
+    if ddc.is_none() && gamma_ramp_scale.is_none() {
+        None
+    } else {
+        Some(DisplayBrightness {
+            ddc,
+            gamma_ramp_scale,
+        })
+    }
+}
+
+pub(crate) fn set_brightness_win(
+    display: &DisplayInfoWin,
+    setting: BrightnessSetting,
+) -> Result<(), ()> {
+    match setting {
+        BrightnessSetting::Ddc(level) => PhysicalMonitorsWin::new(display)
+            .ok_or(())?
+            .set_brightness(level),
+        BrightnessSetting::GammaRamp(scale) => set_gamma_ramp_win(display, &gamma_ramp(scale)),
+    }
+}
+
+/// Reads the display's (per-channel) gamma ramp.
+pub(crate) fn gamma_ramp_win(display: &DisplayInfoWin) -> Option<Vec<u16>> {
+    let dc = DeviceContext::new(display)?;
+    let mut ramp = vec![0u16; GAMMA_RAMP_SIZE * 3];
+
+    if 0 != unsafe { GetDeviceGammaRamp(dc.0, ramp.as_mut_ptr() as _) } {
+        Some(ramp)
+    } else {
+        None
+    }
+}
+
+/// Sets the display's (per-channel) gamma `ramp`.
+///
+/// NOTE - the OS / driver may reject the ramps which deviate too much from the identity one.
+pub(crate) fn set_gamma_ramp_win(display: &DisplayInfoWin, ramp: &[u16]) -> Result<(), ()> {
+    if ramp.len() != GAMMA_RAMP_SIZE * 3 {
+        return Err(());
+    }
+
+    let dc = DeviceContext::new(display).ok_or(())?;
+
+    if 0 != unsafe { SetDeviceGammaRamp(dc.0, ramp.as_ptr() as _) } {
+        Ok(())
+    } else {
+        Err(())
+    }
+}
+
+/// Device context of the display's GDI device, for the gamma ramp access.
+struct DeviceContext(HDC);
+
+impl DeviceContext {
+    fn new(display: &DisplayInfoWin) -> Option<Self> {
+        let gdi_device_name = display.gdi_device_name?;
+
+        let dc = unsafe {
+            CreateDCW(
+                std::ptr::null(),
+                gdi_device_name.as_ptr(),
+                std::ptr::null(),
+                std::ptr::null(),
+            )
+        };
+
+        if dc.is_null() {
+            None
+        } else {
+            Some(Self(dc))
+        }
+    }
+}
+
+impl Drop for DeviceContext {
+    fn drop(&mut self) {
+        unsafe { DeleteDC(self.0) };
+    }
+}
@@ -0,0 +1,241 @@
+use std::ffi::OsStr;
+use std::os::windows::ffi::OsStrExt;
+use std::path::Path;
+
+use super::display_info::DisplayInfoWin;
+
+use windows_sys::Win32::{
+    Graphics::Gdi::{EnumDisplayDevicesW, DISPLAY_DEVICEW, DISPLAY_DEVICE_ACTIVE},
+    UI::{
+        ColorSystem::{
+            GetColorDirectoryW, InstallColorProfileW, UninstallColorProfileW,
+            WcsAssociateColorProfileWithDevice, WcsDisassociateColorProfileFromDevice,
+            WcsGetDefaultColorProfile, WcsGetDefaultColorProfileSize, WcsSetDefaultColorProfile,
+            WcsSetUsePerUserProfiles, CPST_NONE, CPT_ICC,
+            WCS_PROFILE_MANAGEMENT_SCOPE_CURRENT_USER,
+        },
+        WindowsAndMessaging::EDD_GET_DEVICE_INTERFACE_NAME,
+    },
+};
+
+/// Monitor device class (`'mntr'`), as used by the WCS per-user profile API's.
+const CLASS_MONITOR: u32 = u32::from_be_bytes(*b"mntr");
+
+/// Installs the ICC profile at `path` (unless it's already in the system color directory),
+/// associates it with the display's monitor and makes it the monitor's default profile for the current user.
+///
+/// The changes made so far are rolled back if any step fails.
+pub(crate) fn apply_color_profile_win(display: &DisplayInfoWin, path: &Path) -> Result<(), ()> {
+    let device_key = monitor_device_key(display).ok_or(())?;
+    let profile_name = nul_terminated(path.file_name().ok_or(())?);
+
+    let mut rollback = Rollback::default();
+
+    // Copy the profile to the system color directory, unless it's already there.
+    if path.parent() != color_directory().as_deref() {
+        let path = nul_terminated(path.as_os_str());
+
+        if 0 == unsafe { InstallColorProfileW(std::ptr::null(), path.as_ptr()) } {
+            return Err(());
+        }
+
+        let profile_name = profile_name.clone();
+        rollback.push(move || unsafe {
+            UninstallColorProfileW(std::ptr::null(), profile_name.as_ptr(), 1);
+        });
+    }
+
+    let previous_default = default_color_profile(&device_key);
+
+    if 0 == unsafe {
+        WcsAssociateColorProfileWithDevice(
+            WCS_PROFILE_MANAGEMENT_SCOPE_CURRENT_USER,
+            profile_name.as_ptr(),
+            device_key.as_ptr(),
+        )
+    } {
+        return Err(());
+    }
+
+    {
+        let (profile_name, device_key) = (profile_name.clone(), device_key.clone());
+        rollback.push(move || unsafe {
+            WcsDisassociateColorProfileFromDevice(
+                WCS_PROFILE_MANAGEMENT_SCOPE_CURRENT_USER,
+                profile_name.as_ptr(),
+                device_key.as_ptr(),
+            );
+        });
+    }
+
+    if 0 == unsafe {
+        WcsSetDefaultColorProfile(
+            WCS_PROFILE_MANAGEMENT_SCOPE_CURRENT_USER,
+            device_key.as_ptr(),
+            CPT_ICC,
+            CPST_NONE,
+            0,
+            profile_name.as_ptr(),
+        )
+    } {
+        return Err(());
+    }
+
+    if let Some(previous_default) = previous_default {
+        let device_key = device_key.clone();
+        rollback.push(move || unsafe {
+            WcsSetDefaultColorProfile(
+                WCS_PROFILE_MANAGEMENT_SCOPE_CURRENT_USER,
+                device_key.as_ptr(),
+                CPT_ICC,
+                CPST_NONE,
+                0,
+                previous_default.as_ptr(),
+            );
+        });
+    }
+
+    // The current user's profile associations are ignored unless enabled for the monitor.
+    if 0 == unsafe { WcsSetUsePerUserProfiles(device_key.as_ptr(), CLASS_MONITOR, 1) } {
+        return Err(());
+    }
+
+    rollback.commit();
+
+    Ok(())
+}
+
+/// Returns the nul-terminated registry device key of the display's monitor
+/// (e.g. `\Registry\Machine\System\CurrentControlSet\Control\Class\{4d36e96e-...}\0001`),
+/// which identifies the monitor to the WCS API's.
+///
+/// The monitor is matched by its device interface path, if known, or is the first active monitor of the display otherwise.
+fn monitor_device_key(display: &DisplayInfoWin) -> Option<Vec<u16>> {
+    let gdi_device_name = display.gdi_device_name.as_ref()?;
+
+    let mut monitor_index = 0;
+
+    loop {
+        let mut monitor: DISPLAY_DEVICEW = unsafe { std::mem::zeroed() };
+        monitor.cb = std::mem::size_of_val(&monitor) as u32;
+
+        if 0 == unsafe {
+            EnumDisplayDevicesW(
+                gdi_device_name.as_ptr(),
+                monitor_index,
+                &mut monitor,
+                EDD_GET_DEVICE_INTERFACE_NAME,
+            )
+        } {
+            return None;
+        }
+
+        monitor_index += 1;
+
+        let matches = match display.device_path_utf16() {
+            Some(device_path) => until_nul(&monitor.DeviceID) == device_path,
+            None => monitor.StateFlags & DISPLAY_DEVICE_ACTIVE != 0,
+        };
+
+        if matches {
+            let device_key = until_nul(&monitor.DeviceKey);
+
+            return if device_key.is_empty() {
+                None
+            } else {
+                Some(device_key.iter().copied().chain(Some(0)).collect())
+            };
+        }
+    }
+}
+
+/// Returns the current user's nul-terminated default ICC profile name for the monitor with the `device_key`, if any.
+fn default_color_profile(device_key: &[u16]) -> Option<Vec<u16>> {
+    let mut size = 0;
+
+    if 0 == unsafe {
+        WcsGetDefaultColorProfileSize(
+            WCS_PROFILE_MANAGEMENT_SCOPE_CURRENT_USER,
+            device_key.as_ptr(),
+            CPT_ICC,
+            CPST_NONE,
+            0,
+            &mut size,
+        )
+    } {
+        return None;
+    }
+
+    // Size is in bytes.
+    let mut profile_name = vec![0u16; (size as usize).div_ceil(2)];
+
+    if 0 == unsafe {
+        WcsGetDefaultColorProfile(
+            WCS_PROFILE_MANAGEMENT_SCOPE_CURRENT_USER,
+            device_key.as_ptr(),
+            CPT_ICC,
+            CPST_NONE,
+            0,
+            size,
+            profile_name.as_mut_ptr(),
+        )
+    } {
+        return None;
+    }
+
+    let len = until_nul(&profile_name).len();
+    profile_name.truncate(len);
+    profile_name.push(0);
+
+    Some(profile_name)
+}
+
+/// Returns the system color directory (e.g. `C:\Windows\System32\spool\drivers\color`), if known.
+fn color_directory() -> Option<std::path::PathBuf> {
+    let mut size = 0;
+
+    unsafe { GetColorDirectoryW(std::ptr::null(), std::ptr::null_mut(), &mut size) };
+
+    if size == 0 {
+        return None;
+    }
+
+    // Size is in bytes.
+    let mut directory = vec![0u16; (size as usize).div_ceil(2)];
+
+    if 0 == unsafe { GetColorDirectoryW(std::ptr::null(), directory.as_mut_ptr(), &mut size) } {
+        return None;
+    }
+
+    Some(String::from_utf16_lossy(until_nul(&directory)).into())
+}
+
+fn nul_terminated(string: &OsStr) -> Vec<u16> {
+    string.encode_wide().chain(Some(0)).collect()
+}
+
+fn until_nul(string: &[u16]) -> &[u16] {
+    string.split(|&c| c == 0).next().unwrap_or(string)
+}
+
+/// Undoes the changes made so far, in reverse order, when dropped without being committed.
+#[derive(Default)]
+struct Rollback(Vec<Box<dyn FnOnce()>>);
+
+impl Rollback {
+    fn push<F: FnOnce() + 'static>(&mut self, undo: F) {
+        self.0.push(Box::new(undo));
+    }
+
+    fn commit(mut self) {
+        self.0.clear();
+    }
+}
+
+impl Drop for Rollback {
+    fn drop(&mut self) {
+        while let Some(undo) = self.0.pop() {
+            undo();
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use super::enumerate_displays::ThreadDPIAwarenessGuard;
+use crate::Rectangle;
+
+use windows_sys::Win32::{
+    Foundation::RECT,
+    UI::WindowsAndMessaging::{
+        ClipCursor, GetClipCursor, GetSystemMetrics, SM_CXVIRTUALSCREEN, SM_CYVIRTUALSCREEN,
+        SM_XVIRTUALSCREEN, SM_YVIRTUALSCREEN,
+    },
+};
+
+/// Confines the cursor to the rectangle via `ClipCursor()`,
+/// restoring the previous cursor clip rectangle (if any) when dropped.
+pub(crate) struct CursorClipGuardWin {
+    /// `None` if the cursor was not confined.
+    previous: Option<RECT>,
+}
+
+impl CursorClipGuardWin {
+    /// Confines the cursor to the `rect` (in physical pixels, as reported by the display enumeration).
+    pub(crate) fn new(rect: &Rectangle) -> Result<Self, ()> {
+        // Same as the display rectangles, the cursor clip rectangles are in physical pixels.
+        let _dpi_guard = ThreadDPIAwarenessGuard::new();
+
+        let mut previous: RECT = unsafe { std::mem::zeroed() };
+
+        if 0 == unsafe { GetClipCursor(&mut previous) } {
+            return Err(());
+        }
+
+        if 0 == unsafe { ClipCursor(&rect.to_win_rect()) } {
+            return Err(());
+        }
+
+        // The cursor is "confined" to the whole virtual screen if not confined.
+        let previous = if is_virtual_screen(&previous) {
+            None
+        } else {
+            Some(previous)
+        };
+
+        Ok(Self { previous })
+    }
+}
+
+impl Drop for CursorClipGuardWin {
+    fn drop(&mut self) {
+        let _dpi_guard = ThreadDPIAwarenessGuard::new();
+
+        let previous = self
+            .previous
+            .as_ref()
+            .map_or(std::ptr::null(), |previous| previous as *const _);
+
+        unsafe { ClipCursor(previous) };
+    }
+}
+
+/// Returns `true` if the `rect` covers the whole virtual screen.
+/// NOTE - the calling thread must be DPI-aware.
+fn is_virtual_screen(rect: &RECT) -> bool {
+    let left = unsafe { GetSystemMetrics(SM_XVIRTUALSCREEN) };
+    let top = unsafe { GetSystemMetrics(SM_YVIRTUALSCREEN) };
+    let width = unsafe { GetSystemMetrics(SM_CXVIRTUALSCREEN) };
+    let height = unsafe { GetSystemMetrics(SM_CYVIRTUALSCREEN) };
+
+    rect.left == left
+        && rect.top == top
+        && rect.right == left.saturating_add(width)
+        && rect.bottom == top.saturating_add(height)
+}
@@ -0,0 +1,255 @@
+use super::display_info::DisplayInfoWin;
+use crate::brightness::BrightnessLevel;
+use crate::{PhysicalMonitor, VcpValue};
+
+use windows_sys::Win32::{
+    Devices::Display::{
+        CapabilitiesRequestAndCapabilitiesReply, DestroyPhysicalMonitors,
+        GetCapabilitiesStringLength, GetMonitorBrightness, GetNumberOfPhysicalMonitorsFromHMONITOR,
+        GetPhysicalMonitorsFromHMONITOR, GetVCPFeatureAndVCPFeatureReply, SetMonitorBrightness,
+        SetVCPFeature, PHYSICAL_MONITOR,
+    },
+    Foundation::HANDLE,
+};
+
+/// Reads the current and maximum values of the VCP feature with the `code` of the display's monitor via DDC/CI.
+///
+/// Reads it from the `physical_monitor` with the provided index, if any,
+/// otherwise from the first physical monitor which supports it.
+pub(crate) fn vcp_feature_win(
+    display: &DisplayInfoWin,
+    physical_monitor: Option<u32>,
+    code: u8,
+) -> Result<VcpValue, ()> {
+    PhysicalMonitorsWin::new(display)
+        .ok_or(())?
+        .select(physical_monitor)?
+        .vcp_feature(code)
+        .ok_or(())
+}
+
+/// Sets the `value` of the VCP feature with the `code` of the display's monitor via DDC/CI.
+///
+/// Sets it on the `physical_monitor` with the provided index, if any, otherwise on all physical monitors.
+pub(crate) fn set_vcp_feature_win(
+    display: &DisplayInfoWin,
+    physical_monitor: Option<u32>,
+    code: u8,
+    value: u32,
+) -> Result<(), ()> {
+    PhysicalMonitorsWin::new(display)
+        .ok_or(())?
+        .select(physical_monitor)?
+        .set_vcp_feature(code, value)
+}
+
+/// Returns the physical monitors associated with the display.
+pub(crate) fn physical_monitors_win(display: &DisplayInfoWin) -> Result<Vec<PhysicalMonitor>, ()> {
+    let monitors = PhysicalMonitorsWin::new(display).ok_or(())?;
+
+    Ok((0..monitors.len())
+        .map(|index| PhysicalMonitor {
+            index,
+            description: monitors.description(index).unwrap_or_default(),
+        })
+        .collect())
+}
+
+/// Reads the DDC/CI capabilities string of the display's monitor.
+pub(crate) fn capabilities_win(display: &DisplayInfoWin) -> Result<String, ()> {
+    PhysicalMonitorsWin::new(display)
+        .and_then(|monitors| monitors.capabilities())
+        .ok_or(())
+}
+
+/// Physical monitors associated with the display (usually one, but e.g. more in the clone mode
+/// or behind some docks), for DDC/CI access.
+///
+/// Owns the physical monitor handles, which are destroyed when this is dropped.
+pub struct PhysicalMonitorsWin(Vec<PHYSICAL_MONITOR>);
+
+impl PhysicalMonitorsWin {
+    /// Opens the physical monitors associated with the `display`.
+    ///
+    /// Returns `None` if the display's monitor handle is null, or if it has no physical monitors.
+    pub fn new(display: &DisplayInfoWin) -> Option<Self> {
+        if display.monitor.is_null() {
+            return None;
+        }
+
+        let mut num_monitors = 0;
+
+        if 0 == unsafe {
+            GetNumberOfPhysicalMonitorsFromHMONITOR(display.monitor, &mut num_monitors)
+        } || num_monitors == 0
+        {
+            return None;
+        }
+
+        let mut monitors = vec![PHYSICAL_MONITOR::default(); num_monitors as usize];
+
+        if 0 == unsafe {
+            GetPhysicalMonitorsFromHMONITOR(display.monitor, num_monitors, monitors.as_mut_ptr())
+        } {
+            return None;
+        }
+
+        Some(Self(monitors))
+    }
+
+    /// Returns the number of the physical monitors.
+    pub fn len(&self) -> u32 {
+        self.0.len() as u32
+    }
+
+    /// Returns `true` if there are no physical monitors.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the handle of the physical monitor with the provided `index`,
+    /// or `None` if `index` is out of bounds.
+    ///
+    /// The handle is valid as long as this is alive.
+    pub fn handle(&self, index: u32) -> Option<HANDLE> {
+        self.0
+            .get(index as usize)
+            .map(|monitor| monitor.hPhysicalMonitor)
+    }
+
+    /// Returns the description of the physical monitor with the provided `index`
+    /// (usually the generic `Generic PnP Monitor`), or `None` if `index` is out of bounds.
+    pub fn description(&self, index: u32) -> Option<String> {
+        // Copy the description out of the packed struct.
+        let description = { self.0.get(index as usize)?.szPhysicalMonitorDescription };
+        let description = description
+            .split(|&c| c == 0)
+            .next()
+            .unwrap_or(&description);
+
+        Some(String::from_utf16_lossy(description))
+    }
+
+    /// Keeps only the `physical_monitor` with the provided index, if any, destroying the other handles.
+    /// Fails if the index is out of bounds.
+    fn select(mut self, physical_monitor: Option<u32>) -> Result<Self, ()> {
+        if let Some(index) = physical_monitor {
+            let index = index as usize;
+
+            if index >= self.0.len() {
+                return Err(());
+            }
+
+            let monitor = self.0.swap_remove(index);
+            let others = std::mem::replace(&mut self.0, vec![monitor]);
+
+            unsafe { DestroyPhysicalMonitors(others.len() as u32, others.as_ptr()) };
+        }
+
+        Ok(self)
+    }
+
+    /// Returns the brightness level of the first physical monitor which supports DDC/CI, if any.
+    pub(crate) fn brightness(&self) -> Option<BrightnessLevel> {
+        self.0.iter().find_map(|monitor| {
+            let (mut min, mut current, mut max) = (0, 0, 0);
+
+            if 0 != unsafe {
+                GetMonitorBrightness(monitor.hPhysicalMonitor, &mut min, &mut current, &mut max)
+            } {
+                Some(BrightnessLevel { current, min, max })
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Returns the current and maximum values of the VCP feature with the `code`
+    /// of the first physical monitor which supports it, if any.
+    pub(crate) fn vcp_feature(&self, code: u8) -> Option<VcpValue> {
+        self.0.iter().find_map(|monitor| {
+            let (mut current, mut max) = (0, 0);
+
+            if 0 != unsafe {
+                GetVCPFeatureAndVCPFeatureReply(
+                    monitor.hPhysicalMonitor,
+                    code,
+                    std::ptr::null_mut(),
+                    &mut current,
+                    &mut max,
+                )
+            } {
+                Some(VcpValue { current, max })
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Returns the DDC/CI capabilities string of the first physical monitor which reports it, if any.
+    pub(crate) fn capabilities(&self) -> Option<String> {
+        self.0.iter().find_map(|monitor| {
+            let mut len = 0;
+
+            if 0 == unsafe { GetCapabilitiesStringLength(monitor.hPhysicalMonitor, &mut len) }
+                || len == 0
+            {
+                return None;
+            }
+
+            let mut capabilities = vec![0u8; len as usize];
+
+            if 0 == unsafe {
+                CapabilitiesRequestAndCapabilitiesReply(
+                    monitor.hPhysicalMonitor,
+                    capabilities.as_mut_ptr(),
+                    len,
+                )
+            } {
+                return None;
+            }
+
+            // Null-terminated ASCII.
+            let capabilities = capabilities
+                .split(|&c| c == 0)
+                .next()
+                .unwrap_or(&capabilities);
+
+            Some(String::from_utf8_lossy(capabilities).into_owned())
+        })
+    }
+
+    /// Sets the `value` of the VCP feature with the `code` of all physical monitors.
+    /// Succeeds if any of them supports it.
+    pub(crate) fn set_vcp_feature(&self, code: u8, value: u32) -> Result<(), ()> {
+        let mut result = Err(());
+
+        for monitor in self.0.iter() {
+            if 0 != unsafe { SetVCPFeature(monitor.hPhysicalMonitor, code, value) } {
+                result = Ok(());
+            }
+        }
+
+        result
+    }
+
+    /// Sets the brightness `level` of all physical monitors.
+    /// Succeeds if any of them supports DDC/CI.
+    pub(crate) fn set_brightness(&self, level: u32) -> Result<(), ()> {
+        let mut result = Err(());
+
+        for monitor in self.0.iter() {
+            if 0 != unsafe { SetMonitorBrightness(monitor.hPhysicalMonitor, level) } {
+                result = Ok(());
+            }
+        }
+
+        result
+    }
+}
+
+impl Drop for PhysicalMonitorsWin {
+    fn drop(&mut self) {
+        unsafe { DestroyPhysicalMonitors(self.0.len() as u32, self.0.as_ptr()) };
+    }
+}
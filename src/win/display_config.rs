@@ -0,0 +1,379 @@
+use super::display_info::{DisplayConfigPath, DisplayInfoWin};
+use crate::{Dimensions, DisplayConfigChange, Orientation, Projection};
+
+use windows_sys::Win32::{
+    Devices::Display::{
+        GetDisplayConfigBufferSizes, QueryDisplayConfig, SetDisplayConfig, DISPLAYCONFIG_MODE_INFO,
+        DISPLAYCONFIG_MODE_INFO_TYPE_SOURCE, DISPLAYCONFIG_PATH_INFO,
+        DISPLAYCONFIG_ROTATION_IDENTITY, DISPLAYCONFIG_ROTATION_ROTATE180,
+        DISPLAYCONFIG_ROTATION_ROTATE270, DISPLAYCONFIG_ROTATION_ROTATE90,
+        DISPLAYCONFIG_TOPOLOGY_CLONE, DISPLAYCONFIG_TOPOLOGY_EXTEND,
+        DISPLAYCONFIG_TOPOLOGY_EXTERNAL, DISPLAYCONFIG_TOPOLOGY_ID,
+        DISPLAYCONFIG_TOPOLOGY_INTERNAL, QDC_DATABASE_CURRENT, QDC_ONLY_ACTIVE_PATHS,
+        QUERY_DISPLAY_CONFIG_FLAGS, SDC_ALLOW_CHANGES, SDC_APPLY, SDC_SAVE_TO_DATABASE,
+        SDC_TOPOLOGY_CLONE, SDC_TOPOLOGY_EXTEND, SDC_TOPOLOGY_EXTERNAL, SDC_TOPOLOGY_INTERNAL,
+        SDC_USE_SUPPLIED_DISPLAY_CONFIG, SDC_VALIDATE, SET_DISPLAY_CONFIG_FLAGS,
+    },
+    Foundation::ERROR_SUCCESS,
+    Graphics::Gdi::{
+        ChangeDisplaySettingsExW, EnumDisplaySettingsW, CDS_TEST, CDS_UPDATEREGISTRY, DEVMODEW,
+        DISPLAYCONFIG_PATH_ACTIVE, DISPLAYCONFIG_PATH_MODE_IDX_INVALID, DISP_CHANGE_SUCCESSFUL,
+        DMDO_180, DMDO_270, DMDO_90, DMDO_DEFAULT, DM_DISPLAYORIENTATION, DM_PELSHEIGHT,
+        DM_PELSWIDTH, ENUM_CURRENT_SETTINGS,
+    },
+};
+
+/// Queries the display configuration path and mode info arrays via `QueryDisplayConfig()`
+/// with the provided `QDC_...` `flags`.
+pub(crate) fn query_display_config_raw(
+    flags: QUERY_DISPLAY_CONFIG_FLAGS,
+) -> Result<(Vec<DISPLAYCONFIG_PATH_INFO>, Vec<DISPLAYCONFIG_MODE_INFO>), ()> {
+    query_display_config_inner(flags, std::ptr::null_mut())
+}
+
+fn query_display_config_inner(
+    flags: QUERY_DISPLAY_CONFIG_FLAGS,
+    topology_id: *mut DISPLAYCONFIG_TOPOLOGY_ID,
+) -> Result<(Vec<DISPLAYCONFIG_PATH_INFO>, Vec<DISPLAYCONFIG_MODE_INFO>), ()> {
+    let mut num_paths: u32 = 0;
+    let mut num_modes: u32 = 0;
+
+    let res = unsafe { GetDisplayConfigBufferSizes(flags, &mut num_paths, &mut num_modes) };
+
+    if res != ERROR_SUCCESS || num_paths == 0 || num_modes == 0 {
+        return Err(());
+    }
+
+    let mut path_infos: Vec<DISPLAYCONFIG_PATH_INFO> = Vec::with_capacity(num_paths as usize);
+    let mut mode_infos: Vec<DISPLAYCONFIG_MODE_INFO> = Vec::with_capacity(num_modes as usize);
+
+    let res = unsafe {
+        QueryDisplayConfig(
+            flags,
+            &mut num_paths,
+            path_infos.as_mut_ptr(),
+            &mut num_modes,
+            mode_infos.as_mut_ptr(),
+            topology_id,
+        )
+    };
+
+    if res != ERROR_SUCCESS
+        || (num_paths as usize) != path_infos.capacity()
+        || (num_modes as usize) != mode_infos.capacity()
+    {
+        return Err(());
+    }
+
+    unsafe {
+        path_infos.set_len(num_paths as usize);
+        mode_infos.set_len(num_modes as usize);
+    }
+
+    Ok((path_infos, mode_infos))
+}
+
+/// Calls `SetDisplayConfig()` with the supplied path and mode info arrays and additional `SDC_...` `flags`.
+fn set_display_config(
+    path_infos: &[DISPLAYCONFIG_PATH_INFO],
+    mode_infos: &[DISPLAYCONFIG_MODE_INFO],
+    flags: SET_DISPLAY_CONFIG_FLAGS,
+) -> Result<(), ()> {
+    let res = unsafe {
+        SetDisplayConfig(
+            path_infos.len() as u32,
+            path_infos.as_ptr(),
+            mode_infos.len() as u32,
+            mode_infos.as_ptr(),
+            SDC_USE_SUPPLIED_DISPLAY_CONFIG | SDC_ALLOW_CHANGES | flags,
+        )
+    };
+
+    if res == ERROR_SUCCESS as i32 {
+        Ok(())
+    } else {
+        Err(())
+    }
+}
+
+/// Active display configuration (path and mode info arrays) captured via `QueryDisplayConfig()`,
+/// which may be later re-applied via `SetDisplayConfig()`.
+#[derive(Clone)]
+pub(crate) struct DisplayConfigSnapshotWin {
+    path_infos: Vec<DISPLAYCONFIG_PATH_INFO>,
+    mode_infos: Vec<DISPLAYCONFIG_MODE_INFO>,
+}
+
+impl DisplayConfigSnapshotWin {
+    /// Captures the current active display configuration.
+    pub(crate) fn capture() -> Result<Self, ()> {
+        let (path_infos, mode_infos) = query_display_config_raw(QDC_ONLY_ACTIVE_PATHS)?;
+
+        Ok(Self {
+            path_infos,
+            mode_infos,
+        })
+    }
+
+    /// Re-applies the captured display configuration.
+    pub(crate) fn restore(&self) -> Result<(), ()> {
+        set_display_config(
+            &self.path_infos,
+            &self.mode_infos,
+            SDC_APPLY | SDC_SAVE_TO_DATABASE,
+        )
+    }
+
+    /// Finds the index of the (active) path in the captured configuration corresponding to the display config `path`.
+    fn find_path(&self, path: &DisplayConfigPath) -> Option<usize> {
+        self.path_infos.iter().position(|path_info| {
+            path_info.flags & DISPLAYCONFIG_PATH_ACTIVE != 0
+                && path_info.sourceInfo.id == path.source_id
+                && path_info.sourceInfo.adapterId.LowPart == path.source_adapter.low_part
+                && path_info.sourceInfo.adapterId.HighPart == path.source_adapter.high_part
+                && path_info.targetInfo.id == path.target_id
+                && path_info.targetInfo.adapterId.LowPart == path.target_adapter.low_part
+                && path_info.targetInfo.adapterId.HighPart == path.target_adapter.high_part
+        })
+    }
+
+    /// Returns the source mode index of the path at `path_index`, if valid.
+    fn source_mode_index(&self, path_index: usize) -> Option<usize> {
+        let mode_index = unsafe { self.path_infos[path_index].sourceInfo.Anonymous.modeInfoIdx };
+
+        if mode_index == DISPLAYCONFIG_PATH_MODE_IDX_INVALID {
+            return None;
+        }
+
+        let mode_index = mode_index as usize;
+
+        let mode_info = self.mode_infos.get(mode_index)?;
+
+        if mode_info.infoType == DISPLAYCONFIG_MODE_INFO_TYPE_SOURCE {
+            Some(mode_index)
+        } else {
+            None
+        }
+    }
+
+    /// Applies the display configuration `changes` (and the new `primary` display, if any) to the captured configuration.
+    fn apply_changes(
+        &mut self,
+        changes: &[(&DisplayInfoWin, DisplayConfigChange)],
+        primary: Option<&DisplayInfoWin>,
+    ) -> Result<(), ()> {
+        for (platform, change) in changes {
+            let path = platform.path.as_ref().ok_or(())?;
+            let path_index = self.find_path(path).ok_or(())?;
+            let source_mode_index = self.source_mode_index(path_index).ok_or(())?;
+
+            if change.disable {
+                let path_info = &mut self.path_infos[path_index];
+                path_info.flags &= !DISPLAYCONFIG_PATH_ACTIVE;
+                path_info.sourceInfo.Anonymous.modeInfoIdx = DISPLAYCONFIG_PATH_MODE_IDX_INVALID;
+                path_info.targetInfo.Anonymous.modeInfoIdx = DISPLAYCONFIG_PATH_MODE_IDX_INVALID;
+                continue;
+            }
+
+            if let Some(mode) = change.mode {
+                let source_mode =
+                    unsafe { &mut self.mode_infos[source_mode_index].Anonymous.sourceMode };
+                source_mode.width = mode.dimensions.width;
+                source_mode.height = mode.dimensions.height;
+
+                // Let the OS pick the target mode matching the new source mode and refresh rate.
+                let path_info = &mut self.path_infos[path_index];
+                path_info.targetInfo.Anonymous.modeInfoIdx = DISPLAYCONFIG_PATH_MODE_IDX_INVALID;
+                path_info.targetInfo.refreshRate.Numerator = mode.refresh_rate_num;
+                path_info.targetInfo.refreshRate.Denominator = mode.refresh_rate_denom;
+            }
+
+            if let Some(orientation) = change.orientation {
+                let path_info = &mut self.path_infos[path_index];
+
+                let current_orientation = match path_info.targetInfo.rotation {
+                    DISPLAYCONFIG_ROTATION_ROTATE90 => Orientation::Portrait,
+                    DISPLAYCONFIG_ROTATION_ROTATE180 => Orientation::LandscapeFlipped,
+                    DISPLAYCONFIG_ROTATION_ROTATE270 => Orientation::PortraitFlipped,
+                    _ => Orientation::Landscape,
+                };
+
+                path_info.targetInfo.rotation = match orientation {
+                    Orientation::Landscape => DISPLAYCONFIG_ROTATION_IDENTITY,
+                    Orientation::Portrait => DISPLAYCONFIG_ROTATION_ROTATE90,
+                    Orientation::LandscapeFlipped => DISPLAYCONFIG_ROTATION_ROTATE180,
+                    Orientation::PortraitFlipped => DISPLAYCONFIG_ROTATION_ROTATE270,
+                };
+                path_info.targetInfo.Anonymous.modeInfoIdx = DISPLAYCONFIG_PATH_MODE_IDX_INVALID;
+
+                // Source (desktop) dimensions are rotated.
+                if orientation.is_portrait() != current_orientation.is_portrait() {
+                    let source_mode =
+                        unsafe { &mut self.mode_infos[source_mode_index].Anonymous.sourceMode };
+                    std::mem::swap(&mut source_mode.width, &mut source_mode.height);
+                }
+            }
+
+            if let Some(position) = change.position {
+                let source_mode =
+                    unsafe { &mut self.mode_infos[source_mode_index].Anonymous.sourceMode };
+                source_mode.position.x = position.left;
+                source_mode.position.y = position.top;
+            }
+        }
+
+        // The primary display is the one at the virtual desktop origin -
+        // offset all displays so that the new primary display is at the origin.
+        if let Some(primary) = primary {
+            let path = primary.path.as_ref().ok_or(())?;
+            let path_index = self.find_path(path).ok_or(())?;
+            let source_mode_index = self.source_mode_index(path_index).ok_or(())?;
+
+            let origin = unsafe {
+                self.mode_infos[source_mode_index]
+                    .Anonymous
+                    .sourceMode
+                    .position
+            };
+
+            for mode_info in self
+                .mode_infos
+                .iter_mut()
+                .filter(|mode_info| mode_info.infoType == DISPLAYCONFIG_MODE_INFO_TYPE_SOURCE)
+            {
+                let source_mode = unsafe { &mut mode_info.Anonymous.sourceMode };
+                source_mode.position.x = source_mode.position.x.saturating_sub(origin.x);
+                source_mode.position.y = source_mode.position.y.saturating_sub(origin.y);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Applies the display configuration `changes` (and the new `primary` display, if any) in one `SetDisplayConfig()` call,
+/// after validating them.
+/// Only validates the changes if `validate_only` is `true`.
+///
+/// Returns the display configuration captured before the changes were applied.
+/// Reverts to it if applying the changes failed.
+pub(crate) fn apply_display_config_win(
+    changes: &[(&DisplayInfoWin, DisplayConfigChange)],
+    primary: Option<&DisplayInfoWin>,
+    validate_only: bool,
+) -> Result<DisplayConfigSnapshotWin, ()> {
+    let previous = DisplayConfigSnapshotWin::capture()?;
+
+    let mut config = previous.clone();
+    config.apply_changes(changes, primary)?;
+
+    set_display_config(&config.path_infos, &config.mode_infos, SDC_VALIDATE)?;
+
+    if !validate_only
+        && set_display_config(
+            &config.path_infos,
+            &config.mode_infos,
+            SDC_APPLY | SDC_SAVE_TO_DATABASE,
+        )
+        .is_err()
+    {
+        // The OS is not supposed to apply anything on failure, but just in case.
+        let _ = previous.restore();
+        return Err(());
+    }
+
+    Ok(previous)
+}
+
+/// Returns the current display topology (projection mode), as used by the `Win+P` OS UI.
+pub(crate) fn projection_win() -> Result<Projection, ()> {
+    let mut topology_id: DISPLAYCONFIG_TOPOLOGY_ID = 0;
+
+    query_display_config_inner(QDC_DATABASE_CURRENT, &mut topology_id)?;
+
+    match topology_id {
+        DISPLAYCONFIG_TOPOLOGY_INTERNAL => Ok(Projection::InternalOnly),
+        DISPLAYCONFIG_TOPOLOGY_CLONE => Ok(Projection::Clone),
+        DISPLAYCONFIG_TOPOLOGY_EXTEND => Ok(Projection::Extend),
+        DISPLAYCONFIG_TOPOLOGY_EXTERNAL => Ok(Projection::ExternalOnly),
+        _ => Err(()),
+    }
+}
+
+/// Sets the display topology (projection mode), like the `Win+P` OS UI does.
+pub(crate) fn set_projection_win(projection: Projection) -> Result<(), ()> {
+    let topology = match projection {
+        Projection::InternalOnly => SDC_TOPOLOGY_INTERNAL,
+        Projection::Clone => SDC_TOPOLOGY_CLONE,
+        Projection::Extend => SDC_TOPOLOGY_EXTEND,
+        Projection::ExternalOnly => SDC_TOPOLOGY_EXTERNAL,
+    };
+
+    let res = unsafe {
+        SetDisplayConfig(
+            0,
+            std::ptr::null(),
+            0,
+            std::ptr::null(),
+            SDC_APPLY | topology,
+        )
+    };
+
+    if res == ERROR_SUCCESS as i32 {
+        Ok(())
+    } else {
+        Err(())
+    }
+}
+
+/// Changes the `orientation` of the display via `ChangeDisplaySettingsExW()`,
+/// setting its desktop `dimensions` (already swapped if necessary).
+pub(crate) fn set_orientation_win(
+    platform: &DisplayInfoWin,
+    orientation: Orientation,
+    dimensions: Dimensions,
+) -> Result<(), ()> {
+    let device_name = platform.gdi_device_name.ok_or(())?;
+
+    let mut display_mode: DEVMODEW = unsafe { std::mem::zeroed() };
+    display_mode.dmSize = std::mem::size_of_val(&display_mode) as u16;
+
+    if 0 == unsafe {
+        EnumDisplaySettingsW(
+            device_name.as_ptr(),
+            ENUM_CURRENT_SETTINGS,
+            &mut display_mode,
+        )
+    } {
+        return Err(());
+    }
+
+    display_mode.dmPelsWidth = dimensions.width;
+    display_mode.dmPelsHeight = dimensions.height;
+    display_mode.Anonymous1.Anonymous2.dmDisplayOrientation = match orientation {
+        Orientation::Landscape => DMDO_DEFAULT,
+        Orientation::Portrait => DMDO_90,
+        Orientation::LandscapeFlipped => DMDO_180,
+        Orientation::PortraitFlipped => DMDO_270,
+    };
+    display_mode.dmFields |= DM_PELSWIDTH | DM_PELSHEIGHT | DM_DISPLAYORIENTATION;
+
+    // Test the change first, then apply it.
+    for &flags in [CDS_TEST, CDS_UPDATEREGISTRY].iter() {
+        if DISP_CHANGE_SUCCESSFUL
+            != unsafe {
+                ChangeDisplaySettingsExW(
+                    device_name.as_ptr(),
+                    &display_mode,
+                    std::ptr::null_mut(),
+                    flags,
+                    std::ptr::null_mut(),
+                )
+            }
+        {
+            return Err(());
+        }
+    }
+
+    Ok(())
+}
@@ -1,7 +1,320 @@
-use winapi::shared::windef::HMONITOR;
+use std::sync::Arc;
 
-/// Windows-specific display info contains the native monitor handle.
-#[derive(Clone, Copy, Debug)]
+use super::ddc::PhysicalMonitorsWin;
+use super::fullscreen::has_fullscreen_window_win;
+#[cfg(feature = "wmi")]
+use super::wmi::WmiMonitorInfo;
+use crate::DisplayName;
+
+use windows_sys::{
+    Wdk::Graphics::Direct3D::{
+        D3DKMTCloseAdapter, D3DKMTOpenAdapterFromGdiDisplayName, D3DKMTWaitForVerticalBlankEvent,
+        D3DKMT_CLOSEADAPTER, D3DKMT_OPENADAPTERFROMGDIDISPLAYNAME,
+        D3DKMT_WAITFORVERTICALBLANKEVENT,
+    },
+    Win32::{
+        Devices::Display::{DISPLAYCONFIG_MODE_INFO, DISPLAYCONFIG_PATH_INFO},
+        Foundation::LUID,
+        Graphics::Gdi::HMONITOR,
+    },
+};
+
+/// Graphics adapter locally unique identifier (`LUID`),
+/// as used by the display configuration API's.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct AdapterLuid {
+    pub low_part: u32,
+    pub high_part: i32,
+}
+
+impl AdapterLuid {
+    /// Returns the adapter identifier as the native `LUID`.
+    pub fn to_luid(self) -> LUID {
+        LUID {
+            LowPart: self.low_part,
+            HighPart: self.high_part,
+        }
+    }
+}
+
+impl From<LUID> for AdapterLuid {
+    fn from(luid: LUID) -> Self {
+        Self {
+            low_part: luid.LowPart,
+            high_part: luid.HighPart,
+        }
+    }
+}
+
+/// Identifies the display configuration path the display was resolved to during enumeration.
+///
+/// May be used to make further `DisplayConfigGetDeviceInfo()` / `SetDisplayConfig()` calls for the display.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DisplayConfigPath {
+    /// Identifier of the graphics adapter the display's source is on.
+    pub source_adapter: AdapterLuid,
+    /// Source identifier, unique per adapter.
+    pub source_id: u32,
+    /// Index of the source's mode in the mode info array returned by `QueryDisplayConfig()`,
+    /// if any.
+    pub source_mode_index: Option<u32>,
+    /// Identifier of the graphics adapter the display's target is on.
+    pub target_adapter: AdapterLuid,
+    /// Target identifier, unique per adapter.
+    pub target_id: u32,
+    /// Index of the target's mode in the mode info array returned by `QueryDisplayConfig()`,
+    /// if any.
+    pub target_mode_index: Option<u32>,
+}
+
+/// The display's monitor device and display configuration path state, as reported by the OS during enumeration.
+///
+/// By default, only the displays whose monitor device is active and attached are enumerated.
+/// Opt in to also enumerating the inactive ones (e.g. while the monitor is turned off)
+/// with [`Displays::set_include_inactive`], so that the applications may decide how to treat them
+/// (e.g. list them grayed-out).
+///
+/// [`Displays::set_include_inactive`]: struct.Displays.html#method.set_include_inactive
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DisplayStateWin {
+    /// Whether the display's monitor device is active (`DISPLAY_DEVICE_ACTIVE`).
+    pub device_active: bool,
+    /// Whether the display's monitor device is attached (`DISPLAY_DEVICE_ATTACHED`).
+    pub device_attached: bool,
+    /// Whether the display's display configuration path is active (`DISPLAYCONFIG_PATH_ACTIVE`),
+    /// if the display was enumerated via the display configuration API's.
+    pub path_active: Option<bool>,
+    /// Whether the display's display configuration path target is available (`targetAvailable`),
+    /// i.e. a monitor is connected to it, if the display was enumerated via the display configuration API's.
+    pub target_available: Option<bool>,
+}
+
+impl DisplayStateWin {
+    /// Returns `true` if the display's monitor device is active and attached,
+    /// and its display configuration path (if known) is active and available.
+    pub fn is_active(&self) -> bool {
+        self.device_active
+            && self.device_attached
+            && self.path_active != Some(false)
+            && self.target_available != Some(false)
+    }
+}
+
+/// Raw display configuration path and mode info arrays, as returned by `QueryDisplayConfig(QDC_ONLY_ACTIVE_PATHS)`
+/// when the displays were enumerated.
+///
+/// Allows doing anything the crate does not wrap yet without re-querying the display configuration
+/// and re-matching it to the enumerated displays:
+/// the [`display configuration path`] mode indices index the `mode_infos` array.
+///
+/// [`display configuration path`]: struct.DisplayConfigPath.html
+#[derive(Clone)]
+#[cfg_attr(not(feature = "raw-win"), allow(dead_code))]
+pub struct RawDisplayConfig {
+    /// Active display configuration paths.
+    pub path_infos: Vec<DISPLAYCONFIG_PATH_INFO>,
+    /// Source and target modes of the active display configuration paths.
+    pub mode_infos: Vec<DISPLAYCONFIG_MODE_INFO>,
+}
+
+/// Windows-specific display info contains the native monitor handle
+/// and the raw UTF-16 strings identifying the display.
+#[derive(Clone, Debug)]
 pub struct DisplayInfoWin {
     pub monitor: HMONITOR,
+    pub(crate) name: Option<DisplayName>,
+    pub(crate) device_path: Option<Arc<[u16]>>,
+    /// Nul-terminated GDI device name (e.g. `\\.\DISPLAY1`), if known.
+    pub(crate) gdi_device_name: Option<[u16; 32]>,
+    /// Display's number, as shown in the OS display settings UI (e.g. `2` for `\\.\DISPLAY2`), if known.
+    ///
+    /// NOTE - does not necessarily match the display's enumeration index.
+    pub settings_number: Option<u32>,
+    /// Display configuration path, if the display was enumerated via the display configuration API's.
+    pub(crate) path: Option<DisplayConfigPath>,
+    /// Monitor device and display configuration path state, if the display was enumerated by the crate.
+    pub(crate) state: Option<DisplayStateWin>,
+    #[cfg(feature = "wmi")]
+    pub(crate) wmi: Option<WmiMonitorInfo>,
 }
+
+impl DisplayInfoWin {
+    /// Creates the Windows-specific display info with provided native `monitor` handle
+    /// (which may be null, e.g. for the displays provided by a custom [`display backend`]) and no other info.
+    ///
+    /// [`display backend`]: trait.DisplayBackend.html
+    pub fn new(monitor: HMONITOR) -> Self {
+        Self {
+            monitor,
+            name: None,
+            device_path: None,
+            gdi_device_name: None,
+            settings_number: None,
+            path: None,
+            state: None,
+            #[cfg(feature = "wmi")]
+            wmi: None,
+        }
+    }
+
+    /// Returns the display's friendly name as the original UTF-16 string (not nul-terminated), if any.
+    ///
+    /// Unlike the (lossy) UTF-8 display name, may be losslessly passed to other Windows API's.
+    pub fn name_utf16(&self) -> Option<&[u16]> {
+        self.name.as_ref().map(DisplayName::as_utf16)
+    }
+
+    /// Sets the display's nul-terminated GDI device name (e.g. `\\.\DISPLAY1`),
+    /// and the display's settings number derived from it.
+    pub(crate) fn set_gdi_device_name(&mut self, gdi_device_name: [u16; 32]) {
+        self.gdi_device_name.replace(gdi_device_name);
+        self.settings_number = settings_number(&gdi_device_name);
+    }
+
+    /// Returns the display monitor's device interface path
+    /// (e.g. `\\?\DISPLAY#DEL40F0#5&1a2b3c4d&0&UID4352#{e6f07b5f-ee97-4a90-b076-33f57bf4eaa7}`)
+    /// as the original UTF-16 string (not nul-terminated), if known.
+    pub fn device_path_utf16(&self) -> Option<&[u16]> {
+        self.device_path.as_deref()
+    }
+
+    /// Returns the display configuration [`path`] the display was resolved to,
+    /// if it was enumerated via the display configuration API's.
+    ///
+    /// [`path`]: struct.DisplayConfigPath.html
+    pub fn display_config_path(&self) -> Option<&DisplayConfigPath> {
+        self.path.as_ref()
+    }
+
+    /// Returns the display's monitor device and display configuration path [`state`],
+    /// if the display was enumerated by the crate (i.e. not provided by a custom [`display backend`]).
+    ///
+    /// [`state`]: struct.DisplayStateWin.html
+    /// [`display backend`]: trait.DisplayBackend.html
+    pub fn state(&self) -> Option<DisplayStateWin> {
+        self.state
+    }
+
+    /// Returns the identifier of the graphics adapter the display is on,
+    /// if it was enumerated via the display configuration API's.
+    pub fn adapter_luid(&self) -> Option<AdapterLuid> {
+        self.path.map(|path| path.target_adapter)
+    }
+
+    /// Returns the identifier of the graphics adapter a (high-performance) application
+    /// should render on to present to the display without a cross-adapter copy
+    /// (i.e. the adapter which drives the display's source), if the display was enumerated via the display configuration API's.
+    ///
+    /// NOTE - on hybrid graphics systems this is often the integrated adapter,
+    /// even if a discrete adapter is available.
+    pub fn render_adapter_luid(&self) -> Option<AdapterLuid> {
+        self.path.map(|path| path.source_adapter)
+    }
+
+    /// Returns `true` if this and the `other` display are driven by the same graphics adapter,
+    /// i.e. content may be presented to / moved between both without a cross-adapter copy.
+    ///
+    /// Returns `false` if the render adapter of either display is unknown.
+    pub fn same_adapter(&self, other: &DisplayInfoWin) -> bool {
+        match (self.render_adapter_luid(), other.render_adapter_luid()) {
+            (Some(adapter), Some(other_adapter)) => adapter == other_adapter,
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if the display currently shows a fullscreen foreground window
+    /// (e.g. an exclusive or borderless fullscreen game, or a fullscreen video player),
+    /// in which case popups / notifications should not be shown on it.
+    ///
+    /// NOTE - queries the current foreground window on each call.
+    pub fn has_fullscreen_window(&self) -> bool {
+        !self.monitor.is_null() && has_fullscreen_window_win(self.monitor)
+    }
+
+    /// Opens the display's physical monitors, e.g. to pass their handles to the DDC/CI API's directly.
+    ///
+    /// Returns `None` if the display's monitor handle is null, or if it has no physical monitors.
+    pub fn physical_monitors(&self) -> Option<PhysicalMonitorsWin> {
+        PhysicalMonitorsWin::new(self)
+    }
+
+    /// Blocks the calling thread until the display's next vertical blank.
+    ///
+    /// Returns an error if the display's GDI device name is unknown (e.g. the display was not fully enumerated),
+    /// or the wait failed (e.g. the display was disconnected).
+    ///
+    /// NOTE - opens (and closes) the display's graphics adapter on each call.
+    pub fn wait_for_vblank(&self) -> Result<(), ()> {
+        let gdi_device_name = self.gdi_device_name.ok_or(())?;
+
+        let mut open_adapter = D3DKMT_OPENADAPTERFROMGDIDISPLAYNAME {
+            DeviceName: gdi_device_name,
+            hAdapter: 0,
+            AdapterLuid: LUID {
+                LowPart: 0,
+                HighPart: 0,
+            },
+            VidPnSourceId: 0,
+        };
+
+        if 0 != unsafe { D3DKMTOpenAdapterFromGdiDisplayName(&mut open_adapter) } {
+            return Err(());
+        }
+
+        let wait = D3DKMT_WAITFORVERTICALBLANKEVENT {
+            hAdapter: open_adapter.hAdapter,
+            hDevice: 0,
+            VidPnSourceId: open_adapter.VidPnSourceId,
+        };
+
+        let res = unsafe { D3DKMTWaitForVerticalBlankEvent(&wait) };
+
+        let close_adapter = D3DKMT_CLOSEADAPTER {
+            hAdapter: open_adapter.hAdapter,
+        };
+        unsafe { D3DKMTCloseAdapter(&close_adapter) };
+
+        if res == 0 {
+            Ok(())
+        } else {
+            Err(())
+        }
+    }
+
+    /// Returns the display monitor's info (serial number, manufacture date, etc.) as queried via WMI,
+    /// if available.
+    #[cfg(feature = "wmi")]
+    pub fn wmi_info(&self) -> Option<&WmiMonitorInfo> {
+        self.wmi.as_ref()
+    }
+}
+
+/// Parses the display's number, as shown in the OS display settings UI,
+/// from the nul-terminated GDI device name (e.g. `2` for `\\.\DISPLAY2`).
+fn settings_number(gdi_device_name: &[u16]) -> Option<u32> {
+    const PREFIX: &str = r"\\.\DISPLAY";
+
+    let gdi_device_name = gdi_device_name
+        .split(|&c| c == 0)
+        .next()
+        .unwrap_or(gdi_device_name);
+    let gdi_device_name = String::from_utf16(gdi_device_name).ok()?;
+
+    let number = gdi_device_name.get(..PREFIX.len()).and_then(|prefix| {
+        if prefix.eq_ignore_ascii_case(PREFIX) {
+            gdi_device_name.get(PREFIX.len()..)
+        } else {
+            None
+        }
+    })?;
+
+    if number.is_empty() || !number.bytes().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    number.parse().ok()
+}
+
+// `HMONITOR` is an opaque handle which may be used from any thread.
+unsafe impl Send for DisplayInfoWin {}
+unsafe impl Sync for DisplayInfoWin {}
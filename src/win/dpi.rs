@@ -0,0 +1,131 @@
+//! Display DPI scaling options query / DPI scale setter (`unstable-scaling` feature) via the undocumented (but widely used)
+//! `DisplayConfigGetDeviceInfo()` / `DisplayConfigSetDeviceInfo()` DPI scale packets,
+//! and the process / thread DPI awareness query.
+
+use super::display_info::DisplayInfoWin;
+use crate::{DpiAwareness, DpiOptions};
+
+#[cfg(feature = "unstable-scaling")]
+use windows_sys::Win32::Devices::Display::DisplayConfigSetDeviceInfo;
+use windows_sys::Win32::{
+    Devices::Display::{
+        DisplayConfigGetDeviceInfo, DISPLAYCONFIG_DEVICE_INFO_HEADER,
+        DISPLAYCONFIG_DEVICE_INFO_TYPE,
+    },
+    Foundation::ERROR_SUCCESS,
+    UI::HiDpi::{
+        AreDpiAwarenessContextsEqual, GetAwarenessFromDpiAwarenessContext,
+        GetDpiAwarenessContextForProcess, GetThreadDpiAwarenessContext, DPI_AWARENESS_CONTEXT,
+        DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2, DPI_AWARENESS_PER_MONITOR_AWARE,
+        DPI_AWARENESS_SYSTEM_AWARE, DPI_AWARENESS_UNAWARE,
+    },
+};
+
+/// Undocumented - returns the display's DPI scale step indices relative to the recommended DPI scale step.
+const DISPLAYCONFIG_DEVICE_INFO_GET_DPI_SCALE: DISPLAYCONFIG_DEVICE_INFO_TYPE = -3;
+/// Undocumented - sets the display's DPI scale step index relative to the recommended DPI scale step.
+#[cfg(feature = "unstable-scaling")]
+const DISPLAYCONFIG_DEVICE_INFO_SET_DPI_SCALE: DISPLAYCONFIG_DEVICE_INFO_TYPE = -4;
+
+#[allow(non_camel_case_types, non_snake_case)]
+#[repr(C)]
+struct DISPLAYCONFIG_SOURCE_DPI_SCALE_GET {
+    header: DISPLAYCONFIG_DEVICE_INFO_HEADER,
+    minScaleRel: i32,
+    curScaleRel: i32,
+    maxScaleRel: i32,
+}
+
+#[cfg(feature = "unstable-scaling")]
+#[allow(non_camel_case_types, non_snake_case)]
+#[repr(C)]
+struct DISPLAYCONFIG_SOURCE_DPI_SCALE_SET {
+    header: DISPLAYCONFIG_DEVICE_INFO_HEADER,
+    scaleRel: i32,
+}
+
+/// Returns the display's DPI scaling options,
+/// or an error if the display was not enumerated via the display configuration API's, or on failure.
+pub(crate) fn dpi_options_win(platform: &DisplayInfoWin) -> Result<DpiOptions, ()> {
+    let path = platform.path.as_ref().ok_or(())?;
+
+    let mut get_dpi_scale = DISPLAYCONFIG_SOURCE_DPI_SCALE_GET {
+        header: DISPLAYCONFIG_DEVICE_INFO_HEADER {
+            r#type: DISPLAYCONFIG_DEVICE_INFO_GET_DPI_SCALE,
+            size: std::mem::size_of::<DISPLAYCONFIG_SOURCE_DPI_SCALE_GET>() as u32,
+            adapterId: path.source_adapter.to_luid(),
+            id: path.source_id,
+        },
+        minScaleRel: 0,
+        curScaleRel: 0,
+        maxScaleRel: 0,
+    };
+
+    if ERROR_SUCCESS as i32 != unsafe { DisplayConfigGetDeviceInfo(&mut get_dpi_scale.header) } {
+        return Err(());
+    }
+
+    DpiOptions::from_relative(
+        get_dpi_scale.minScaleRel,
+        get_dpi_scale.curScaleRel,
+        get_dpi_scale.maxScaleRel,
+    )
+    .ok_or(())
+}
+
+/// Sets the display's DPI scale `percent`, which must be one of the display's allowed DPI scale steps.
+#[cfg(feature = "unstable-scaling")]
+pub(crate) fn set_dpi_scale_win(platform: &DisplayInfoWin, percent: u32) -> Result<(), ()> {
+    let path = platform.path.as_ref().ok_or(())?;
+    let scale_rel = dpi_options_win(platform)?
+        .relative_step(percent)
+        .ok_or(())?;
+
+    let set_dpi_scale = DISPLAYCONFIG_SOURCE_DPI_SCALE_SET {
+        header: DISPLAYCONFIG_DEVICE_INFO_HEADER {
+            r#type: DISPLAYCONFIG_DEVICE_INFO_SET_DPI_SCALE,
+            size: std::mem::size_of::<DISPLAYCONFIG_SOURCE_DPI_SCALE_SET>() as u32,
+            adapterId: path.source_adapter.to_luid(),
+            id: path.source_id,
+        },
+        scaleRel: scale_rel,
+    };
+
+    if ERROR_SUCCESS as i32 == unsafe { DisplayConfigSetDeviceInfo(&set_dpi_scale.header) } {
+        Ok(())
+    } else {
+        Err(())
+    }
+}
+
+/// Returns the DPI awareness of the current process.
+pub(crate) fn process_dpi_awareness_win() -> Result<DpiAwareness, ()> {
+    // Null process handle means the current process.
+    dpi_awareness(unsafe { GetDpiAwarenessContextForProcess(std::ptr::null_mut()) })
+}
+
+/// Returns the DPI awareness of the calling thread (the same as the process' one, unless overridden).
+pub(crate) fn thread_dpi_awareness_win() -> Result<DpiAwareness, ()> {
+    dpi_awareness(unsafe { GetThreadDpiAwarenessContext() })
+}
+
+fn dpi_awareness(context: DPI_AWARENESS_CONTEXT) -> Result<DpiAwareness, ()> {
+    if context.is_null() {
+        return Err(());
+    }
+
+    match unsafe { GetAwarenessFromDpiAwarenessContext(context) } {
+        DPI_AWARENESS_UNAWARE => Ok(DpiAwareness::Unaware),
+        DPI_AWARENESS_SYSTEM_AWARE => Ok(DpiAwareness::System),
+        DPI_AWARENESS_PER_MONITOR_AWARE => {
+            if 0 != unsafe {
+                AreDpiAwarenessContextsEqual(context, DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2)
+            } {
+                Ok(DpiAwareness::PerMonitorV2)
+            } else {
+                Ok(DpiAwareness::PerMonitor)
+            }
+        }
+        _ => Err(()),
+    }
+}
@@ -1,65 +1,67 @@
-use super::display_info::DisplayInfoWin;
-use super::util::from_wstr;
-use crate::displays::EnumeratedDisplayInfo;
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+use super::display_config::query_display_config_raw;
+use super::display_info::{DisplayConfigPath, DisplayInfoWin, DisplayStateWin, RawDisplayConfig};
+use super::dpi::dpi_options_win;
+use super::inventory::{enumerate_inventory_win, find_edid, is_service_session, monitor_edids_win};
+#[cfg(feature = "wmi")]
+use super::wmi::{find_wmi_monitor_info, query_wmi_monitors};
+use crate::edid::Edid;
+use crate::DisplayRectsFull;
 use crate::{
-    ConnectionType, Dimensions, DisplayInfo, DisplayMode, DisplayRects, Rectangle, UpscaleMode,
+    find_overlap, indirect_driver, ClipRectFlags, ConnectionType, Dimensions, DisplayId,
+    DisplayInfo, DisplayMetrics, DisplayMode, DisplayName, DisplayNames, DisplayRects,
+    DisplaySource, MonitorMetrics, Orientation, Position, Rectangle, SignalTiming, UpscaleMode,
 };
-
-use winapi::{
-    shared::{
-        basetsd::UINT32,
-        minwindef::{BOOL, DWORD, LPARAM, WORD},
-        ntdef::LONG,
-        windef::{
-            DPI_AWARENESS_CONTEXT, DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE, HDC, HMONITOR, LPRECT,
-        },
-        winerror::{ERROR_SUCCESS, S_OK},
-    },
-    um::{
-        shellscalingapi::{GetDpiForMonitor, MDT_EFFECTIVE_DPI},
-        wingdi::{
-            DEVMODEW, DISPLAYCONFIG_DEVICE_INFO_GET_SOURCE_NAME,
-            DISPLAYCONFIG_DEVICE_INFO_GET_TARGET_NAME,
+use crate::{EnumerateOptions, EnumeratedDisplayInfo, Error};
+
+use windows_sys::{
+    core::BOOL,
+    Win32::{
+        Devices::Display::{
+            DisplayConfigGetDeviceInfo, DISPLAYCONFIG_DEVICE_INFO_GET_ADVANCED_COLOR_INFO,
+            DISPLAYCONFIG_DEVICE_INFO_GET_SOURCE_NAME, DISPLAYCONFIG_DEVICE_INFO_GET_TARGET_NAME,
             DISPLAYCONFIG_DEVICE_INFO_GET_TARGET_PREFERRED_MODE, DISPLAYCONFIG_DEVICE_INFO_HEADER,
-            DISPLAYCONFIG_MODE_INFO, DISPLAYCONFIG_MODE_INFO_TYPE_SOURCE,
+            DISPLAYCONFIG_GET_ADVANCED_COLOR_INFO, DISPLAYCONFIG_MODE_INFO,
             DISPLAYCONFIG_MODE_INFO_TYPE_TARGET,
             DISPLAYCONFIG_OUTPUT_TECHNOLOGY_DISPLAYPORT_EMBEDDED,
             DISPLAYCONFIG_OUTPUT_TECHNOLOGY_DISPLAYPORT_EXTERNAL,
             DISPLAYCONFIG_OUTPUT_TECHNOLOGY_DVI, DISPLAYCONFIG_OUTPUT_TECHNOLOGY_HD15,
-            DISPLAYCONFIG_OUTPUT_TECHNOLOGY_HDMI, DISPLAYCONFIG_OUTPUT_TECHNOLOGY_INTERNAL,
-            DISPLAYCONFIG_PATH_INFO, DISPLAYCONFIG_SOURCE_DEVICE_NAME,
-            DISPLAYCONFIG_TARGET_DEVICE_NAME, DISPLAYCONFIG_TARGET_PREFERRED_MODE,
-            DISPLAYCONFIG_TOPOLOGY_ID, DISPLAY_DEVICEW, DISPLAY_DEVICE_ACTIVE,
-            DISPLAY_DEVICE_ATTACHED, DISPLAY_DEVICE_MIRRORING_DRIVER, DMDFO_CENTER, DMDFO_DEFAULT,
-            DMDFO_STRETCH, DM_BITSPERPEL, DM_DISPLAYFIXEDOUTPUT, DM_DISPLAYFREQUENCY,
-            DM_PELSHEIGHT, DM_PELSWIDTH, QDC_ONLY_ACTIVE_PATHS,
+            DISPLAYCONFIG_OUTPUT_TECHNOLOGY_HDMI, DISPLAYCONFIG_OUTPUT_TECHNOLOGY_INDIRECT_VIRTUAL,
+            DISPLAYCONFIG_OUTPUT_TECHNOLOGY_INDIRECT_WIRED,
+            DISPLAYCONFIG_OUTPUT_TECHNOLOGY_INTERNAL, DISPLAYCONFIG_PATH_INFO,
+            DISPLAYCONFIG_SOURCE_DEVICE_NAME, DISPLAYCONFIG_TARGET_DEVICE_NAME,
+            DISPLAYCONFIG_TARGET_PREFERRED_MODE, QDC_ONLY_ACTIVE_PATHS,
         },
-        winnt::WCHAR,
-        winuser::{
+        Foundation::{ERROR_SUCCESS, LPARAM, POINT, RECT, S_OK},
+        Graphics::Gdi::{
             EnumDisplayDevicesW, EnumDisplayMonitors, EnumDisplaySettingsW, GetMonitorInfoW,
-            SetThreadDpiAwarenessContext, ENUM_CURRENT_SETTINGS, MONITORINFO, MONITORINFOEXW,
-            MONITORINFOF_PRIMARY, USER_DEFAULT_SCREEN_DPI,
+            MonitorFromPoint, DEVMODEW, DISPLAYCONFIG_PATH_ACTIVE,
+            DISPLAYCONFIG_PATH_MODE_IDX_INVALID, DISPLAY_DEVICEW, DISPLAY_DEVICE_ACTIVE,
+            DISPLAY_DEVICE_ATTACHED, DISPLAY_DEVICE_MIRRORING_DRIVER, DMDFO_CENTER, DMDFO_DEFAULT,
+            DMDFO_STRETCH, DMDO_180, DMDO_270, DMDO_90, DM_BITSPERPEL, DM_DISPLAYFIXEDOUTPUT,
+            DM_DISPLAYFREQUENCY, DM_DISPLAYORIENTATION, DM_PELSHEIGHT, DM_PELSWIDTH,
+            ENUM_CURRENT_SETTINGS, HDC, HMONITOR, MONITORINFO, MONITORINFOEXW,
+            MONITOR_DEFAULTTOPRIMARY,
+        },
+        UI::{
+            HiDpi::{
+                GetDpiForMonitor, SetThreadDpiAwarenessContext, DPI_AWARENESS_CONTEXT,
+                DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE, MDT_EFFECTIVE_DPI,
+            },
+            WindowsAndMessaging::{
+                GetSystemMetrics, SystemParametersInfoW, EDD_GET_DEVICE_INTERFACE_NAME,
+                MONITORINFOF_PRIMARY, SM_CMONITORS, SM_CXVIRTUALSCREEN, SM_CYVIRTUALSCREEN,
+                SM_XVIRTUALSCREEN, SM_YVIRTUALSCREEN, SPI_GETWORKAREA, USER_DEFAULT_SCREEN_DPI,
+            },
         },
     },
 };
 
-/// TODO: why are these not in `winapi`? Submit a PR?
-extern "system" {
-    fn GetDisplayConfigBufferSizes(
-        flags: UINT32,
-        numPathArrayElements: *mut UINT32,
-        numModeInfoArrayElements: *mut UINT32,
-    ) -> LONG;
-    fn QueryDisplayConfig(
-        flags: UINT32,
-        numPathArrayElements: *mut UINT32,
-        pathArray: *mut DISPLAYCONFIG_PATH_INFO,
-        numModeInfoArrayElements: *mut UINT32,
-        modeInfoArray: *mut DISPLAYCONFIG_MODE_INFO,
-        currentTopologyId: *mut DISPLAYCONFIG_TOPOLOGY_ID,
-    ) -> LONG;
-    fn DisplayConfigGetDeviceInfo(requestPacket: *mut DISPLAYCONFIG_DEVICE_INFO_HEADER) -> LONG;
-}
+/// Set if the path's refresh rate may be dynamically boosted (Dynamic Refresh Rate).
+/// TODO: why is this not in `windows-sys`?
+const DISPLAYCONFIG_PATH_BOOST_REFRESH_RATE: u32 = 0x0000_0010;
 
 /// Display enumeration callback context.
 struct DisplayInfoContext {
@@ -67,10 +69,152 @@ struct DisplayInfoContext {
     path_infos: Vec<DISPLAYCONFIG_PATH_INFO>,
     mode_infos: Vec<DISPLAYCONFIG_MODE_INFO>,
 
-    device_names: Vec<[WCHAR; 32]>,
+    device_names: Vec<[u16; 32]>,
+
+    /// Names of the previously enumerated displays, reused if unchanged.
+    previous_names: Vec<DisplayName>,
+
+    /// Whether the displays whose monitor device is not active / attached are enumerated.
+    include_inactive: bool,
+
+    /// `DisplaySource::Gdi` if the display configuration API's failed
+    /// and the read-only context above is empty.
+    source: DisplaySource,
 
     /// We'll push the successfully enumerated displays in the callback here.
-    displays: Vec<EnumeratedDisplayInfo>,
+    displays: Vec<PendingDisplayInfo>,
+}
+
+/// Display info as enumerated by the callback, before the display's supported display modes are enumerated.
+struct PendingDisplayInfo {
+    device_name: [u16; 32],
+    name: Option<DisplayName>,
+    /// `true` if the display configuration API's failed to return the display's friendly name
+    /// and `name` is a generic one (e.g. `Generic PnP Monitor`), if any.
+    #[cfg_attr(not(feature = "wmi"), allow(dead_code))]
+    generic_name: bool,
+    device_path: Option<Arc<[u16]>>,
+    is_primary: bool,
+    rects: DisplayRects,
+    connection: ConnectionType,
+    current_mode: DisplayMode,
+    orientation: Orientation,
+    /// `None` if unknown - the largest supported display mode is used then.
+    preferred_mode: Option<DisplayMode>,
+    names: DisplayNames,
+    /// `Some` (refresh rate numerator / denominator) if the display's refresh rate is dynamic.
+    dynamic_refresh_ceiling: Option<(u32, u32)>,
+    /// `Some` if the display was enumerated via the display configuration API's and the driver reported it.
+    signal_timing: Option<SignalTiming>,
+    /// `Some` if the display was enumerated via the display configuration API's and the driver reported it.
+    bits_per_channel: Option<u8>,
+    /// `Some` if the display was enumerated via the display configuration API's.
+    path: Option<DisplayConfigPath>,
+    /// `Some` (driver name) if the display was enumerated via the display configuration API's
+    /// and was created by an indirect display driver.
+    indirect_driver: Option<String>,
+    /// Monitor device and display configuration path state.
+    state: DisplayStateWin,
+    /// (horizontal, vertical)
+    dpi_scale: (f32, f32),
+    monitor: HMONITOR,
+}
+
+/// Helper function to extract a `DisplayMode` from `DEVMODEW`.
+/// Returns `None` if one of the mandatory fields (dimensions, refresh rate) are not present in `display_mode`.
+fn display_mode_from_dev_mode(display_mode: &DEVMODEW) -> Option<DisplayMode> {
+    // Skip if width not specified.
+    let width = if (display_mode.dmFields & DM_PELSWIDTH) > 0 {
+        display_mode.dmPelsWidth
+    } else {
+        return None;
+    };
+
+    // Skip if height not specified.
+    let height = if (display_mode.dmFields & DM_PELSHEIGHT) > 0 {
+        display_mode.dmPelsHeight
+    } else {
+        return None;
+    };
+
+    let dimensions = Dimensions { width, height };
+
+    // Skip if refresh rate not specified.
+    let refresh_rate = if (display_mode.dmFields & DM_DISPLAYFREQUENCY) > 0 {
+        display_mode.dmDisplayFrequency
+    } else {
+        return None;
+    };
+
+    // Skip unknown and non-32bpp modes.
+    if (display_mode.dmFields & DM_BITSPERPEL) > 0 {
+        match display_mode.dmBitsPerPel {
+            32 => {}
+            _ => return None,
+        }
+    } else {
+        return None;
+    };
+
+    let upscale_mode = if (display_mode.dmFields & DM_DISPLAYFIXEDOUTPUT) > 0 {
+        match unsafe { display_mode.Anonymous1.Anonymous2.dmDisplayFixedOutput } {
+            DMDFO_DEFAULT => UpscaleMode::Unknown,
+            DMDFO_CENTER => UpscaleMode::Center,
+            DMDFO_STRETCH => UpscaleMode::Stretch,
+            _ => UpscaleMode::Unknown,
+        }
+    } else {
+        UpscaleMode::Unknown
+    };
+
+    Some(DisplayMode {
+        dimensions,
+        refresh_rate,
+        refresh_rate_num: refresh_rate,
+        refresh_rate_denom: 1,
+        upscale_mode,
+    })
+}
+
+/// Helper function to extract the display `Orientation` from `DEVMODEW`.
+/// Returns the default orientation if not present in `display_mode`.
+fn orientation_from_dev_mode(display_mode: &DEVMODEW) -> Orientation {
+    if (display_mode.dmFields & DM_DISPLAYORIENTATION) > 0 {
+        match unsafe { display_mode.Anonymous1.Anonymous2.dmDisplayOrientation } {
+            DMDO_90 => Orientation::Portrait,
+            DMDO_180 => Orientation::LandscapeFlipped,
+            DMDO_270 => Orientation::PortraitFlipped,
+            _ => Orientation::Landscape,
+        }
+    } else {
+        Orientation::Landscape
+    }
+}
+
+/// Returns the `monitor`'s current DPI scale, or `None` on error.
+/// NOTE - the calling thread must be DPI-aware.
+fn monitor_dpi_scale(monitor: HMONITOR) -> Option<(f32, f32)> {
+    let mut display_dpi_x = 0;
+    let mut display_dpi_y = 0;
+
+    if S_OK
+        != unsafe {
+            GetDpiForMonitor(
+                monitor,
+                MDT_EFFECTIVE_DPI,
+                &mut display_dpi_x,
+                &mut display_dpi_y,
+            )
+        }
+    {
+        return None;
+    };
+
+    // Usually the same, but non-square DPI scaling does exist.
+    Some((
+        display_dpi_x as f32 / USER_DEFAULT_SCREEN_DPI as f32,
+        display_dpi_y as f32 / USER_DEFAULT_SCREEN_DPI as f32,
+    ))
 }
 
 // https://docs.microsoft.com/en-us/windows/win32/api/winuser/nc-winuser-monitorenumproc
@@ -79,156 +223,67 @@ struct DisplayInfoContext {
 extern "system" fn add_display_callback(
     monitor: HMONITOR,
     _hdcmonitor: HDC,
-    lprcmonitor: LPRECT,
+    lprcmonitor: *mut RECT,
     dwdata: LPARAM,
 ) -> BOOL {
-    assert!(dwdata != 0);
+    // NOTE - panics must not unwind out of the callback;
+    // skip the display (or fall back to GDI data) instead on any unexpected API results.
+    if dwdata == 0 {
+        return 0;
+    }
 
     let context: &mut DisplayInfoContext = unsafe { &mut *(dwdata as *mut _) };
 
     let mut monitor_info: MONITORINFOEXW = unsafe { std::mem::zeroed() };
-    monitor_info.cbSize = std::mem::size_of_val(&monitor_info) as DWORD;
+    monitor_info.monitorInfo.cbSize = std::mem::size_of_val(&monitor_info) as u32;
 
     if 0 == unsafe { GetMonitorInfoW(monitor, &mut monitor_info as *mut _ as *mut MONITORINFO) } {
         return 1;
     }
 
-    assert!(!lprcmonitor.is_null());
-    let rcmonitor = unsafe { &*lprcmonitor };
-
-    assert_eq!(
-        monitor_info.rcMonitor.left, rcmonitor.left,
-        "Display rectangle size mismatch."
-    );
-    assert_eq!(
-        monitor_info.rcMonitor.right, rcmonitor.right,
-        "Display rectangle size mismatch."
-    );
-    assert_eq!(
-        monitor_info.rcMonitor.top, rcmonitor.top,
-        "Display rectangle size mismatch."
-    );
-    assert_eq!(
-        monitor_info.rcMonitor.bottom, rcmonitor.bottom,
-        "Display rectangle size mismatch."
-    );
+    // Skip this display if its rectangle changed since the enumeration started.
+    if !lprcmonitor.is_null() {
+        let rcmonitor = unsafe { &*lprcmonitor };
+        let rc = &monitor_info.monitorInfo.rcMonitor;
+
+        if (rc.left, rc.top, rc.right, rc.bottom)
+            != (
+                rcmonitor.left,
+                rcmonitor.top,
+                rcmonitor.right,
+                rcmonitor.bottom,
+            )
+        {
+            return 1;
+        }
+    }
 
     // Display rectangles.
-    let virtual_rect = Rectangle::from_win_rect(&monitor_info.rcMonitor);
-    let work_rect = Rectangle::from_win_rect(&monitor_info.rcWork);
+    let virtual_rect = Rectangle::from_win_rect(&monitor_info.monitorInfo.rcMonitor);
+    let work_rect = Rectangle::from_win_rect(&monitor_info.monitorInfo.rcWork);
+
+    // Work rectangle must always be contained in the virtual rectangle.
+    // Clamp it / fall back to the virtual rectangle otherwise.
+    let work_rect = if virtual_rect.contains(&work_rect) {
+        work_rect
+    } else {
+        work_rect
+            .intersection(&virtual_rect)
+            .unwrap_or(virtual_rect)
+    };
+
     let rectangles = DisplayRects {
         virtual_rect,
         work_rect,
     };
 
-    // Work rectangle must always be smaller or equal.
-    assert!(
-        work_rect.width() <= virtual_rect.width(),
-        "Expected a work rectangle to be smaller or equal."
-    );
-    assert!(
-        work_rect.height() <= virtual_rect.height(),
-        "Expected a work rectangle to be smaller or equal."
-    );
-
-    let is_primary = (monitor_info.dwFlags & MONITORINFOF_PRIMARY) > 0;
-
-    // Helper function to extract a `DisplayMode` from `DEVMODEW`.
-    // Returns `None` if one of the mandatory fields (dimensions, refresh rate) are not present in `display_mode`.
-    fn display_mode_from_dev_mode(display_mode: &DEVMODEW) -> Option<DisplayMode> {
-        // Skip if width not specified.
-        let width = if (display_mode.dmFields & DM_PELSWIDTH) > 0 {
-            display_mode.dmPelsWidth
-        } else {
-            return None;
-        };
-
-        // Skip if height not specified.
-        let height = if (display_mode.dmFields & DM_PELSHEIGHT) > 0 {
-            display_mode.dmPelsHeight
-        } else {
-            return None;
-        };
-
-        let dimensions = Dimensions { width, height };
-
-        // Skip if refresh rate not specified.
-        let refresh_rate = if (display_mode.dmFields & DM_DISPLAYFREQUENCY) > 0 {
-            display_mode.dmDisplayFrequency
-        } else {
-            return None;
-        };
-
-        // Skip unknown and non-32bpp modes.
-        if (display_mode.dmFields & DM_BITSPERPEL) > 0 {
-            match display_mode.dmBitsPerPel {
-                32 => {}
-                _ => return None,
-            }
-        } else {
-            return None;
-        };
-
-        let upscale_mode = if (display_mode.dmFields & DM_DISPLAYFIXEDOUTPUT) > 0 {
-            match unsafe { display_mode.u1.s2().dmDisplayFixedOutput } {
-                DMDFO_DEFAULT => UpscaleMode::Unknown,
-                DMDFO_CENTER => UpscaleMode::Center,
-                DMDFO_STRETCH => UpscaleMode::Stretch,
-                _ => UpscaleMode::Unknown,
-            }
-        } else {
-            UpscaleMode::Unknown
-        };
-
-        Some(DisplayMode {
-            dimensions,
-            refresh_rate,
-            refresh_rate_num: refresh_rate,
-            refresh_rate_denom: 1,
-            upscale_mode,
-        })
-    }
-
-    // Enumerate the supported display modes.
-    let mut display_modes = Vec::new();
-
-    {
-        let mut display_mode: DEVMODEW = unsafe { std::mem::zeroed() };
-        display_mode.dmSize = std::mem::size_of_val(&display_mode) as WORD;
-
-        let mut mode_index = 0;
-
-        while 0
-            != unsafe {
-                EnumDisplaySettingsW(
-                    monitor_info.szDevice.as_ptr(),
-                    mode_index,
-                    &mut display_mode,
-                )
-            }
-        {
-            if let Some(display_mode) = display_mode_from_dev_mode(&display_mode) {
-                display_modes.push(display_mode);
-
-            // Skip display modes with missing mandatory fields.
-            } else {
-                continue;
-            }
-
-            mode_index += 1;
-        }
-    }
-
-    // Skip this display and continue enumeration if no supported modes enumerated somehow.
-    if display_modes.is_empty() {
-        return 1;
-    }
+    let is_primary = (monitor_info.monitorInfo.dwFlags & MONITORINFOF_PRIMARY) > 0;
 
     // Get the current display mode.
     // Skip this display and continue enumeration on error.
-    let mut current_mode = {
+    let (mut current_mode, orientation) = {
         let mut display_mode: DEVMODEW = unsafe { std::mem::zeroed() };
-        display_mode.dmSize = std::mem::size_of_val(&display_mode) as WORD;
+        display_mode.dmSize = std::mem::size_of_val(&display_mode) as u16;
 
         if 0 != unsafe {
             EnumDisplaySettingsW(
@@ -237,8 +292,8 @@ extern "system" fn add_display_callback(
                 &mut display_mode,
             )
         } {
-            if let Some(display_mode) = display_mode_from_dev_mode(&display_mode) {
-                display_mode
+            if let Some(current_mode) = display_mode_from_dev_mode(&display_mode) {
+                (current_mode, orientation_from_dev_mode(&display_mode))
             } else {
                 return 1;
             }
@@ -247,31 +302,62 @@ extern "system" fn add_display_callback(
         }
     };
 
-    // Check if the display is active / not pseudo.
-    // Skip this display and continue enumeration on error / if not active.
+    // Check if the display is not pseudo.
+    // Skip this display and continue enumeration on error / if it's a mirroring driver's pseudo display.
     let mut display_device: DISPLAY_DEVICEW = unsafe { std::mem::zeroed() };
-    display_device.cb = std::mem::size_of_val(&display_device) as DWORD;
+    display_device.cb = std::mem::size_of_val(&display_device) as u32;
 
     if 0 == unsafe {
-        EnumDisplayDevicesW(monitor_info.szDevice.as_ptr(), 0, &mut display_device, 0)
+        EnumDisplayDevicesW(
+            monitor_info.szDevice.as_ptr(),
+            0,
+            &mut display_device,
+            EDD_GET_DEVICE_INTERFACE_NAME,
+        )
     } {
         return 1;
     }
 
-    if display_device.StateFlags & DISPLAY_DEVICE_ACTIVE == 0 {
+    if display_device.StateFlags & DISPLAY_DEVICE_MIRRORING_DRIVER != 0 {
         return 1;
     }
 
-    if display_device.StateFlags & DISPLAY_DEVICE_ATTACHED == 0 {
-        return 1;
-    }
+    // Skip this display if its monitor device is not active / attached, unless the user opted in;
+    // the state is exposed to the user then.
+    let mut state = DisplayStateWin {
+        device_active: display_device.StateFlags & DISPLAY_DEVICE_ACTIVE != 0,
+        device_attached: display_device.StateFlags & DISPLAY_DEVICE_ATTACHED != 0,
+        path_active: None,
+        target_available: None,
+    };
 
-    if display_device.StateFlags & DISPLAY_DEVICE_MIRRORING_DRIVER != 0 {
+    if !context.include_inactive && (!state.device_active || !state.device_attached) {
         return 1;
     }
 
-    // Find the monitor by name in the passed in context.
-    if let Some(found) = context
+    // `Some` if the display's refresh rate is dynamic.
+    let mut dynamic_refresh_ceiling = None;
+
+    // `Some` if the display was found in the display configuration API's context.
+    let mut path = None;
+
+    // `Some` if the display was found in the display configuration API's context and the driver reported it.
+    let mut signal_timing = None;
+
+    // `Some` if the display was found in the display configuration API's context and the driver reported it.
+    let mut bits_per_channel = None;
+
+    // `Some` if the display was found in the display configuration API's context and was created by an indirect display driver.
+    let mut indirect = None;
+
+    let mut names = DisplayNames {
+        friendly: None,
+        device_string: DisplayName::from_utf16(&display_device.DeviceString)
+            .map(|name| name.to_string()),
+        gdi_device: DisplayName::from_utf16(&monitor_info.szDevice).map(|name| name.to_string()),
+    };
+
+    let (name, generic_name, connection, preferred_mode) = if let Some(found) = context
         .device_names
         .iter()
         .position(|name| monitor_info.szDevice == *name)
@@ -281,29 +367,97 @@ extern "system" fn add_display_callback(
 
         let path_info = &context.path_infos[found];
 
-        // Get a more precise refresh rate value.
-        current_mode.refresh_rate_num = path_info.targetInfo.refreshRate.Numerator;
-        current_mode.refresh_rate_denom = path_info.targetInfo.refreshRate.Denominator;
+        state.path_active = Some(path_info.flags & DISPLAYCONFIG_PATH_ACTIVE != 0);
+        state.target_available = Some(path_info.targetInfo.targetAvailable != 0);
 
-        // Sanity check.
-        assert_eq!(
-            current_mode.refresh_rate,
-            ((current_mode.refresh_rate_num as f32) / (current_mode.refresh_rate_denom as f32))
-                .floor() as u32,
-            "Refresh rate mismatch between API's."
-        );
+        let mode_index = |mode_index: u32| {
+            if mode_index == DISPLAYCONFIG_PATH_MODE_IDX_INVALID {
+                None
+            } else {
+                Some(mode_index)
+            }
+        };
 
-        // Get the display friendly name.
-        let target_index = path_info.targetInfo.modeInfoIdx as usize;
-        let target_info = &context.mode_infos[target_index];
-        debug_assert_eq!(target_info.infoType, DISPLAYCONFIG_MODE_INFO_TYPE_TARGET);
+        path = Some(DisplayConfigPath {
+            source_adapter: path_info.sourceInfo.adapterId.into(),
+            source_id: path_info.sourceInfo.id,
+            source_mode_index: mode_index(unsafe { path_info.sourceInfo.Anonymous.modeInfoIdx }),
+            target_adapter: path_info.targetInfo.adapterId.into(),
+            target_id: path_info.targetInfo.id,
+            target_mode_index: mode_index(unsafe { path_info.targetInfo.Anonymous.modeInfoIdx }),
+        });
+
+        let refresh_rate_num = path_info.targetInfo.refreshRate.Numerator;
+        let refresh_rate_denom = path_info.targetInfo.refreshRate.Denominator;
+
+        // With Dynamic Refresh Rate enabled, the OS may dynamically lower the refresh rate
+        // (e.g. when the content is static, or to save power);
+        // the path's refresh rate is the ceiling it may be boosted to then,
+        // and might not match the current refresh rate reported by GDI.
+        if path_info.flags & DISPLAYCONFIG_PATH_BOOST_REFRESH_RATE != 0 {
+            if refresh_rate_denom != 0 {
+                dynamic_refresh_ceiling = Some((refresh_rate_num, refresh_rate_denom));
+            }
+        } else if refresh_rate_denom != 0
+            // Sanity check - keep the GDI refresh rate on mismatch between API's.
+            && current_mode.refresh_rate
+                == ((refresh_rate_num as f32) / (refresh_rate_denom as f32)).floor() as u32
+        {
+            // Get a more precise refresh rate value.
+            current_mode.refresh_rate_num = refresh_rate_num;
+            current_mode.refresh_rate_denom = refresh_rate_denom;
+        }
+
+        // Current video signal timing, if the path has a valid target mode.
+        let target_mode_info = path
+            .as_ref()
+            .and_then(|path| path.target_mode_index)
+            .and_then(|target_index| context.mode_infos.get(target_index as usize))
+            .filter(|target_info| target_info.infoType == DISPLAYCONFIG_MODE_INFO_TYPE_TARGET);
+
+        if let Some(target_info) = target_mode_info {
+            let video_signal_info =
+                unsafe { &target_info.Anonymous.targetMode.targetVideoSignalInfo };
+
+            if video_signal_info.pixelRate != 0 {
+                signal_timing = Some(SignalTiming {
+                    pixel_rate_hz: video_signal_info.pixelRate,
+                    h_total: video_signal_info.totalSize.cx,
+                    v_total: video_signal_info.totalSize.cy,
+                });
+            }
+        }
+
+        // The path's target adapter / ID are used to query the rest of the target info.
+        let target_info = &path_info.targetInfo;
+
+        // Current output bit depth per color channel (Windows 10 1709+).
+        let mut advanced_color_info: DISPLAYCONFIG_GET_ADVANCED_COLOR_INFO =
+            unsafe { std::mem::zeroed() };
+        advanced_color_info.header = DISPLAYCONFIG_DEVICE_INFO_HEADER {
+            size: std::mem::size_of_val(&advanced_color_info) as u32,
+            adapterId: target_info.adapterId,
+            id: target_info.id,
+            r#type: DISPLAYCONFIG_DEVICE_INFO_GET_ADVANCED_COLOR_INFO,
+        };
+
+        if ERROR_SUCCESS
+            == unsafe {
+                DisplayConfigGetDeviceInfo(
+                    &mut advanced_color_info as *mut _ as *mut DISPLAYCONFIG_DEVICE_INFO_HEADER,
+                ) as u32
+            }
+            && advanced_color_info.bitsPerColorChannel != 0
+        {
+            bits_per_channel = u8::try_from(advanced_color_info.bitsPerColorChannel).ok();
+        }
 
         let mut device_name: DISPLAYCONFIG_TARGET_DEVICE_NAME = unsafe { std::mem::zeroed() };
         let mut header = DISPLAYCONFIG_DEVICE_INFO_HEADER {
-            size: std::mem::size_of_val(&device_name) as DWORD,
+            size: std::mem::size_of_val(&device_name) as u32,
             adapterId: target_info.adapterId,
             id: target_info.id,
-            _type: DISPLAYCONFIG_DEVICE_INFO_GET_TARGET_NAME,
+            r#type: DISPLAYCONFIG_DEVICE_INFO_GET_TARGET_NAME,
         };
         device_name.header = header;
 
@@ -311,16 +465,26 @@ extern "system" fn add_display_callback(
             == unsafe {
                 DisplayConfigGetDeviceInfo(
                     &mut device_name as *mut _ as *mut DISPLAYCONFIG_DEVICE_INFO_HEADER,
-                ) as DWORD
+                ) as u32
             } {
-            from_wstr(&device_name.monitorFriendlyDeviceName)
+            DisplayName::from_utf16_reuse(
+                &device_name.monitorFriendlyDeviceName,
+                &context.previous_names,
+            )
         } else {
             None
         };
 
+        names.friendly = name.as_ref().map(DisplayName::to_string);
+
         // Backup name if above failed (e.g. `Generic PnP Monitor`).
-        if name.is_none() {
-            name = from_wstr(&display_device.DeviceString);
+        let generic_name = name.is_none();
+
+        if generic_name {
+            name = DisplayName::from_utf16_reuse(
+                &display_device.DeviceString,
+                &context.previous_names,
+            );
         }
 
         // Connection type.
@@ -334,20 +498,35 @@ extern "system" fn add_display_callback(
             _ => ConnectionType::Unknown,
         };
 
+        // Indirect display (virtual monitor, USB display adapter, etc.) detection.
+        let indirect_output = matches!(
+            path_info.targetInfo.outputTechnology,
+            DISPLAYCONFIG_OUTPUT_TECHNOLOGY_INDIRECT_WIRED
+                | DISPLAYCONFIG_OUTPUT_TECHNOLOGY_INDIRECT_VIRTUAL
+        );
+        let device_path = device_path(&display_device.DeviceID)
+            .map(|device_path| String::from_utf16_lossy(&device_path));
+
+        indirect = indirect_driver(
+            adapter_description(&monitor_info.szDevice).as_deref(),
+            device_path.as_deref(),
+            indirect_output,
+        );
+
         // Get the display preferred mode.
-        // Skip this display and continue enumeration on error.
+        // Fall back to the largest supported display mode on error (see below).
         let preferred_mode = {
             let mut preferred_mode: DISPLAYCONFIG_TARGET_PREFERRED_MODE =
                 unsafe { std::mem::zeroed() };
-            header.size = std::mem::size_of_val(&preferred_mode) as DWORD;
-            header._type = DISPLAYCONFIG_DEVICE_INFO_GET_TARGET_PREFERRED_MODE;
+            header.size = std::mem::size_of_val(&preferred_mode) as u32;
+            header.r#type = DISPLAYCONFIG_DEVICE_INFO_GET_TARGET_PREFERRED_MODE;
             preferred_mode.header = header;
 
             if ERROR_SUCCESS
                 == unsafe {
                     DisplayConfigGetDeviceInfo(
                         &mut preferred_mode as *mut _ as *mut DISPLAYCONFIG_DEVICE_INFO_HEADER,
-                    ) as DWORD
+                    ) as u32
                 }
             {
                 let dimensions = Dimensions {
@@ -367,75 +546,166 @@ extern "system" fn add_display_callback(
                 let refresh_rate =
                     ((refresh_rate_num as f32) / (refresh_rate_denom as f32)).floor() as u32;
 
-                DisplayMode {
+                Some(DisplayMode {
                     dimensions,
                     refresh_rate,
                     refresh_rate_num,
                     refresh_rate_denom,
                     upscale_mode: UpscaleMode::Unknown,
-                }
+                })
             } else {
-                return 1;
+                None
             }
         };
 
-        // Get the display's current DPI scale.
-        // Skip this display and continue enumeration on error.
+        (name, generic_name, connection, preferred_mode)
 
-        let mut display_dpi_x = 0;
-        let mut display_dpi_y = 0;
+    // GDI-only enumeration - use whatever info we have.
+    } else if context.source == DisplaySource::Gdi {
+        (
+            DisplayName::from_utf16_reuse(&display_device.DeviceString, &context.previous_names),
+            true,
+            ConnectionType::Unknown,
+            None,
+        )
 
-        if S_OK
-            != unsafe {
-                GetDpiForMonitor(
-                    monitor,
-                    MDT_EFFECTIVE_DPI,
-                    &mut display_dpi_x,
-                    &mut display_dpi_y,
-                )
-            }
-        {
-            return 1;
-        };
+    // Failed to find the display with this name in the context - how?
+    } else {
+        return 1;
+    };
 
-        assert_eq!(
-            display_dpi_x, display_dpi_y,
-            "Horizontal / vertical DPI scale value mismatch."
-        );
+    // Get the display's current DPI scale.
+    // Skip this display and continue enumeration on error.
+    let dpi_scale = if let Some(dpi_scale) = monitor_dpi_scale(monitor) {
+        dpi_scale
+    } else {
+        return 1;
+    };
 
-        let dpi_scale = display_dpi_x as f32 / USER_DEFAULT_SCREEN_DPI as f32;
-
-        // Store the final display info to the context.
-        let info = DisplayInfo::new(
-            name,
-            is_primary,
-            rectangles,
-            connection,
-            current_mode,
-            preferred_mode,
-            display_modes,
-            dpi_scale,
-        );
+    // Store the display info to the context.
+    // The display's supported display modes are enumerated later.
+    context.displays.push(PendingDisplayInfo {
+        device_name: monitor_info.szDevice,
+        name,
+        generic_name,
+        device_path: device_path(&display_device.DeviceID),
+        is_primary,
+        rects: rectangles,
+        connection,
+        current_mode,
+        orientation,
+        preferred_mode,
+        names,
+        dynamic_refresh_ceiling,
+        signal_timing,
+        bits_per_channel,
+        path,
+        indirect_driver: indirect,
+        state,
+        dpi_scale,
+        monitor,
+    });
 
-        context.displays.push(EnumeratedDisplayInfo {
-            info,
-            platform: DisplayInfoWin { monitor },
-        });
+    1
+}
 
-    // Failed to find the display with this name in the context - how?
+/// Returns the display monitor's device interface path from the nul-terminated `device_id`,
+/// as returned by `EnumDisplayDevicesW(.., EDD_GET_DEVICE_INTERFACE_NAME)`, or `None` if it's empty.
+fn device_path(device_id: &[u16]) -> Option<Arc<[u16]>> {
+    let device_path = device_id.split(|&c| c == 0).next().unwrap_or(device_id);
+
+    if device_path.is_empty() {
+        None
     } else {
-        return 1;
+        Some(device_path.into())
     }
+}
 
-    1
+/// Returns the description of the display adapter (e.g. `NVIDIA GeForce RTX 3080`, `Parsec Virtual Display Adapter`)
+/// which drives the display with GDI `device_name`, if found.
+fn adapter_description(device_name: &[u16; 32]) -> Option<String> {
+    let mut adapter_index = 0;
+
+    loop {
+        let mut adapter: DISPLAY_DEVICEW = unsafe { std::mem::zeroed() };
+        adapter.cb = std::mem::size_of_val(&adapter) as u32;
+
+        if 0 == unsafe { EnumDisplayDevicesW(std::ptr::null(), adapter_index, &mut adapter, 0) } {
+            return None;
+        }
+
+        adapter_index += 1;
+
+        if adapter.DeviceName == *device_name {
+            return DisplayName::from_utf16(&adapter.DeviceString).map(|name| name.to_string());
+        }
+    }
+}
+
+/// Enumerates the supported display modes of the display with GDI `device_name`.
+#[cfg(not(feature = "no-modes"))]
+fn enumerate_display_modes(device_name: &[u16; 32]) -> Vec<DisplayMode> {
+    let mut display_modes = Vec::new();
+
+    let mut display_mode: DEVMODEW = unsafe { std::mem::zeroed() };
+    display_mode.dmSize = std::mem::size_of_val(&display_mode) as u16;
+
+    let mut mode_index = 0;
+
+    while 0 != unsafe { EnumDisplaySettingsW(device_name.as_ptr(), mode_index, &mut display_mode) }
+    {
+        // Skip display modes with missing mandatory fields.
+        if let Some(display_mode) = display_mode_from_dev_mode(&display_mode) {
+            display_modes.push(display_mode);
+        }
+
+        mode_index += 1;
+    }
+
+    display_modes
+}
+
+/// Enumerates the supported display modes of the displays with GDI `device_names`.
+/// Returns the display modes in the same order as `device_names`.
+///
+/// Mode enumeration is relatively slow (especially for displays with many supported modes),
+/// so multiple displays are processed in parallel, one thread per display.
+/// `EnumDisplaySettingsW()` only reads the display modes list and is safe to call from any thread,
+/// and does not depend on the calling thread's DPI awareness.
+#[cfg(not(feature = "no-modes"))]
+fn enumerate_display_modes_parallel(device_names: &[[u16; 32]]) -> Vec<Vec<DisplayMode>> {
+    if device_names.len() <= 1 {
+        return device_names.iter().map(enumerate_display_modes).collect();
+    }
+
+    let threads: Vec<_> = device_names
+        .iter()
+        .map(|&device_name| {
+            std::thread::Builder::new()
+                .name("minidisplay mode enumeration".into())
+                .spawn(move || enumerate_display_modes(&device_name))
+        })
+        .collect();
+
+    threads
+        .into_iter()
+        .zip(device_names)
+        .map(|(thread, device_name)| {
+            thread
+                .ok()
+                .and_then(|thread| thread.join().ok())
+                // Fall back to enumerating on this thread if failed to spawn the thread.
+                .unwrap_or_else(|| enumerate_display_modes(device_name))
+        })
+        .collect()
 }
 
 /// Makes the enumerating thread DPI-aware to query the monitors' current DPI.
 /// Restores the thread's previous DPI awareness value when dropped.
-struct ThreadDPIAwarenessGuard(DPI_AWARENESS_CONTEXT);
+pub(crate) struct ThreadDPIAwarenessGuard(DPI_AWARENESS_CONTEXT);
 
 impl ThreadDPIAwarenessGuard {
-    fn new() -> Self {
+    pub(crate) fn new() -> Self {
         Self(unsafe { SetThreadDpiAwarenessContext(DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE) })
     }
 }
@@ -446,80 +716,88 @@ impl Drop for ThreadDPIAwarenessGuard {
     }
 }
 
-/// Enumerates the displays via WinAPI.
-pub(crate) fn enumerate_displays_win() -> Result<Vec<EnumeratedDisplayInfo>, ()> {
-    // Build the context containing some info about the displays we cannot (or do not know how to) get otherwise
-    // (namely the connection between the display device name and info like friendly display name, connection type, and other).
+/// Enumerates the displays via WinAPI with provided `options`.
+/// Reuses the unchanged previous names of the displays.
+/// Also returns the raw display configuration the displays were resolved with, if the display configuration API's succeeded.
+pub(crate) fn enumerate_displays_win(
+    options: &EnumerateOptions<'_>,
+) -> Result<(Vec<EnumeratedDisplayInfo>, Option<RawDisplayConfig>), Error> {
+    // Services only see their own (invisible) desktop, if anything -
+    // fall back to the inventory of the attached monitors.
+    if is_service_session() {
+        return enumerate_inventory_win(options.previous_names)
+            .map(|displays| (displays, None))
+            .map_err(|_| Error::EnumerationFailed);
+    }
+
+    // Make the thread DPI-aware to query the monitors' current DPI.
+    let _dpi_guard = ThreadDPIAwarenessGuard::new();
+
+    // Inside Windows containers and some VMs the display configuration API's fail,
+    // while GDI enumeration still (at least partially) works - fall back to it.
+    query_display_config(options)
+        .and_then(enumerate_monitors)
+        .or_else(|_| enumerate_monitors(DisplayInfoContext::new(DisplaySource::Gdi, options)))
+        // When everything else fails, make up a single pseudo display covering the whole virtual screen,
+        // so that the user at least has something sane to clamp the windows to.
+        .or_else(|_| enumerate_virtual_screen().map(|displays| (displays, None)))
+        .map_err(|_| Error::EnumerationFailed)
+}
+
+impl DisplayInfoContext {
+    fn new(source: DisplaySource, options: &EnumerateOptions<'_>) -> Self {
+        Self {
+            path_infos: Vec::new(),
+            mode_infos: Vec::new(),
 
-    let mut num_paths: u32 = 0;
-    let mut num_modes: u32 = 0;
+            device_names: Vec::new(),
 
-    let res = unsafe {
-        GetDisplayConfigBufferSizes(QDC_ONLY_ACTIVE_PATHS, &mut num_paths, &mut num_modes)
-    };
+            previous_names: options.previous_names.to_vec(),
 
-    if res != (ERROR_SUCCESS as LONG) || num_paths == 0 || num_modes == 0 {
-        return Err(());
+            include_inactive: options.include_inactive,
+
+            source,
+
+            displays: Vec::new(),
+        }
     }
+}
+
+/// Builds the context containing some info about the displays we cannot (or do not know how to) get otherwise
+/// (namely the connection between the display device name and info like friendly display name, connection type, and other).
+fn query_display_config(options: &EnumerateOptions<'_>) -> Result<DisplayInfoContext, ()> {
+    let (path_infos, mode_infos) = query_display_config_raw(QDC_ONLY_ACTIVE_PATHS)?;
 
     let mut context = DisplayInfoContext {
-        path_infos: Vec::with_capacity(num_paths as usize),
-        mode_infos: Vec::with_capacity(num_modes as usize),
+        device_names: Vec::with_capacity(path_infos.len()),
 
-        device_names: Vec::with_capacity(num_paths as usize),
+        path_infos,
+        mode_infos,
 
-        displays: Vec::new(),
-    };
+        previous_names: options.previous_names.to_vec(),
 
-    let res = unsafe {
-        QueryDisplayConfig(
-            QDC_ONLY_ACTIVE_PATHS,
-            &mut num_paths,
-            context.path_infos.as_mut_ptr() as *mut _,
-            &mut num_modes,
-            context.mode_infos.as_mut_ptr() as *mut _,
-            std::ptr::null_mut(),
-        )
-    };
+        include_inactive: options.include_inactive,
 
-    if res != (ERROR_SUCCESS as LONG)
-        || (num_paths as usize) != context.path_infos.capacity()
-        || (num_modes as usize) != context.mode_infos.capacity()
-    {
-        return Err(());
-    }
+        source: DisplaySource::DisplayConfig,
 
-    unsafe {
-        context.path_infos.set_len(num_paths as usize);
-        context.mode_infos.set_len(num_modes as usize);
-    }
+        displays: Vec::new(),
+    };
 
     // Get and associate the display device names with indices in the mode array.
 
     for path_info in context.path_infos.iter() {
-        debug_assert!(
-            path_info.targetInfo.targetAvailable > 0,
-            "We requested only active paths."
-        );
-
-        let source_index = path_info.sourceInfo.modeInfoIdx as usize;
-        let source_mode_info = context.mode_infos[source_index];
-        debug_assert_eq!(
-            source_mode_info.infoType,
-            DISPLAYCONFIG_MODE_INFO_TYPE_SOURCE
-        );
-
+        // The path's source adapter / ID match the ones of its source mode, if any.
         let mut source_device_name = DISPLAYCONFIG_SOURCE_DEVICE_NAME {
             header: DISPLAYCONFIG_DEVICE_INFO_HEADER {
                 size: std::mem::size_of::<DISPLAYCONFIG_SOURCE_DEVICE_NAME>() as u32,
-                adapterId: source_mode_info.adapterId,
-                id: source_mode_info.id,
-                _type: DISPLAYCONFIG_DEVICE_INFO_GET_SOURCE_NAME,
+                adapterId: path_info.sourceInfo.adapterId,
+                id: path_info.sourceInfo.id,
+                r#type: DISPLAYCONFIG_DEVICE_INFO_GET_SOURCE_NAME,
             },
             viewGdiDeviceName: [0; 32],
         };
 
-        if ERROR_SUCCESS as LONG
+        if ERROR_SUCCESS as i32
             != unsafe { DisplayConfigGetDeviceInfo(&mut source_device_name.header) }
         {
             return Err(());
@@ -530,21 +808,180 @@ pub(crate) fn enumerate_displays_win() -> Result<Vec<EnumeratedDisplayInfo>, ()>
             .push(source_device_name.viewGdiDeviceName);
     }
 
-    // Make the thread DPI-aware to query the monitors' current DPI.
-    let _dpi_guard = ThreadDPIAwarenessGuard::new();
+    Ok(context)
+}
 
+/// Enumerates the display monitors via GDI, using the display configuration API's `context` (if any) for additional info.
+/// Also returns the display configuration API's `context` raw display configuration, if any.
+fn enumerate_monitors(
+    mut context: DisplayInfoContext,
+) -> Result<(Vec<EnumeratedDisplayInfo>, Option<RawDisplayConfig>), ()> {
     if 0 == unsafe {
         EnumDisplayMonitors(
             std::ptr::null_mut(),
-            std::ptr::null_mut(),
+            std::ptr::null(),
             Some(add_display_callback),
-            &context as *const _ as _,
+            &mut context as *mut _ as _,
         )
     } {
         return Err(());
     }
 
-    let mut displays = context.displays;
+    #[cfg(not(feature = "no-modes"))]
+    let display_modes = {
+        let device_names: Vec<_> = context
+            .displays
+            .iter()
+            .map(|display| display.device_name)
+            .collect();
+        enumerate_display_modes_parallel(&device_names)
+    };
+    // Mode enumeration is the slowest part - skip it if the display modes are not needed.
+    #[cfg(feature = "no-modes")]
+    let display_modes = vec![Vec::<DisplayMode>::new(); context.displays.len()];
+    let source = context.source;
+
+    let raw_config = if source == DisplaySource::DisplayConfig {
+        Some(RawDisplayConfig {
+            path_infos: std::mem::take(&mut context.path_infos),
+            mode_infos: std::mem::take(&mut context.mode_infos),
+        })
+    } else {
+        None
+    };
+
+    // Only query WMI if there are any displays to supplement the info of -
+    // it's relatively slow.
+    #[cfg(feature = "wmi")]
+    let wmi_monitors = if context
+        .displays
+        .iter()
+        .any(|display| display.device_path.is_some())
+    {
+        query_wmi_monitors()
+    } else {
+        Vec::new()
+    };
+
+    // Same for the monitors' EDID's.
+    let edids = if context
+        .displays
+        .iter()
+        .any(|display| display.device_path.is_some())
+    {
+        monitor_edids_win()
+    } else {
+        Vec::new()
+    };
+
+    let mut displays: Vec<_> = context
+        .displays
+        .into_iter()
+        .zip(display_modes)
+        // Skip the displays with no supported modes enumerated somehow.
+        .filter(|(_, display_modes)| cfg!(feature = "no-modes") || !display_modes.is_empty())
+        .map(|(display, display_modes)| {
+            // Assume the largest (by area, then by refresh rate) supported display mode is the native one
+            // if the preferred display mode is unknown
+            // (or the current one, if the display modes were not enumerated with the `no-modes` feature).
+            let preferred_mode_is_fallback = display.preferred_mode.is_none();
+            let preferred_mode = display.preferred_mode.unwrap_or_else(|| {
+                display_modes
+                    .iter()
+                    .max_by_key(|mode| (mode.dimensions.area(), mode.refresh_rate))
+                    .copied()
+                    .unwrap_or(display.current_mode)
+            });
+
+            // Display monitor's device interface path is the most stable identifier we have,
+            // GDI device name is the next best thing.
+            let id = DisplayId::from_utf16(display.device_path.as_deref().unwrap_or_else(|| {
+                let device_name = &display.device_name;
+                device_name.split(|&c| c == 0).next().unwrap_or(device_name)
+            }));
+
+            #[cfg(feature = "wmi")]
+            let wmi = display
+                .device_path
+                .as_ref()
+                .and_then(|device_path| find_wmi_monitor_info(&wmi_monitors, device_path));
+
+            // Prefer the monitor's EDID friendly name, as reported by WMI, to the generic one.
+            #[cfg_attr(not(feature = "wmi"), allow(unused_mut))]
+            let mut name = display.name;
+            #[cfg_attr(not(feature = "wmi"), allow(unused_mut))]
+            let mut names = display.names;
+
+            #[cfg(feature = "wmi")]
+            if display.generic_name {
+                if let Some(friendly_name) = wmi
+                    .as_ref()
+                    .and_then(|wmi| wmi.friendly_name.as_ref())
+                    .and_then(|friendly_name| {
+                        DisplayName::from_utf16(&friendly_name.encode_utf16().collect::<Vec<_>>())
+                    })
+                {
+                    names.friendly = Some(friendly_name.to_string());
+                    name = Some(friendly_name);
+                }
+            }
+
+            let edid = display
+                .device_path
+                .as_ref()
+                .and_then(|device_path| find_edid(&edids, device_path));
+
+            let mut platform = DisplayInfoWin {
+                name: name.clone(),
+                device_path: display.device_path,
+                path: display.path,
+                state: Some(display.state),
+                #[cfg(feature = "wmi")]
+                wmi,
+                ..DisplayInfoWin::new(display.monitor)
+            };
+            platform.set_gdi_device_name(display.device_name);
+
+            let (dpi_scale, dpi_scale_y) = display.dpi_scale;
+
+            let source = match display.indirect_driver {
+                Some(driver) => DisplaySource::Indirect(driver),
+                None => source.clone(),
+            };
+
+            EnumeratedDisplayInfo {
+                info: DisplayInfo {
+                    names,
+                    orientation: display.orientation,
+                    preferred_mode_is_fallback,
+                    current_mode_is_dynamic: display.dynamic_refresh_ceiling.is_some(),
+                    dynamic_refresh_ceiling: display.dynamic_refresh_ceiling,
+                    dpi_scale_y: Some(dpi_scale_y).filter(|&dpi_scale_y| dpi_scale_y != dpi_scale),
+                    dpi_options: dpi_options_win(&platform).ok(),
+                    physical_size: edid.and_then(|edid| edid.physical_size),
+                    signal_timing: display.signal_timing,
+                    hdr_caps: edid.and_then(|edid| edid.hdr_caps),
+                    colorimetry: edid.and_then(|edid| edid.colorimetry),
+                    max_bits_per_channel: edid.and_then(|edid| edid.max_bits_per_channel),
+                    current_bits_per_channel: display.bits_per_channel,
+                    asset: edid.map(Edid::asset_info),
+                    ..DisplayInfo::new(
+                        id,
+                        name,
+                        display.is_primary,
+                        display.rects,
+                        display.connection,
+                        display.current_mode,
+                        preferred_mode,
+                        display_modes,
+                        dpi_scale,
+                        source,
+                    )
+                },
+                platform: platform.into(),
+            }
+        })
+        .collect();
 
     if displays.is_empty() {
         return Err(());
@@ -564,21 +1001,243 @@ pub(crate) fn enumerate_displays_win() -> Result<Vec<EnumeratedDisplayInfo>, ()>
         displays.swap(0, primary_display);
     }
 
+    // Must have some display modes.
+    #[cfg(not(feature = "no-modes"))]
+    debug_assert!(displays
+        .iter()
+        .all(|display| !display.info.display_modes.is_empty()));
+
     // Another sanity check - must have no overlapping rectangles.
-    for i in 0..displays.len() {
-        // Must have some display modes.
-        debug_assert!(!displays[i].info.display_modes.is_empty());
+    let rects = |rect: fn(&DisplayRects) -> Rectangle| {
+        displays
+            .iter()
+            .map(|display| rect(&display.info.rects))
+            .collect::<Vec<_>>()
+    };
+
+    if find_overlap(&rects(|rects| rects.virtual_rect)).is_some()
+        || find_overlap(&rects(|rects| rects.work_rect)).is_some()
+    {
+        return Err(());
+    }
 
-        for j in i + 1..displays.len() {
-            let left = &displays[i].info.rects;
-            let right = &displays[j].info.rects;
+    Ok((displays, raw_config))
+}
 
-            if left.virtual_rect.overlaps(&right.virtual_rect)
-                || left.work_rect.overlaps(&right.work_rect)
-            {
-                return Err(());
-            }
+// https://docs.microsoft.com/en-us/windows/win32/api/winuser/nc-winuser-monitorenumproc
+// Return `TRUE` (a.k.a. `1`) to continue enumeration.
+// Return `FALSE` (a.k.a. `0`) to stop enumeration.
+extern "system" fn add_monitor_metrics_callback(
+    monitor: HMONITOR,
+    _hdcmonitor: HDC,
+    _lprcmonitor: *mut RECT,
+    dwdata: LPARAM,
+) -> BOOL {
+    if dwdata == 0 {
+        return 0;
+    }
+
+    let monitors: &mut Vec<MonitorMetrics> = unsafe { &mut *(dwdata as *mut _) };
+
+    let mut monitor_info: MONITORINFO = unsafe { std::mem::zeroed() };
+    monitor_info.cbSize = std::mem::size_of_val(&monitor_info) as u32;
+
+    // Skip this display and continue enumeration on error.
+    if 0 == unsafe { GetMonitorInfoW(monitor, &mut monitor_info) } {
+        return 1;
+    }
+
+    monitors.push(MonitorMetrics {
+        virtual_rect: Rectangle::from_win_rect(&monitor_info.rcMonitor),
+        work_rect: Rectangle::from_win_rect(&monitor_info.rcWork),
+        dpi_scale: monitor_dpi_scale(monitor),
+    });
+
+    1
+}
+
+/// Returns the number of the display monitors and the virtual screen rectangle, as reported by `GetSystemMetrics()`,
+/// and each monitor's rectangles and DPI scale, as reported by `GetMonitorInfoW()` / `GetDpiForMonitor()`.
+pub(crate) fn display_metrics_win() -> Option<DisplayMetrics> {
+    // Make the thread DPI-aware to get the physical display rectangles and the actual DPI scales,
+    // consistent with the full display enumeration.
+    let _dpi_guard = ThreadDPIAwarenessGuard::new();
+
+    let num_monitors = unsafe { GetSystemMetrics(SM_CMONITORS) };
+
+    let left = unsafe { GetSystemMetrics(SM_XVIRTUALSCREEN) };
+    let top = unsafe { GetSystemMetrics(SM_YVIRTUALSCREEN) };
+    let width = unsafe { GetSystemMetrics(SM_CXVIRTUALSCREEN) };
+    let height = unsafe { GetSystemMetrics(SM_CYVIRTUALSCREEN) };
+
+    if num_monitors < 0 || width < 0 || height < 0 {
+        return None;
+    }
+
+    let mut monitors: Vec<MonitorMetrics> = Vec::new();
+
+    if 0 == unsafe {
+        EnumDisplayMonitors(
+            std::ptr::null_mut(),
+            std::ptr::null(),
+            Some(add_monitor_metrics_callback),
+            &mut monitors as *mut _ as _,
+        )
+    } {
+        return None;
+    }
+
+    Some(DisplayMetrics {
+        num_monitors: num_monitors as u32,
+        virtual_screen: Rectangle::new(
+            Position::new(left, top),
+            Dimensions::new(width as u32, height as u32),
+        ),
+        monitors,
+    })
+}
+
+/// Last-resort display enumeration.
+/// Returns a single pseudo display covering the whole virtual screen, as reported by `GetSystemMetrics()`.
+/// The work rectangle is the one reported by `SystemParametersInfoW(SPI_GETWORKAREA)`.
+/// The only supported display mode has the virtual screen dimensions and
+/// the primary display's current refresh rate, if available, or a nominal 60Hz otherwise.
+fn enumerate_virtual_screen() -> Result<Vec<EnumeratedDisplayInfo>, ()> {
+    let left = unsafe { GetSystemMetrics(SM_XVIRTUALSCREEN) };
+    let top = unsafe { GetSystemMetrics(SM_YVIRTUALSCREEN) };
+    let width = unsafe { GetSystemMetrics(SM_CXVIRTUALSCREEN) };
+    let height = unsafe { GetSystemMetrics(SM_CYVIRTUALSCREEN) };
+
+    if width <= 0 || height <= 0 {
+        return Err(());
+    }
+
+    let dimensions = Dimensions::new(width as u32, height as u32);
+    let virtual_rect = Rectangle::new(Position::new(left, top), dimensions);
+
+    let work_rect = {
+        let mut work_rect: RECT = unsafe { std::mem::zeroed() };
+
+        if 0 != unsafe {
+            SystemParametersInfoW(SPI_GETWORKAREA, 0, &mut work_rect as *mut _ as _, 0)
+        } {
+            Rectangle::from_win_rect(&work_rect)
+                .clip(&virtual_rect, ClipRectFlags::KEEP_ALL)
+                .unwrap_or(virtual_rect)
+        } else {
+            virtual_rect
+        }
+    };
+
+    let refresh_rate = {
+        let mut display_mode: DEVMODEW = unsafe { std::mem::zeroed() };
+        display_mode.dmSize = std::mem::size_of_val(&display_mode) as u16;
+
+        if 0 != unsafe {
+            EnumDisplaySettingsW(std::ptr::null(), ENUM_CURRENT_SETTINGS, &mut display_mode)
+        } {
+            display_mode_from_dev_mode(&display_mode).map(|display_mode| display_mode.refresh_rate)
+        } else {
+            None
         }
+        .unwrap_or(60)
+    };
+
+    let display_mode = DisplayMode {
+        dimensions,
+        refresh_rate,
+        refresh_rate_num: refresh_rate,
+        refresh_rate_denom: 1,
+        upscale_mode: UpscaleMode::Unknown,
+    };
+
+    let monitor = unsafe { MonitorFromPoint(POINT { x: 0, y: 0 }, MONITOR_DEFAULTTOPRIMARY) };
+    let dpi_scale = monitor_dpi_scale(monitor).map_or(1.0, |(dpi_scale, _)| dpi_scale);
+
+    let info = DisplayInfo::new(
+        DisplayId::default(),
+        None,
+        true,
+        DisplayRects {
+            virtual_rect,
+            work_rect,
+        },
+        ConnectionType::Unknown,
+        display_mode,
+        display_mode,
+        vec![display_mode],
+        dpi_scale,
+        DisplaySource::Fallback,
+    );
+
+    Ok(vec![EnumeratedDisplayInfo {
+        info,
+        platform: DisplayInfoWin::new(monitor).into(),
+    }])
+}
+
+// https://docs.microsoft.com/en-us/windows/win32/api/winuser/nc-winuser-monitorenumproc
+// Return `TRUE` (a.k.a. `1`) to continue enumeration.
+// Return `FALSE` (a.k.a. `0`) to stop enumeration.
+extern "system" fn add_display_rects_callback(
+    monitor: HMONITOR,
+    _hdcmonitor: HDC,
+    _lprcmonitor: *mut RECT,
+    dwdata: LPARAM,
+) -> BOOL {
+    if dwdata == 0 {
+        return 0;
+    }
+
+    let displays: &mut Vec<DisplayRectsFull> = unsafe { &mut *(dwdata as *mut _) };
+
+    let mut monitor_info: MONITORINFO = unsafe { std::mem::zeroed() };
+    monitor_info.cbSize = std::mem::size_of_val(&monitor_info) as u32;
+
+    // Skip this display and continue enumeration on error.
+    if 0 == unsafe { GetMonitorInfoW(monitor, &mut monitor_info) } {
+        return 1;
+    }
+
+    displays.push(DisplayRectsFull {
+        rects: DisplayRects {
+            virtual_rect: Rectangle::from_win_rect(&monitor_info.rcMonitor),
+            work_rect: Rectangle::from_win_rect(&monitor_info.rcWork),
+        },
+        is_primary: (monitor_info.dwFlags & MONITORINFOF_PRIMARY) > 0,
+        platform: DisplayInfoWin::new(monitor).into(),
+    });
+
+    1
+}
+
+/// Enumerates the display rectangles only via `EnumDisplayMonitors()` / `GetMonitorInfoW()`.
+pub(crate) fn enumerate_display_rects_win() -> Result<Vec<DisplayRectsFull>, Error> {
+    // Make the thread DPI-aware to get the physical display rectangles,
+    // consistent with the full display enumeration.
+    let _dpi_guard = ThreadDPIAwarenessGuard::new();
+
+    let mut displays: Vec<DisplayRectsFull> = Vec::new();
+
+    if 0 == unsafe {
+        EnumDisplayMonitors(
+            std::ptr::null_mut(),
+            std::ptr::null(),
+            Some(add_display_rects_callback),
+            &mut displays as *mut _ as _,
+        )
+    } {
+        return Err(Error::EnumerationFailed);
+    }
+
+    // Make sure the primary display is at index `0`.
+    let primary_display = displays
+        .iter()
+        .position(|display| display.is_primary)
+        .ok_or(Error::NoPrimaryDisplay)?;
+
+    if primary_display != 0 {
+        displays.swap(0, primary_display);
     }
 
     Ok(displays)
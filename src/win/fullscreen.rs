@@ -0,0 +1,57 @@
+use super::power::user_notification_state;
+use crate::Rectangle;
+
+use windows_sys::Win32::{
+    Foundation::RECT,
+    Graphics::Gdi::{
+        GetMonitorInfoW, MonitorFromWindow, HMONITOR, MONITORINFO, MONITOR_DEFAULTTONULL,
+    },
+    UI::{
+        Shell::{QUNS_BUSY, QUNS_RUNNING_D3D_FULL_SCREEN},
+        WindowsAndMessaging::{
+            GetDesktopWindow, GetForegroundWindow, GetShellWindow, GetWindowRect,
+        },
+    },
+};
+
+/// Returns `true` if the foreground window is on the `monitor` and is fullscreen -
+/// either an exclusive fullscreen Direct3D application, or a (borderless) window which covers the whole monitor.
+///
+/// The desktop and the shell windows are never considered fullscreen.
+pub(crate) fn has_fullscreen_window_win(monitor: HMONITOR) -> bool {
+    let window = unsafe { GetForegroundWindow() };
+
+    if window.is_null()
+        || window == unsafe { GetDesktopWindow() }
+        || window == unsafe { GetShellWindow() }
+    {
+        return false;
+    }
+
+    if unsafe { MonitorFromWindow(window, MONITOR_DEFAULTTONULL) } != monitor {
+        return false;
+    }
+
+    // The shell reports the exclusive fullscreen applications, and the fullscreen windows on the primary monitor.
+    if let Ok(state) = user_notification_state() {
+        if state == QUNS_RUNNING_D3D_FULL_SCREEN || state == QUNS_BUSY {
+            return true;
+        }
+    }
+
+    let mut monitor_info: MONITORINFO = unsafe { std::mem::zeroed() };
+    monitor_info.cbSize = std::mem::size_of_val(&monitor_info) as u32;
+
+    if 0 == unsafe { GetMonitorInfoW(monitor, &mut monitor_info) } {
+        return false;
+    }
+
+    let mut window_rect: RECT = unsafe { std::mem::zeroed() };
+
+    if 0 == unsafe { GetWindowRect(window, &mut window_rect) } {
+        return false;
+    }
+
+    Rectangle::from_win_rect(&window_rect)
+        .contains(&Rectangle::from_win_rect(&monitor_info.rcMonitor))
+}
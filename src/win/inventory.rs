@@ -0,0 +1,362 @@
+use std::sync::Arc;
+
+use super::display_info::DisplayInfoWin;
+#[cfg(feature = "wmi")]
+use super::wmi::{find_wmi_monitor_info, query_wmi_monitors};
+use crate::edid::{inventory_display_info, Edid};
+use crate::EnumeratedDisplayInfo;
+use crate::MonitorHardware;
+use crate::{DisplayId, DisplayName, Error};
+
+use windows_sys::Win32::{
+    Devices::{
+        DeviceAndDriverInstallation::{
+            SetupDiDestroyDeviceInfoList, SetupDiEnumDeviceInterfaces, SetupDiGetClassDevsW,
+            SetupDiGetDeviceInterfaceDetailW, SetupDiOpenDevRegKey, DICS_FLAG_GLOBAL,
+            DIGCF_DEVICEINTERFACE, DIGCF_PRESENT, DIREG_DEV, HDEVINFO, SPINT_ACTIVE,
+            SP_DEVICE_INTERFACE_DATA, SP_DEVICE_INTERFACE_DETAIL_DATA_W, SP_DEVINFO_DATA,
+        },
+        Display::GUID_DEVINTERFACE_MONITOR,
+    },
+    Foundation::{ERROR_SUCCESS, INVALID_HANDLE_VALUE},
+    System::{
+        Registry::{RegCloseKey, RegQueryValueExW, HKEY, KEY_READ},
+        RemoteDesktop::ProcessIdToSessionId,
+        Threading::GetCurrentProcessId,
+    },
+};
+
+/// Returns `true` if the process runs in the non-interactive services session (session 0),
+/// where the display enumeration API's only see the (invisible) services desktop, if anything.
+pub(crate) fn is_service_session() -> bool {
+    let mut session_id = 0;
+
+    0 != unsafe { ProcessIdToSessionId(GetCurrentProcessId(), &mut session_id) } && session_id == 0
+}
+
+/// Degraded display enumeration for the services session.
+///
+/// Returns the monitors currently attached to the system, as reported by the PnP manager,
+/// with their info read from the monitors' EDID (stored in the registry) and, optionally, WMI.
+///
+/// The monitors have no (empty) rectangles, and a single display mode - the monitor's preferred one, if known.
+pub(crate) fn enumerate_inventory_win(
+    previous_names: &[DisplayName],
+) -> Result<Vec<EnumeratedDisplayInfo>, ()> {
+    let devices = DeviceInfoList::new(true)?;
+
+    #[cfg(feature = "wmi")]
+    let wmi_monitors = query_wmi_monitors();
+
+    let mut displays = Vec::new();
+    let mut member_index = 0;
+
+    while let Some(interface) = devices.device_interface(member_index) {
+        member_index += 1;
+
+        let DeviceInterface {
+            device_path,
+            device_info,
+            ..
+        } = interface;
+
+        let edid = devices
+            .edid(&device_info)
+            .and_then(|edid| Edid::parse(&edid));
+
+        #[cfg(feature = "wmi")]
+        let wmi = find_wmi_monitor_info(&wmi_monitors, &device_path);
+
+        // Prefer the monitor's EDID name, as read from the registry, to the one reported by WMI.
+        let name = edid.as_ref().and_then(|edid| edid.name.as_ref());
+        #[cfg(feature = "wmi")]
+        let name = name.or_else(|| wmi.as_ref().and_then(|wmi| wmi.friendly_name.as_ref()));
+        let name = name.and_then(|name| {
+            DisplayName::from_utf16_reuse(&name.encode_utf16().collect::<Vec<_>>(), previous_names)
+        });
+
+        let platform = DisplayInfoWin {
+            name: name.clone(),
+            device_path: Some(device_path.clone()),
+            #[cfg(feature = "wmi")]
+            wmi,
+            ..DisplayInfoWin::new(std::ptr::null_mut())
+        };
+
+        displays.push(EnumeratedDisplayInfo {
+            info: inventory_display_info(DisplayId::from_utf16(&device_path), name, edid.as_ref()),
+            platform: platform.into(),
+        });
+    }
+
+    if displays.is_empty() {
+        Err(())
+    } else {
+        Ok(displays)
+    }
+}
+
+/// Returns the parsed EDID's of the present monitors, keyed by the monitors' device interface paths.
+/// Returns an empty vector on error.
+pub(crate) fn monitor_edids_win() -> Vec<(Arc<[u16]>, Edid)> {
+    let devices = match DeviceInfoList::new(true) {
+        Ok(devices) => devices,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut edids = Vec::new();
+    let mut member_index = 0;
+
+    while let Some(interface) = devices.device_interface(member_index) {
+        member_index += 1;
+
+        if let Some(edid) = devices
+            .edid(&interface.device_info)
+            .and_then(|edid| Edid::parse(&edid))
+        {
+            edids.push((interface.device_path, edid));
+        }
+    }
+
+    edids
+}
+
+/// Finds the parsed EDID of the monitor with the `device_path` among the `edids`
+/// returned by [`monitor_edids_win`].
+///
+/// [`monitor_edids_win`]: fn.monitor_edids_win.html
+pub(crate) fn find_edid<'e>(
+    edids: &'e [(Arc<[u16]>, Edid)],
+    device_path: &[u16],
+) -> Option<&'e Edid> {
+    let device_path = String::from_utf16_lossy(device_path);
+
+    edids
+        .iter()
+        .find(|(path, _)| String::from_utf16_lossy(path).eq_ignore_ascii_case(&device_path))
+        .map(|(_, edid)| edid)
+}
+
+/// Enumerates all monitors known to the PnP manager, including the ones not currently present,
+/// with their info read from the monitors' EDID (stored in the registry).
+pub(crate) fn enumerate_attached_hardware_win() -> Result<Vec<MonitorHardware>, Error> {
+    let devices = DeviceInfoList::new(false).map_err(|_| Error::EnumerationFailed)?;
+
+    let mut monitors = Vec::new();
+    let mut member_index = 0;
+
+    while let Some(interface) = devices.device_interface(member_index) {
+        member_index += 1;
+
+        let raw_edid = devices.edid(&interface.device_info);
+        let edid = raw_edid.as_ref().and_then(|edid| Edid::parse(edid));
+
+        let mut monitor = MonitorHardware {
+            id: DisplayId::from_utf16(&interface.device_path),
+            is_attached: interface.is_active,
+            name: None,
+            manufacturer: None,
+            product_code: None,
+            serial_number: None,
+            manufacture_year: None,
+            manufacture_week: None,
+            edid: raw_edid,
+        };
+
+        if let Some(edid) = edid {
+            monitor.name = edid.name;
+            monitor.manufacturer = edid.manufacturer;
+            monitor.product_code = Some(edid.product_code);
+            monitor.serial_number = edid.serial_number;
+            monitor.manufacture_year = edid.manufacture_year;
+            monitor.manufacture_week = edid.manufacture_week;
+        }
+
+        monitors.push(monitor);
+    }
+
+    Ok(monitors)
+}
+
+/// Monitor device interface, as enumerated by the [`DeviceInfoList`].
+struct DeviceInterface {
+    device_path: Arc<[u16]>,
+    device_info: SP_DEVINFO_DATA,
+    /// Whether the device interface is active (i.e. the monitor is present).
+    is_active: bool,
+}
+
+/// Owns the SetupAPI device information set of the monitor device interfaces.
+struct DeviceInfoList(HDEVINFO);
+
+impl DeviceInfoList {
+    /// Only includes the currently present monitors if `present_only` is `true`.
+    fn new(present_only: bool) -> Result<Self, ()> {
+        let flags = if present_only {
+            DIGCF_PRESENT | DIGCF_DEVICEINTERFACE
+        } else {
+            DIGCF_DEVICEINTERFACE
+        };
+
+        let devices = unsafe {
+            SetupDiGetClassDevsW(
+                &GUID_DEVINTERFACE_MONITOR,
+                std::ptr::null(),
+                std::ptr::null_mut(),
+                flags,
+            )
+        };
+
+        if devices == INVALID_HANDLE_VALUE as HDEVINFO {
+            Err(())
+        } else {
+            Ok(Self(devices))
+        }
+    }
+
+    /// Returns the device interface of the monitor at `member_index`,
+    /// or `None` if there are no more monitors.
+    fn device_interface(&self, member_index: u32) -> Option<DeviceInterface> {
+        let mut interface_data = SP_DEVICE_INTERFACE_DATA {
+            cbSize: std::mem::size_of::<SP_DEVICE_INTERFACE_DATA>() as u32,
+            ..Default::default()
+        };
+
+        if 0 == unsafe {
+            SetupDiEnumDeviceInterfaces(
+                self.0,
+                std::ptr::null(),
+                &GUID_DEVINTERFACE_MONITOR,
+                member_index,
+                &mut interface_data,
+            )
+        } {
+            return None;
+        }
+
+        let mut required_size = 0;
+
+        unsafe {
+            SetupDiGetDeviceInterfaceDetailW(
+                self.0,
+                &interface_data,
+                std::ptr::null_mut(),
+                0,
+                &mut required_size,
+                std::ptr::null_mut(),
+            )
+        };
+
+        // `u32`'s to keep the detail data aligned.
+        let detail_size = std::mem::size_of::<SP_DEVICE_INTERFACE_DETAIL_DATA_W>() as u32;
+        let mut buffer = vec![0u32; (required_size.max(detail_size) as usize).div_ceil(4)];
+        let detail_data = buffer.as_mut_ptr() as *mut SP_DEVICE_INTERFACE_DETAIL_DATA_W;
+        unsafe { (*detail_data).cbSize = detail_size };
+
+        let mut device_info = SP_DEVINFO_DATA {
+            cbSize: std::mem::size_of::<SP_DEVINFO_DATA>() as u32,
+            ..Default::default()
+        };
+
+        if 0 == unsafe {
+            SetupDiGetDeviceInterfaceDetailW(
+                self.0,
+                &interface_data,
+                detail_data,
+                (buffer.len() * 4) as u32,
+                std::ptr::null_mut(),
+                &mut device_info,
+            )
+        } {
+            return None;
+        }
+
+        let device_path = unsafe {
+            let device_path = std::ptr::addr_of!((*detail_data).DevicePath) as *const u16;
+            let max_len =
+                (buffer.len() * 4 - (device_path as usize - buffer.as_ptr() as usize)) / 2;
+            std::slice::from_raw_parts(device_path, max_len)
+        };
+        let device_path = device_path.split(|&c| c == 0).next().unwrap_or(device_path);
+
+        Some(DeviceInterface {
+            device_path: device_path.into(),
+            device_info,
+            is_active: interface_data.Flags & SPINT_ACTIVE != 0,
+        })
+    }
+
+    /// Reads the raw EDID of the monitor from its device registry key, if any.
+    fn edid(&self, device_info: &SP_DEVINFO_DATA) -> Option<Vec<u8>> {
+        let key = unsafe {
+            SetupDiOpenDevRegKey(
+                self.0,
+                device_info,
+                DICS_FLAG_GLOBAL,
+                0,
+                DIREG_DEV,
+                KEY_READ,
+            )
+        };
+
+        if std::ptr::eq(key, INVALID_HANDLE_VALUE) {
+            return None;
+        }
+
+        let key = RegKey(key);
+        let value_name: Vec<u16> = "EDID\0".encode_utf16().collect();
+
+        let mut size = 0;
+
+        if ERROR_SUCCESS
+            != unsafe {
+                RegQueryValueExW(
+                    key.0,
+                    value_name.as_ptr(),
+                    std::ptr::null(),
+                    std::ptr::null_mut(),
+                    std::ptr::null_mut(),
+                    &mut size,
+                )
+            }
+        {
+            return None;
+        }
+
+        let mut edid = vec![0u8; size as usize];
+
+        if ERROR_SUCCESS
+            != unsafe {
+                RegQueryValueExW(
+                    key.0,
+                    value_name.as_ptr(),
+                    std::ptr::null(),
+                    std::ptr::null_mut(),
+                    edid.as_mut_ptr(),
+                    &mut size,
+                )
+            }
+        {
+            return None;
+        }
+
+        edid.truncate(size as usize);
+
+        Some(edid)
+    }
+}
+
+impl Drop for DeviceInfoList {
+    fn drop(&mut self) {
+        unsafe { SetupDiDestroyDeviceInfoList(self.0) };
+    }
+}
+
+/// Closes the owned registry key when dropped.
+struct RegKey(HKEY);
+
+impl Drop for RegKey {
+    fn drop(&mut self) {
+        unsafe { RegCloseKey(self.0) };
+    }
+}
@@ -1,8 +1,50 @@
+mod brightness;
+mod color_profile;
+mod cursor;
+mod ddc;
+mod display_config;
 mod display_info;
+mod dpi;
 mod enumerate_displays;
+mod fullscreen;
+mod inventory;
+mod night_light;
+mod power;
 mod rectangle;
-mod util;
+#[cfg(feature = "ipc")]
+mod shared_memory;
+#[cfg(feature = "wmi")]
+mod wmi;
 
-pub(crate) use enumerate_displays::enumerate_displays_win;
+pub(crate) use brightness::{
+    brightness_win, gamma_ramp_win, set_brightness_win, set_gamma_ramp_win,
+};
+pub(crate) use color_profile::apply_color_profile_win;
+pub(crate) use cursor::CursorClipGuardWin;
+pub(crate) use ddc::{
+    capabilities_win, physical_monitors_win, set_vcp_feature_win, vcp_feature_win,
+};
+pub(crate) use display_config::{
+    apply_display_config_win, projection_win, set_orientation_win, set_projection_win,
+    DisplayConfigSnapshotWin,
+};
+#[cfg(feature = "unstable-scaling")]
+pub(crate) use dpi::set_dpi_scale_win;
+pub(crate) use dpi::{process_dpi_awareness_win, thread_dpi_awareness_win};
+pub(crate) use enumerate_displays::{
+    display_metrics_win, enumerate_display_rects_win, enumerate_displays_win,
+};
+pub(crate) use inventory::enumerate_attached_hardware_win;
+pub(crate) use night_light::night_light_win;
+pub(crate) use power::{
+    adaptive_brightness_win, presentation_mode_active_win, set_thread_display_required_win,
+};
+#[cfg(feature = "ipc")]
+pub(crate) use shared_memory::SharedMemoryWin;
 
-pub use display_info::DisplayInfoWin;
+pub use ddc::PhysicalMonitorsWin;
+#[cfg(feature = "raw-win")]
+pub use display_info::RawDisplayConfig;
+pub use display_info::{AdapterLuid, DisplayConfigPath, DisplayInfoWin, DisplayStateWin};
+#[cfg(feature = "wmi")]
+pub use wmi::{VideoInputType, WmiMonitorInfo};
@@ -0,0 +1,73 @@
+use crate::night_light::{parse_night_light, NightLightState};
+
+use windows_sys::Win32::{
+    Foundation::ERROR_SUCCESS,
+    System::Registry::{RegGetValueW, HKEY_CURRENT_USER, RRF_RT_REG_BINARY},
+};
+
+/// Registry key (under `HKEY_CURRENT_USER`) of the cloud store, where the Night Light state and settings are stored.
+const CLOUD_STORE_KEY: &str =
+    "Software\\Microsoft\\Windows\\CurrentVersion\\CloudStore\\Store\\DefaultAccount\\Current\\";
+const STATE_KEY: &str = "default$windows.data.bluelightreduction.bluelightreductionstate\\windows.data.bluelightreduction.bluelightreductionstate";
+const SETTINGS_KEY: &str =
+    "default$windows.data.bluelightreduction.settings\\windows.data.bluelightreduction.settings";
+
+/// Reads the current user's Night Light state from the registry.
+///
+/// Returns `None` if Night Light is not supported, or was never configured.
+pub(crate) fn night_light_win() -> Option<NightLightState> {
+    let state = read_cloud_store_data(STATE_KEY)?;
+    let settings = read_cloud_store_data(SETTINGS_KEY);
+
+    parse_night_light(&state, settings.as_deref())
+}
+
+/// Reads the binary `Data` value of the cloud store `key`.
+fn read_cloud_store_data(key: &str) -> Option<Vec<u8>> {
+    let key: Vec<u16> = CLOUD_STORE_KEY
+        .encode_utf16()
+        .chain(key.encode_utf16())
+        .chain(std::iter::once(0))
+        .collect();
+    let value_name: Vec<u16> = "Data\0".encode_utf16().collect();
+
+    let mut size = 0;
+
+    if ERROR_SUCCESS
+        != unsafe {
+            RegGetValueW(
+                HKEY_CURRENT_USER,
+                key.as_ptr(),
+                value_name.as_ptr(),
+                RRF_RT_REG_BINARY,
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+                &mut size,
+            )
+        }
+    {
+        return None;
+    }
+
+    let mut data = vec![0u8; size as usize];
+
+    if ERROR_SUCCESS
+        != unsafe {
+            RegGetValueW(
+                HKEY_CURRENT_USER,
+                key.as_ptr(),
+                value_name.as_ptr(),
+                RRF_RT_REG_BINARY,
+                std::ptr::null_mut(),
+                data.as_mut_ptr() as _,
+                &mut size,
+            )
+        }
+    {
+        return None;
+    }
+
+    data.truncate(size as usize);
+
+    Some(data)
+}
@@ -0,0 +1,115 @@
+use crate::AdaptiveBrightness;
+
+use windows_sys::{
+    core::GUID,
+    Win32::{
+        Foundation::{LocalFree, ERROR_SUCCESS, WIN32_ERROR},
+        System::{
+            Power::{
+                GetSystemPowerStatus, PowerGetActiveScheme, PowerReadACValueIndex,
+                PowerReadDCValueIndex, SetThreadExecutionState, ES_CONTINUOUS, ES_DISPLAY_REQUIRED,
+                SYSTEM_POWER_STATUS,
+            },
+            Registry::HKEY,
+        },
+        UI::Shell::{
+            SHQueryUserNotificationState, QUERY_USER_NOTIFICATION_STATE, QUNS_PRESENTATION_MODE,
+            QUNS_RUNNING_D3D_FULL_SCREEN,
+        },
+    },
+};
+
+/// Returns `true` if the user turned on the presentation mode,
+/// or an exclusive full screen Direct3D application is running.
+pub(crate) fn presentation_mode_active_win() -> Result<bool, ()> {
+    let state = user_notification_state()?;
+
+    Ok(state == QUNS_PRESENTATION_MODE || state == QUNS_RUNNING_D3D_FULL_SCREEN)
+}
+
+/// Returns the shell's user notification state (i.e. whether it's OK to show the notifications to the user now).
+pub(crate) fn user_notification_state() -> Result<QUERY_USER_NOTIFICATION_STATE, ()> {
+    let mut state = 0;
+
+    if unsafe { SHQueryUserNotificationState(&mut state) } < 0 {
+        Err(())
+    } else {
+        Ok(state)
+    }
+}
+
+pub(crate) fn set_thread_display_required_win(display_required: bool) -> Result<(), ()> {
+    let flags = if display_required {
+        ES_CONTINUOUS | ES_DISPLAY_REQUIRED
+    } else {
+        ES_CONTINUOUS
+    };
+
+    // Returns the previous execution state, or `0` on failure.
+    if unsafe { SetThreadExecutionState(flags) } == 0 {
+        Err(())
+    } else {
+        Ok(())
+    }
+}
+
+/// Power setting subgroup of the display settings (`GUID_VIDEO_SUBGROUP`).
+const VIDEO_SUBGROUP: GUID = GUID::from_u128(0x7516b95f_f776_4464_8c53_06167f40cc99);
+/// Power setting which enables the adaptive display brightness (`GUID_VIDEO_ADAPTIVE_DISPLAY_BRIGHTNESS`).
+const VIDEO_ADAPTIVE_DISPLAY_BRIGHTNESS: GUID =
+    GUID::from_u128(0xfbd9aa66_9553_4097_ba44_ed6e9d65eab8);
+
+/// Returns the adaptive brightness settings of the active power plan, and the current power source, if known.
+pub(crate) fn adaptive_brightness_win() -> Option<AdaptiveBrightness> {
+    let mut scheme: *mut GUID = std::ptr::null_mut();
+
+    if unsafe { PowerGetActiveScheme(std::ptr::null_mut(), &mut scheme) } != ERROR_SUCCESS {
+        return None;
+    }
+
+    let read_value = |read: PowerReadValueIndex| {
+        let mut value = 0;
+
+        if unsafe {
+            read(
+                std::ptr::null_mut(),
+                scheme,
+                &VIDEO_SUBGROUP,
+                &VIDEO_ADAPTIVE_DISPLAY_BRIGHTNESS,
+                &mut value,
+            )
+        } == ERROR_SUCCESS
+        {
+            Some(value != 0)
+        } else {
+            None
+        }
+    };
+
+    let enabled_on_ac = read_value(PowerReadACValueIndex);
+    let enabled_on_battery = read_value(PowerReadDCValueIndex);
+
+    unsafe { LocalFree(scheme as _) };
+
+    let mut power_status: SYSTEM_POWER_STATUS = unsafe { std::mem::zeroed() };
+
+    let on_battery = if unsafe { GetSystemPowerStatus(&mut power_status) } != 0 {
+        // `255` means the status is unknown.
+        match power_status.ACLineStatus {
+            0 => Some(true),
+            1 => Some(false),
+            _ => None,
+        }
+    } else {
+        None
+    };
+
+    Some(AdaptiveBrightness {
+        enabled_on_ac: enabled_on_ac?,
+        enabled_on_battery: enabled_on_battery?,
+        on_battery,
+    })
+}
+
+type PowerReadValueIndex =
+    unsafe extern "system" fn(HKEY, *const GUID, *const GUID, *const GUID, *mut u32) -> WIN32_ERROR;
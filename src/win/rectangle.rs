@@ -1,24 +1,21 @@
 use crate::{Dimensions, Position, Rectangle};
 
-use winapi::shared::windef::RECT;
+use windows_sys::Win32::Foundation::RECT;
 
 impl Rectangle {
+    /// Inverted (negative width / height) Windows rectangles have zero dimensions.
     pub fn from_win_rect(rect: &RECT) -> Rectangle {
-        assert!(rect.right >= rect.left, "Negative width Windows rectangle.");
-        assert!(
-            rect.bottom >= rect.top,
-            "Negative height Windows rectangle."
-        );
+        let extent = |from: i32, to: i32| (to as i64 - from as i64).max(0) as u32;
 
         Rectangle::new(
             Position::new(rect.left, rect.top),
-            Dimensions::new(
-                (rect.right - rect.left) as u32,
-                (rect.bottom - rect.top) as u32,
-            ),
+            Dimensions::new(extent(rect.left, rect.right), extent(rect.top, rect.bottom)),
         )
     }
 
+    /// Right / bottom sides of [`degenerate`] rectangles saturate at `i32::MAX`.
+    ///
+    /// [`degenerate`]: struct.Rectangle.html#method.is_degenerate
     pub fn to_win_rect(&self) -> RECT {
         RECT {
             left: self.left(),
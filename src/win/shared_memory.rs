@@ -0,0 +1,108 @@
+use windows_sys::Win32::{
+    Foundation::{CloseHandle, GetLastError, ERROR_ALREADY_EXISTS, HANDLE, INVALID_HANDLE_VALUE},
+    System::Memory::{
+        CreateFileMappingW, MapViewOfFile, OpenFileMappingW, UnmapViewOfFile, VirtualQuery,
+        FILE_MAP_ALL_ACCESS, FILE_MAP_READ, MEMORY_BASIC_INFORMATION, MEMORY_MAPPED_VIEW_ADDRESS,
+        PAGE_READWRITE,
+    },
+};
+
+/// Owns the view of a named shared memory section (a pagefile-backed file mapping).
+pub(crate) struct SharedMemoryWin {
+    mapping: HANDLE,
+    view: MEMORY_MAPPED_VIEW_ADDRESS,
+    /// Size of the view, in bytes.
+    len: usize,
+}
+
+// The view is only accessed via atomics / by the single writer.
+unsafe impl Send for SharedMemoryWin {}
+unsafe impl Sync for SharedMemoryWin {}
+
+impl SharedMemoryWin {
+    /// Creates the zeroed shared memory section of `size` bytes with the `name`, mapped for reading and writing.
+    ///
+    /// Fails if the section exists already.
+    pub(crate) fn create(name: &str, size: usize) -> Result<Self, ()> {
+        let name = to_utf16(name);
+
+        let mapping = unsafe {
+            CreateFileMappingW(
+                INVALID_HANDLE_VALUE,
+                std::ptr::null(),
+                PAGE_READWRITE,
+                (size as u64 >> 32) as u32,
+                size as u32,
+                name.as_ptr(),
+            )
+        };
+
+        if mapping.is_null() {
+            return Err(());
+        }
+
+        // Don't publish into someone else's section.
+        if unsafe { GetLastError() } == ERROR_ALREADY_EXISTS {
+            unsafe { CloseHandle(mapping) };
+            return Err(());
+        }
+
+        Self::map(mapping, FILE_MAP_ALL_ACCESS)
+    }
+
+    /// Opens the existing shared memory section with the `name`, mapped for reading only.
+    pub(crate) fn open(name: &str) -> Result<Self, ()> {
+        let name = to_utf16(name);
+
+        let mapping = unsafe { OpenFileMappingW(FILE_MAP_READ, 0, name.as_ptr()) };
+
+        if mapping.is_null() {
+            return Err(());
+        }
+
+        Self::map(mapping, FILE_MAP_READ)
+    }
+
+    pub(crate) fn as_ptr(&self) -> *mut u8 {
+        self.view.Value as *mut u8
+    }
+
+    /// Returns the size of the view, in bytes (rounded up to the page size).
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Maps the whole section, closing the `mapping` on failure.
+    fn map(mapping: HANDLE, access: u32) -> Result<Self, ()> {
+        let view = unsafe { MapViewOfFile(mapping, access, 0, 0, 0) };
+
+        if view.Value.is_null() {
+            unsafe { CloseHandle(mapping) };
+            return Err(());
+        }
+
+        let mut info: MEMORY_BASIC_INFORMATION = unsafe { std::mem::zeroed() };
+
+        let len =
+            if 0 != unsafe { VirtualQuery(view.Value, &mut info, std::mem::size_of_val(&info)) } {
+                info.RegionSize
+            } else {
+                0
+            };
+
+        Ok(Self { mapping, view, len })
+    }
+}
+
+impl Drop for SharedMemoryWin {
+    fn drop(&mut self) {
+        unsafe {
+            UnmapViewOfFile(self.view);
+            CloseHandle(self.mapping);
+        }
+    }
+}
+
+fn to_utf16(string: &str) -> Vec<u16> {
+    string.encode_utf16().chain(std::iter::once(0)).collect()
+}
@@ -0,0 +1,701 @@
+//! Optional display monitor info queried via WMI,
+//! from the `root\WMI` `WmiMonitorID` and `WmiMonitorBasicDisplayParams` classes.
+//!
+//! Used to supplement the info reported by the display configuration API's,
+//! which (especially with old drivers) often only report generic display names (e.g. `Generic PnP Monitor`).
+
+use std::fmt::{Display, Formatter};
+
+use windows_sys::{
+    core::{IUnknown_Vtbl, BSTR, GUID, HRESULT, PCWSTR},
+    Win32::{
+        Foundation::{SysAllocString, SysFreeString, RPC_E_CHANGED_MODE, S_OK},
+        System::{
+            Com::{
+                CoCreateInstance, CoInitializeEx, CoSetProxyBlanket, CoUninitialize,
+                CLSCTX_INPROC_SERVER, COINIT_MULTITHREADED, EOAC_NONE, RPC_C_AUTHN_LEVEL_CALL,
+                RPC_C_IMP_LEVEL_IMPERSONATE, SAFEARRAY,
+            },
+            Ole::{
+                SafeArrayAccessData, SafeArrayGetLBound, SafeArrayGetUBound, SafeArrayUnaccessData,
+            },
+            Rpc::{RPC_C_AUTHN_WINNT, RPC_C_AUTHZ_NONE},
+            Variant::{
+                VariantClear, VariantInit, VARENUM, VARIANT, VT_ARRAY, VT_BSTR, VT_I2, VT_I4,
+                VT_UI1, VT_UI2, VT_UI4,
+            },
+            Wmi::{
+                WbemLocator, WBEM_FLAG_FORWARD_ONLY, WBEM_FLAG_RETURN_IMMEDIATELY, WBEM_INFINITE,
+            },
+        },
+    },
+};
+
+// `windows-sys` does not provide the COM interfaces -
+// declare the (used parts of the) WMI interfaces by hand.
+
+#[allow(non_upper_case_globals)]
+const IID_IWbemLocator: GUID = GUID::from_u128(0xdc12a687_737f_11cf_884d_00aa004b2e24);
+
+/// Unused COM interface vtable entry.
+type UnusedMethod = usize;
+
+/// COM interface pointed to by a [`ComPtr`].
+///
+/// NOTE - the vtable `V` must start with the `IUnknown` methods.
+///
+/// [`ComPtr`]: struct.ComPtr.html
+#[repr(C)]
+struct ComInterface<V> {
+    vtbl: *const V,
+}
+
+#[allow(non_snake_case)]
+#[repr(C)]
+struct IWbemLocatorVtbl {
+    base: IUnknown_Vtbl,
+    ConnectServer: unsafe extern "system" fn(
+        this: *mut IWbemLocator,
+        network_resource: BSTR,
+        user: BSTR,
+        password: BSTR,
+        locale: BSTR,
+        security_flags: i32,
+        authority: BSTR,
+        context: *mut std::ffi::c_void,
+        namespace: *mut *mut IWbemServices,
+    ) -> HRESULT,
+}
+
+#[allow(non_snake_case)]
+#[repr(C)]
+struct IWbemServicesVtbl {
+    base: IUnknown_Vtbl,
+    /// `OpenNamespace()` .. `CreateInstanceEnumAsync()`.
+    _unused: [UnusedMethod; 17],
+    ExecQuery: unsafe extern "system" fn(
+        this: *mut IWbemServices,
+        query_language: BSTR,
+        query: BSTR,
+        flags: i32,
+        context: *mut std::ffi::c_void,
+        enumerator: *mut *mut IEnumWbemClassObject,
+    ) -> HRESULT,
+}
+
+#[allow(non_snake_case)]
+#[repr(C)]
+struct IEnumWbemClassObjectVtbl {
+    base: IUnknown_Vtbl,
+    /// `Reset()`.
+    _unused: UnusedMethod,
+    Next: unsafe extern "system" fn(
+        this: *mut IEnumWbemClassObject,
+        timeout: i32,
+        count: u32,
+        objects: *mut *mut IWbemClassObject,
+        num_returned: *mut u32,
+    ) -> HRESULT,
+}
+
+#[allow(non_snake_case)]
+#[repr(C)]
+struct IWbemClassObjectVtbl {
+    base: IUnknown_Vtbl,
+    /// `GetQualifierSet()`.
+    _unused: UnusedMethod,
+    Get: unsafe extern "system" fn(
+        this: *mut IWbemClassObject,
+        name: PCWSTR,
+        flags: i32,
+        value: *mut VARIANT,
+        cim_type: *mut i32,
+        flavor: *mut i32,
+    ) -> HRESULT,
+}
+
+type IWbemLocator = ComInterface<IWbemLocatorVtbl>;
+type IWbemServices = ComInterface<IWbemServicesVtbl>;
+type IEnumWbemClassObject = ComInterface<IEnumWbemClassObjectVtbl>;
+type IWbemClassObject = ComInterface<IWbemClassObjectVtbl>;
+
+impl<V> ComInterface<V> {
+    fn this(&self) -> *mut Self {
+        self as *const _ as *mut _
+    }
+}
+
+impl IWbemLocator {
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn connect_server(
+        &self,
+        network_resource: BSTR,
+        user: BSTR,
+        password: BSTR,
+        locale: BSTR,
+        security_flags: i32,
+        authority: BSTR,
+        context: *mut std::ffi::c_void,
+        namespace: *mut *mut IWbemServices,
+    ) -> HRESULT {
+        ((*self.vtbl).ConnectServer)(
+            self.this(),
+            network_resource,
+            user,
+            password,
+            locale,
+            security_flags,
+            authority,
+            context,
+            namespace,
+        )
+    }
+}
+
+impl IWbemServices {
+    unsafe fn exec_query(
+        &self,
+        query_language: BSTR,
+        query: BSTR,
+        flags: i32,
+        context: *mut std::ffi::c_void,
+        enumerator: *mut *mut IEnumWbemClassObject,
+    ) -> HRESULT {
+        ((*self.vtbl).ExecQuery)(
+            self.this(),
+            query_language,
+            query,
+            flags,
+            context,
+            enumerator,
+        )
+    }
+}
+
+impl IEnumWbemClassObject {
+    unsafe fn next(
+        &self,
+        timeout: i32,
+        count: u32,
+        objects: *mut *mut IWbemClassObject,
+        num_returned: *mut u32,
+    ) -> HRESULT {
+        ((*self.vtbl).Next)(self.this(), timeout, count, objects, num_returned)
+    }
+}
+
+impl IWbemClassObject {
+    unsafe fn get(
+        &self,
+        name: PCWSTR,
+        flags: i32,
+        value: *mut VARIANT,
+        cim_type: *mut i32,
+        flavor: *mut i32,
+    ) -> HRESULT {
+        ((*self.vtbl).Get)(self.this(), name, flags, value, cim_type, flavor)
+    }
+}
+
+fn succeeded(res: HRESULT) -> bool {
+    res >= 0
+}
+
+fn failed(res: HRESULT) -> bool {
+    res < 0
+}
+
+/// Display monitor video input type, as reported by the monitor's EDID.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum VideoInputType {
+    Analog,
+    Digital,
+}
+
+impl Display for VideoInputType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        use VideoInputType::*;
+
+        match self {
+            Analog => "Analog".fmt(f),
+            Digital => "Digital".fmt(f),
+        }
+    }
+}
+
+/// Display monitor info queried via WMI.
+///
+/// All fields are `None` if not reported by the monitor / driver.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct WmiMonitorInfo {
+    /// Monitor's friendly name, as reported by the monitor's EDID.
+    pub friendly_name: Option<String>,
+    /// Monitor manufacturer's three-letter PnP ID (e.g. `DEL`).
+    pub manufacturer: Option<String>,
+    /// Monitor's product code.
+    pub product_code: Option<String>,
+    /// Monitor's serial number.
+    pub serial_number: Option<String>,
+    /// Monitor's year of manufacture.
+    pub manufacture_year: Option<u16>,
+    /// Monitor's week of manufacture.
+    pub manufacture_week: Option<u8>,
+    /// Monitor's video input type.
+    pub video_input_type: Option<VideoInputType>,
+}
+
+/// Monitor info queried via WMI, associated with the monitor's instance name
+/// (e.g. `DISPLAY\DEL40F0\5&1a2b3c4d&0&UID4352_0`).
+pub(crate) struct WmiMonitor {
+    instance_name: String,
+    info: WmiMonitorInfo,
+}
+
+impl WmiMonitor {
+    /// Returns `true` if the monitor's WMI instance name corresponds to the monitor device interface `device_path`
+    /// (e.g. `\\?\DISPLAY#DEL40F0#5&1a2b3c4d&0&UID4352#{e6f07b5f-ee97-4a90-b076-33f57bf4eaa7}`).
+    fn matches_device_path(&self, device_path: &str) -> bool {
+        let device_path = device_path.strip_prefix(r"\\?\").unwrap_or(device_path);
+
+        // Strip the device interface class GUID.
+        let device_path = match device_path.rfind('#') {
+            Some(guid) => &device_path[..guid],
+            None => device_path,
+        };
+
+        // Strip the instance index suffix.
+        let instance_name = match self.instance_name.rfind('_') {
+            Some(index) => &self.instance_name[..index],
+            None => &self.instance_name,
+        };
+
+        instance_name.len() == device_path.len()
+            && instance_name
+                .chars()
+                .zip(device_path.chars())
+                .all(|(l, r)| {
+                    let r = if r == '#' { '\\' } else { r };
+                    l.eq_ignore_ascii_case(&r)
+                })
+    }
+}
+
+/// Finds the WMI monitor info corresponding to the monitor device interface `device_path`, if any.
+pub(crate) fn find_wmi_monitor_info(
+    monitors: &[WmiMonitor],
+    device_path: &[u16],
+) -> Option<WmiMonitorInfo> {
+    let device_path = String::from_utf16_lossy(device_path);
+
+    monitors
+        .iter()
+        .find(|monitor| monitor.matches_device_path(&device_path))
+        .map(|monitor| monitor.info.clone())
+}
+
+/// Queries the monitor info of all active monitors via WMI.
+/// Returns an empty vector on error.
+pub(crate) fn query_wmi_monitors() -> Vec<WmiMonitor> {
+    let _com_guard = if let Some(com_guard) = ComGuard::new() {
+        com_guard
+    } else {
+        return Vec::new();
+    };
+
+    let services = if let Some(services) = connect_services() {
+        services
+    } else {
+        return Vec::new();
+    };
+
+    let mut monitors = Vec::new();
+
+    query(&services, "SELECT * FROM WmiMonitorID", |object| {
+        let instance_name = if let Some(instance_name) = get_string(object, "InstanceName") {
+            instance_name
+        } else {
+            return;
+        };
+
+        monitors.push(WmiMonitor {
+            instance_name,
+            info: WmiMonitorInfo {
+                friendly_name: get_u16_string(object, "UserFriendlyName"),
+                manufacturer: get_u16_string(object, "ManufacturerName"),
+                product_code: get_u16_string(object, "ProductCodeID"),
+                serial_number: get_u16_string(object, "SerialNumberID"),
+                manufacture_year: get_u32(object, "YearOfManufacture")
+                    .filter(|&year| year > 0)
+                    .map(|year| year as u16),
+                manufacture_week: get_u32(object, "WeekOfManufacture")
+                    .filter(|&week| week > 0 && week <= 54)
+                    .map(|week| week as u8),
+                video_input_type: None,
+            },
+        });
+    });
+
+    query(
+        &services,
+        "SELECT * FROM WmiMonitorBasicDisplayParams",
+        |object| {
+            let instance_name = if let Some(instance_name) = get_string(object, "InstanceName") {
+                instance_name
+            } else {
+                return;
+            };
+
+            if let Some(monitor) = monitors
+                .iter_mut()
+                .find(|monitor| monitor.instance_name == instance_name)
+            {
+                monitor.info.video_input_type =
+                    get_u32(object, "VideoInputType").map(|video_input_type| {
+                        if video_input_type == 0 {
+                            VideoInputType::Analog
+                        } else {
+                            VideoInputType::Digital
+                        }
+                    });
+            }
+        },
+    );
+
+    monitors
+}
+
+/// Initializes COM on the calling thread (if not initialized yet).
+/// Uninitializes COM when dropped (if successfully initialized).
+struct ComGuard(bool);
+
+impl ComGuard {
+    fn new() -> Option<Self> {
+        let res = unsafe { CoInitializeEx(std::ptr::null(), COINIT_MULTITHREADED as _) };
+
+        if succeeded(res) {
+            Some(Self(true))
+        // COM is already initialized on this thread with a different concurrency model - which is fine for us.
+        } else if res == RPC_E_CHANGED_MODE {
+            Some(Self(false))
+        } else {
+            None
+        }
+    }
+}
+
+impl Drop for ComGuard {
+    fn drop(&mut self) {
+        if self.0 {
+            unsafe { CoUninitialize() };
+        }
+    }
+}
+
+/// Owned COM interface pointer, released when dropped.
+struct ComPtr<V>(*mut ComInterface<V>);
+
+impl<V> ComPtr<V> {
+    fn new(ptr: *mut ComInterface<V>) -> Option<Self> {
+        if ptr.is_null() {
+            None
+        } else {
+            Some(Self(ptr))
+        }
+    }
+
+    fn as_unknown(&self) -> *mut std::ffi::c_void {
+        self.0 as *mut _
+    }
+}
+
+impl<V> std::ops::Deref for ComPtr<V> {
+    type Target = ComInterface<V>;
+
+    fn deref(&self) -> &ComInterface<V> {
+        unsafe { &*self.0 }
+    }
+}
+
+impl<V> Drop for ComPtr<V> {
+    fn drop(&mut self) {
+        // The vtable starts with the `IUnknown` methods.
+        unsafe {
+            let vtbl = (*self.0).vtbl as *const IUnknown_Vtbl;
+            ((*vtbl).Release)(self.as_unknown());
+        }
+    }
+}
+
+/// Owned `BSTR`, freed when dropped.
+struct BStr(BSTR);
+
+impl BStr {
+    fn new(string: &str) -> Self {
+        let string = to_wide(string);
+        Self(unsafe { SysAllocString(string.as_ptr()) })
+    }
+}
+
+impl Drop for BStr {
+    fn drop(&mut self) {
+        unsafe { SysFreeString(self.0) };
+    }
+}
+
+/// Owned `VARIANT`, cleared when dropped.
+struct Variant(VARIANT);
+
+impl Variant {
+    fn new() -> Self {
+        let mut variant: VARIANT = unsafe { std::mem::zeroed() };
+        unsafe { VariantInit(&mut variant) };
+        Self(variant)
+    }
+
+    fn vt(&self) -> VARENUM {
+        unsafe { self.0.Anonymous.Anonymous.vt }
+    }
+}
+
+impl Drop for Variant {
+    fn drop(&mut self) {
+        unsafe { VariantClear(&mut self.0) };
+    }
+}
+
+/// Returns the nul-terminated UTF-16 representation of the `string`.
+fn to_wide(string: &str) -> Vec<u16> {
+    string.encode_utf16().chain(std::iter::once(0)).collect()
+}
+
+/// Connects to the local `root\WMI` namespace.
+fn connect_services() -> Option<ComPtr<IWbemServicesVtbl>> {
+    let mut locator: *mut IWbemLocator = std::ptr::null_mut();
+
+    if failed(unsafe {
+        CoCreateInstance(
+            &WbemLocator,
+            std::ptr::null_mut(),
+            CLSCTX_INPROC_SERVER,
+            &IID_IWbemLocator,
+            &mut locator as *mut _ as _,
+        )
+    }) {
+        return None;
+    }
+
+    let locator = ComPtr::new(locator)?;
+
+    let namespace = BStr::new(r"ROOT\WMI");
+    let mut services: *mut IWbemServices = std::ptr::null_mut();
+
+    if failed(unsafe {
+        locator.connect_server(
+            namespace.0,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            0,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            &mut services,
+        )
+    }) {
+        return None;
+    }
+
+    let services = ComPtr::new(services)?;
+
+    if failed(unsafe {
+        CoSetProxyBlanket(
+            services.as_unknown(),
+            RPC_C_AUTHN_WINNT,
+            RPC_C_AUTHZ_NONE,
+            std::ptr::null(),
+            RPC_C_AUTHN_LEVEL_CALL,
+            RPC_C_IMP_LEVEL_IMPERSONATE,
+            std::ptr::null(),
+            EOAC_NONE as _,
+        )
+    }) {
+        return None;
+    }
+
+    Some(services)
+}
+
+/// Executes the WQL `query`, calling `f` for each returned object.
+fn query<F: FnMut(&IWbemClassObject)>(services: &IWbemServices, query: &str, mut f: F) {
+    let language = BStr::new("WQL");
+    let query = BStr::new(query);
+
+    let mut enumerator: *mut IEnumWbemClassObject = std::ptr::null_mut();
+
+    if failed(unsafe {
+        services.exec_query(
+            language.0,
+            query.0,
+            WBEM_FLAG_FORWARD_ONLY | WBEM_FLAG_RETURN_IMMEDIATELY,
+            std::ptr::null_mut(),
+            &mut enumerator,
+        )
+    }) {
+        return;
+    }
+
+    let enumerator = if let Some(enumerator) = ComPtr::new(enumerator) {
+        enumerator
+    } else {
+        return;
+    };
+
+    loop {
+        let mut object: *mut IWbemClassObject = std::ptr::null_mut();
+        let mut num_returned = 0;
+
+        if S_OK != unsafe { enumerator.next(WBEM_INFINITE, 1, &mut object, &mut num_returned) }
+            || num_returned == 0
+        {
+            break;
+        }
+
+        if let Some(object) = ComPtr::new(object) {
+            f(&object);
+        }
+    }
+}
+
+/// Returns the value of the object's `property`, or `None` on error.
+fn get_property(object: &IWbemClassObject, property: &str) -> Option<Variant> {
+    let property = to_wide(property);
+    let mut value = Variant::new();
+
+    if failed(unsafe {
+        object.get(
+            property.as_ptr(),
+            0,
+            &mut value.0,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+        )
+    }) {
+        return None;
+    }
+
+    Some(value)
+}
+
+/// Returns the value of the object's string `property`, or `None` on error / if empty.
+fn get_string(object: &IWbemClassObject, property: &str) -> Option<String> {
+    let value = get_property(object, property)?;
+
+    if value.vt() != VT_BSTR {
+        return None;
+    }
+
+    let string = unsafe { value.0.Anonymous.Anonymous.Anonymous.bstrVal };
+
+    if string.is_null() {
+        return None;
+    }
+
+    let len = (0..)
+        .take_while(|&i| unsafe { *string.offset(i) } != 0)
+        .count();
+    let string = String::from_utf16_lossy(unsafe { std::slice::from_raw_parts(string, len) });
+
+    if string.is_empty() {
+        None
+    } else {
+        Some(string)
+    }
+}
+
+/// Returns the value of the object's unsigned integer `property`, or `None` on error.
+fn get_u32(object: &IWbemClassObject, property: &str) -> Option<u32> {
+    let value = get_property(object, property)?;
+
+    let vt = value.vt();
+    let value = unsafe { &value.0.Anonymous.Anonymous.Anonymous };
+
+    // NOTE - WMI returns `uint16` values as `VT_I4`.
+    unsafe {
+        match vt {
+            VT_UI1 => Some(value.bVal as u32),
+            VT_I2 => Some(value.iVal as u16 as u32),
+            VT_UI2 => Some(value.uiVal as u32),
+            VT_I4 => Some(value.lVal as u32),
+            VT_UI4 => Some(value.ulVal),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the value of the object's `uint16[]` `property`, containing a nul-terminated string,
+/// or `None` on error / if empty.
+fn get_u16_string(object: &IWbemClassObject, property: &str) -> Option<String> {
+    let value = get_property(object, property)?;
+
+    let vt = value.vt();
+
+    if vt & VT_ARRAY == 0 {
+        return None;
+    }
+
+    let array: *mut SAFEARRAY = unsafe { value.0.Anonymous.Anonymous.Anonymous.parray };
+
+    if array.is_null() {
+        return None;
+    }
+
+    let mut lower = 0;
+    let mut upper = 0;
+
+    if failed(unsafe { SafeArrayGetLBound(array, 1, &mut lower) })
+        || failed(unsafe { SafeArrayGetUBound(array, 1, &mut upper) })
+    {
+        return None;
+    }
+
+    let len = (upper - lower + 1).max(0) as usize;
+
+    let mut data: *mut std::ffi::c_void = std::ptr::null_mut();
+
+    if failed(unsafe { SafeArrayAccessData(array, &mut data) }) {
+        return None;
+    }
+
+    // NOTE - WMI returns `uint16` array elements as `VT_I4`.
+    let string: Option<Vec<u16>> = unsafe {
+        match vt & !VT_ARRAY {
+            VT_I4 | VT_UI4 => Some(
+                std::slice::from_raw_parts(data as *const u32, len)
+                    .iter()
+                    .map(|&c| c as u16)
+                    .collect(),
+            ),
+            VT_I2 | VT_UI2 => Some(std::slice::from_raw_parts(data as *const u16, len).to_vec()),
+            VT_UI1 => Some(
+                std::slice::from_raw_parts(data as *const u8, len)
+                    .iter()
+                    .map(|&c| c as u16)
+                    .collect(),
+            ),
+            _ => None,
+        }
+    };
+
+    unsafe { SafeArrayUnaccessData(array) };
+
+    let string = string?;
+    let string = string.split(|&c| c == 0).next().unwrap_or(&string);
+
+    let string = String::from_utf16_lossy(string);
+    let string = string.trim();
+
+    if string.is_empty() {
+        None
+    } else {
+        Some(string.to_owned())
+    }
+}
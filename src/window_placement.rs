@@ -0,0 +1,338 @@
+use crate::{
+    ClipRectFlags, Dimensions, DisplayId, DisplayInfo, Displays, DisplaysSnapshot, Position,
+    Rectangle,
+};
+
+/// Window's show state, as persisted in the [`window placement`].
+///
+/// [`window placement`]: struct.WindowPlacement.html
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WindowState {
+    #[default]
+    Normal,
+    Maximized,
+    Fullscreen,
+}
+
+/// Determines which display the [`window placement`] is restored to if the display the window was on is gone.
+///
+/// [`window placement`]: struct.WindowPlacement.html
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RestorePolicy {
+    /// Restore the window to the system's primary display.
+    #[default]
+    Primary,
+    /// Restore the window to the display nearest to where the window was
+    /// (e.g. the display which took over the gone display's part of the virtual desktop).
+    Nearest,
+}
+
+/// Window's placement (e.g. as persisted between the application runs), relative to the display it was on,
+/// identified by its [`stable identifier`].
+///
+/// [`Captured`] from the window's rectangle, and [`restored`] to the window's rectangle
+/// against the (possibly changed) enumerated displays.
+///
+/// Enable the `serde` feature to (de)serialize the placement.
+///
+/// [`stable identifier`]: struct.DisplayId.html
+/// [`Captured`]: #method.capture
+/// [`restored`]: #method.restore
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WindowPlacement {
+    /// Stable identifier of the display the window was (mostly) on.
+    pub display: DisplayId,
+    /// The window's position relative to the top left corner of the display's work rectangle, in physical pixels.
+    pub position: Position,
+    /// The window's dimensions, in physical pixels.
+    pub dimensions: Dimensions,
+    /// The display's DPI scale value, to keep the window's logical size when the display's DPI scale changes.
+    pub dpi_scale: f32,
+    /// The display's work rectangle w.r.t. the virtual display, to find the nearest display if it's gone.
+    pub display_rect: Rectangle,
+    /// The window's show state.
+    ///
+    /// NOTE - the rectangles are the window's normal (i.e. not maximized / fullscreen) rectangles;
+    /// the application is expected to maximize / make the restored window fullscreen on the display it's restored to.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub state: WindowState,
+}
+
+impl WindowPlacement {
+    /// Captures the placement of the window with the (normal) `rect` in the virtual desktop,
+    /// relative to the [`enumerated display`] the window is mostly on (or the nearest one, if it's on none).
+    ///
+    /// The window's state is [`Normal`]; set the [`state`] as appropriate.
+    ///
+    /// Returns an error if there are no displays with the rectangles.
+    ///
+    /// [`enumerated display`]: struct.Displays.html
+    /// [`Normal`]: enum.WindowState.html#variant.Normal
+    /// [`state`]: #structfield.state
+    pub fn capture(rect: Rectangle, displays: &Displays) -> Result<Self, ()> {
+        displays.with_snapshot(|snapshot| Self::capture_snapshot(rect, snapshot))
+    }
+
+    /// Same as [`capture`], but relative to the displays in the [`snapshot`].
+    ///
+    /// [`capture`]: #method.capture
+    /// [`snapshot`]: struct.DisplaysSnapshot.html
+    pub fn capture_snapshot(rect: Rectangle, snapshot: &DisplaysSnapshot) -> Result<Self, ()> {
+        Self::capture_impl(rect, &snapshot.displays().iter().collect::<Vec<_>>())
+    }
+
+    /// Restores the window's (normal) rectangle in the virtual desktop against the [`enumerated displays`].
+    ///
+    /// If the display the window was on is still enumerated, the window keeps its position relative to the display's work rectangle
+    /// (i.e. follows the display if it was moved). Otherwise, the window is centered on the display chosen by the `policy`.
+    /// The window's dimensions are scaled by the change of the display's DPI scale, if any.
+    ///
+    /// The restored rectangle is moved / shrunk to fit within the display's work rectangle (see [`Rectangle::clip`]).
+    ///
+    /// Returns an error if there are no displays with the rectangles.
+    ///
+    /// [`enumerated displays`]: struct.Displays.html
+    /// [`Rectangle::clip`]: struct.Rectangle.html#method.clip
+    pub fn restore(&self, displays: &Displays, policy: RestorePolicy) -> Result<Rectangle, ()> {
+        displays.with_snapshot(|snapshot| self.restore_snapshot(snapshot, policy))
+    }
+
+    /// Same as [`restore`], but against the displays in the [`snapshot`].
+    ///
+    /// [`restore`]: #method.restore
+    /// [`snapshot`]: struct.DisplaysSnapshot.html
+    pub fn restore_snapshot(
+        &self,
+        snapshot: &DisplaysSnapshot,
+        policy: RestorePolicy,
+    ) -> Result<Rectangle, ()> {
+        self.restore_impl(&snapshot.displays().iter().collect::<Vec<_>>(), policy)
+    }
+
+    fn capture_impl(rect: Rectangle, displays: &[&DisplayInfo]) -> Result<Self, ()> {
+        let overlap = |display: &DisplayInfo| {
+            display
+                .rects
+                .virtual_rect
+                .intersection(&rect)
+                .map_or(0, |intersection| intersection.dimensions.area() as u64)
+        };
+
+        let display = displays
+            .iter()
+            .filter(|display| overlap(display) > 0)
+            .max_by_key(|display| overlap(display))
+            .copied()
+            .or_else(|| nearest_display(displays, &rect))
+            .ok_or(())?;
+
+        let display_rect = display.rects.work_rect;
+
+        Ok(Self {
+            display: display.id,
+            position: rect.position - display_rect.position,
+            dimensions: rect.dimensions,
+            dpi_scale: display.dpi_scale,
+            display_rect,
+            state: WindowState::Normal,
+        })
+    }
+
+    fn restore_impl(
+        &self,
+        displays: &[&DisplayInfo],
+        policy: RestorePolicy,
+    ) -> Result<Rectangle, ()> {
+        let display = displays
+            .iter()
+            .find(|display| display.id == self.display)
+            .copied();
+
+        let fallback = display.is_none();
+
+        let display = display
+            .or_else(|| match policy {
+                RestorePolicy::Primary => displays
+                    .iter()
+                    .find(|display| display.is_primary)
+                    .or_else(|| displays.first())
+                    .copied(),
+                RestorePolicy::Nearest => nearest_display(
+                    displays,
+                    &Rectangle::new(self.display_rect.position + self.position, self.dimensions),
+                ),
+            })
+            .ok_or(())?;
+
+        let bounds = display.rects.work_rect;
+
+        let scale = if self.dpi_scale > 0.0 {
+            display.dpi_scale / self.dpi_scale
+        } else {
+            1.0
+        };
+        let scale = |value: u32| (value as f32 * scale).round() as u32;
+        let dimensions =
+            Dimensions::new(scale(self.dimensions.width), scale(self.dimensions.height));
+
+        let position = if fallback {
+            // Use the exact `i64` coordinates - the window may be larger than the display.
+            let center = |start: i32, size: u32, window_size: u32| {
+                (start as i64 + (size as i64 - window_size as i64) / 2)
+                    .clamp(i32::MIN as i64, i32::MAX as i64) as i32
+            };
+
+            Position::new(
+                center(bounds.left(), bounds.width(), dimensions.width),
+                center(bounds.top(), bounds.height(), dimensions.height),
+            )
+        } else {
+            let offset = |start: i32, offset: i32| start.saturating_add(offset);
+
+            Position::new(
+                offset(bounds.left(), self.position.left),
+                offset(bounds.top(), self.position.top),
+            )
+        };
+
+        Rectangle::new(position, dimensions)
+            .clip(&bounds, ClipRectFlags::KEEP_NONE)
+            .ok_or(())
+    }
+}
+
+/// Returns the display (with a non-empty rectangle) nearest to the center of the `rect`, if any.
+fn nearest_display<'d>(displays: &[&'d DisplayInfo], rect: &Rectangle) -> Option<&'d DisplayInfo> {
+    let center_x = rect.left() as i64 + rect.width() as i64 / 2;
+    let center_y = rect.top() as i64 + rect.height() as i64 / 2;
+
+    // Squared distance from the center to the display's rectangle.
+    let distance = |display: &DisplayInfo| {
+        let display_rect = &display.rects.virtual_rect;
+
+        let distance = |center: i64, start: i64, end: i64| {
+            if center < start {
+                start - center
+            } else if center >= end {
+                center - end + 1
+            } else {
+                0
+            }
+        };
+
+        let dx = distance(
+            center_x,
+            display_rect.left() as i64,
+            display_rect.exact_right(),
+        );
+        let dy = distance(
+            center_y,
+            display_rect.top() as i64,
+            display_rect.exact_bottom(),
+        );
+
+        dx * dx + dy * dy
+    };
+
+    displays
+        .iter()
+        .filter(|display| !display.rects.virtual_rect.is_empty())
+        .min_by_key(|display| distance(display))
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::TestDisplay;
+
+    fn display(
+        id: u64,
+        left: i32,
+        top: i32,
+        width: u32,
+        height: u32,
+        dpi_scale: f32,
+    ) -> DisplayInfo {
+        TestDisplay::new(left, top, width, height)
+            .id(id)
+            .taskbar(40)
+            .dpi_scale(dpi_scale)
+            .build()
+    }
+
+    fn rect(left: i32, top: i32, width: u32, height: u32) -> Rectangle {
+        Rectangle::new(Position::new(left, top), Dimensions::new(width, height))
+    }
+
+    #[test]
+    fn window_placement() {
+        let snapshot = DisplaysSnapshot::new(vec![
+            display(1, 0, 0, 1920, 1080, 1.0),
+            display(2, 1920, 0, 2560, 1440, 1.5),
+        ]);
+
+        // Mostly on the second display.
+        let mut placement =
+            WindowPlacement::capture_snapshot(rect(1800, 100, 800, 600), &snapshot).unwrap();
+        assert_eq!(placement.display, DisplayId(2));
+        assert_eq!(placement.position, Position::new(-120, 100));
+        assert_eq!(placement.dpi_scale, 1.5);
+        placement.state = WindowState::Maximized;
+
+        // Same displays - moved into the display.
+        assert_eq!(
+            placement.restore_snapshot(&snapshot, RestorePolicy::Primary),
+            Ok(rect(1920, 100, 800, 600))
+        );
+
+        // Display moved to the left of the primary one, with a different DPI scale.
+        let moved = DisplaysSnapshot::new(vec![
+            display(1, 0, 0, 1920, 1080, 1.0),
+            display(2, -2560, -200, 2560, 1440, 1.0),
+        ]);
+        assert_eq!(
+            placement.restore_snapshot(&moved, RestorePolicy::Primary),
+            Ok(rect(-2560, -100, 533, 400))
+        );
+
+        // Display gone - centered on the fallback display.
+        let gone = DisplaysSnapshot::new(vec![
+            display(1, 0, 0, 1920, 1080, 1.0),
+            display(3, 1920, 0, 1920, 1080, 1.5),
+        ]);
+        assert_eq!(
+            placement.restore_snapshot(&gone, RestorePolicy::Primary),
+            Ok(rect(693, 320, 533, 400))
+        );
+        assert_eq!(
+            placement.restore_snapshot(&gone, RestorePolicy::Nearest),
+            Ok(rect(2480, 220, 800, 600))
+        );
+
+        // Window larger than the display is shrunk.
+        let placement =
+            WindowPlacement::capture_snapshot(rect(0, 0, 1920, 1080), &snapshot).unwrap();
+        assert_eq!(placement.display, DisplayId(1));
+        assert_eq!(
+            placement.restore_snapshot(&snapshot, RestorePolicy::Primary),
+            Ok(rect(0, 0, 1920, 1040))
+        );
+
+        // Off all displays - captured relative to the nearest one.
+        let placement =
+            WindowPlacement::capture_snapshot(rect(5000, 2000, 100, 100), &snapshot).unwrap();
+        assert_eq!(placement.display, DisplayId(2));
+
+        // No displays.
+        let empty = DisplaysSnapshot::default();
+        assert!(WindowPlacement::capture_snapshot(rect(0, 0, 100, 100), &empty).is_err());
+        assert_eq!(
+            placement.restore_snapshot(&empty, RestorePolicy::Nearest),
+            Err(())
+        );
+    }
+}